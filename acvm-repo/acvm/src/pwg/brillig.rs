@@ -8,7 +8,7 @@ use acir::{
     FieldElement,
 };
 use acvm_blackbox_solver::BlackBoxFunctionSolver;
-use brillig_vm::{Registers, VMStatus, VM};
+use brillig_vm::{ExecutionLimits, FailureReason, Registers, VMStatus, VM};
 
 use crate::{pwg::OpcodeNotSolvable, OpcodeResolutionError};
 
@@ -67,6 +67,7 @@ impl<'b, B: BlackBoxFunctionSolver> BrilligSolver<'b, B> {
         brillig: &'b Brillig,
         bb_solver: &'b B,
         acir_index: usize,
+        limits: ExecutionLimits,
     ) -> Result<Self, OpcodeResolutionError> {
         // Set input values
         let mut input_register_values: Vec<Value> = Vec::new();
@@ -108,7 +109,8 @@ impl<'b, B: BlackBoxFunctionSolver> BrilligSolver<'b, B> {
         // Instantiate a Brillig VM given the solved input registers and memory
         // along with the Brillig bytecode.
         let input_registers = Registers::load(input_register_values);
-        let vm = VM::new(input_registers, input_memory, &brillig.bytecode, vec![], bb_solver);
+        let vm = VM::new(input_registers, input_memory, &brillig.bytecode, vec![], bb_solver)
+            .with_limits(limits);
         Ok(Self { vm, acir_index })
     }
 
@@ -128,6 +130,12 @@ impl<'b, B: BlackBoxFunctionSolver> BrilligSolver<'b, B> {
         self.vm.write_memory_at(ptr, value);
     }
 
+    /// Enables counting the number of times each Brillig opcode is executed.
+    pub(super) fn with_profiling(mut self) -> Self {
+        self.vm = self.vm.with_profiling();
+        self
+    }
+
     pub(super) fn solve(&mut self) -> Result<BrilligSolverStatus, OpcodeResolutionError> {
         let status = self.vm.process_opcodes();
         self.handle_vm_status(status)
@@ -142,6 +150,29 @@ impl<'b, B: BlackBoxFunctionSolver> BrilligSolver<'b, B> {
         self.vm.program_counter()
     }
 
+    /// Returns the number of Brillig opcodes which have been executed so far.
+    pub fn step_count(&self) -> usize {
+        self.vm.step_count()
+    }
+
+    /// Returns the number of foreign calls which have been resolved so far.
+    pub fn foreign_call_count(&self) -> usize {
+        self.vm.foreign_call_count()
+    }
+
+    /// Returns the number of times each Brillig opcode has been executed, keyed by [OpcodeLocation].
+    ///
+    /// This is empty unless profiling has been enabled on the ACVM.
+    pub fn opcode_counts(&self) -> impl Iterator<Item = (OpcodeLocation, usize)> + '_ {
+        let acir_index = self.acir_index;
+        self.vm.opcode_counts().unwrap_or_default().iter().enumerate().filter_map(
+            move |(brillig_index, count)| {
+                (*count > 0)
+                    .then_some((OpcodeLocation::Brillig { acir_index, brillig_index }, *count))
+            },
+        )
+    }
+
     fn handle_vm_status(
         &self,
         vm_status: VMStatus,
@@ -153,17 +184,26 @@ impl<'b, B: BlackBoxFunctionSolver> BrilligSolver<'b, B> {
         match vm_status {
             VMStatus::Finished => Ok(BrilligSolverStatus::Finished),
             VMStatus::InProgress => Ok(BrilligSolverStatus::InProgress),
-            VMStatus::Failure { message, call_stack } => {
-                Err(OpcodeResolutionError::BrilligFunctionFailed {
-                    message,
-                    call_stack: call_stack
-                        .iter()
-                        .map(|brillig_index| OpcodeLocation::Brillig {
-                            acir_index: self.acir_index,
-                            brillig_index: *brillig_index,
+            VMStatus::Failure { reason, call_stack } => {
+                let call_stack = call_stack
+                    .iter()
+                    .map(|brillig_index| OpcodeLocation::Brillig {
+                        acir_index: self.acir_index,
+                        brillig_index: *brillig_index,
+                    })
+                    .collect();
+                match reason {
+                    FailureReason::RuntimeError { message } => {
+                        Err(OpcodeResolutionError::BrilligFunctionFailed { message, call_stack })
+                    }
+                    FailureReason::ResourceExhausted { resource, limit } => {
+                        Err(OpcodeResolutionError::BrilligResourceExhausted {
+                            resource,
+                            limit,
+                            call_stack,
                         })
-                        .collect(),
-                })
+                    }
+                }
             }
             VMStatus::ForeignCallWait { function, inputs } => {
                 Ok(BrilligSolverStatus::ForeignCallWait(ForeignCallWaitInfo { function, inputs }))
@@ -1,6 +1,6 @@
 // Re-usable methods that backends can use to implement their PWG
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use acir::{
     brillig::ForeignCallResult,
@@ -9,6 +9,7 @@ use acir::{
     BlackBoxFunc, FieldElement,
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
+use brillig_vm::{ExecutionLimits, Resource};
 
 use self::{arithmetic::ExpressionSolver, directives::solve_directives, memory_op::MemoryOpSolver};
 use crate::BlackBoxFunctionSolver;
@@ -112,6 +113,8 @@ pub enum OpcodeResolutionError {
     BlackBoxFunctionFailed(BlackBoxFunc, String),
     #[error("Failed to solve brillig function, reason: {message}")]
     BrilligFunctionFailed { message: String, call_stack: Vec<OpcodeLocation> },
+    #[error("Brillig function exceeded the limit of {limit} {resource}")]
+    BrilligResourceExhausted { resource: Resource, limit: usize, call_stack: Vec<OpcodeLocation> },
 }
 
 impl From<BlackBoxResolutionError> for OpcodeResolutionError {
@@ -140,6 +143,16 @@ pub struct ACVM<'a, B: BlackBoxFunctionSolver> {
     witness_map: WitnessMap,
 
     brillig_solver: Option<BrilligSolver<'a, B>>,
+
+    /// Limits on the resources consumed by all Brillig processes executed by the ACVM.
+    brillig_limits: ExecutionLimits,
+    /// The total number of Brillig opcodes executed by completed Brillig processes.
+    brillig_step_count: usize,
+    /// The total number of foreign calls made by completed Brillig processes.
+    brillig_foreign_call_count: usize,
+    /// The number of times each Brillig opcode has been executed by completed Brillig processes,
+    /// if profiling is enabled.
+    brillig_profile: Option<BTreeMap<OpcodeLocation, usize>>,
 }
 
 impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
@@ -153,9 +166,41 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
             instruction_pointer: 0,
             witness_map: initial_witness,
             brillig_solver: None,
+            brillig_limits: ExecutionLimits::UNLIMITED,
+            brillig_step_count: 0,
+            brillig_foreign_call_count: 0,
+            brillig_profile: None,
         }
     }
 
+    /// Sets the [ExecutionLimits] shared across all Brillig processes executed by the ACVM.
+    ///
+    /// Step and foreign call limits are cumulative over the whole circuit, whereas the memory
+    /// limit applies to each Brillig process individually.
+    pub fn with_brillig_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.brillig_limits = limits;
+        self
+    }
+
+    /// Enables counting the number of times each Brillig opcode is executed.
+    pub fn with_brillig_profiling(mut self) -> Self {
+        self.brillig_profile = Some(BTreeMap::new());
+        self
+    }
+
+    /// Returns the number of times each Brillig opcode has been executed by completed Brillig processes.
+    ///
+    /// This is only tracked once [profiling][ACVM::with_brillig_profiling] has been enabled.
+    pub fn brillig_profile(&self) -> Option<&BTreeMap<OpcodeLocation, usize>> {
+        self.brillig_profile.as_ref()
+    }
+
+    /// Returns the total number of Brillig opcodes executed so far.
+    pub fn brillig_step_count(&self) -> usize {
+        self.brillig_step_count
+            + self.brillig_solver.as_ref().map_or(0, |solver| solver.step_count())
+    }
+
     /// Returns a reference to the current state of the ACVM's [`WitnessMap`].
     ///
     /// Once execution has completed, the witness map can be extracted using [`ACVM::finalize`]
@@ -318,6 +363,7 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
             unreachable!("Not executing a Brillig opcode");
         };
 
+        let limits = self.remaining_brillig_limits();
         let witness = &mut self.witness_map;
         if BrilligSolver::<B>::should_skip(witness, brillig)? {
             return BrilligSolver::<B>::zero_out_brillig_outputs(witness, brillig).map(|_| None);
@@ -327,9 +373,39 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
         // there will be a cached `BrilligSolver` to avoid recomputation.
         let mut solver: BrilligSolver<'_, B> = match self.brillig_solver.take() {
             Some(solver) => solver,
-            None => BrilligSolver::new(witness, brillig, self.backend, self.instruction_pointer)?,
+            None => {
+                let solver = BrilligSolver::new(
+                    witness,
+                    brillig,
+                    self.backend,
+                    self.instruction_pointer,
+                    limits,
+                )?;
+                if self.brillig_profile.is_some() {
+                    solver.with_profiling()
+                } else {
+                    solver
+                }
+            }
         };
-        match solver.solve()? {
+        let status = solver.solve().map_err(|error| match error {
+            // Report the limit configured on the ACVM rather than the share of it left to this process.
+            OpcodeResolutionError::BrilligResourceExhausted { resource, limit, call_stack } => {
+                let limit = self.brillig_limits.limit(resource).unwrap_or(limit);
+                OpcodeResolutionError::BrilligResourceExhausted { resource, limit, call_stack }
+            }
+            error => error,
+        });
+        if !matches!(status, Ok(BrilligSolverStatus::ForeignCallWait(_))) {
+            self.brillig_step_count += solver.step_count();
+            self.brillig_foreign_call_count += solver.foreign_call_count();
+            if let Some(brillig_profile) = &mut self.brillig_profile {
+                for (opcode_location, count) in solver.opcode_counts() {
+                    *brillig_profile.entry(opcode_location).or_default() += count;
+                }
+            }
+        }
+        match status? {
             BrilligSolverStatus::ForeignCallWait(foreign_call) => {
                 // Cache the current state of the solver
                 self.brillig_solver = Some(solver);
@@ -351,6 +427,7 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
             return StepResult::Status(self.solve_opcode());
         };
 
+        let limits = self.remaining_brillig_limits();
         let witness = &mut self.witness_map;
         let should_skip = match BrilligSolver::<B>::should_skip(witness, brillig) {
             Ok(result) => result,
@@ -362,13 +439,28 @@ impl<'a, B: BlackBoxFunctionSolver> ACVM<'a, B> {
             return StepResult::Status(self.handle_opcode_resolution(resolution));
         }
 
-        let solver = BrilligSolver::new(witness, brillig, self.backend, self.instruction_pointer);
+        let solver =
+            BrilligSolver::new(witness, brillig, self.backend, self.instruction_pointer, limits)
+                .map(
+                    |solver| {
+                        if self.brillig_profile.is_some() {
+                            solver.with_profiling()
+                        } else {
+                            solver
+                        }
+                    },
+                );
         match solver {
             Ok(solver) => StepResult::IntoBrillig(solver),
             Err(..) => StepResult::Status(self.handle_opcode_resolution(solver.map(|_| ()))),
         }
     }
 
+    /// Returns the Brillig limits left over for the next Brillig process.
+    fn remaining_brillig_limits(&self) -> ExecutionLimits {
+        self.brillig_limits.remaining(self.brillig_step_count, self.brillig_foreign_call_count)
+    }
+
     pub fn finish_brillig_with_solver(&mut self, solver: BrilligSolver<'a, B>) -> ACVMStatus {
         if !matches!(&self.opcodes[self.instruction_pointer], Opcode::Brillig(..)) {
             unreachable!("Not executing a Brillig opcode");
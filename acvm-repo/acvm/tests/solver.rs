@@ -12,6 +12,7 @@ use acir::{
 };
 
 use acvm::{
+    brillig_vm::{ExecutionLimits, Resource},
     pwg::{ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, ACVM},
     BlackBoxFunctionSolver,
 };
@@ -609,6 +610,46 @@ fn unsatisfied_opcode_resolved_brillig() {
     );
 }
 
+#[test]
+fn brillig_step_limits_are_shared_across_brillig_opcodes() {
+    // Each Brillig opcode executes 3 Brillig opcodes before stopping.
+    let brillig_opcode = || {
+        Opcode::Brillig(Brillig {
+            inputs: vec![],
+            outputs: vec![],
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: RegisterIndex::from(0),
+                    value: Value::from(1u128),
+                },
+                BrilligOpcode::Mov {
+                    destination: RegisterIndex::from(1),
+                    source: RegisterIndex::from(0),
+                },
+                BrilligOpcode::Stop,
+            ],
+            predicate: None,
+        })
+    };
+    let opcodes = vec![brillig_opcode(), brillig_opcode()];
+
+    let mut acvm = ACVM::new(&StubbedBackend, &opcodes, WitnessMap::new());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.brillig_step_count(), 6);
+
+    let limits = ExecutionLimits { max_steps: Some(4), ..ExecutionLimits::UNLIMITED };
+    let mut acvm =
+        ACVM::new(&StubbedBackend, &opcodes, WitnessMap::new()).with_brillig_limits(limits);
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::BrilligResourceExhausted {
+            resource: Resource::Steps,
+            limit: 4,
+            call_stack: vec![OpcodeLocation::Brillig { acir_index: 1, brillig_index: 1 }]
+        })
+    );
+}
+
 #[test]
 fn memory_operations() {
     let initial_witness = WitnessMap::from(BTreeMap::from_iter([
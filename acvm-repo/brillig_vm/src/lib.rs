@@ -21,6 +21,7 @@ pub use acir::brillig;
 
 mod arithmetic;
 mod black_box;
+mod limits;
 mod memory;
 mod registers;
 
@@ -28,6 +29,7 @@ use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 use arithmetic::{evaluate_binary_bigint_op, evaluate_binary_field_op};
 use black_box::evaluate_black_box;
 
+pub use limits::{ExecutionLimits, Resource};
 pub use memory::Memory;
 use num_bigint::BigUint;
 pub use registers::Registers;
@@ -40,7 +42,7 @@ pub enum VMStatus {
     Finished,
     InProgress,
     Failure {
-        message: String,
+        reason: FailureReason,
         call_stack: ErrorCallStack,
     },
     /// The VM process is not solvable as a [foreign call][Opcode::ForeignCall] has been
@@ -58,6 +60,26 @@ pub enum VMStatus {
     },
 }
 
+/// The reason for which a Brillig process has failed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FailureReason {
+    /// The bytecode hit a [trap][Opcode::Trap] or otherwise reached an invalid state.
+    RuntimeError { message: String },
+    /// The process exceeded one of its [execution limits][ExecutionLimits].
+    ResourceExhausted { resource: Resource, limit: usize },
+}
+
+impl std::fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureReason::RuntimeError { message } => write!(f, "{message}"),
+            FailureReason::ResourceExhausted { resource, limit } => {
+                write!(f, "exceeded the limit of {limit} {resource}")
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// VM encapsulates the state of the Brillig VM during execution.
 pub struct VM<'a, B: BlackBoxFunctionSolver> {
@@ -81,6 +103,12 @@ pub struct VM<'a, B: BlackBoxFunctionSolver> {
     call_stack: Vec<Value>,
     /// The solver for blackbox functions
     black_box_solver: &'a B,
+    /// Limits on the resources which the VM may consume
+    limits: ExecutionLimits,
+    /// The number of opcodes which have been executed so far
+    step_count: usize,
+    /// The number of times each opcode has been executed, if profiling is enabled
+    opcode_counts: Option<Vec<usize>>,
 }
 
 impl<'a, B: BlackBoxFunctionSolver> VM<'a, B> {
@@ -102,9 +130,25 @@ impl<'a, B: BlackBoxFunctionSolver> VM<'a, B> {
             memory: memory.into(),
             call_stack: Vec::new(),
             black_box_solver,
+            limits: ExecutionLimits::UNLIMITED,
+            step_count: 0,
+            opcode_counts: None,
         }
     }
 
+    /// Sets the [ExecutionLimits] which the VM will enforce during execution.
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self.memory.set_max_size(limits.max_memory);
+        self
+    }
+
+    /// Enables counting the number of times each opcode in the bytecode is executed.
+    pub fn with_profiling(mut self) -> Self {
+        self.opcode_counts = Some(vec![0; self.bytecode.len()]);
+        self
+    }
+
     /// Updates the current status of the VM.
     /// Returns the given status.
     fn status(&mut self, status: VMStatus) -> VMStatus {
@@ -146,10 +190,25 @@ impl<'a, B: BlackBoxFunctionSolver> VM<'a, B> {
         let mut error_stack: Vec<_> =
             self.call_stack.iter().map(|value| value.to_usize()).collect();
         error_stack.push(self.program_counter);
-        self.status(VMStatus::Failure { call_stack: error_stack, message });
+        self.status(VMStatus::Failure {
+            call_stack: error_stack,
+            reason: FailureReason::RuntimeError { message },
+        });
         self.status.clone()
     }
 
+    /// Sets the current status of the VM to `fail` due to
+    /// the VM exceeding its limit on `resource`.
+    fn exhaust(&mut self, resource: Resource, limit: usize) -> VMStatus {
+        let mut error_stack: Vec<_> =
+            self.call_stack.iter().map(|value| value.to_usize()).collect();
+        error_stack.push(self.program_counter);
+        self.status(VMStatus::Failure {
+            call_stack: error_stack,
+            reason: FailureReason::ResourceExhausted { resource, limit },
+        })
+    }
+
     /// Loop over the bytecode and update the program counter
     pub fn process_opcodes(&mut self) -> VMStatus {
         while !matches!(
@@ -176,8 +235,54 @@ impl<'a, B: BlackBoxFunctionSolver> VM<'a, B> {
         self.memory.write(ptr, value);
     }
 
+    /// Returns the number of opcodes which have been executed so far.
+    pub fn step_count(&self) -> usize {
+        self.step_count
+    }
+
+    /// Returns the number of foreign calls which have been resolved so far.
+    pub fn foreign_call_count(&self) -> usize {
+        self.foreign_call_counter
+    }
+
+    /// Returns the number of times each opcode has been executed, indexed by program counter.
+    ///
+    /// This is only tracked once [profiling][VM::with_profiling] has been enabled.
+    pub fn opcode_counts(&self) -> Option<&[usize]> {
+        self.opcode_counts.as_deref()
+    }
+
     /// Process a single opcode and modify the program counter.
+    ///
+    /// Fails with [FailureReason::ResourceExhausted] if doing so would exceed the VM's [ExecutionLimits].
     pub fn process_opcode(&mut self) -> VMStatus {
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.step_count >= max_steps {
+                return self.exhaust(Resource::Steps, max_steps);
+            }
+        }
+
+        let program_counter = self.program_counter;
+        let status = self.execute_opcode();
+        // An opcode which is waiting on a foreign call will be executed again once it has been resolved.
+        if !matches!(status, VMStatus::ForeignCallWait { .. }) {
+            self.step_count += 1;
+            if let Some(opcode_counts) = &mut self.opcode_counts {
+                opcode_counts[program_counter] += 1;
+            }
+        }
+
+        // Writes which would grow the memory past its limit are refused before any memory is
+        // allocated, so the failure is reported against the opcode which attempted the write.
+        if self.memory.is_exhausted() && !matches!(status, VMStatus::Failure { .. }) {
+            self.program_counter = program_counter;
+            let max_memory = self.limits.max_memory.unwrap_or_default();
+            return self.exhaust(Resource::Memory, max_memory);
+        }
+        status
+    }
+
+    fn execute_opcode(&mut self) -> VMStatus {
         let opcode = &self.bytecode[self.program_counter];
         match opcode {
             Opcode::BinaryFieldOp { op, lhs, rhs, destination: result } => {
@@ -225,6 +330,11 @@ impl<'a, B: BlackBoxFunctionSolver> VM<'a, B> {
                     // resolved inputs back to the caller. Once the caller pushes to `foreign_call_results`,
                     // they can then make another call to the VM that starts at this opcode
                     // but has the necessary results to proceed with execution.
+                    if let Some(max_foreign_calls) = self.limits.max_foreign_calls {
+                        if self.foreign_call_counter >= max_foreign_calls {
+                            return self.exhaust(Resource::ForeignCalls, max_foreign_calls);
+                        }
+                    }
                     let resolved_inputs = inputs
                         .iter()
                         .map(|input| self.get_register_value_or_memory_values(*input))
@@ -455,6 +565,8 @@ impl BlackBoxFunctionSolver for DummyBlackBoxSolver {
 
 #[cfg(test)]
 mod tests {
+    use acir::brillig::BlackBoxOp;
+
     use super::*;
 
     #[test]
@@ -581,7 +693,9 @@ mod tests {
         assert_eq!(
             status,
             VMStatus::Failure {
-                message: "explicit trap hit in brillig".to_string(),
+                reason: FailureReason::RuntimeError {
+                    message: "explicit trap hit in brillig".to_string()
+                },
                 call_stack: vec![1]
             }
         );
@@ -1254,4 +1368,157 @@ mod tests {
         // Ensure the foreign call counter has been incremented
         assert_eq!(vm.foreign_call_counter, 1);
     }
+
+    #[test]
+    fn step_limit_aborts_infinite_loop() {
+        let opcodes = [Opcode::Jump { location: 0 }];
+        let limits = ExecutionLimits { max_steps: Some(10), ..ExecutionLimits::UNLIMITED };
+        let mut vm = VM::new(empty_registers(), vec![], &opcodes, vec![], &DummyBlackBoxSolver)
+            .with_limits(limits);
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                reason: FailureReason::ResourceExhausted { resource: Resource::Steps, limit: 10 },
+                call_stack: vec![0]
+            }
+        );
+        assert_eq!(vm.step_count(), 10);
+    }
+
+    #[test]
+    fn memory_limit_aborts_large_store() {
+        let r_pointer = RegisterIndex::from(0);
+        let r_value = RegisterIndex::from(1);
+        let opcodes = [
+            Opcode::Const { destination: r_pointer, value: Value::from(1024u128) },
+            Opcode::Const { destination: r_value, value: Value::from(1u128) },
+            Opcode::Store { destination_pointer: r_pointer, source: r_value },
+        ];
+        let limits = ExecutionLimits { max_memory: Some(16), ..ExecutionLimits::UNLIMITED };
+        let mut vm = VM::new(empty_registers(), vec![], &opcodes, vec![], &DummyBlackBoxSolver)
+            .with_limits(limits);
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                reason: FailureReason::ResourceExhausted { resource: Resource::Memory, limit: 16 },
+                call_stack: vec![2]
+            }
+        );
+        assert!(vm.get_memory().is_empty());
+    }
+
+    #[test]
+    fn memory_limit_aborts_black_box_output_before_allocating() {
+        let r_message_pointer = RegisterIndex::from(0);
+        let r_message_size = RegisterIndex::from(1);
+        let r_output_pointer = RegisterIndex::from(2);
+        let opcodes = [
+            Opcode::Const { destination: r_message_pointer, value: Value::from(0u128) },
+            Opcode::Const { destination: r_message_size, value: Value::from(0u128) },
+            Opcode::Const { destination: r_output_pointer, value: Value::from(1u128 << 40) },
+            Opcode::BlackBox(BlackBoxOp::Sha256 {
+                message: HeapVector { pointer: r_message_pointer, size: r_message_size },
+                output: HeapArray { pointer: r_output_pointer, size: 32 },
+            }),
+        ];
+        let limits = ExecutionLimits { max_memory: Some(64), ..ExecutionLimits::UNLIMITED };
+        let mut vm = VM::new(empty_registers(), vec![], &opcodes, vec![], &DummyBlackBoxSolver)
+            .with_limits(limits);
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                reason: FailureReason::ResourceExhausted { resource: Resource::Memory, limit: 64 },
+                call_stack: vec![3]
+            }
+        );
+        assert!(vm.get_memory().is_empty());
+    }
+
+    #[test]
+    fn foreign_call_limit_aborts_before_waiting() {
+        let opcodes = [Opcode::ForeignCall {
+            function: "oracle".into(),
+            destinations: vec![],
+            inputs: vec![],
+        }];
+        let limits = ExecutionLimits { max_foreign_calls: Some(0), ..ExecutionLimits::UNLIMITED };
+        let mut vm = VM::new(empty_registers(), vec![], &opcodes, vec![], &DummyBlackBoxSolver)
+            .with_limits(limits);
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                reason: FailureReason::ResourceExhausted {
+                    resource: Resource::ForeignCalls,
+                    limit: 0
+                },
+                call_stack: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn step_count_excludes_foreign_call_waits() {
+        let r_result = RegisterIndex::from(0);
+        let opcodes = [
+            Opcode::ForeignCall {
+                function: "oracle".into(),
+                destinations: vec![RegisterOrMemory::RegisterIndex(r_result)],
+                inputs: vec![],
+            },
+            Opcode::Stop,
+        ];
+        let mut vm = brillig_execute_and_get_vm(vec![], &opcodes);
+        assert!(matches!(vm.status, VMStatus::ForeignCallWait { .. }));
+        assert_eq!(vm.step_count(), 0);
+
+        vm.resolve_foreign_call(Value::from(1u128).into());
+        brillig_execute(&mut vm);
+
+        assert_eq!(vm.status, VMStatus::Finished);
+        assert_eq!(vm.step_count(), 2);
+    }
+
+    #[test]
+    fn profiling_counts_executions_of_each_opcode() {
+        let r_counter = RegisterIndex::from(0);
+        let r_one = RegisterIndex::from(1);
+        let r_limit = RegisterIndex::from(2);
+        let r_done = RegisterIndex::from(3);
+        let opcodes = [
+            Opcode::Const { destination: r_one, value: Value::from(1u128) },
+            Opcode::Const { destination: r_limit, value: Value::from(3u128) },
+            // Loop body: increment the counter until it reaches the limit
+            Opcode::BinaryIntOp {
+                destination: r_counter,
+                op: BinaryIntOp::Add,
+                bit_size: 32,
+                lhs: r_counter,
+                rhs: r_one,
+            },
+            Opcode::BinaryIntOp {
+                destination: r_done,
+                op: BinaryIntOp::Equals,
+                bit_size: 32,
+                lhs: r_counter,
+                rhs: r_limit,
+            },
+            Opcode::JumpIfNot { condition: r_done, location: 2 },
+            Opcode::Stop,
+        ];
+        let mut vm = VM::new(empty_registers(), vec![], &opcodes, vec![], &DummyBlackBoxSolver)
+            .with_profiling();
+
+        let status = vm.process_opcodes();
+        assert_eq!(status, VMStatus::Finished);
+        assert_eq!(vm.opcode_counts(), Some([1, 1, 3, 3, 3, 1].as_slice()));
+        assert_eq!(vm.step_count(), 12);
+    }
 }
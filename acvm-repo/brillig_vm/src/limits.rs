@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+/// A resource consumed by a Brillig process which may be capped through [ExecutionLimits].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Resource {
+    /// The number of opcodes executed.
+    Steps,
+    /// The number of memory slots allocated.
+    Memory,
+    /// The number of foreign calls made.
+    ForeignCalls,
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::Steps => write!(f, "executed opcodes"),
+            Resource::Memory => write!(f, "memory slots"),
+            Resource::ForeignCalls => write!(f, "foreign calls"),
+        }
+    }
+}
+
+/// Limits on the resources which a Brillig process may consume before it is aborted.
+///
+/// Brillig bytecode is not constrained, so a buggy unconstrained function can loop forever or
+/// allocate without bound. Setting these limits turns such cases into a deterministic
+/// [failure][crate::FailureReason::ResourceExhausted] instead.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    /// The maximum number of opcodes which may be executed.
+    pub max_steps: Option<usize>,
+    /// The maximum number of memory slots which may be allocated.
+    pub max_memory: Option<usize>,
+    /// The maximum number of foreign calls which may be made.
+    pub max_foreign_calls: Option<usize>,
+}
+
+impl ExecutionLimits {
+    /// Limits which never abort execution.
+    pub const UNLIMITED: ExecutionLimits =
+        ExecutionLimits { max_steps: None, max_memory: None, max_foreign_calls: None };
+
+    /// Returns the limits left over once `steps` opcodes have been executed and
+    /// `foreign_calls` foreign calls have been made.
+    ///
+    /// The memory limit applies to each process individually so is left unchanged.
+    pub fn remaining(&self, steps: usize, foreign_calls: usize) -> ExecutionLimits {
+        ExecutionLimits {
+            max_steps: self.max_steps.map(|max| max.saturating_sub(steps)),
+            max_memory: self.max_memory,
+            max_foreign_calls: self.max_foreign_calls.map(|max| max.saturating_sub(foreign_calls)),
+        }
+    }
+
+    /// Returns the limit on `resource`, if there is one.
+    pub fn limit(&self, resource: Resource) -> Option<usize> {
+        match resource {
+            Resource::Steps => self.max_steps,
+            Resource::Memory => self.max_memory,
+            Resource::ForeignCalls => self.max_foreign_calls,
+        }
+    }
+}
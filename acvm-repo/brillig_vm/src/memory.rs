@@ -5,11 +5,15 @@ pub struct Memory {
     // Memory is a vector of values.
     // We grow the memory when values past the end are set, extending with 0s.
     inner: Vec<Value>,
+    // The number of values past which the memory may not grow, if it is limited.
+    max_size: Option<usize>,
+    // Set once a write has been refused for growing the memory past `max_size`.
+    exhausted: bool,
 }
 
 impl From<Vec<Value>> for Memory {
     fn from(values: Vec<Value>) -> Self {
-        Memory { inner: values }
+        Memory { inner: values, max_size: None, exhausted: false }
     }
 }
 
@@ -29,15 +33,32 @@ impl Memory {
     }
 
     /// Sets the values after pointer `ptr` to `values`
+    ///
+    /// If this would grow the memory past its maximum size then nothing is written
+    /// and the memory is marked as [exhausted][Memory::is_exhausted] instead.
     pub fn write_slice(&mut self, ptr: usize, values: &[Value]) {
         // Calculate new memory size
-        let new_size = std::cmp::max(self.inner.len(), ptr + values.len());
+        let new_size = std::cmp::max(self.inner.len(), ptr.saturating_add(values.len()));
+        if matches!(self.max_size, Some(max_size) if new_size > max_size) {
+            self.exhausted = true;
+            return;
+        }
         // Expand memory to new size with default values if needed
         self.inner.resize(new_size, Value::from(0_usize));
 
         self.inner[ptr..ptr + values.len()].copy_from_slice(values);
     }
 
+    /// Limits the number of values which the memory may grow to hold.
+    pub fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.max_size = max_size;
+    }
+
+    /// Returns true if a write has been refused for growing the memory past its maximum size.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
     /// Returns the values of the memory
     pub fn values(&self) -> &Vec<Value> {
         &self.inner
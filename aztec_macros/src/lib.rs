@@ -3,8 +3,8 @@ use iter_extended::vecmap;
 use noirc_frontend::macros_api::FieldElement;
 use noirc_frontend::macros_api::{
     BlockExpression, CallExpression, CastExpression, Distinctness, Expression, ExpressionKind,
-    ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility,
-    HirContext, HirExpression, HirLiteral, HirStatement, Ident, ImportStatement, IndexExpression,
+    ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, HirContext, HirExpression,
+    HirLiteral, HirStatement, Ident, ImportStatement, IndexExpression, ItemVisibility,
     LetStatement, Literal, MemberAccessExpression, MethodCallExpression, NoirFunction, NoirStruct,
     Param, Path, PathKind, Pattern, PrefixExpression, SecondaryAttribute, Signedness, Span,
     Statement, StatementKind, StructType, Type, TypeImpl, UnaryOp, UnresolvedType,
@@ -17,6 +17,10 @@ use noirc_frontend::macros_api::{ModuleDefId, NodeInterner, SortedModule, Struct
 pub struct AztecMacro;
 
 impl MacroProcessor for AztecMacro {
+    fn name(&self) -> &'static str {
+        "aztec"
+    }
+
     fn process_untyped_ast(
         &self,
         ast: SortedModule,
@@ -26,8 +30,13 @@ impl MacroProcessor for AztecMacro {
         transform(ast, crate_id, context)
     }
 
-    fn process_typed_ast(&self, crate_id: &CrateId, context: &mut HirContext) {
-        transform_hir(crate_id, context)
+    fn process_typed_ast(
+        &self,
+        crate_id: &CrateId,
+        context: &mut HirContext,
+    ) -> Vec<(MacroError, FileId)> {
+        transform_hir(crate_id, context);
+        Vec::new()
     }
 }
 
@@ -183,7 +192,7 @@ fn index_array_variable(array: Expression, index: &str) -> Expression {
 }
 
 fn import(path: Path) -> ImportStatement {
-    ImportStatement { path, alias: None }
+    ImportStatement { path, alias: None, attributes: Vec::new() }
 }
 
 //
@@ -509,7 +518,7 @@ fn generate_selector_impl(structure: &NoirStruct) -> TypeImpl {
         &FunctionReturnType::Ty(make_type(UnresolvedTypeData::FieldElement)),
     );
 
-    selector_fn_def.visibility = FunctionVisibility::Public;
+    selector_fn_def.visibility = ItemVisibility::Public;
 
     // Seems to be necessary on contract modules
     selector_fn_def.return_visibility = Visibility::Public;
@@ -2,8 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 use acvm::acir::circuit::Circuit;
+use acvm::blackbox_solver::keccak256;
 use fm::FileId;
-use noirc_abi::{Abi, ContractEvent};
+use noirc_abi::{Abi, AbiType, ContractEvent, Sign};
 use noirc_errors::debug_info::DebugInfo;
 use noirc_evaluator::errors::SsaReport;
 
@@ -59,6 +60,9 @@ pub struct ContractFunction {
 
     pub is_internal: bool,
 
+    /// The selector used to dispatch calls to this function, see [`function_selector`].
+    pub selector: u32,
+
     pub abi: Abi,
 
     #[serde(
@@ -79,3 +83,73 @@ impl ContractFunctionType {
         }
     }
 }
+
+/// Computes the selector of a contract function: the first four bytes (read as big endian)
+/// of the keccak256 hash of its signature, e.g. `transfer(Field,[u8;32])`.
+///
+/// Struct and tuple parameters are written as a parenthesized list of their field types.
+pub fn function_selector(name: &str, abi: &Abi) -> u32 {
+    let parameters: Vec<_> =
+        abi.parameters.iter().map(|param| signature_type(&param.typ)).collect();
+    let signature = format!("{name}({})", parameters.join(","));
+
+    let hash = keccak256(signature.as_bytes()).expect("keccak256 cannot fail on byte inputs");
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+fn signature_type(typ: &AbiType) -> String {
+    match typ {
+        AbiType::Field => "Field".to_string(),
+        AbiType::Boolean => "bool".to_string(),
+        AbiType::Integer { sign: Sign::Unsigned, width } => format!("u{width}"),
+        AbiType::Integer { sign: Sign::Signed, width } => format!("i{width}"),
+        AbiType::Array { length, typ } => format!("[{};{length}]", signature_type(typ)),
+        AbiType::String { length } => format!("str<{length}>"),
+        AbiType::Struct { fields, .. } => {
+            let fields: Vec<_> = fields.iter().map(|(_, typ)| signature_type(typ)).collect();
+            format!("({})", fields.join(","))
+        }
+        AbiType::Tuple { fields } => {
+            let fields: Vec<_> = fields.iter().map(signature_type).collect();
+            format!("({})", fields.join(","))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noirc_abi::{Abi, AbiParameter, AbiType, AbiVisibility, Sign};
+
+    use super::function_selector;
+
+    fn abi_with_parameters(types: Vec<AbiType>) -> Abi {
+        let parameters = types
+            .into_iter()
+            .enumerate()
+            .map(|(index, typ)| AbiParameter {
+                name: format!("param{index}"),
+                typ,
+                visibility: AbiVisibility::Private,
+            })
+            .collect();
+        Abi {
+            parameters,
+            param_witnesses: Default::default(),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn selector_depends_on_name_and_parameter_types() {
+        let field_abi = abi_with_parameters(vec![AbiType::Field]);
+        let integer_abi =
+            abi_with_parameters(vec![AbiType::Integer { sign: Sign::Unsigned, width: 32 }]);
+
+        // The first four bytes of keccak256("transfer(Field)")
+        let selector = function_selector("transfer", &field_abi);
+        assert_eq!(selector, 0x00fe2c6e);
+        assert_ne!(selector, function_selector("mint", &field_abi));
+        assert_ne!(selector, function_selector("transfer", &integer_abi));
+    }
+}
@@ -17,6 +17,7 @@ use noirc_frontend::macros_api::MacroProcessor;
 use noirc_frontend::monomorphization::monomorphize;
 use noirc_frontend::node_interner::FuncId;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use tracing::info;
 
@@ -28,7 +29,7 @@ mod stdlib;
 
 use debug::filter_relevant_files;
 
-pub use contract::{CompiledContract, ContractFunction, ContractFunctionType};
+pub use contract::{function_selector, CompiledContract, ContractFunction, ContractFunctionType};
 pub use debug::DebugFile;
 pub use program::CompiledProgram;
 
@@ -52,6 +53,24 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub show_brillig: bool,
 
+    /// Skip optimizing the generated Brillig bytecode
+    #[arg(long, hide = true)]
+    pub disable_brillig_optimizer: bool,
+
+    /// Re-execute the program with an SSA interpreter after each optimization pass,
+    /// failing on the first pass which changes its behavior
+    #[arg(long, hide = true)]
+    pub check_ssa_passes: bool,
+
+    /// Check that the SSA is well-formed after each optimization pass,
+    /// failing on the first pass which leaves it malformed
+    #[arg(long, hide = true)]
+    pub validate_ssa: bool,
+
+    /// Record why each ACIR opcode was generated, to be reported by `nargo info`
+    #[arg(long)]
+    pub remarks: bool,
+
     /// Display the ACIR for compiled circuit
     #[arg(long)]
     pub print_acir: bool,
@@ -71,6 +90,14 @@ pub struct CompileOptions {
     /// Disables the builtin macros being used in the compiler
     #[arg(long, hide = true)]
     pub disable_macros: bool,
+
+    /// Only run the given macro plugins, by name. All builtin and registered plugins run by default
+    #[arg(long, value_delimiter = ',', conflicts_with = "disable_macros")]
+    pub macros: Option<Vec<String>>,
+
+    /// Use the compilation settings from the `[profile.<PROFILE>]` section of `Nargo.toml`
+    #[arg(long)]
+    pub profile: Option<String>,
 }
 
 /// Helper type used to signify where only warnings are expected in file diagnostics
@@ -157,6 +184,55 @@ pub fn add_dep(
         .expect("cyclic dependency triggered");
 }
 
+/// Returns the macro plugins which are built into the compiler.
+pub fn builtin_macros() -> Vec<&'static dyn MacroProcessor> {
+    vec![&aztec_macros::AztecMacro]
+}
+
+/// Returns the builtin macro plugins along with those registered with the context.
+pub fn available_macros(context: &Context) -> Vec<&'static dyn MacroProcessor> {
+    let mut macros = builtin_macros();
+    macros.extend_from_slice(context.macro_processors());
+    macros
+}
+
+/// Returns the available macro plugins selected by `options` for compiling `crate_id`.
+///
+/// Unless macros are disabled, these are the plugins named by `options.macros`,
+/// or every available plugin if no names were given.
+pub fn select_macros(
+    context: &Context,
+    crate_id: CrateId,
+    options: &CompileOptions,
+) -> Result<Vec<&'static dyn MacroProcessor>, ErrorsAndWarnings> {
+    if options.disable_macros {
+        return Ok(Vec::new());
+    }
+
+    let available_macros = available_macros(context);
+    let names = match &options.macros {
+        Some(names) => names,
+        None => return Ok(available_macros),
+    };
+
+    let mut macros = Vec::new();
+    for name in names {
+        match available_macros.iter().find(|plugin| plugin.name() == name.as_str()) {
+            Some(plugin) => macros.push(*plugin),
+            None => {
+                let available = vecmap(&available_macros, |plugin| plugin.name()).join(", ");
+                let message = format!(
+                    "Unknown macro plugin `{name}`, the available plugins are: {available}"
+                );
+                // The plugin isn't named in the source, so the error is reported without a span
+                let root_file_id = context.crate_graph[crate_id].root_file_id;
+                return Err(vec![CustomDiagnostic::from_message(&message).in_file(root_file_id)]);
+            }
+        }
+    }
+    Ok(macros)
+}
+
 /// Run the lexing, parsing, name resolution, and type checking passes.
 ///
 /// This returns a (possibly empty) vector of any warnings found on success.
@@ -168,14 +244,22 @@ pub fn check_crate(
     deny_warnings: bool,
     disable_macros: bool,
 ) -> CompilationResult<()> {
-    let macros: Vec<&dyn MacroProcessor> = if disable_macros {
-        vec![]
-    } else {
-        vec![&aztec_macros::AztecMacro as &dyn MacroProcessor]
-    };
+    let macros = if disable_macros { Vec::new() } else { available_macros(context) };
+    check_crate_with_macros(context, crate_id, deny_warnings, &macros)
+}
 
+/// Same as [check_crate], running the given macro plugins on each crate instead of the available ones.
+///
+/// This allows tools built on top of the compiler to provide their own plugins.
+#[tracing::instrument(level = "trace", skip(context, macros))]
+pub fn check_crate_with_macros(
+    context: &mut Context,
+    crate_id: CrateId,
+    deny_warnings: bool,
+    macros: &[&dyn MacroProcessor],
+) -> CompilationResult<()> {
     let mut errors = vec![];
-    let diagnostics = CrateDefMap::collect_defs(crate_id, context, macros);
+    let diagnostics = CrateDefMap::collect_defs(crate_id, context, macros.to_vec());
     errors.extend(diagnostics.into_iter().map(|(error, file_id)| {
         let diagnostic: CustomDiagnostic = error.into();
         diagnostic.in_file(file_id)
@@ -208,8 +292,9 @@ pub fn compile_main(
     cached_program: Option<CompiledProgram>,
     force_compile: bool,
 ) -> CompilationResult<CompiledProgram> {
+    let macros = select_macros(context, crate_id, options)?;
     let (_, mut warnings) =
-        check_crate(context, crate_id, options.deny_warnings, options.disable_macros)?;
+        check_crate_with_macros(context, crate_id, options.deny_warnings, &macros)?;
 
     let main = context.get_main_function(&crate_id).ok_or_else(|| {
         // TODO(#2155): This error might be a better to exist in Nargo
@@ -242,8 +327,8 @@ pub fn compile_contract(
     crate_id: CrateId,
     options: &CompileOptions,
 ) -> CompilationResult<CompiledContract> {
-    let (_, warnings) =
-        check_crate(context, crate_id, options.deny_warnings, options.disable_macros)?;
+    let macros = select_macros(context, crate_id, options)?;
+    let (_, warnings) = check_crate_with_macros(context, crate_id, options.deny_warnings, &macros)?;
 
     // TODO: We probably want to error if contracts is empty
     let contracts = context.get_all_contracts(&crate_id);
@@ -308,6 +393,8 @@ fn compile_contract_inner(
     let mut functions = Vec::new();
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
+    // The name of the function using each selector, as calls are dispatched by selector
+    let mut selectors: HashMap<u32, String> = HashMap::new();
     for contract_function in &contract.functions {
         let function_id = contract_function.function_id;
         let is_entry_point = contract_function.is_entry_point;
@@ -337,7 +424,20 @@ fn compile_contract_inner(
 
         let function_type = ContractFunctionType::new(func_type, modifiers.is_unconstrained);
 
+        let selector = function_selector(&name, &function.abi);
+        if let Some(existing) = selectors.insert(selector, name.clone()) {
+            let location = context.def_interner.function_meta(&function_id).name.location;
+            let error = CustomDiagnostic::simple_error(
+                format!("Contract function `{name}` has the same selector as `{existing}`"),
+                format!("selector {selector:#010x} is already used by `{existing}`"),
+                location.span,
+            );
+            errors.push(error.in_file(location.file));
+            continue;
+        }
+
         functions.push(ContractFunction {
+            selector,
             name,
             function_type,
             is_internal: modifiers.is_internal.unwrap_or(false),
@@ -385,21 +485,34 @@ pub fn compile_no_check(
 ) -> Result<CompiledProgram, RuntimeError> {
     let program = monomorphize(main_function, &context.def_interner);
 
-    let hash = fxhash::hash64(&program);
+    // Options which change the generated artifact are part of the hash, so that an artifact
+    // compiled with them is never reused by a compilation without them and vice versa.
+    let hash = fxhash::hash64(&(&program, options.disable_brillig_optimizer, options.remarks));
     let hashes_match = cached_program.as_ref().map_or(false, |program| program.hash == hash);
 
     // If user has specified that they want to see intermediate steps printed then we should
     // force compilation even if the program hasn't changed.
-    let force_compile =
-        force_compile || options.print_acir || options.show_brillig || options.show_ssa;
+    let force_compile = force_compile
+        || options.print_acir
+        || options.show_brillig
+        || options.show_ssa
+        || options.check_ssa_passes
+        || options.validate_ssa;
 
     if !force_compile && hashes_match {
         info!("Program matches existing artifact, returning early");
         return Ok(cached_program.expect("cache must exist for hashes to match"));
     }
     let visibility = program.return_visibility;
-    let (circuit, debug, input_witnesses, return_witnesses, warnings) =
-        create_circuit(program, options.show_ssa, options.show_brillig)?;
+    let (circuit, debug, input_witnesses, return_witnesses, warnings) = create_circuit(
+        program,
+        options.show_ssa,
+        options.show_brillig,
+        !options.disable_brillig_optimizer,
+        options.check_ssa_passes,
+        options.validate_ssa,
+        options.remarks,
+    )?;
 
     let abi =
         abi_gen::gen_abi(context, &main_function, input_witnesses, return_witnesses, visibility);
@@ -415,3 +528,103 @@ pub fn compile_no_check(
         warnings,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use fm::FileId;
+    use noirc_frontend::hir::Context;
+    use noirc_frontend::macros_api::{CrateId, MacroError, MacroProcessor, SortedModule};
+    use noirc_frontend::parse_program;
+
+    use super::{
+        compile_main, compile_no_check, file_manager_with_stdlib, prepare_crate, select_macros,
+    };
+    use crate::CompileOptions;
+
+    struct TestMacro;
+
+    impl MacroProcessor for TestMacro {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn process_untyped_ast(
+            &self,
+            mut ast: SortedModule,
+            _crate_id: &CrateId,
+            _context: &Context,
+        ) -> Result<SortedModule, (MacroError, FileId)> {
+            let (generated, _) = parse_program("fn generated() -> Field { 1 }");
+            ast.functions.extend(generated.into_sorted().functions);
+            Ok(ast)
+        }
+
+        fn process_typed_ast(
+            &self,
+            _crate_id: &CrateId,
+            _context: &mut Context,
+        ) -> Vec<(MacroError, FileId)> {
+            Vec::new()
+        }
+    }
+
+    fn prepare_context(source: &str) -> (Context<'static>, CrateId) {
+        let mut file_manager = file_manager_with_stdlib(Path::new(""));
+        file_manager.add_file_with_source(Path::new("main.nr"), source.to_string());
+
+        let mut context = Context::new(file_manager);
+        let crate_id = prepare_crate(&mut context, Path::new("main.nr"));
+        (context, crate_id)
+    }
+
+    #[test]
+    fn registered_macros_are_run_when_compiling() {
+        let (mut context, crate_id) = prepare_context("fn main() { assert(generated() == 1); }");
+        context.register_macro_processor(&TestMacro);
+
+        let options =
+            CompileOptions { macros: Some(vec!["test".to_string()]), ..Default::default() };
+        let result = compile_main(&mut context, crate_id, &options, None, false);
+        assert!(result.is_ok(), "Expected the generated function to be found");
+    }
+
+    #[test]
+    fn artifacts_are_not_reused_across_options_changing_the_output() {
+        let (mut context, crate_id) =
+            prepare_context("unconstrained fn main(x: u32) -> pub u32 { x + 1 }");
+
+        let options = CompileOptions::default();
+        let optimized = compile_main(&mut context, crate_id, &options, None, false).unwrap().0;
+        let main = context.get_main_function(&crate_id).unwrap();
+
+        let unoptimized_options =
+            CompileOptions { disable_brillig_optimizer: true, ..Default::default() };
+        let unoptimized =
+            compile_no_check(&context, &unoptimized_options, main, Some(optimized.clone()), false)
+                .unwrap();
+        assert_ne!(optimized.hash, unoptimized.hash);
+
+        // The unoptimized artifact must not be returned as a cache hit
+        let recompiled =
+            compile_no_check(&context, &options, main, Some(unoptimized), false).unwrap();
+        assert_eq!(recompiled.hash, optimized.hash);
+    }
+
+    #[test]
+    fn unknown_macros_are_reported_without_a_span() {
+        let (mut context, crate_id) = prepare_context("fn main() {}");
+        context.register_macro_processor(&TestMacro);
+
+        let options =
+            CompileOptions { macros: Some(vec!["unknown".to_string()]), ..Default::default() };
+        let errors = match select_macros(&context, crate_id, &options) {
+            Ok(_) => panic!("Expected the unknown plugin to be reported"),
+            Err(errors) => errors,
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].diagnostic.secondaries.is_empty());
+        assert!(errors[0].diagnostic.message.ends_with("aztec, test"));
+    }
+}
@@ -18,6 +18,17 @@ pub struct DebugInfo {
     /// that they should be serialized to/from strings.
     #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
     pub locations: BTreeMap<OpcodeLocation, Vec<Location>>,
+
+    /// The location of the definition of each function in the program along with its name,
+    /// used to attribute opcodes to the functions they were generated from.
+    #[serde(default)]
+    pub functions: Vec<(Location, String)>,
+
+    /// Map opcode index of an ACIR circuit into a remark explaining why it was generated.
+    /// This is only recorded when compiling with `--remarks`.
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub remarks: BTreeMap<OpcodeLocation, String>,
 }
 
 /// Holds OpCodes Counts for Acir and Brillig Opcodes
@@ -29,8 +40,12 @@ pub struct OpCodesCount {
 }
 
 impl DebugInfo {
-    pub fn new(locations: BTreeMap<OpcodeLocation, Vec<Location>>) -> Self {
-        DebugInfo { locations }
+    pub fn new(
+        locations: BTreeMap<OpcodeLocation, Vec<Location>>,
+        functions: Vec<(Location, String)>,
+        remarks: BTreeMap<OpcodeLocation, String>,
+    ) -> Self {
+        DebugInfo { locations, functions, remarks }
     }
 
     /// Updates the locations map when the [`Circuit`][acvm::acir::circuit::Circuit] is modified.
@@ -47,6 +62,14 @@ impl DebugInfo {
                 self.locations.insert(new_opcode_location, source_locations.clone());
             });
         }
+
+        let old_remarks = mem::take(&mut self.remarks);
+
+        for (old_opcode_location, remark) in old_remarks {
+            update_map.new_locations(old_opcode_location).for_each(|new_opcode_location| {
+                self.remarks.insert(new_opcode_location, remark.clone());
+            });
+        }
     }
 
     pub fn opcode_location(&self, loc: &OpcodeLocation) -> Option<Vec<Location>> {
@@ -86,4 +109,58 @@ impl DebugInfo {
 
         counted_opcodes
     }
+
+    /// Returns the name of the innermost function whose definition contains `location`.
+    pub fn function_name(&self, location: &Location) -> Option<&str> {
+        self.functions
+            .iter()
+            .filter(|(definition, _)| definition.contains(location))
+            .min_by_key(|(definition, _)| definition.span.end() - definition.span.start())
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Counts the opcodes generated by each function in the program, including those of functions
+    /// which were inlined into their callers, keyed by the function's name.
+    ///
+    /// An opcode is attributed to the innermost function containing the last location of its
+    /// call stack. Opcodes which cannot be attributed to a function are attributed to `main_name`.
+    pub fn count_function_opcodes(&self, main_name: &str) -> BTreeMap<String, OpCodesCount> {
+        let mut counted_opcodes: BTreeMap<String, OpCodesCount> = BTreeMap::new();
+        for (opcode_location, locations) in self.locations.iter() {
+            let function = locations
+                .last()
+                .and_then(|location| self.function_name(location))
+                .unwrap_or(main_name);
+            let opcodes_count = counted_opcodes.entry(function.to_owned()).or_default();
+            match opcode_location {
+                OpcodeLocation::Acir(_) => opcodes_count.acir_size += 1,
+                OpcodeLocation::Brillig { .. } => opcodes_count.brillig_size += 1,
+            }
+        }
+        counted_opcodes
+    }
+
+    /// Counts the opcodes with each remark.
+    pub fn count_remark_opcodes(&self) -> BTreeMap<String, usize> {
+        let mut counted_opcodes = BTreeMap::new();
+        for remark in self.remarks.values() {
+            *counted_opcodes.entry(remark.clone()).or_default() += 1;
+        }
+        counted_opcodes
+    }
+
+    /// Counts the opcodes with each remark generated by the source code at each location,
+    /// which is the innermost location of each opcode's call stack.
+    pub fn count_span_remark_opcodes(&self) -> HashMap<Location, BTreeMap<&str, usize>> {
+        let mut counted_opcodes: HashMap<Location, BTreeMap<&str, usize>> = HashMap::new();
+        for (opcode_location, remark) in self.remarks.iter() {
+            let location =
+                self.locations.get(opcode_location).and_then(|locations| locations.last());
+            if let Some(location) = location {
+                let remarks = counted_opcodes.entry(*location).or_default();
+                *remarks.entry(remark.as_str()).or_default() += 1;
+            }
+        }
+        counted_opcodes
+    }
 }
@@ -0,0 +1,242 @@
+//! The registry of error codes assigned to the compiler's diagnostics.
+//!
+//! Codes are stable: once assigned, a code always refers to the same kind of error
+//! and is never reused, even if the compiler stops emitting it. Some codes also have an
+//! extended explanation with an example of erroneous code and how to fix it, which is
+//! printed by `nargo explain`.
+
+/// Every error code along with its extended explanation, if one has been written.
+///
+/// The first digits of a code group it by the part of the compiler which reports it.
+const ERROR_CODES: &[(&str, Option<&str>)] = &[
+    // Parser errors
+    ("E0001", Some(include_str!("error_codes/E0001.md"))),
+    ("E0002", Some(include_str!("error_codes/E0002.md"))),
+    ("E0003", Some(include_str!("error_codes/E0003.md"))),
+    ("E0004", Some(include_str!("error_codes/E0004.md"))),
+    ("E0005", Some(include_str!("error_codes/E0005.md"))),
+    ("E0006", Some(include_str!("error_codes/E0006.md"))),
+    ("E0007", Some(include_str!("error_codes/E0007.md"))),
+    ("E0008", Some(include_str!("error_codes/E0008.md"))),
+    ("E0009", Some(include_str!("error_codes/E0009.md"))),
+    ("E0010", Some(include_str!("error_codes/E0010.md"))),
+    ("E0011", Some(include_str!("error_codes/E0011.md"))),
+    ("E0012", Some(include_str!("error_codes/E0012.md"))),
+    ("E0013", Some(include_str!("error_codes/E0013.md"))),
+    ("E0014", Some(include_str!("error_codes/E0014.md"))),
+    // Name resolution errors
+    ("E0101", Some(include_str!("error_codes/E0101.md"))),
+    ("E0102", Some(include_str!("error_codes/E0102.md"))),
+    ("E0103", Some(include_str!("error_codes/E0103.md"))),
+    ("E0104", Some(include_str!("error_codes/E0104.md"))),
+    ("E0105", Some(include_str!("error_codes/E0105.md"))),
+    ("E0106", Some(include_str!("error_codes/E0106.md"))),
+    // E0107 is no longer emitted.
+    ("E0107", None),
+    ("E0108", Some(include_str!("error_codes/E0108.md"))),
+    ("E0109", Some(include_str!("error_codes/E0109.md"))),
+    ("E0110", Some(include_str!("error_codes/E0110.md"))),
+    ("E0111", Some(include_str!("error_codes/E0111.md"))),
+    ("E0112", Some(include_str!("error_codes/E0112.md"))),
+    ("E0113", Some(include_str!("error_codes/E0113.md"))),
+    ("E0114", Some(include_str!("error_codes/E0114.md"))),
+    ("E0115", Some(include_str!("error_codes/E0115.md"))),
+    // E0116 is no longer emitted.
+    ("E0116", None),
+    ("E0117", Some(include_str!("error_codes/E0117.md"))),
+    ("E0118", Some(include_str!("error_codes/E0118.md"))),
+    ("E0119", Some(include_str!("error_codes/E0119.md"))),
+    ("E0120", Some(include_str!("error_codes/E0120.md"))),
+    // E0121 is no longer emitted: test functions may now take parameters.
+    ("E0121", None),
+    ("E0122", Some(include_str!("error_codes/E0122.md"))),
+    // E0123 is no longer emitted.
+    ("E0123", None),
+    ("E0124", Some(include_str!("error_codes/E0124.md"))),
+    ("E0125", Some(include_str!("error_codes/E0125.md"))),
+    ("E0126", Some(include_str!("error_codes/E0126.md"))),
+    ("E0127", Some(include_str!("error_codes/E0127.md"))),
+    ("E0128", Some(include_str!("error_codes/E0128.md"))),
+    ("E0129", Some(include_str!("error_codes/E0129.md"))),
+    ("E0130", Some(include_str!("error_codes/E0130.md"))),
+    ("E0131", Some(include_str!("error_codes/E0131.md"))),
+    ("E0132", Some(include_str!("error_codes/E0132.md"))),
+    ("E0133", Some(include_str!("error_codes/E0133.md"))),
+    // E0134 is no longer emitted: private struct fields are reported as E0234.
+    ("E0134", None),
+    ("E0135", Some(include_str!("error_codes/E0135.md"))),
+    ("E0136", Some(include_str!("error_codes/E0136.md"))),
+    // Type checking errors
+    // E0201 is no longer emitted.
+    ("E0201", None),
+    ("E0202", Some(include_str!("error_codes/E0202.md"))),
+    ("E0203", Some(include_str!("error_codes/E0203.md"))),
+    ("E0204", Some(include_str!("error_codes/E0204.md"))),
+    ("E0205", Some(include_str!("error_codes/E0205.md"))),
+    ("E0206", Some(include_str!("error_codes/E0206.md"))),
+    // E0207 is no longer emitted.
+    ("E0207", None),
+    ("E0208", Some(include_str!("error_codes/E0208.md"))),
+    ("E0209", Some(include_str!("error_codes/E0209.md"))),
+    ("E0210", Some(include_str!("error_codes/E0210.md"))),
+    ("E0211", Some(include_str!("error_codes/E0211.md"))),
+    ("E0212", Some(include_str!("error_codes/E0212.md"))),
+    ("E0213", Some(include_str!("error_codes/E0213.md"))),
+    ("E0214", Some(include_str!("error_codes/E0214.md"))),
+    ("E0215", Some(include_str!("error_codes/E0215.md"))),
+    ("E0216", Some(include_str!("error_codes/E0216.md"))),
+    ("E0217", Some(include_str!("error_codes/E0217.md"))),
+    ("E0218", Some(include_str!("error_codes/E0218.md"))),
+    ("E0219", Some(include_str!("error_codes/E0219.md"))),
+    ("E0220", Some(include_str!("error_codes/E0220.md"))),
+    ("E0221", Some(include_str!("error_codes/E0221.md"))),
+    ("E0222", Some(include_str!("error_codes/E0222.md"))),
+    ("E0223", Some(include_str!("error_codes/E0223.md"))),
+    ("E0224", Some(include_str!("error_codes/E0224.md"))),
+    ("E0225", Some(include_str!("error_codes/E0225.md"))),
+    ("E0226", Some(include_str!("error_codes/E0226.md"))),
+    ("E0227", Some(include_str!("error_codes/E0227.md"))),
+    ("E0228", Some(include_str!("error_codes/E0228.md"))),
+    ("E0229", Some(include_str!("error_codes/E0229.md"))),
+    ("E0230", Some(include_str!("error_codes/E0230.md"))),
+    ("E0231", Some(include_str!("error_codes/E0231.md"))),
+    ("E0232", Some(include_str!("error_codes/E0232.md"))),
+    ("E0233", Some(include_str!("error_codes/E0233.md"))),
+    ("E0234", Some(include_str!("error_codes/E0234.md"))),
+    // Errors found while generating ACIR
+    // E0301 is no longer emitted.
+    ("E0301", None),
+    // E0302 is only emitted for bugs in the compiler.
+    ("E0302", None),
+    ("E0303", Some(include_str!("error_codes/E0303.md"))),
+    // E0304 is only emitted for bugs in the compiler: integer types are at most 127 bits.
+    ("E0304", None),
+    ("E0305", Some(include_str!("error_codes/E0305.md"))),
+    ("E0306", Some(include_str!("error_codes/E0306.md"))),
+    // E0307 is no longer emitted.
+    ("E0307", None),
+    // E0308 is only emitted for bugs in the compiler: the lexer rejects larger types as E0505.
+    ("E0308", None),
+    ("E0309", Some(include_str!("error_codes/E0309.md"))),
+    ("E0310", Some(include_str!("error_codes/E0310.md"))),
+    ("E0311", Some(include_str!("error_codes/E0311.md"))),
+    ("E0312", Some(include_str!("error_codes/E0312.md"))),
+    // Definition collection errors
+    ("E0401", Some(include_str!("error_codes/E0401.md"))),
+    ("E0402", Some(include_str!("error_codes/E0402.md"))),
+    ("E0403", Some(include_str!("error_codes/E0403.md"))),
+    ("E0404", Some(include_str!("error_codes/E0404.md"))),
+    ("E0405", Some(include_str!("error_codes/E0405.md"))),
+    ("E0406", Some(include_str!("error_codes/E0406.md"))),
+    ("E0407", Some(include_str!("error_codes/E0407.md"))),
+    ("E0408", Some(include_str!("error_codes/E0408.md"))),
+    ("E0409", Some(include_str!("error_codes/E0409.md"))),
+    ("E0410", Some(include_str!("error_codes/E0410.md"))),
+    ("E0411", Some(include_str!("error_codes/E0411.md"))),
+    ("E0412", Some(include_str!("error_codes/E0412.md"))),
+    ("E0413", Some(include_str!("error_codes/E0413.md"))),
+    ("E0414", Some(include_str!("error_codes/E0414.md"))),
+    ("E0415", Some(include_str!("error_codes/E0415.md"))),
+    ("E0416", Some(include_str!("error_codes/E0416.md"))),
+    ("E0417", Some(include_str!("error_codes/E0417.md"))),
+    ("E0418", Some(include_str!("error_codes/E0418.md"))),
+    // Lexer errors
+    ("E0501", Some(include_str!("error_codes/E0501.md"))),
+    // E0502 is only emitted for bugs in the lexer.
+    ("E0502", None),
+    ("E0503", Some(include_str!("error_codes/E0503.md"))),
+    ("E0504", Some(include_str!("error_codes/E0504.md"))),
+    ("E0505", Some(include_str!("error_codes/E0505.md"))),
+    ("E0506", Some(include_str!("error_codes/E0506.md"))),
+    ("E0507", Some(include_str!("error_codes/E0507.md"))),
+    ("E0508", Some(include_str!("error_codes/E0508.md"))),
+    ("E0509", Some(include_str!("error_codes/E0509.md"))),
+    // Path resolution errors
+    ("E0601", Some(include_str!("error_codes/E0601.md"))),
+    ("E0602", Some(include_str!("error_codes/E0602.md"))),
+    ("E0603", Some(include_str!("error_codes/E0603.md"))),
+    // Errors evaluating `comptime` code
+    ("E0701", Some(include_str!("error_codes/E0701.md"))),
+    ("E0702", Some(include_str!("error_codes/E0702.md"))),
+    ("E0703", Some(include_str!("error_codes/E0703.md"))),
+    ("E0704", Some(include_str!("error_codes/E0704.md"))),
+    // E0705 is only emitted for bugs in the compiler: programs are type checked beforehand.
+    ("E0705", None),
+    ("E0706", Some(include_str!("error_codes/E0706.md"))),
+    ("E0707", Some(include_str!("error_codes/E0707.md"))),
+    ("E0708", Some(include_str!("error_codes/E0708.md"))),
+    ("E0709", Some(include_str!("error_codes/E0709.md"))),
+    ("E0710", Some(include_str!("error_codes/E0710.md"))),
+    ("E0711", Some(include_str!("error_codes/E0711.md"))),
+    ("E0712", Some(include_str!("error_codes/E0712.md"))),
+    // E0713 is never reported: the expression already failed to resolve or type check.
+    ("E0713", None),
+    // Warnings found while generating ACIR
+    ("E0801", Some(include_str!("error_codes/E0801.md"))),
+    ("E0802", Some(include_str!("error_codes/E0802.md"))),
+    ("E0803", Some(include_str!("error_codes/E0803.md"))),
+    ("E0804", Some(include_str!("error_codes/E0804.md"))),
+];
+
+/// Returns true if `code` has been assigned to a diagnostic.
+pub fn is_known_code(code: &str) -> bool {
+    ERROR_CODES.iter().any(|(known_code, _)| *known_code == code)
+}
+
+/// Returns the extended explanation of the given error code, if it has one.
+pub fn explanation(code: &str) -> Option<&'static str> {
+    ERROR_CODES.iter().find(|(known_code, _)| *known_code == code).and_then(|(_, text)| *text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ERROR_CODES;
+
+    #[test]
+    fn codes_are_sorted_and_unique() {
+        for window in ERROR_CODES.windows(2) {
+            assert!(
+                window[0].0 < window[1].0,
+                "{} should come before {}",
+                window[0].0,
+                window[1].0
+            );
+        }
+    }
+
+    #[test]
+    fn every_emitted_code_has_an_explanation() {
+        // Codes which are no longer emitted, or only emitted for bugs in the compiler
+        const WITHOUT_EXPLANATION: &[&str] = &[
+            "E0107", "E0116", "E0121", "E0123", "E0134", "E0201", "E0207", "E0301", "E0302",
+            "E0304", "E0307", "E0308", "E0502", "E0705", "E0713",
+        ];
+        for (code, explanation) in ERROR_CODES {
+            if WITHOUT_EXPLANATION.contains(code) {
+                assert!(
+                    explanation.is_none(),
+                    "{code} is listed as not emitted but has an explanation"
+                );
+            } else {
+                assert!(explanation.is_some(), "{code} should have an explanation");
+            }
+        }
+    }
+
+    #[test]
+    fn explanations_include_an_erroneous_and_a_fixed_example() {
+        let erroneous_example = "Erroneous code example:\n\n```noir\n";
+        for (code, explanation) in ERROR_CODES {
+            if let Some(explanation) = explanation {
+                let (_, rest) = explanation
+                    .split_once(erroneous_example)
+                    .unwrap_or_else(|| panic!("{code} should include an erroneous code example"));
+                // The fixed example follows the erroneous one
+                assert!(
+                    rest.contains("```noir\n"),
+                    "{code} should include an example of how to fix the error"
+                );
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@
 #![warn(clippy::semicolon_if_nothing_returned)]
 
 pub mod debug_info;
+pub mod error_codes;
 mod position;
 pub mod reporter;
 pub use position::{Location, Position, Span, Spanned};
@@ -1,8 +1,11 @@
-use crate::{FileDiagnostic, Location, Span};
+use std::str::FromStr;
+
+use crate::{error_codes, FileDiagnostic, Location, Span};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::Files;
 use codespan_reporting::term;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use serde::Serialize;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CustomDiagnostic {
@@ -10,6 +13,8 @@ pub struct CustomDiagnostic {
     pub secondaries: Vec<CustomLabel>,
     notes: Vec<String>,
     pub kind: DiagnosticKind,
+    /// A stable code identifying the kind of error, e.g. `E0201`.
+    pub code: Option<&'static str>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -18,6 +23,28 @@ pub enum DiagnosticKind {
     Warning,
 }
 
+/// The format in which diagnostics are written to stderr.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Diagnostics are rendered for humans, along with snippets of the source code.
+    #[default]
+    Human,
+    /// Each diagnostic is written as a JSON object on its own line.
+    Json,
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!("unknown message format `{format}`, expected `human` or `json`")),
+        }
+    }
+}
+
 /// A count of errors that have been already reported to stderr
 #[derive(Debug, Copy, Clone)]
 pub struct ReportedErrors {
@@ -31,6 +58,7 @@ impl CustomDiagnostic {
             secondaries: Vec::new(),
             notes: Vec::new(),
             kind: DiagnosticKind::Error,
+            code: None,
         }
     }
 
@@ -44,6 +72,7 @@ impl CustomDiagnostic {
             secondaries: vec![CustomLabel::new(secondary_message, secondary_span)],
             notes: Vec::new(),
             kind: DiagnosticKind::Error,
+            code: None,
         }
     }
 
@@ -57,6 +86,7 @@ impl CustomDiagnostic {
             secondaries: vec![CustomLabel::new(secondary_message, secondary_span)],
             notes: Vec::new(),
             kind: DiagnosticKind::Warning,
+            code: None,
         }
     }
 
@@ -116,6 +146,7 @@ pub fn report_all<'files>(
     diagnostics: &[FileDiagnostic],
     deny_warnings: bool,
     silence_warnings: bool,
+    format: MessageFormat,
 ) -> ReportedErrors {
     // Report warnings before any errors
     let (warnings, mut errors): (Vec<_>, _) =
@@ -125,7 +156,7 @@ pub fn report_all<'files>(
     diagnostics.append(&mut errors);
 
     let error_count =
-        diagnostics.iter().map(|error| error.report(files, deny_warnings) as u32).sum();
+        diagnostics.iter().map(|error| error.report(files, deny_warnings, format) as u32).sum();
 
     ReportedErrors { error_count }
 }
@@ -135,8 +166,9 @@ impl FileDiagnostic {
         &self,
         files: &'files impl Files<'files, FileId = fm::FileId>,
         deny_warnings: bool,
+        format: MessageFormat,
     ) -> bool {
-        report(files, &self.diagnostic, Some(self.file_id), &self.call_stack, deny_warnings)
+        report(files, &self.diagnostic, Some(self.file_id), &self.call_stack, deny_warnings, format)
     }
 }
 
@@ -147,17 +179,125 @@ pub fn report<'files>(
     file: Option<fm::FileId>,
     call_stack: &[Location],
     deny_warnings: bool,
+    format: MessageFormat,
 ) -> bool {
-    let writer = StandardStream::stderr(ColorChoice::Always);
-    let config = codespan_reporting::term::Config::default();
-
-    let stack_trace = stack_trace(files, call_stack);
-    let diagnostic = convert_diagnostic(custom_diagnostic, file, stack_trace, deny_warnings);
-    term::emit(&mut writer.lock(), &config, files, &diagnostic).unwrap();
+    match format {
+        MessageFormat::Human => {
+            let writer = StandardStream::stderr(ColorChoice::Always);
+            let config = codespan_reporting::term::Config::default();
+
+            let stack_trace = stack_trace(files, call_stack);
+            let diagnostic =
+                convert_diagnostic(custom_diagnostic, file, stack_trace, deny_warnings);
+            term::emit(&mut writer.lock(), &config, files, &diagnostic).unwrap();
+        }
+        MessageFormat::Json => {
+            let diagnostic =
+                json_diagnostic(files, custom_diagnostic, file, call_stack, deny_warnings);
+            eprintln!("{diagnostic}");
+        }
+    }
 
     deny_warnings || custom_diagnostic.is_error()
 }
 
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    severity: &'static str,
+    code: Option<&'static str>,
+    message: &'a str,
+    spans: Vec<JsonSpan<'a>>,
+    notes: &'a [String],
+    call_stack: Vec<JsonLocation>,
+}
+
+#[derive(Serialize)]
+struct JsonSpan<'a> {
+    file: String,
+    byte_start: u32,
+    byte_end: u32,
+    start: JsonLocation,
+    end: JsonLocation,
+    /// The first label of a diagnostic points at the location the diagnostic is about,
+    /// any others give additional context.
+    is_primary: bool,
+    label: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonLocation {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+/// Renders the given diagnostic as a single line of JSON.
+fn json_diagnostic<'files>(
+    files: &'files impl Files<'files, FileId = fm::FileId>,
+    cd: &CustomDiagnostic,
+    file: Option<fm::FileId>,
+    call_stack: &[Location],
+    deny_warnings: bool,
+) -> String {
+    let severity = match (cd.kind, deny_warnings) {
+        (DiagnosticKind::Warning, false) => "warning",
+        _ => "error",
+    };
+
+    let spans = if let Some(file_id) = file {
+        cd.secondaries
+            .iter()
+            .enumerate()
+            .map(|(index, label)| JsonSpan {
+                file: file_name(files, file_id),
+                byte_start: label.span.start(),
+                byte_end: label.span.end(),
+                start: json_location(files, file_id, label.span.start()),
+                end: json_location(files, file_id, label.span.end()),
+                is_primary: index == 0,
+                label: &label.message,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let call_stack = call_stack
+        .iter()
+        .map(|location| json_location(files, location.file, location.span.start()))
+        .collect();
+
+    let diagnostic = JsonDiagnostic {
+        severity,
+        code: cd.code,
+        message: &cd.message,
+        spans,
+        notes: &cd.notes,
+        call_stack,
+    };
+    serde_json::to_string(&diagnostic).expect("diagnostics should always be serializable")
+}
+
+fn file_name<'files>(
+    files: &'files impl Files<'files, FileId = fm::FileId>,
+    file_id: fm::FileId,
+) -> String {
+    files.name(file_id).map(|name| name.to_string()).unwrap_or_default()
+}
+
+/// Returns the 1-based line and column of the given byte index.
+fn json_location<'files>(
+    files: &'files impl Files<'files, FileId = fm::FileId>,
+    file_id: fm::FileId,
+    byte_index: u32,
+) -> JsonLocation {
+    let (line, column) = files
+        .location(file_id, byte_index as usize)
+        .map(|location| (location.line_number, location.column_number))
+        .unwrap_or_default();
+    JsonLocation { file: file_name(files, file_id), line, column }
+}
+
 fn convert_diagnostic(
     cd: &CustomDiagnostic,
     file: Option<fm::FileId>,
@@ -182,7 +322,15 @@ fn convert_diagnostic(
         vec![]
     };
 
+    let diagnostic = match cd.code {
+        Some(code) => diagnostic.with_code(code),
+        None => diagnostic,
+    };
+
     let mut notes = cd.notes.clone();
+    if let Some(code) = cd.code.filter(|code| error_codes::explanation(code).is_some()) {
+        notes.push(format!("For more information about this error, try `nargo explain {code}`"));
+    }
     notes.push(stack_trace);
 
     diagnostic.with_message(&cd.message).with_labels(secondary_labels).with_notes(notes)
@@ -228,3 +376,48 @@ fn location(source: &str, span_start: u32) -> (u32, u32) {
 
     (line, column)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use fm::FileMap;
+
+    use super::{json_diagnostic, CustomDiagnostic};
+    use crate::{Location, Span};
+
+    #[test]
+    fn json_diagnostics_include_codes_spans_and_call_stacks() {
+        let mut files = FileMap::default();
+        let source = "fn main() {\n    let x = 1;\n}\n".to_string();
+        let file_id = files.add_file(PathBuf::from("src/main.nr").into(), source);
+
+        let mut diagnostic = CustomDiagnostic::simple_warning(
+            "unused variable x".to_string(),
+            "unused variable".to_string(),
+            Span::from(20..21),
+        );
+        diagnostic.code = Some("E0102");
+        diagnostic.add_note("a note".to_string());
+        let call_stack = vec![Location::new(Span::from(20..21), file_id)];
+
+        let json = json_diagnostic(&files, &diagnostic, Some(file_id), &call_stack, false);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["code"], "E0102");
+        assert_eq!(json["message"], "unused variable x");
+        assert_eq!(json["notes"][0], "a note");
+
+        let span = &json["spans"][0];
+        assert_eq!(span["file"], "src/main.nr");
+        assert_eq!(span["is_primary"], true);
+        assert_eq!(span["start"]["line"], 2);
+        assert_eq!(span["start"]["column"], 9);
+        assert_eq!(json["call_stack"][0]["line"], 2);
+
+        let json = json_diagnostic(&files, &diagnostic, Some(file_id), &call_stack, true);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["severity"], "error");
+    }
+}
@@ -1,5 +1,3 @@
-use iter_extended::vecmap;
-
 use crate::{
     brillig::brillig_ir::{
         artifact::{BrilligParameter, Label},
@@ -70,26 +68,50 @@ impl FunctionContext {
         function_id.to_string()
     }
 
-    fn ssa_type_to_parameter(typ: &Type) -> BrilligParameter {
+    /// Converts the type of an entry point parameter or return value into a [BrilligParameter].
+    ///
+    /// The length of a slice is not part of its type, so this returns `None` for slices and
+    /// for arrays containing them. Top-level slices can be converted with
+    /// [FunctionContext::call_site_parameter] instead.
+    pub(crate) fn ssa_type_to_parameter(typ: &Type) -> Option<BrilligParameter> {
+        match typ {
+            Type::Numeric(_) | Type::Reference(_) => Some(BrilligParameter::Simple),
+            Type::Array(item_type, size) => {
+                let item_type = item_type
+                    .iter()
+                    .map(FunctionContext::ssa_type_to_parameter)
+                    .collect::<Option<_>>()?;
+                Some(BrilligParameter::Array(item_type, *size))
+            }
+            Type::Slice(_) => None,
+            Type::Function => {
+                unreachable!("ICE: Function values should have been removed by defunctionalization")
+            }
+        }
+    }
+
+    /// Converts a value passed to or returned from an entry point into a [BrilligParameter].
+    /// For slices, `slice_capacity` is the number of items held by the slice, which may be
+    /// more than its length. Returns `None` for nested slices, which are not supported.
+    pub(crate) fn call_site_parameter(
+        typ: &Type,
+        slice_capacity: usize,
+    ) -> Option<BrilligParameter> {
         match typ {
-            Type::Numeric(_) | Type::Reference(_) => BrilligParameter::Simple,
-            Type::Array(item_type, size) => BrilligParameter::Array(
-                vecmap(item_type.iter(), |item_typ| {
-                    FunctionContext::ssa_type_to_parameter(item_typ)
-                }),
-                *size,
-            ),
-            Type::Slice(item_type) => {
-                BrilligParameter::Slice(vecmap(item_type.iter(), |item_typ| {
-                    FunctionContext::ssa_type_to_parameter(item_typ)
-                }))
+            Type::Slice(item_types) => {
+                let item_types = item_types
+                    .iter()
+                    .map(FunctionContext::ssa_type_to_parameter)
+                    .collect::<Option<_>>()?;
+                Some(BrilligParameter::Slice(item_types, slice_capacity))
             }
-            _ => unimplemented!("Unsupported function parameter/return type {typ:?}"),
+            _ => FunctionContext::ssa_type_to_parameter(typ),
         }
     }
 
-    /// Collects the parameters of a given function
-    pub(crate) fn parameters(func: &Function) -> Vec<BrilligParameter> {
+    /// Collects the parameters of a given function.
+    /// Returns `None` if any of them contains a slice.
+    pub(crate) fn parameters(func: &Function) -> Option<Vec<BrilligParameter>> {
         func.parameters()
             .iter()
             .map(|&value_id| {
@@ -99,8 +121,9 @@ impl FunctionContext {
             .collect()
     }
 
-    /// Collects the return values of a given function
-    pub(crate) fn return_values(func: &Function) -> Vec<BrilligParameter> {
+    /// Collects the return values of a given function.
+    /// Returns `None` if any of them contains a slice.
+    pub(crate) fn return_values(func: &Function) -> Option<Vec<BrilligParameter>> {
         func.returns()
             .iter()
             .map(|&value_id| {
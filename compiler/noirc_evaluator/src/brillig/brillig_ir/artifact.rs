@@ -3,12 +3,16 @@ use std::collections::{BTreeMap, HashMap};
 
 use crate::ssa::ir::dfg::CallStack;
 
-/// Represents a parameter or a return value of a function.
+/// Represents a parameter or a return value of an entry point function.
 #[derive(Debug, Clone)]
 pub(crate) enum BrilligParameter {
+    /// A single value.
     Simple,
+    /// An array with the types of each of its items and the number of items.
     Array(Vec<BrilligParameter>, usize),
-    Slice(Vec<BrilligParameter>),
+    /// A slice with the types of each of its items and the number of items,
+    /// which is only known for the slices passed in at a specific call site.
+    Slice(Vec<BrilligParameter>, usize),
 }
 
 /// The result of compiling and linking brillig artifacts.
@@ -2,7 +2,7 @@ use crate::brillig::brillig_ir::ReservedRegisters;
 
 use super::{
     artifact::{BrilligArtifact, BrilligParameter},
-    brillig_variable::{BrilligArray, BrilligVariable},
+    brillig_variable::{BrilligArray, BrilligVariable, BrilligVector},
     debug_show::DebugShow,
     registers::BrilligRegistersContext,
     BrilligContext,
@@ -35,7 +35,8 @@ impl BrilligContext {
 
     /// Adds the instructions needed to handle entry point parameters
     /// The runtime will leave the parameters in the first `n` registers.
-    /// Arrays will be passed as pointers to the first element, with all the nested arrays flattened.
+    /// Arrays and slices will be passed as pointers to the first element, with all the nested arrays flattened.
+    /// Slices are always passed right after their length, which may be smaller than the number of items passed.
     /// First, reserve the registers that contain the parameters.
     /// This function also sets the starting value of the reserved registers
     fn entry_point_instruction(&mut self, arguments: Vec<BrilligParameter>) {
@@ -44,10 +45,9 @@ impl BrilligContext {
         self.set_allocated_registers(preallocated_registers.clone());
 
         // Then allocate and initialize the variables that will hold the parameters
-        let argument_variables: Vec<_> = arguments
-            .iter()
-            .zip(preallocated_registers)
-            .map(|(argument, param_register)| match argument {
+        let mut argument_variables: Vec<BrilligVariable> = Vec::with_capacity(arguments.len());
+        for (argument, param_register) in arguments.iter().zip(preallocated_registers) {
+            let variable = match argument {
                 BrilligParameter::Simple => {
                     let variable_register = self.allocate_register();
                     self.mov_instruction(variable_register, param_register);
@@ -64,9 +64,33 @@ impl BrilligContext {
                         rc: rc_register,
                     })
                 }
-                BrilligParameter::Slice(_) => unimplemented!("Unsupported slices as parameter"),
-            })
-            .collect();
+                BrilligParameter::Slice(item_types, _) => {
+                    let length_register = match argument_variables.last() {
+                        Some(BrilligVariable::Simple(length_register)) => *length_register,
+                        _ => unreachable!("ICE: Slices should be passed right after their length"),
+                    };
+                    let pointer_register = self.allocate_register();
+                    let size_register = self.allocate_register();
+                    let rc_register = self.allocate_register();
+                    self.mov_instruction(pointer_register, param_register);
+                    // The size of the vector is taken from the length of the slice rather than
+                    // the number of items passed in, as the length may only be known at runtime.
+                    self.usize_op(
+                        length_register,
+                        size_register,
+                        acvm::brillig_vm::brillig::BinaryIntOp::Mul,
+                        item_types.len(),
+                    );
+                    self.const_instruction(rc_register, 1_usize.into());
+                    BrilligVariable::BrilligVector(BrilligVector {
+                        pointer: pointer_register,
+                        size: size_register,
+                        rc: rc_register,
+                    })
+                }
+            };
+            argument_variables.push(variable);
+        }
 
         // Calculate the initial value for the stack pointer register
         let size_arguments_memory: usize = arguments
@@ -88,15 +112,23 @@ impl BrilligContext {
             value: 0_usize.into(),
         });
 
-        // Deflatten the arrays
+        // Deflatten the arrays and slices
         for (parameter, assigned_variable) in arguments.iter().zip(&argument_variables) {
-            if let BrilligParameter::Array(item_type, item_count) = parameter {
-                if item_type.iter().any(|param| !matches!(param, BrilligParameter::Simple)) {
-                    let pointer_register = assigned_variable.extract_array().pointer;
-                    let deflattened_register =
-                        self.deflatten_array(item_type, *item_count, pointer_register);
-                    self.mov_instruction(pointer_register, deflattened_register);
-                }
+            let (item_type, item_count, pointer_register) = match (parameter, assigned_variable) {
+                (
+                    BrilligParameter::Array(item_type, item_count),
+                    BrilligVariable::BrilligArray(array),
+                ) => (item_type, item_count, array.pointer),
+                (
+                    BrilligParameter::Slice(item_type, item_count),
+                    BrilligVariable::BrilligVector(vector),
+                ) => (item_type, item_count, vector.pointer),
+                _ => continue,
+            };
+            if item_type.iter().any(|param| !matches!(param, BrilligParameter::Simple)) {
+                let deflattened_register =
+                    self.deflatten_array(item_type, *item_count, pointer_register);
+                self.mov_instruction(pointer_register, deflattened_register);
             }
         }
 
@@ -112,13 +144,11 @@ impl BrilligContext {
     fn flattened_size(param: &BrilligParameter) -> usize {
         match param {
             BrilligParameter::Simple => 1,
-            BrilligParameter::Array(item_types, item_count) => {
+            BrilligParameter::Array(item_types, item_count)
+            | BrilligParameter::Slice(item_types, item_count) => {
                 let item_size: usize = item_types.iter().map(BrilligContext::flattened_size).sum();
                 item_count * item_size
             }
-            BrilligParameter::Slice(_) => {
-                unreachable!("ICE: Slices cannot be passed as entry point arguments")
-            }
         }
     }
 
@@ -193,7 +223,11 @@ impl BrilligContext {
 
                         source_offset += BrilligContext::flattened_size(subitem);
                     }
-                    BrilligParameter::Slice(..) => unreachable!("ICE: Cannot deflatten slices"),
+                    BrilligParameter::Slice(..) => {
+                        unreachable!(
+                            "ICE: Nested slices should be rejected before generating entry points"
+                        )
+                    }
                 }
 
                 self.deallocate_register(source_index);
@@ -208,7 +242,7 @@ impl BrilligContext {
 
     /// Adds the instructions needed to handle return parameters
     /// The runtime expects the results in the first `n` registers.
-    /// Arrays are expected to be returned as pointers to the first element with all the nested arrays flattened.
+    /// Arrays and slices are expected to be returned as pointers to the first element with all the nested arrays flattened.
     /// However, the function called returns variables (that have extra data) and the returned arrays are unflattened.
     fn exit_point_instruction(&mut self, return_parameters: Vec<BrilligParameter>) {
         // First, we allocate the registers that hold the returned variables from the function call.
@@ -224,30 +258,43 @@ impl BrilligContext {
                         rc: self.allocate_register(),
                     })
                 }
-                BrilligParameter::Slice(..) => unreachable!("ICE: Cannot return slices"),
+                BrilligParameter::Slice(..) => BrilligVariable::BrilligVector(BrilligVector {
+                    pointer: self.allocate_register(),
+                    size: self.allocate_register(),
+                    rc: self.allocate_register(),
+                }),
             })
             .collect();
-        // Now, we unflatten the returned arrays
+        // Now, we unflatten the returned arrays and slices
         for (return_param, returned_variable) in return_parameters.iter().zip(&returned_variables) {
-            if let BrilligParameter::Array(item_type, item_count) = return_param {
-                if item_type.iter().any(|item| !matches!(item, BrilligParameter::Simple)) {
-                    let returned_pointer = returned_variable.extract_array().pointer;
-                    let flattened_array_pointer = self.allocate_register();
-
-                    self.allocate_fixed_length_array(
-                        flattened_array_pointer,
-                        BrilligContext::flattened_size(return_param),
-                    );
-
-                    self.flatten_array(
-                        item_type,
-                        *item_count,
-                        flattened_array_pointer,
-                        returned_pointer,
-                    );
-
-                    self.mov_instruction(returned_pointer, flattened_array_pointer);
-                }
+            let (item_type, item_count, returned_pointer) = match (return_param, returned_variable)
+            {
+                (
+                    BrilligParameter::Array(item_type, item_count),
+                    BrilligVariable::BrilligArray(array),
+                ) => (item_type, item_count, array.pointer),
+                (
+                    BrilligParameter::Slice(item_type, item_count),
+                    BrilligVariable::BrilligVector(vector),
+                ) => (item_type, item_count, vector.pointer),
+                _ => continue,
+            };
+            if item_type.iter().any(|item| !matches!(item, BrilligParameter::Simple)) {
+                let flattened_array_pointer = self.allocate_register();
+
+                self.allocate_fixed_length_array(
+                    flattened_array_pointer,
+                    BrilligContext::flattened_size(return_param),
+                );
+
+                self.flatten_array(
+                    item_type,
+                    *item_count,
+                    flattened_array_pointer,
+                    returned_pointer,
+                );
+
+                self.mov_instruction(returned_pointer, flattened_array_pointer);
             }
         }
         // The VM expects us to follow the calling convention of returning
@@ -346,7 +393,11 @@ impl BrilligContext {
 
                         target_offset += BrilligContext::flattened_size(subitem);
                     }
-                    BrilligParameter::Slice(..) => unreachable!("ICE: Cannot flatten slices"),
+                    BrilligParameter::Slice(..) => {
+                        unreachable!(
+                            "ICE: Nested slices should be rejected before generating entry points"
+                        )
+                    }
                 }
 
                 self.deallocate_register(source_index);
@@ -364,7 +415,7 @@ mod tests {
 
     use crate::brillig::brillig_ir::{
         artifact::BrilligParameter,
-        brillig_variable::BrilligArray,
+        brillig_variable::{BrilligArray, BrilligVector},
         tests::{create_and_run_vm, create_context, create_entry_point_bytecode},
     };
 
@@ -505,4 +556,131 @@ mod tests {
         );
         assert_eq!(vm.get_registers().get(RegisterIndex(0)), 18_usize.into());
     }
+
+    #[test]
+    fn entry_point_with_nested_slice_parameter() {
+        let flattened_slice = vec![
+            Value::from(1_usize),
+            Value::from(2_usize),
+            Value::from(3_usize),
+            Value::from(4_usize),
+            Value::from(5_usize),
+            Value::from(6_usize),
+        ];
+        // The slice has capacity for two items, but only the first one is in use
+        let arguments = vec![
+            BrilligParameter::Simple,
+            BrilligParameter::Slice(
+                vec![
+                    BrilligParameter::Array(vec![BrilligParameter::Simple], 2),
+                    BrilligParameter::Simple,
+                ],
+                2,
+            ),
+        ];
+        let returns = vec![BrilligParameter::Simple, BrilligParameter::Simple];
+
+        let mut context = create_context();
+
+        // Allocate the parameters
+        let _length = context.allocate_register();
+        let brillig_vector = BrilligVector {
+            pointer: context.allocate_register(),
+            size: context.allocate_register(),
+            rc: context.allocate_register(),
+        };
+
+        context.return_instruction(&[brillig_vector.pointer, brillig_vector.size]);
+
+        let bytecode = create_entry_point_bytecode(context, arguments, returns).byte_code;
+        let vm = create_and_run_vm(
+            flattened_slice.clone(),
+            vec![Value::from(1_usize), Value::from(0_usize)],
+            &bytecode,
+        );
+        let memory = vm.get_memory();
+
+        // The slice is deflattened in the same way as an array, with two values per item,
+        // while its size is taken from its length rather than its capacity
+        assert_eq!(vm.get_registers().get(RegisterIndex(0)), Value::from(flattened_slice.len()));
+        assert_eq!(vm.get_registers().get(RegisterIndex(1)), Value::from(2_usize));
+        assert_eq!(
+            memory,
+            &vec![
+                // The original flattened values
+                Value::from(1_usize),
+                Value::from(2_usize),
+                Value::from(3_usize),
+                Value::from(4_usize),
+                Value::from(5_usize),
+                Value::from(6_usize),
+                // The pointer to the nested reference of the first item
+                Value::from(12_usize),
+                Value::from(3_usize),
+                // The pointer to the nested reference of the second item
+                Value::from(16_usize),
+                Value::from(6_usize),
+                // The nested array of the first item
+                Value::from(1_usize),
+                Value::from(2_usize),
+                // The nested reference of the first item
+                Value::from(10_usize),
+                Value::from(1_usize),
+                // The nested array of the second item
+                Value::from(4_usize),
+                Value::from(5_usize),
+                // The nested reference of the second item
+                Value::from(14_usize),
+                Value::from(1_usize),
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_point_with_nested_slice_return() {
+        let flattened_slice = vec![
+            Value::from(1_usize),
+            Value::from(2_usize),
+            Value::from(3_usize),
+            Value::from(4_usize),
+            Value::from(5_usize),
+            Value::from(6_usize),
+        ];
+        let slice_param = BrilligParameter::Slice(
+            vec![
+                BrilligParameter::Array(vec![BrilligParameter::Simple], 2),
+                BrilligParameter::Simple,
+            ],
+            2,
+        );
+        let arguments = vec![BrilligParameter::Simple, slice_param.clone()];
+        let returns = vec![BrilligParameter::Simple, slice_param];
+
+        let mut context = create_context();
+
+        // Allocate the parameters
+        let length = context.allocate_register();
+        let brillig_vector = BrilligVector {
+            pointer: context.allocate_register(),
+            size: context.allocate_register(),
+            rc: context.allocate_register(),
+        };
+
+        let mut returned_registers = vec![length];
+        returned_registers.extend(brillig_vector.extract_registers());
+        context.return_instruction(&returned_registers);
+
+        let bytecode = create_entry_point_bytecode(context, arguments, returns).byte_code;
+        let vm = create_and_run_vm(
+            flattened_slice.clone(),
+            vec![Value::from(2_usize), Value::from(0_usize)],
+            &bytecode,
+        );
+        let memory = vm.get_memory();
+
+        // The length is returned as is, followed by a pointer to the slice flattened again
+        assert_eq!(vm.get_registers().get(RegisterIndex(0)), Value::from(2_usize));
+        assert_eq!(vm.get_registers().get(RegisterIndex(1)), Value::from(18_usize));
+        assert_eq!(&memory[18..], &flattened_slice[..]);
+    }
 }
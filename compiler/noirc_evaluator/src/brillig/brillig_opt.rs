@@ -0,0 +1,537 @@
+//! Optimizations over linked Brillig bytecode.
+//!
+//! Brillig generation favours simplicity over compactness, emitting many redundant `Mov` and `Const`
+//! opcodes as well as jumps which only lead to other jumps. This pass runs over a [GeneratedBrillig]
+//! once all of its jumps have been resolved and removes these redundancies, without changing the
+//! result of executing the bytecode.
+//!
+//! Register contents are only tracked within a basic block, so any opcode which may transfer control
+//! elsewhere (or hand registers over to the outside world) is treated as reading every register.
+use std::collections::{HashMap, HashSet};
+
+use acvm::{
+    acir::brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex, Value},
+    FieldElement,
+};
+
+use super::brillig_ir::artifact::{GeneratedBrillig, OpcodeLocation};
+
+impl GeneratedBrillig {
+    /// Performs jump threading, unreachable code removal, constant propagation,
+    /// dead store elimination and peephole simplifications over the bytecode.
+    pub(crate) fn optimize(mut self) -> GeneratedBrillig {
+        loop {
+            thread_jumps(&mut self.byte_code);
+
+            let mut removed = vec![false; self.byte_code.len()];
+            mark_unreachable_opcodes(&self.byte_code, &mut removed);
+
+            let leaders = find_block_leaders(&self.byte_code);
+            propagate_constants(&mut self.byte_code, &leaders, &mut removed);
+            eliminate_dead_stores(&self.byte_code, &leaders, &mut removed);
+            mark_redundant_jumps(&self.byte_code, &mut removed);
+
+            if !removed.contains(&true) {
+                return self;
+            }
+            self = self.remove_opcodes(&removed);
+        }
+    }
+
+    /// Removes each opcode flagged in `removed`, updating jump locations and metadata to match.
+    ///
+    /// A jump to a removed opcode is redirected to the next opcode which remains.
+    fn remove_opcodes(self, removed: &[bool]) -> GeneratedBrillig {
+        let mut new_locations = Vec::with_capacity(removed.len() + 1);
+        let mut next_location = 0;
+        for is_removed in removed {
+            new_locations.push(next_location);
+            if !is_removed {
+                next_location += 1;
+            }
+        }
+        // Jumping past the final opcode halts the VM, so this must be preserved.
+        new_locations.push(next_location);
+        let relocate = |location: OpcodeLocation| new_locations[location.min(removed.len())];
+
+        let byte_code = self
+            .byte_code
+            .into_iter()
+            .zip(removed)
+            .filter(|(_, is_removed)| !**is_removed)
+            .map(|(opcode, _)| match opcode {
+                BrilligOpcode::Jump { location } => {
+                    BrilligOpcode::Jump { location: relocate(location) }
+                }
+                BrilligOpcode::JumpIf { condition, location } => {
+                    BrilligOpcode::JumpIf { condition, location: relocate(location) }
+                }
+                BrilligOpcode::JumpIfNot { condition, location } => {
+                    BrilligOpcode::JumpIfNot { condition, location: relocate(location) }
+                }
+                BrilligOpcode::Call { location } => {
+                    BrilligOpcode::Call { location: relocate(location) }
+                }
+                opcode => opcode,
+            })
+            .collect();
+
+        let locations = self
+            .locations
+            .into_iter()
+            .filter(|(location, _)| !removed[*location])
+            .map(|(location, call_stack)| (relocate(location), call_stack))
+            .collect();
+        let assert_messages = self
+            .assert_messages
+            .into_iter()
+            .filter(|(location, _)| !removed[*location])
+            .map(|(location, message)| (relocate(location), message))
+            .collect();
+
+        GeneratedBrillig { byte_code, locations, assert_messages }
+    }
+}
+
+/// Redirects jumps which land on an unconditional jump to that jump's final destination.
+fn thread_jumps(byte_code: &mut [BrilligOpcode]) {
+    let final_destination = |mut location: OpcodeLocation| {
+        // Bound the number of hops so that an infinite loop of jumps is left untouched.
+        for _ in 0..byte_code.len() {
+            match byte_code.get(location) {
+                Some(BrilligOpcode::Jump { location: next }) if *next != location => {
+                    location = *next;
+                }
+                _ => break,
+            }
+        }
+        location
+    };
+
+    let threaded_jumps: Vec<_> = byte_code
+        .iter()
+        .enumerate()
+        .filter_map(|(index, opcode)| match opcode {
+            BrilligOpcode::Jump { location }
+            | BrilligOpcode::JumpIf { location, .. }
+            | BrilligOpcode::JumpIfNot { location, .. } => {
+                let destination = final_destination(*location);
+                (destination != *location).then_some((index, destination))
+            }
+            _ => None,
+        })
+        .collect();
+
+    for (index, destination) in threaded_jumps {
+        match &mut byte_code[index] {
+            BrilligOpcode::Jump { location }
+            | BrilligOpcode::JumpIf { location, .. }
+            | BrilligOpcode::JumpIfNot { location, .. } => *location = destination,
+            _ => unreachable!("only jumps are threaded"),
+        }
+    }
+}
+
+/// Flags every opcode which cannot be reached from the start of the bytecode.
+fn mark_unreachable_opcodes(byte_code: &[BrilligOpcode], removed: &mut [bool]) {
+    let mut reachable = vec![false; byte_code.len()];
+    let mut worklist = vec![0];
+    while let Some(index) = worklist.pop() {
+        if index >= byte_code.len() || reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+
+        match &byte_code[index] {
+            BrilligOpcode::Jump { location } => worklist.push(*location),
+            // Calls return to the following opcode.
+            BrilligOpcode::JumpIf { location, .. }
+            | BrilligOpcode::JumpIfNot { location, .. }
+            | BrilligOpcode::Call { location } => {
+                worklist.push(*location);
+                worklist.push(index + 1);
+            }
+            BrilligOpcode::Return | BrilligOpcode::Stop | BrilligOpcode::Trap => (),
+            _ => worklist.push(index + 1),
+        }
+    }
+
+    for (is_removed, is_reachable) in removed.iter_mut().zip(reachable) {
+        *is_removed |= !is_reachable;
+    }
+}
+
+/// Returns the locations of the first opcode of each basic block.
+fn find_block_leaders(byte_code: &[BrilligOpcode]) -> HashSet<OpcodeLocation> {
+    let mut leaders = HashSet::from([0]);
+    for (index, opcode) in byte_code.iter().enumerate() {
+        match opcode {
+            BrilligOpcode::Jump { location }
+            | BrilligOpcode::JumpIf { location, .. }
+            | BrilligOpcode::JumpIfNot { location, .. }
+            | BrilligOpcode::Call { location } => {
+                leaders.insert(*location);
+                leaders.insert(index + 1);
+            }
+            BrilligOpcode::Return | BrilligOpcode::Stop | BrilligOpcode::Trap => {
+                leaders.insert(index + 1);
+            }
+            _ => (),
+        }
+    }
+    leaders
+}
+
+/// Tracks the registers holding known constants within each basic block.
+///
+/// Opcodes which set a register to the value it already holds are flagged for removal,
+/// moves of constants become constants and field operations over constants are folded.
+fn propagate_constants(
+    byte_code: &mut [BrilligOpcode],
+    leaders: &HashSet<OpcodeLocation>,
+    removed: &mut [bool],
+) {
+    let mut known_values: HashMap<RegisterIndex, Value> = HashMap::new();
+    for index in 0..byte_code.len() {
+        if leaders.contains(&index) {
+            known_values.clear();
+        }
+        if removed[index] {
+            continue;
+        }
+
+        let (destination, value) = match byte_code[index].clone() {
+            BrilligOpcode::Const { destination, value } => (destination, Some(value)),
+            BrilligOpcode::Mov { destination, source } if destination == source => {
+                removed[index] = true;
+                continue;
+            }
+            BrilligOpcode::Mov { destination, source } => {
+                (destination, known_values.get(&source).copied())
+            }
+            BrilligOpcode::BinaryFieldOp { destination, op, lhs, rhs } => {
+                let folded = match (known_values.get(&lhs), known_values.get(&rhs)) {
+                    (Some(lhs), Some(rhs)) => fold_binary_field_op(op, *lhs, *rhs),
+                    _ => None,
+                };
+                (destination, folded)
+            }
+            BrilligOpcode::BinaryIntOp { destination, .. }
+            | BrilligOpcode::Load { destination, .. } => (destination, None),
+            BrilligOpcode::Call { .. }
+            | BrilligOpcode::ForeignCall { .. }
+            | BrilligOpcode::BlackBox(_) => {
+                known_values.clear();
+                continue;
+            }
+            BrilligOpcode::Store { .. }
+            | BrilligOpcode::Jump { .. }
+            | BrilligOpcode::JumpIf { .. }
+            | BrilligOpcode::JumpIfNot { .. }
+            | BrilligOpcode::Return
+            | BrilligOpcode::Trap
+            | BrilligOpcode::Stop => continue,
+        };
+
+        match value {
+            Some(value) if known_values.get(&destination) == Some(&value) => removed[index] = true,
+            Some(value) => {
+                byte_code[index] = BrilligOpcode::Const { destination, value };
+                known_values.insert(destination, value);
+            }
+            None => {
+                known_values.remove(&destination);
+            }
+        }
+    }
+}
+
+/// Evaluates a field operation over constant operands.
+///
+/// Division is left unfolded as dividing by zero must still be observed at runtime.
+fn fold_binary_field_op(op: BinaryFieldOp, lhs: Value, rhs: Value) -> Option<Value> {
+    let (lhs, rhs) = (lhs.to_field(), rhs.to_field());
+    let result = match op {
+        BinaryFieldOp::Add => lhs + rhs,
+        BinaryFieldOp::Sub => lhs - rhs,
+        BinaryFieldOp::Mul => lhs * rhs,
+        BinaryFieldOp::Equals => FieldElement::from(lhs == rhs),
+        BinaryFieldOp::Div => return None,
+    };
+    Some(result.into())
+}
+
+/// Flags side-effect free opcodes whose result is overwritten before it is read within the same basic block.
+fn eliminate_dead_stores(
+    byte_code: &[BrilligOpcode],
+    leaders: &HashSet<OpcodeLocation>,
+    removed: &mut [bool],
+) {
+    // Registers which are written to later in the block without being read in between.
+    let mut overwritten: HashSet<RegisterIndex> = HashSet::new();
+    for index in (0..byte_code.len()).rev() {
+        // Successor blocks may read any register.
+        if leaders.contains(&(index + 1)) {
+            overwritten.clear();
+        }
+        if removed[index] {
+            continue;
+        }
+
+        let opcode = &byte_code[index];
+        if let Some(destination) = pure_destination(opcode) {
+            if overwritten.contains(&destination) {
+                removed[index] = true;
+                continue;
+            }
+        }
+
+        match register_effects(opcode) {
+            Some((reads, writes)) => {
+                overwritten.extend(writes);
+                for read in reads {
+                    overwritten.remove(&read);
+                }
+            }
+            None => overwritten.clear(),
+        }
+    }
+}
+
+/// Returns the register written by `opcode` if writing it is the only effect of executing `opcode`.
+fn pure_destination(opcode: &BrilligOpcode) -> Option<RegisterIndex> {
+    match opcode {
+        BrilligOpcode::Const { destination, .. } | BrilligOpcode::Mov { destination, .. } => {
+            Some(*destination)
+        }
+        BrilligOpcode::BinaryFieldOp { destination, op, .. } if *op != BinaryFieldOp::Div => {
+            Some(*destination)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the registers read and written by `opcode`,
+/// or `None` if it may read any register.
+fn register_effects(opcode: &BrilligOpcode) -> Option<(Vec<RegisterIndex>, Vec<RegisterIndex>)> {
+    let effects = match opcode {
+        BrilligOpcode::BinaryFieldOp { destination, lhs, rhs, .. }
+        | BrilligOpcode::BinaryIntOp { destination, lhs, rhs, .. } => {
+            (vec![*lhs, *rhs], vec![*destination])
+        }
+        BrilligOpcode::Const { destination, .. } => (vec![], vec![*destination]),
+        BrilligOpcode::Mov { destination, source } => (vec![*source], vec![*destination]),
+        BrilligOpcode::Load { destination, source_pointer } => {
+            (vec![*source_pointer], vec![*destination])
+        }
+        BrilligOpcode::Store { destination_pointer, source } => {
+            (vec![*destination_pointer, *source], vec![])
+        }
+        BrilligOpcode::JumpIf { condition, .. } | BrilligOpcode::JumpIfNot { condition, .. } => {
+            (vec![*condition], vec![])
+        }
+        BrilligOpcode::Jump { .. } | BrilligOpcode::Trap => (vec![], vec![]),
+        BrilligOpcode::Call { .. }
+        | BrilligOpcode::Return
+        | BrilligOpcode::Stop
+        | BrilligOpcode::ForeignCall { .. }
+        | BrilligOpcode::BlackBox(_) => return None,
+    };
+    Some(effects)
+}
+
+/// Flags unconditional jumps whose destination is the next opcode which will remain.
+fn mark_redundant_jumps(byte_code: &[BrilligOpcode], removed: &mut [bool]) {
+    for index in 0..byte_code.len() {
+        if removed[index] {
+            continue;
+        }
+        if let BrilligOpcode::Jump { location } = byte_code[index] {
+            let skips_only_removed_opcodes = location > index
+                && location <= removed.len()
+                && removed[index + 1..location].iter().all(|is_removed| *is_removed);
+            if skips_only_removed_opcodes {
+                removed[index] = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::brillig::{
+        BinaryFieldOp, BinaryIntOp, Opcode as BrilligOpcode, RegisterIndex, Value,
+    };
+
+    use crate::brillig::brillig_ir::{artifact::GeneratedBrillig, tests::create_and_run_vm};
+
+    fn generated_brillig(byte_code: Vec<BrilligOpcode>) -> GeneratedBrillig {
+        GeneratedBrillig {
+            byte_code,
+            locations: Default::default(),
+            assert_messages: Default::default(),
+        }
+    }
+
+    /// Executes the bytecode before and after optimization, checking that the
+    /// registers and memory are left in the same state.
+    fn assert_optimization_preserves_execution(
+        byte_code: Vec<BrilligOpcode>,
+        inputs: Vec<Value>,
+        outputs: &[RegisterIndex],
+    ) -> GeneratedBrillig {
+        let optimized = generated_brillig(byte_code.clone()).optimize();
+
+        let vm = create_and_run_vm(vec![], inputs.clone(), &byte_code);
+        let optimized_vm = create_and_run_vm(vec![], inputs, &optimized.byte_code);
+        for output in outputs {
+            assert_eq!(
+                vm.get_registers().get(*output),
+                optimized_vm.get_registers().get(*output),
+                "register {output:?} differs after optimization"
+            );
+        }
+        assert_eq!(vm.get_memory(), optimized_vm.get_memory());
+        optimized
+    }
+
+    #[test]
+    fn removes_redundant_constants_and_moves() {
+        let r0 = RegisterIndex::from(0);
+        let r1 = RegisterIndex::from(1);
+        let r2 = RegisterIndex::from(2);
+        let byte_code = vec![
+            BrilligOpcode::Const { destination: r1, value: Value::from(5u128) },
+            BrilligOpcode::Mov { destination: r2, source: r1 },
+            // r1 already holds 5
+            BrilligOpcode::Const { destination: r1, value: Value::from(5u128) },
+            // Moving a register into itself does nothing
+            BrilligOpcode::Mov { destination: r0, source: r0 },
+            BrilligOpcode::BinaryFieldOp {
+                destination: r0,
+                op: BinaryFieldOp::Add,
+                lhs: r0,
+                rhs: r2,
+            },
+            BrilligOpcode::Stop,
+        ];
+
+        let optimized =
+            assert_optimization_preserves_execution(byte_code, vec![Value::from(3u128)], &[r0]);
+        assert_eq!(optimized.byte_code.len(), 4);
+    }
+
+    #[test]
+    fn folds_constant_field_operations() {
+        let r0 = RegisterIndex::from(0);
+        let r1 = RegisterIndex::from(1);
+        let byte_code = vec![
+            BrilligOpcode::Const { destination: r0, value: Value::from(2u128) },
+            BrilligOpcode::Const { destination: r1, value: Value::from(3u128) },
+            BrilligOpcode::BinaryFieldOp {
+                destination: r0,
+                op: BinaryFieldOp::Mul,
+                lhs: r0,
+                rhs: r1,
+            },
+            BrilligOpcode::Stop,
+        ];
+
+        let optimized = assert_optimization_preserves_execution(byte_code, vec![], &[r0, r1]);
+        assert_eq!(
+            optimized.byte_code,
+            vec![
+                BrilligOpcode::Const { destination: r1, value: Value::from(3u128) },
+                BrilligOpcode::Const { destination: r0, value: Value::from(6u128) },
+                BrilligOpcode::Stop,
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_dead_stores_but_keeps_fallible_operations() {
+        let r0 = RegisterIndex::from(0);
+        let r1 = RegisterIndex::from(1);
+        let byte_code = vec![
+            // Overwritten before being read
+            BrilligOpcode::Mov { destination: r1, source: r0 },
+            // Division may fail so must be kept even though its result is overwritten
+            BrilligOpcode::BinaryIntOp {
+                destination: r1,
+                op: BinaryIntOp::UnsignedDiv,
+                bit_size: 32,
+                lhs: r0,
+                rhs: r0,
+            },
+            BrilligOpcode::Const { destination: r1, value: Value::from(7u128) },
+            BrilligOpcode::Stop,
+        ];
+
+        let optimized =
+            assert_optimization_preserves_execution(byte_code, vec![Value::from(4u128)], &[r0, r1]);
+        assert_eq!(optimized.byte_code.len(), 3);
+        assert!(matches!(optimized.byte_code[0], BrilligOpcode::BinaryIntOp { .. }));
+    }
+
+    #[test]
+    fn threads_jumps_and_removes_unreachable_code() {
+        let r0 = RegisterIndex::from(0);
+        let r1 = RegisterIndex::from(1);
+        let byte_code = vec![
+            // 0: jumps to another jump
+            BrilligOpcode::JumpIf { condition: r0, location: 3 },
+            // 1
+            BrilligOpcode::Const { destination: r1, value: Value::from(1u128) },
+            // 2: jumps to another jump
+            BrilligOpcode::Jump { location: 3 },
+            // 3
+            BrilligOpcode::Jump { location: 6 },
+            // 4: unreachable
+            BrilligOpcode::Trap,
+            // 5: unreachable
+            BrilligOpcode::Const { destination: r1, value: Value::from(2u128) },
+            // 6
+            BrilligOpcode::Stop,
+        ];
+
+        for input in [0u128, 1u128] {
+            let optimized = assert_optimization_preserves_execution(
+                byte_code.clone(),
+                vec![Value::from(input)],
+                &[r0, r1],
+            );
+            assert_eq!(
+                optimized.byte_code,
+                vec![
+                    BrilligOpcode::JumpIf { condition: r0, location: 2 },
+                    BrilligOpcode::Const { destination: r1, value: Value::from(1u128) },
+                    BrilligOpcode::Stop,
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_values_read_after_calls() {
+        let r0 = RegisterIndex::from(0);
+        let r1 = RegisterIndex::from(1);
+        let byte_code = vec![
+            BrilligOpcode::Const { destination: r1, value: Value::from(10u128) },
+            BrilligOpcode::Call { location: 4 },
+            BrilligOpcode::Const { destination: r1, value: Value::from(10u128) },
+            BrilligOpcode::Stop,
+            // Callee: doubles r1 into r0 and clobbers r1
+            BrilligOpcode::BinaryFieldOp {
+                destination: r0,
+                op: BinaryFieldOp::Add,
+                lhs: r1,
+                rhs: r1,
+            },
+            BrilligOpcode::Const { destination: r1, value: Value::from(0u128) },
+            BrilligOpcode::Return,
+        ];
+
+        let optimized = assert_optimization_preserves_execution(byte_code, vec![], &[r0, r1]);
+        assert_eq!(optimized.byte_code.len(), 7);
+    }
+}
@@ -1,5 +1,6 @@
 pub(crate) mod brillig_gen;
 pub(crate) mod brillig_ir;
+pub(crate) mod brillig_opt;
 
 use self::{
     brillig_gen::{brillig_fn::FunctionContext, convert_ssa_function},
@@ -17,6 +18,8 @@ use std::collections::{BTreeSet, HashMap};
 pub struct Brillig {
     /// Maps SSA function labels to their brillig artifact
     ssa_function_to_brillig: HashMap<FunctionId, BrilligArtifact>,
+    /// Whether the linked bytecode should be optimized before being embedded into ACIR
+    optimize_bytecode: bool,
 }
 
 impl Brillig {
@@ -26,6 +29,11 @@ impl Brillig {
         self.ssa_function_to_brillig.insert(func.id(), obj);
     }
 
+    /// Returns whether the linked bytecode should be optimized before being embedded into ACIR
+    pub(crate) fn optimize_bytecode(&self) -> bool {
+        self.optimize_bytecode
+    }
+
     /// Finds a brillig function artifact by its function label
     pub(crate) fn find_by_function_label(&self, function_label: Label) -> Option<&BrilligArtifact> {
         self.ssa_function_to_brillig.iter().find_map(|(function_id, obj)| {
@@ -47,7 +55,7 @@ impl std::ops::Index<FunctionId> for Brillig {
 
 impl Ssa {
     /// Compile to brillig brillig functions and ACIR functions reachable from them
-    pub(crate) fn to_brillig(&self, enable_debug_trace: bool) -> Brillig {
+    pub(crate) fn to_brillig(&self, enable_debug_trace: bool, optimize_bytecode: bool) -> Brillig {
         // Collect all the function ids that are reachable from brillig
         // That means all the functions marked as brillig and ACIR functions called by them
         let brillig_reachable_function_ids = self
@@ -56,7 +64,7 @@ impl Ssa {
             .filter_map(|(id, func)| (func.runtime() == RuntimeType::Brillig).then_some(*id))
             .collect::<BTreeSet<_>>();
 
-        let mut brillig = Brillig::default();
+        let mut brillig = Brillig { optimize_bytecode, ..Brillig::default() };
         for brillig_function_id in brillig_reachable_function_ids {
             let func = &self.functions[&brillig_function_id];
             brillig.compile(func, enable_debug_trace);
@@ -42,6 +42,10 @@ pub enum RuntimeError {
     UnknownLoopBound { call_stack: CallStack },
     #[error("Argument is not constant")]
     AssertConstantFailed { call_stack: CallStack },
+    #[error("Cannot return a slice whose length is only known at runtime from an unconstrained runtime to a constrained runtime")]
+    UnconstrainedSliceReturnToConstrained { call_stack: CallStack },
+    #[error("Nested slices cannot be passed between constrained and unconstrained runtimes")]
+    NestedSliceBetweenRuntimes { call_stack: CallStack },
 }
 
 // We avoid showing the actual lhs and rhs since most of the time they are just 0
@@ -64,6 +68,7 @@ impl From<SsaReport> for FileDiagnostic {
         match error {
             SsaReport::Warning(warning) => {
                 let message = warning.to_string();
+                let code = warning.code();
                 let (secondary_message, call_stack) = match warning {
                     InternalWarning::ReturnConstant { call_stack } => {
                         ("constant value".to_string(), call_stack)
@@ -71,12 +76,19 @@ impl From<SsaReport> for FileDiagnostic {
                     InternalWarning::VerifyProof { call_stack } => {
                         ("verify_proof(...) aggregates data for the verifier, the actual verification will be done when the full proof is verified using nargo verify. nargo prove may generate an invalid proof if bad data is used as input to verify_proof".to_string(), call_stack)
                     },
+                    InternalWarning::DynamicIndexInLoop { array_size, call_stack } => {
+                        (format!("each iteration reads or writes this array of {array_size} elements through memory opcodes, consider indexing it with a constant or moving the access out of the loop"), call_stack)
+                    },
+                    InternalWarning::InlinedNoInlineFunction { call_stack, .. } => {
+                        ("ACIR has no function calls, so functions called from constrained code are always inlined. `#[no_inline]` only keeps calls from unconstrained code".to_string(), call_stack)
+                    },
                 };
                 let call_stack = vecmap(call_stack, |location| location);
                 let file_id = call_stack.last().map(|location| location.file).unwrap_or_default();
                 let location = call_stack.last().expect("Expected RuntimeError to have a location");
-                let diagnostic =
+                let mut diagnostic =
                     Diagnostic::simple_warning(message, secondary_message, location.span);
+                diagnostic.code = Some(code);
                 diagnostic.in_file(file_id).with_call_stack(call_stack)
             }
         }
@@ -89,6 +101,22 @@ pub enum InternalWarning {
     ReturnConstant { call_stack: CallStack },
     #[error("Calling std::verify_proof(...) does not verify a proof")]
     VerifyProof { call_stack: CallStack },
+    #[error("Dynamic indexing of a large array inside of a loop")]
+    DynamicIndexInLoop { array_size: usize, call_stack: CallStack },
+    #[error("`{name}` is marked `#[no_inline]` but is inlined into constrained code")]
+    InlinedNoInlineFunction { name: String, call_stack: CallStack },
+}
+
+impl InternalWarning {
+    /// The stable error code of this warning.
+    pub fn code(&self) -> &'static str {
+        match self {
+            InternalWarning::ReturnConstant { .. } => "E0801",
+            InternalWarning::VerifyProof { .. } => "E0802",
+            InternalWarning::DynamicIndexInLoop { .. } => "E0803",
+            InternalWarning::InlinedNoInlineFunction { .. } => "E0804",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Error)]
@@ -110,6 +138,24 @@ pub enum InternalError {
 }
 
 impl RuntimeError {
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RuntimeError::FailedConstraint { .. } => "E0301",
+            RuntimeError::InternalError(_) => "E0302",
+            RuntimeError::IndexOutOfBounds { .. } => "E0303",
+            RuntimeError::InvalidRangeConstraint { .. } => "E0304",
+            RuntimeError::IntegerOutOfBounds { .. } => "E0305",
+            RuntimeError::TypeConversion { .. } => "E0306",
+            RuntimeError::UnInitialized { .. } => "E0307",
+            RuntimeError::UnsupportedIntegerSize { .. } => "E0308",
+            RuntimeError::UnknownLoopBound { .. } => "E0309",
+            RuntimeError::AssertConstantFailed { .. } => "E0310",
+            RuntimeError::UnconstrainedSliceReturnToConstrained { .. } => "E0311",
+            RuntimeError::NestedSliceBetweenRuntimes { .. } => "E0312",
+        }
+    }
+
     fn call_stack(&self) -> &CallStack {
         match self {
             RuntimeError::InternalError(
@@ -128,6 +174,8 @@ impl RuntimeError {
             | RuntimeError::UnInitialized { call_stack, .. }
             | RuntimeError::UnknownLoopBound { call_stack }
             | RuntimeError::AssertConstantFailed { call_stack }
+            | RuntimeError::UnconstrainedSliceReturnToConstrained { call_stack }
+            | RuntimeError::NestedSliceBetweenRuntimes { call_stack }
             | RuntimeError::IntegerOutOfBounds { call_stack, .. }
             | RuntimeError::UnsupportedIntegerSize { call_stack, .. } => call_stack,
         }
@@ -145,7 +193,8 @@ impl From<RuntimeError> for FileDiagnostic {
 
 impl RuntimeError {
     fn into_diagnostic(self) -> Diagnostic {
-        match self {
+        let code = self.code();
+        let mut diagnostic = match self {
             RuntimeError::InternalError(cause) => {
                 Diagnostic::simple_error(
                     "Internal Consistency Evaluators Errors: \n
@@ -161,6 +210,27 @@ impl RuntimeError {
 
                 Diagnostic::simple_error(message, String::new(), location.span)
             }
+        };
+        diagnostic.code = Some(code);
+        diagnostic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn every_error_code_is_registered() {
+        let source = include_str!("errors.rs");
+
+        let mut codes = 0;
+        for (index, _) in source.match_indices("=> \"E") {
+            let code = &source[index + 4..index + 9];
+            assert!(
+                noirc_errors::error_codes::is_known_code(code),
+                "{code} is not registered in noirc_errors::error_codes"
+            );
+            codes += 1;
         }
+        assert_eq!(codes, 15, "Expected to find the evaluator's error codes");
     }
 }
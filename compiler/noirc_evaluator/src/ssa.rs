@@ -18,6 +18,7 @@ use acvm::acir::{
     native_types::Witness,
 };
 
+use iter_extended::vecmap;
 use noirc_errors::debug_info::DebugInfo;
 
 use noirc_frontend::{
@@ -25,13 +26,17 @@ use noirc_frontend::{
 };
 use tracing::{span, Level};
 
-use self::{acir_gen::GeneratedAcir, ssa_gen::Ssa};
+use self::{acir_gen::GeneratedAcir, interpreter::DifferentialCheck, ssa_gen::Ssa};
 
 mod acir_gen;
 pub(super) mod function_builder;
+mod interpreter;
 pub mod ir;
 mod opt;
+#[cfg(test)]
+mod parser;
 pub mod ssa_gen;
+mod validation;
 
 /// Optimize the given program by converting it into SSA
 /// form and performing optimizations there. When finished,
@@ -40,43 +45,58 @@ pub(crate) fn optimize_into_acir(
     program: Program,
     print_ssa_passes: bool,
     print_brillig_trace: bool,
+    optimize_brillig: bool,
+    check_ssa_passes: bool,
+    validate_ssa: bool,
+    enable_remarks: bool,
 ) -> Result<GeneratedAcir, RuntimeError> {
     let abi_distinctness = program.return_distinctness;
 
+    // Functions are inlined into their callers, so their definitions are recorded to attribute
+    // the opcodes generated from them.
+    let functions =
+        vecmap(&program.functions, |function| (function.location, function.name.clone()));
+
     let ssa_gen_span = span!(Level::TRACE, "ssa_generation");
     let ssa_gen_span_guard = ssa_gen_span.enter();
-    let ssa_builder = SsaBuilder::new(program, print_ssa_passes)?
-        .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
-        .run_pass(Ssa::inline_functions, "After Inlining:")
+    let ssa_builder = SsaBuilder::new(program, print_ssa_passes, check_ssa_passes, validate_ssa)?
+        .run_pass(Ssa::defunctionalize, "After Defunctionalization:")?
+        .run_pass(Ssa::inline_functions, "After Inlining:")?
         // Run mem2reg with the CFG separated into blocks
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
+        .run_pass(Ssa::mem2reg, "After Mem2Reg:")?
         .try_run_pass(Ssa::evaluate_assert_constant, "After Assert Constant:")?
         .try_run_pass(Ssa::unroll_loops, "After Unrolling:")?
-        .run_pass(Ssa::simplify_cfg, "After Simplifying:")
+        .run_pass(Ssa::simplify_cfg, "After Simplifying:")?
         // Run mem2reg before flattening to handle any promotion
         // of values that can be accessed after loop unrolling.
         // If there are slice mergers uncovered by loop unrolling
         // and this pass is missed, slice merging will fail inside of flattening.
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::flatten_cfg, "After Flattening:")
+        .run_pass(Ssa::mem2reg, "After Mem2Reg:")?
+        .run_pass(Ssa::flatten_cfg, "After Flattening:")?
         // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::fold_constants, "After Constant Folding:")
-        .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:");
+        .run_pass(Ssa::mem2reg, "After Mem2Reg:")?
+        .require_promoted_memory()?
+        .run_pass(Ssa::remove_redundant_range_checks, "After Removing Redundant Range Checks:")?
+        .run_pass(Ssa::fold_constants, "After Constant Folding:")?
+        .run_pass(Ssa::loop_invariant_code_motion, "After Loop Invariant Code Motion:")?
+        .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:")?;
 
-    let brillig = ssa_builder.to_brillig(print_brillig_trace);
+    let brillig = ssa_builder.to_brillig(print_brillig_trace, optimize_brillig);
 
     // Split off any passes the are not necessary for Brillig generation but are necessary for ACIR generation.
     // We only need to fill out nested slices as we need to have a known length when dealing with memory operations
     // in ACIR gen while this is not necessary in the Brillig IR.
     let ssa = ssa_builder
-        .run_pass(Ssa::fill_internal_slices, "After Fill Internal Slice Dummy Data:")
+        .run_pass(Ssa::fill_internal_slices, "After Fill Internal Slice Dummy Data:")?
         .finish();
     drop(ssa_gen_span_guard);
 
     let last_array_uses = ssa.find_last_array_uses();
 
-    ssa.into_acir(brillig, abi_distinctness, &last_array_uses)
+    let mut generated_acir =
+        ssa.into_acir(brillig, abi_distinctness, &last_array_uses, enable_remarks)?;
+    generated_acir.functions = functions;
+    Ok(generated_acir)
 }
 
 /// Compiles the [`Program`] into [`ACIR`][acvm::acir::circuit::Circuit].
@@ -88,10 +108,21 @@ pub fn create_circuit(
     program: Program,
     enable_ssa_logging: bool,
     enable_brillig_logging: bool,
+    optimize_brillig: bool,
+    check_ssa_passes: bool,
+    validate_ssa: bool,
+    enable_remarks: bool,
 ) -> Result<(Circuit, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>), RuntimeError> {
     let func_sig = program.main_function_signature.clone();
-    let mut generated_acir =
-        optimize_into_acir(program, enable_ssa_logging, enable_brillig_logging)?;
+    let mut generated_acir = optimize_into_acir(
+        program,
+        enable_ssa_logging,
+        enable_brillig_logging,
+        optimize_brillig,
+        check_ssa_passes,
+        validate_ssa,
+        enable_remarks,
+    )?;
     let opcodes = generated_acir.take_opcodes();
     let GeneratedAcir {
         current_witness_index,
@@ -100,6 +131,8 @@ pub fn create_circuit(
         input_witnesses,
         assert_messages,
         warnings,
+        functions,
+        remarks,
         ..
     } = generated_acir;
 
@@ -124,7 +157,7 @@ pub fn create_circuit(
         .map(|(index, locations)| (index, locations.into_iter().collect()))
         .collect();
 
-    let mut debug_info = DebugInfo::new(locations);
+    let mut debug_info = DebugInfo::new(locations, functions, remarks.unwrap_or_default());
 
     // Perform any ACIR-level optimizations
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
@@ -171,12 +204,34 @@ fn split_public_and_private_inputs(
 struct SsaBuilder {
     ssa: Ssa,
     print_ssa_passes: bool,
+    /// The behavior of the unoptimized program, which is checked against after each pass if set.
+    differential_check: Option<DifferentialCheck>,
+    /// Whether the SSA is validated after each pass.
+    validate_ssa: bool,
+    /// Whether all references in ACIR functions are expected to have been removed by mem2reg.
+    memory_promoted: bool,
 }
 
 impl SsaBuilder {
-    fn new(program: Program, print_ssa_passes: bool) -> Result<SsaBuilder, RuntimeError> {
+    fn new(
+        program: Program,
+        print_ssa_passes: bool,
+        check_ssa_passes: bool,
+        validate_ssa: bool,
+    ) -> Result<SsaBuilder, RuntimeError> {
         let ssa = ssa_gen::generate_ssa(program)?;
-        Ok(SsaBuilder { print_ssa_passes, ssa }.print("Initial SSA:"))
+        if validate_ssa {
+            ssa.validate("SSA Generation", false)?;
+        }
+        let differential_check = check_ssa_passes.then(|| DifferentialCheck::new(&ssa));
+        let builder = SsaBuilder {
+            print_ssa_passes,
+            ssa,
+            differential_check,
+            validate_ssa,
+            memory_promoted: false,
+        };
+        Ok(builder.print("Initial SSA:"))
     }
 
     fn finish(self) -> Ssa {
@@ -184,9 +239,12 @@ impl SsaBuilder {
     }
 
     /// Runs the given SSA pass and prints the SSA afterward if `print_ssa_passes` is true.
-    fn run_pass(mut self, pass: fn(Ssa) -> Ssa, msg: &str) -> Self {
+    ///
+    /// If a differential check is enabled, this fails if the pass changed the program's behavior.
+    /// If validation is enabled, this fails if the pass left the SSA malformed.
+    fn run_pass(mut self, pass: fn(Ssa) -> Ssa, msg: &str) -> Result<Self, RuntimeError> {
         self.ssa = pass(self.ssa);
-        self.print(msg)
+        self.print(msg).check(msg)
     }
 
     /// The same as `run_pass` but for passes that may fail
@@ -196,11 +254,36 @@ impl SsaBuilder {
         msg: &str,
     ) -> Result<Self, RuntimeError> {
         self.ssa = pass(self.ssa)?;
-        Ok(self.print(msg))
+        self.print(msg).check(msg)
+    }
+
+    /// Validates and re-executes the program to check that the pass described by `msg`
+    /// left it well-formed and did not change its behavior.
+    fn check(self, msg: &str) -> Result<Self, RuntimeError> {
+        let pass = msg.trim_start_matches("After ").trim_end_matches(':');
+        if self.validate_ssa {
+            self.ssa.validate(pass, self.memory_promoted)?;
+        }
+        if let Some(differential_check) = &self.differential_check {
+            differential_check.check(&self.ssa, pass)?;
+        }
+        Ok(self)
+    }
+
+    /// Marks the point after which ACIR functions must not contain any references,
+    /// as ACIR generation requires every load and store to have been removed by mem2reg.
+    ///
+    /// The preceding mem2reg pass has already been checked, so this only validates the SSA again.
+    fn require_promoted_memory(mut self) -> Result<Self, RuntimeError> {
+        self.memory_promoted = true;
+        if self.validate_ssa {
+            self.ssa.validate("Mem2Reg", self.memory_promoted)?;
+        }
+        Ok(self)
     }
 
-    fn to_brillig(&self, print_brillig_trace: bool) -> Brillig {
-        self.ssa.to_brillig(print_brillig_trace)
+    fn to_brillig(&self, print_brillig_trace: bool, optimize_brillig: bool) -> Brillig {
+        self.ssa.to_brillig(print_brillig_trace, optimize_brillig)
     }
 
     fn print(self, msg: &str) -> Self {
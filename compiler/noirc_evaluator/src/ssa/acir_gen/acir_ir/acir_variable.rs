@@ -23,7 +23,7 @@ use acvm::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 use fxhash::FxHashMap as HashMap;
 use iter_extended::{try_vecmap, vecmap};
 use num_bigint::BigUint;
-use std::{borrow::Cow, hash::Hash};
+use std::{borrow::Cow, collections::BTreeMap, hash::Hash};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 /// High level Type descriptor for Variables.
@@ -212,6 +212,20 @@ impl AcirContext {
         self.acir_ir.call_stack = call_stack;
     }
 
+    /// Enables recording a remark for each opcode explaining why it was generated.
+    pub(crate) fn enable_remarks(&mut self) {
+        self.acir_ir.remarks = Some(BTreeMap::new());
+    }
+
+    pub(crate) fn remarks_enabled(&self) -> bool {
+        self.acir_ir.remarks.is_some()
+    }
+
+    /// Sets the description of the SSA instruction which the following opcodes are generated for.
+    pub(crate) fn set_remark_instruction(&mut self, instruction: String) {
+        self.acir_ir.remark_instruction = Some(instruction);
+    }
+
     fn get_or_create_witness_var(&mut self, var: AcirVar) -> Result<AcirVar, InternalError> {
         if self.var_to_expression(var)?.to_witness().is_some() {
             // If called with a variable which is already a witness then return the same variable.
@@ -22,6 +22,7 @@ use acvm::{
     FieldElement,
 };
 use iter_extended::vecmap;
+use noirc_errors::Location;
 use num_bigint::BigUint;
 
 #[derive(Debug, Default)]
@@ -55,6 +56,16 @@ pub(crate) struct GeneratedAcir {
     pub(crate) assert_messages: BTreeMap<OpcodeLocation, String>,
 
     pub(crate) warnings: Vec<SsaReport>,
+
+    /// The location of the definition of each function in the program along with its name.
+    pub(crate) functions: Vec<(Location, String)>,
+
+    /// Correspondence between an opcode index and a remark explaining why it was generated.
+    /// This is only recorded if remarks are enabled.
+    pub(crate) remarks: Option<BTreeMap<OpcodeLocation, String>>,
+
+    /// A description of the SSA instruction currently being converted, used in remarks.
+    pub(crate) remark_instruction: Option<String>,
 }
 
 impl GeneratedAcir {
@@ -65,10 +76,36 @@ impl GeneratedAcir {
 
     /// Adds a new opcode into ACIR.
     pub(crate) fn push_opcode(&mut self, opcode: AcirOpcode) {
+        let remark = self.remarks.is_some().then(|| self.remark(&opcode));
         self.opcodes.push(opcode);
         if !self.call_stack.is_empty() {
             self.locations.insert(self.last_acir_opcode_location(), self.call_stack.clone());
         }
+        if let (Some(remarks), Some(remark)) = (&mut self.remarks, remark) {
+            remarks.insert(OpcodeLocation::Acir(self.opcodes.len() - 1), remark);
+        }
+    }
+
+    /// Explains why `opcode` is being generated, from the SSA instruction being converted
+    /// and the kind of operation the opcode performs for it.
+    fn remark(&self, opcode: &AcirOpcode) -> String {
+        let operation = match opcode {
+            AcirOpcode::AssertZero(_) => "arithmetic constraint".to_owned(),
+            AcirOpcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { .. }) => {
+                "range check".to_owned()
+            }
+            AcirOpcode::BlackBoxFuncCall(call) => format!("{} black box call", call.name()),
+            AcirOpcode::Directive(Directive::Quotient(_)) => "division hint".to_owned(),
+            AcirOpcode::Directive(Directive::ToLeRadix { .. }) => {
+                "radix decomposition hint".to_owned()
+            }
+            AcirOpcode::Directive(Directive::PermutationSort { .. }) => "sorting hint".to_owned(),
+            AcirOpcode::Brillig(_) => "unconstrained code".to_owned(),
+            AcirOpcode::MemoryOp { .. } => "dynamic array memory operation".to_owned(),
+            AcirOpcode::MemoryInit { .. } => "array memory initialization".to_owned(),
+        };
+        let instruction = self.remark_instruction.as_deref().unwrap_or("program setup");
+        format!("{instruction}: {operation}")
     }
 
     pub(crate) fn take_opcodes(&mut self) -> Vec<AcirOpcode> {
@@ -20,7 +20,7 @@ use super::{
     },
     ssa_gen::Ssa,
 };
-use crate::brillig::brillig_ir::artifact::GeneratedBrillig;
+use crate::brillig::brillig_ir::artifact::{BrilligParameter, GeneratedBrillig};
 use crate::brillig::brillig_ir::BrilligContext;
 use crate::brillig::{brillig_gen::brillig_fn::FunctionContext as BrilligFunctionContext, Brillig};
 use crate::errors::{InternalError, InternalWarning, RuntimeError, SsaReport};
@@ -37,6 +37,10 @@ use im::Vector;
 use iter_extended::{try_vecmap, vecmap};
 use noirc_frontend::Distinctness;
 
+/// Arrays with at least this many elements which are dynamically indexed
+/// inside of a loop produce a `DynamicIndexInLoop` warning.
+const DYNAMIC_INDEX_WARNING_ARRAY_SIZE: usize = 64;
+
 /// Context struct for the acir generation pass.
 /// May be similar to the Evaluator struct in the current SSA IR.
 struct Context {
@@ -92,6 +96,10 @@ struct Context {
     /// This enables us to maintain the slice structure of a slice when performing an array get.
     slice_sizes: HashMap<Id<Value>, Vec<usize>>,
 
+    /// The call stacks of the array operations within unrolled loops which have already been
+    /// warned about for using a dynamic index on a large array.
+    dynamic_index_call_stacks: HashSet<CallStack>,
+
     data_bus: DataBus,
 }
 
@@ -162,8 +170,9 @@ impl Ssa {
         brillig: Brillig,
         abi_distinctness: Distinctness,
         last_array_uses: &HashMap<ValueId, InstructionId>,
+        enable_remarks: bool,
     ) -> Result<GeneratedAcir, RuntimeError> {
-        let context = Context::new();
+        let context = Context::new(enable_remarks);
         let mut generated_acir = context.convert_ssa(self, brillig, last_array_uses)?;
 
         match abi_distinctness {
@@ -189,8 +198,11 @@ impl Ssa {
 }
 
 impl Context {
-    fn new() -> Context {
+    fn new(enable_remarks: bool) -> Context {
         let mut acir_context = AcirContext::default();
+        if enable_remarks {
+            acir_context.enable_remarks();
+        }
         let current_side_effects_enabled_var = acir_context.add_constant(FieldElement::one());
 
         Context {
@@ -203,6 +215,7 @@ impl Context {
             internal_mem_block_lengths: HashMap::default(),
             max_block_id: 0,
             slice_sizes: HashMap::default(),
+            dynamic_index_call_stacks: HashSet::new(),
             data_bus: DataBus::default(),
         }
     }
@@ -230,6 +243,7 @@ impl Context {
     ) -> Result<GeneratedAcir, RuntimeError> {
         let dfg = &main_func.dfg;
         let entry_block = &dfg[main_func.entry_block()];
+        self.set_remark_instruction(|| "main parameters".to_owned());
         let input_witness = self.convert_ssa_block_params(entry_block.parameters(), dfg)?;
 
         self.data_bus = dfg.data_bus.to_owned();
@@ -244,7 +258,17 @@ impl Context {
             )?);
         }
 
+        self.set_remark_instruction(|| "return".to_owned());
         warnings.extend(self.convert_ssa_return(entry_block.unwrap_terminator(), dfg)?);
+        // Calls created by the defunctionalization pass have no location to warn at
+        let inlined_no_inline_calls =
+            dfg.inlined_no_inline_calls().iter().filter(|(_, call_stack)| !call_stack.is_empty());
+        warnings.extend(inlined_no_inline_calls.map(|(name, call_stack)| {
+            SsaReport::Warning(InternalWarning::InlinedNoInlineFunction {
+                name: name.clone(),
+                call_stack: call_stack.clone(),
+            })
+        }));
         Ok(self.acir_context.finish(input_witness, warnings))
     }
 
@@ -254,6 +278,7 @@ impl Context {
         brillig: Brillig,
     ) -> Result<GeneratedAcir, RuntimeError> {
         let dfg = &main_func.dfg;
+        self.set_remark_instruction(|| format!("call to unconstrained {}", main_func.name()));
 
         let inputs = try_vecmap(dfg[main_func.entry_block()].parameters(), |param_id| {
             let typ = dfg.type_of_value(*param_id);
@@ -264,7 +289,16 @@ impl Context {
         let outputs: Vec<AcirType> =
             vecmap(main_func.returns(), |result_id| dfg.type_of_value(*result_id).into());
 
-        let code = self.gen_brillig_for(main_func, &brillig)?;
+        let entry_point_error = || InternalError::General {
+            message: "Slices cannot be passed to or returned from the entry point of a program"
+                .to_owned(),
+            call_stack: self.acir_context.get_call_stack(),
+        };
+        let arguments =
+            BrilligFunctionContext::parameters(main_func).ok_or_else(entry_point_error)?;
+        let returns =
+            BrilligFunctionContext::return_values(main_func).ok_or_else(entry_point_error)?;
+        let code = self.gen_brillig_for(main_func, arguments, returns, &brillig)?;
 
         let output_values = self.acir_context.brillig(
             self.current_side_effects_enabled_var,
@@ -405,6 +439,7 @@ impl Context {
     ) -> Result<Vec<SsaReport>, RuntimeError> {
         let instruction = &dfg[instruction_id];
         self.acir_context.set_call_stack(dfg.get_call_stack(instruction_id));
+        self.set_remark_instruction(|| describe_instruction(instruction, dfg, ssa));
         let mut warnings = Vec::new();
         match instruction {
             Instruction::Binary(binary) => {
@@ -484,10 +519,10 @@ impl Context {
                             ),
                             RuntimeType::Brillig => {
                                 let inputs = vecmap(arguments, |arg| self.convert_value(*arg, dfg));
+                                let brillig_arguments = self.brillig_call_arguments(arguments, &inputs, dfg)?;
+                                let (brillig_returns, outputs) = self.brillig_call_returns(func, result_ids, dfg)?;
 
-                                let code = self.gen_brillig_for(func, brillig)?;
-
-                                let outputs: Vec<AcirType> = vecmap(result_ids, |result_id| dfg.type_of_value(*result_id).into());
+                                let code = self.gen_brillig_for(func, brillig_arguments, brillig_returns, brillig)?;
 
                                 let output_values = self.acir_context.brillig(self.current_side_effects_enabled_var, code, inputs, outputs)?;
 
@@ -498,8 +533,8 @@ impl Context {
                                     if let AcirValue::Array(_) = &result.1 {
                                         let array_id = dfg.resolve(*result.0);
                                         let block_id = self.block_id(&array_id);
-                                        let array_typ = dfg.type_of_value(array_id);
-                                        self.initialize_array(block_id, array_typ.flattened_size(), Some(result.1.clone()))?;
+                                        let len = Self::flattened_value_size(&result.1);
+                                        self.initialize_array(block_id, len, Some(result.1.clone()))?;
                                     }
                                     self.ssa_values.insert(*result.0, result.1);
                                 }
@@ -570,7 +605,11 @@ impl Context {
                 self.current_side_effects_enabled_var = acir_var;
             }
             Instruction::ArrayGet { .. } | Instruction::ArraySet { .. } => {
-                self.handle_array_operation(instruction_id, dfg, last_array_uses)?;
+                warnings.extend(self.handle_array_operation(
+                    instruction_id,
+                    dfg,
+                    last_array_uses,
+                )?);
             }
             Instruction::Allocate => {
                 unreachable!("Expected all allocate instructions to be removed before acir_gen")
@@ -597,15 +636,108 @@ impl Context {
         Ok(warnings)
     }
 
+    /// Sets the description of the instruction being converted used in remarks,
+    /// if they are enabled.
+    fn set_remark_instruction(&mut self, describe: impl FnOnce() -> String) {
+        if self.acir_context.remarks_enabled() {
+            self.acir_context.set_remark_instruction(describe());
+        }
+    }
+
+    /// Converts the arguments of a call to an unconstrained function into the parameters of its entry point.
+    fn brillig_call_arguments(
+        &self,
+        arguments: &[ValueId],
+        inputs: &[AcirValue],
+        dfg: &DataFlowGraph,
+    ) -> Result<Vec<BrilligParameter>, RuntimeError> {
+        try_vecmap(arguments.iter().zip(inputs), |(argument, input)| {
+            let typ = dfg.type_of_value(*argument);
+            // A slice passes all of the items it has capacity for, while its length is passed separately
+            let capacity = match &typ {
+                Type::Slice(item_types) if !typ.is_nested_slice() => {
+                    let item_size: usize = item_types.iter().map(Type::flattened_size).sum();
+                    if item_size == 0 {
+                        0
+                    } else {
+                        Self::flattened_value_size(input) / item_size
+                    }
+                }
+                _ => 0,
+            };
+            BrilligFunctionContext::call_site_parameter(&typ, capacity).ok_or_else(|| {
+                RuntimeError::NestedSliceBetweenRuntimes {
+                    call_stack: self.acir_context.get_call_stack(),
+                }
+            })
+        })
+    }
+
+    /// Converts the results of a call to an unconstrained function into the return values of its
+    /// entry point, along with the types of the values they are returned in.
+    ///
+    /// The circuit needs a fixed number of witnesses to hold each result, so the length of each
+    /// returned slice must be a constant in the unconstrained function.
+    fn brillig_call_returns(
+        &self,
+        func: &Function,
+        result_ids: &[ValueId],
+        dfg: &DataFlowGraph,
+    ) -> Result<(Vec<BrilligParameter>, Vec<AcirType>), RuntimeError> {
+        let return_values = func.returns();
+        let call_stack = self.acir_context.get_call_stack();
+
+        let mut returns = Vec::with_capacity(result_ids.len());
+        let mut outputs = Vec::with_capacity(result_ids.len());
+        for (index, result_id) in result_ids.iter().enumerate() {
+            let typ = dfg.type_of_value(*result_id);
+            let nested_slice_error =
+                || RuntimeError::NestedSliceBetweenRuntimes { call_stack: call_stack.clone() };
+            match &typ {
+                Type::Slice(item_types) => {
+                    // Slices are always returned right after their length
+                    let length = index
+                        .checked_sub(1)
+                        .and_then(|length_index| {
+                            func.dfg.get_numeric_constant(return_values[length_index])
+                        })
+                        .and_then(|length| length.try_to_u64());
+                    let length = match length {
+                        Some(length) => length as usize,
+                        None => {
+                            return Err(RuntimeError::UnconstrainedSliceReturnToConstrained {
+                                call_stack,
+                            })
+                        }
+                    };
+                    let parameter = BrilligFunctionContext::call_site_parameter(&typ, length)
+                        .ok_or_else(nested_slice_error)?;
+                    returns.push(parameter);
+                    outputs
+                        .push(AcirType::Array(vecmap(item_types.iter(), AcirType::from), length));
+                }
+                _ => {
+                    let parameter = BrilligFunctionContext::ssa_type_to_parameter(&typ)
+                        .ok_or_else(nested_slice_error)?;
+                    returns.push(parameter);
+                    outputs.push(AcirType::from(&typ));
+                }
+            }
+        }
+        Ok((returns, outputs))
+    }
+
     fn gen_brillig_for(
         &self,
         func: &Function,
+        arguments: Vec<BrilligParameter>,
+        returns: Vec<BrilligParameter>,
         brillig: &Brillig,
     ) -> Result<GeneratedBrillig, InternalError> {
         // Create the entry point artifact
         let mut entry_point = BrilligContext::new_entry_point_artifact(
-            BrilligFunctionContext::parameters(func),
-            BrilligFunctionContext::return_values(func),
+            arguments,
+            returns,
             BrilligFunctionContext::function_id_to_function_label(func.id()),
         );
         // Link the entry point with all dependencies
@@ -623,18 +755,25 @@ impl Context {
             entry_point.link_with(artifact);
         }
         // Generate the final bytecode
-        Ok(entry_point.finish())
+        let generated_brillig = entry_point.finish();
+        if brillig.optimize_bytecode() {
+            Ok(generated_brillig.optimize())
+        } else {
+            Ok(generated_brillig)
+        }
     }
 
     /// Handles an ArrayGet or ArraySet instruction.
     /// To set an index of the array (and create a new array in doing so), pass Some(value) for
     /// store_value. To just retrieve an index of the array, pass None for store_value.
+    ///
+    /// Returns a warning if a large array is dynamically indexed inside of a loop.
     fn handle_array_operation(
         &mut self,
         instruction: InstructionId,
         dfg: &DataFlowGraph,
         last_array_uses: &HashMap<ValueId, InstructionId>,
-    ) -> Result<(), RuntimeError> {
+    ) -> Result<Option<SsaReport>, RuntimeError> {
         // Pass the instruction between array methods rather than the internal fields themselves
         let (array, index, store_value) = match dfg[instruction] {
             Instruction::ArrayGet { array, index } => (array, index, None),
@@ -650,9 +789,11 @@ impl Context {
         };
 
         if self.handle_constant_index(instruction, dfg, index, array, store_value)? {
-            return Ok(());
+            return Ok(None);
         }
 
+        let warning = self.check_dynamic_index_in_loop(instruction, array, dfg);
+
         let (new_index, new_value) =
             self.convert_array_operation_inputs(array, dfg, index, store_value)?;
 
@@ -665,7 +806,35 @@ impl Context {
             self.array_get(instruction, array, new_index, dfg)?;
         }
 
-        Ok(())
+        Ok(warning)
+    }
+
+    /// Warns once for each dynamically indexed array operation on a large array which was
+    /// within a loop before the loop was unrolled.
+    fn check_dynamic_index_in_loop(
+        &mut self,
+        instruction: InstructionId,
+        array: ValueId,
+        dfg: &DataFlowGraph,
+    ) -> Option<SsaReport> {
+        let array_type = dfg.type_of_value(array);
+        if !matches!(array_type, Type::Array(..)) {
+            return None;
+        }
+        let array_size = array_type.flattened_size();
+        if array_size < DYNAMIC_INDEX_WARNING_ARRAY_SIZE {
+            return None;
+        }
+
+        let call_stack = dfg.get_call_stack(instruction);
+        if !dfg.is_loop_array_call_stack(&call_stack) {
+            return None;
+        }
+        if self.dynamic_index_call_stacks.insert(call_stack.clone()) {
+            Some(SsaReport::Warning(InternalWarning::DynamicIndexInLoop { array_size, call_stack }))
+        } else {
+            None
+        }
     }
 
     /// Handle constant index: if there is no predicate and we have the array values,
@@ -2474,6 +2643,44 @@ impl Context {
     }
 }
 
+/// Describes `instruction` in the remarks of the opcodes generated for it. The instruction's values
+/// are left out so that remarks can be aggregated over similar instructions.
+fn describe_instruction(instruction: &Instruction, dfg: &DataFlowGraph, ssa: &Ssa) -> String {
+    match instruction {
+        Instruction::Binary(binary) => match dfg.type_of_value(binary.lhs) {
+            Type::Numeric(NumericType::NativeField) => format!("field {}", binary.operator),
+            _ => format!("integer {}", binary.operator),
+        },
+        Instruction::Cast(value, typ) => {
+            format!("cast from {} to {typ}", dfg.type_of_value(*value))
+        }
+        Instruction::Not(_) => "not".to_owned(),
+        Instruction::Truncate { .. } => "truncate".to_owned(),
+        Instruction::Constrain(..) => "constrain".to_owned(),
+        Instruction::RangeCheck { .. } => "range_check".to_owned(),
+        Instruction::Call { func, .. } => match &dfg[*func] {
+            Value::Function(id) => format!("call to unconstrained {}", ssa.functions[id].name()),
+            Value::Intrinsic(intrinsic) => format!("call to {intrinsic}"),
+            _ => "call".to_owned(),
+        },
+        Instruction::ArrayGet { index, .. } | Instruction::ArraySet { index, .. } => {
+            let operation = match instruction {
+                Instruction::ArrayGet { .. } => "array_get",
+                _ => "array_set",
+            };
+            match dfg.get_numeric_constant(*index) {
+                Some(_) => format!("{operation} with constant index"),
+                None => format!("{operation} with dynamic index"),
+            }
+        }
+        Instruction::EnableSideEffects { .. } => "enable_side_effects".to_owned(),
+        Instruction::Allocate => "allocate".to_owned(),
+        Instruction::Load { .. } => "load".to_owned(),
+        Instruction::Store { .. } => "store".to_owned(),
+        Instruction::IncrementRc { .. } => "inc_rc".to_owned(),
+    }
+}
+
 // We can omit the element size array for arrays which have elements of size 1 and do not contain slices.
 // TODO: remove restriction on size 1 elements.
 fn can_omit_element_sizes_array(array_typ: &Type) -> bool {
@@ -2486,3 +2693,114 @@ fn can_omit_element_sizes_array(array_typ: &Type) -> bool {
 
     types.len() == 1 && types[0].flattened_size() == 1
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use fxhash::FxHashMap as HashMap;
+    use noirc_errors::Location;
+    use noirc_frontend::Distinctness;
+
+    use crate::{
+        brillig::Brillig,
+        errors::{InternalWarning, SsaReport},
+        ssa::{ir::instruction::Instruction, ssa_gen::Ssa},
+    };
+
+    #[test]
+    fn records_a_remark_for_each_opcode() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field, v1: u8):
+    v2 = cast v0 as u8
+    range_check v1 to 4 bits
+    return v2
+}";
+        let ssa = Ssa::from_str(src).unwrap_or_else(|error| panic!("{error}"));
+        let distinctness = Distinctness::DuplicationAllowed;
+        let mut acir =
+            ssa.into_acir(Brillig::default(), distinctness, &HashMap::default(), true).unwrap();
+
+        let remarks = acir.remarks.take().expect("remarks are enabled");
+        assert_eq!(remarks.len(), acir.take_opcodes().len());
+
+        let remarks: Vec<_> = remarks.into_values().collect();
+        for expected in [
+            "main parameters: range check",
+            "cast from Field to u8: range check",
+            "range_check: range check",
+        ] {
+            assert!(remarks.iter().any(|remark| remark == expected), "{expected}: {remarks:?}");
+        }
+    }
+
+    /// Returns the number of `DynamicIndexInLoop` warnings produced by compiling `src` to ACIR.
+    ///
+    /// The parser does not record locations, so every array operation is given the same one.
+    fn count_dynamic_index_warnings(src: &str) -> usize {
+        let mut ssa = Ssa::from_str(src).unwrap_or_else(|error| panic!("{error}"));
+        let main = ssa.main_mut();
+        let array_operations: Vec<_> = main
+            .reachable_blocks()
+            .into_iter()
+            .flat_map(|block| main.dfg[block].instructions().to_vec())
+            .filter(|instruction| {
+                matches!(
+                    main.dfg[*instruction],
+                    Instruction::ArrayGet { .. } | Instruction::ArraySet { .. }
+                )
+            })
+            .collect();
+        for instruction in array_operations {
+            main.dfg.add_location(instruction, Location::dummy());
+        }
+
+        let ssa = ssa.unroll_loops().expect("loops unroll").simplify_cfg();
+        let distinctness = Distinctness::DuplicationAllowed;
+        let acir =
+            ssa.into_acir(Brillig::default(), distinctness, &HashMap::default(), false).unwrap();
+        acir.warnings
+            .iter()
+            .filter(|warning| {
+                matches!(warning, SsaReport::Warning(InternalWarning::DynamicIndexInLoop { .. }))
+            })
+            .count()
+    }
+
+    #[test]
+    fn warns_once_about_dynamic_index_in_unrolled_loop() {
+        let src = "
+acir fn main f0 {
+  b0(v0: [Field; 64], v1: u32):
+    jmp b1(u32 0)
+  b1(v2: u32):
+    v3 = lt v2, u32 3
+    jmpif v3 then: b2, else: b3
+  b2():
+    v4 = add v1, v2
+    v5 = array_get v0, index v4 -> Field
+    constrain v5 == Field 0
+    v6 = add v2, u32 1
+    jmp b1(v6)
+  b3():
+    return
+}";
+        assert_eq!(count_dynamic_index_warnings(src), 1);
+    }
+
+    #[test]
+    fn does_not_warn_about_dynamic_index_outside_of_loop() {
+        // Both array operations share a location, but neither was within a loop.
+        let src = "
+acir fn main f0 {
+  b0(v0: [Field; 64], v1: u32):
+    v2 = array_get v0, index v1 -> Field
+    v3 = add v1, u32 1
+    v4 = array_get v0, index v3 -> Field
+    constrain v2 == v4
+    return
+}";
+        assert_eq!(count_dynamic_index_warnings(src), 0);
+    }
+}
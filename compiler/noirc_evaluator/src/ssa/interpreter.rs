@@ -0,0 +1,709 @@
+//! A reference interpreter for SSA.
+//!
+//! The interpreter executes an [`Ssa`] program directly, independent of ACIR and Brillig generation.
+//! Since it can run the program after any optimization pass, comparing its results before and after a
+//! pass is a straightforward way of catching passes which change a program's semantics.
+//!
+//! Numeric values are always represented as field elements. Integer arithmetic is performed in the field
+//! in the same way as in ACIR, relying on the `Truncate` and `RangeCheck` instructions inserted during SSA
+//! generation to give integers their wrapping and overflow semantics. Functions with the Brillig runtime
+//! are executed with these same semantics.
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use acvm::FieldElement;
+use iter_extended::{try_vecmap, vecmap};
+use num_bigint::BigUint;
+use thiserror::Error;
+
+use super::{
+    ir::{
+        basic_block::BasicBlockId,
+        function::{Function, FunctionId, RuntimeType},
+        instruction::{
+            Binary, BinaryOp, Instruction, InstructionId, Intrinsic, TerminatorInstruction,
+        },
+        types::{NumericType, Type},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+mod differential;
+mod intrinsics;
+
+pub(crate) use differential::DifferentialCheck;
+
+/// The maximum number of instructions executed before interpretation is abandoned.
+const MAX_STEPS: usize = 10_000_000;
+/// The maximum depth of nested function calls before interpretation is abandoned.
+const MAX_CALL_DEPTH: usize = 1_000;
+
+/// A value produced while interpreting SSA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InterpreterValue {
+    Numeric {
+        value: FieldElement,
+        typ: NumericType,
+    },
+    /// The flattened elements of an array or slice.
+    Array {
+        elements: im::Vector<InterpreterValue>,
+        typ: Type,
+    },
+    /// A reference to a value created by an `Allocate` instruction, which is `None` until stored to.
+    Reference(Rc<RefCell<Option<InterpreterValue>>>),
+    Function(FunctionId),
+    Intrinsic(Intrinsic),
+    ForeignFunction(String),
+}
+
+impl InterpreterValue {
+    fn bool(value: bool) -> Self {
+        InterpreterValue::Numeric {
+            value: value.into(),
+            typ: NumericType::Unsigned { bit_size: 1 },
+        }
+    }
+
+    /// Returns the default value of type `typ`, as produced by disabled Brillig calls and array reads.
+    fn zero(typ: &Type) -> InterpreterResult<Self> {
+        Ok(match typ {
+            Type::Numeric(typ) => {
+                InterpreterValue::Numeric { value: FieldElement::zero(), typ: *typ }
+            }
+            Type::Array(element_types, length) => {
+                let mut elements = im::Vector::new();
+                for _ in 0..*length {
+                    for element_type in element_types.iter() {
+                        elements.push_back(InterpreterValue::zero(element_type)?);
+                    }
+                }
+                InterpreterValue::Array { elements, typ: typ.clone() }
+            }
+            Type::Slice(_) => {
+                InterpreterValue::Array { elements: im::Vector::new(), typ: typ.clone() }
+            }
+            Type::Reference(_) => InterpreterValue::Reference(Rc::new(RefCell::new(None))),
+            Type::Function => {
+                return Err(InterpreterError::Unsupported("default function values".to_owned()));
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for InterpreterValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterpreterValue::Numeric { value, typ } => write!(f, "{typ} {value}"),
+            InterpreterValue::Array { elements, .. } => {
+                let elements = vecmap(elements, ToString::to_string);
+                write!(f, "[{}]", elements.join(", "))
+            }
+            InterpreterValue::Reference(reference) => match &*reference.borrow() {
+                Some(value) => write!(f, "&mut {value}"),
+                None => write!(f, "&mut <uninitialized>"),
+            },
+            InterpreterValue::Function(id) => write!(f, "{id}"),
+            InterpreterValue::Intrinsic(intrinsic) => write!(f, "{intrinsic}"),
+            InterpreterValue::ForeignFunction(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub(crate) enum InterpreterError {
+    #[error("constraint failed: {}", .message.as_deref().unwrap_or("no message"))]
+    FailedConstraint { message: Option<String> },
+    #[error("index {index} is out of bounds for an array of length {length}")]
+    IndexOutOfBounds { index: usize, length: usize },
+    #[error("attempted to divide by zero")]
+    DivisionByZero,
+    #[error("loaded from a reference before it was stored to")]
+    UninitializedReference,
+    /// The program is not well-formed SSA, e.g. a value is used before it is defined.
+    #[error("invalid SSA: {0}")]
+    InvalidSsa(String),
+    /// The program uses a feature which the interpreter does not support.
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+    #[error("exceeded the maximum number of execution steps")]
+    StepLimitExceeded,
+}
+
+impl InterpreterError {
+    /// Returns true if this error is a failure of the program itself rather than
+    /// a problem with the SSA or a limitation of the interpreter.
+    pub(crate) fn is_program_failure(&self) -> bool {
+        matches!(
+            self,
+            InterpreterError::FailedConstraint { .. }
+                | InterpreterError::IndexOutOfBounds { .. }
+                | InterpreterError::DivisionByZero
+        )
+    }
+
+    /// Returns true if the interpreter could not determine how the program behaves.
+    pub(crate) fn is_inconclusive(&self) -> bool {
+        matches!(self, InterpreterError::Unsupported(_) | InterpreterError::StepLimitExceeded)
+    }
+}
+
+pub(crate) type InterpreterResult<T> = Result<T, InterpreterError>;
+
+impl Ssa {
+    /// Executes the program's main function on `inputs`, returning the values it returns.
+    pub(crate) fn interpret(
+        &self,
+        inputs: Vec<InterpreterValue>,
+    ) -> InterpreterResult<Vec<InterpreterValue>> {
+        Interpreter { ssa: self, steps: 0, call_depth: 0 }.call_function(self.main_id, inputs)
+    }
+}
+
+struct Interpreter<'ssa> {
+    ssa: &'ssa Ssa,
+    steps: usize,
+    call_depth: usize,
+}
+
+/// The state of a single function call.
+struct Frame<'ssa> {
+    function: &'ssa Function,
+    values: HashMap<ValueId, InterpreterValue>,
+    side_effects_enabled: bool,
+}
+
+impl<'ssa> Frame<'ssa> {
+    fn new(function: &'ssa Function) -> Self {
+        Frame { function, values: HashMap::new(), side_effects_enabled: true }
+    }
+
+    fn define(&mut self, id: ValueId, value: InterpreterValue) {
+        self.values.insert(id, value);
+    }
+
+    fn lookup(&self, id: ValueId) -> InterpreterResult<InterpreterValue> {
+        let dfg = &self.function.dfg;
+        let id = dfg.resolve(id);
+        match &dfg[id] {
+            Value::NumericConstant { constant, typ } => match typ {
+                Type::Numeric(typ) => Ok(InterpreterValue::Numeric { value: *constant, typ: *typ }),
+                other => Err(invalid(format!("numeric constant {id} has type {other}"))),
+            },
+            Value::Array { array, typ } => {
+                let elements = try_vecmap(array, |element| self.lookup(*element))?;
+                Ok(InterpreterValue::Array { elements: elements.into(), typ: typ.clone() })
+            }
+            Value::Function(function) => Ok(InterpreterValue::Function(*function)),
+            Value::Intrinsic(intrinsic) => Ok(InterpreterValue::Intrinsic(*intrinsic)),
+            Value::ForeignFunction(name) => Ok(InterpreterValue::ForeignFunction(name.clone())),
+            Value::Param { .. } | Value::Instruction { .. } => {
+                self.values.get(&id).cloned().ok_or_else(|| {
+                    invalid(format!("{id} is used before it is defined in {}", self.function.id()))
+                })
+            }
+        }
+    }
+
+    fn lookup_numeric(&self, id: ValueId) -> InterpreterResult<(FieldElement, NumericType)> {
+        match self.lookup(id)? {
+            InterpreterValue::Numeric { value, typ } => Ok((value, typ)),
+            other => Err(invalid(format!("expected {id} to be numeric, found {other}"))),
+        }
+    }
+
+    fn lookup_index(&self, id: ValueId) -> InterpreterResult<usize> {
+        let (index, _) = self.lookup_numeric(id)?;
+        index.try_to_u64().map(|index| index as usize).ok_or_else(|| {
+            InterpreterError::Unsupported(format!("index {index} does not fit in a u64"))
+        })
+    }
+
+    fn lookup_array(&self, id: ValueId) -> InterpreterResult<(im::Vector<InterpreterValue>, Type)> {
+        match self.lookup(id)? {
+            InterpreterValue::Array { elements, typ } => Ok((elements, typ)),
+            other => Err(invalid(format!("expected {id} to be an array, found {other}"))),
+        }
+    }
+
+    fn lookup_reference(
+        &self,
+        id: ValueId,
+    ) -> InterpreterResult<Rc<RefCell<Option<InterpreterValue>>>> {
+        match self.lookup(id)? {
+            InterpreterValue::Reference(reference) => Ok(reference),
+            other => Err(invalid(format!("expected {id} to be a reference, found {other}"))),
+        }
+    }
+
+    fn result_type(&self, instruction: InstructionId, position: usize) -> Type {
+        let result = self.function.dfg.instruction_results(instruction)[position];
+        self.function.dfg.type_of_value(result)
+    }
+}
+
+fn invalid(message: String) -> InterpreterError {
+    InterpreterError::InvalidSsa(message)
+}
+
+impl<'ssa> Interpreter<'ssa> {
+    fn call_function(
+        &mut self,
+        id: FunctionId,
+        mut arguments: Vec<InterpreterValue>,
+    ) -> InterpreterResult<Vec<InterpreterValue>> {
+        let ssa = self.ssa;
+        let function = ssa
+            .functions
+            .get(&id)
+            .ok_or_else(|| invalid(format!("call to unknown function {id}")))?;
+
+        self.call_depth += 1;
+        if self.call_depth > MAX_CALL_DEPTH {
+            return Err(InterpreterError::StepLimitExceeded);
+        }
+
+        let mut frame = Frame::new(function);
+        let mut block = function.entry_block();
+        let return_values = loop {
+            self.define_block_parameters(&mut frame, block, arguments)?;
+            for instruction in function.dfg[block].instructions() {
+                self.steps += 1;
+                if self.steps > MAX_STEPS {
+                    return Err(InterpreterError::StepLimitExceeded);
+                }
+                self.execute_instruction(&mut frame, *instruction)?;
+            }
+
+            match function.dfg[block].terminator() {
+                Some(TerminatorInstruction::Jmp {
+                    destination, arguments: jump_arguments, ..
+                }) => {
+                    arguments = try_vecmap(jump_arguments, |argument| frame.lookup(*argument))?;
+                    block = *destination;
+                }
+                Some(TerminatorInstruction::JmpIf {
+                    condition,
+                    then_destination,
+                    else_destination,
+                }) => {
+                    let (condition, _) = frame.lookup_numeric(*condition)?;
+                    arguments = Vec::new();
+                    block = if condition.is_zero() { *else_destination } else { *then_destination };
+                }
+                Some(TerminatorInstruction::Return { return_values, .. }) => {
+                    break try_vecmap(return_values, |value| frame.lookup(*value))?;
+                }
+                None => return Err(invalid(format!("{block} in {id} has no terminator"))),
+            }
+        };
+
+        self.call_depth -= 1;
+        Ok(return_values)
+    }
+
+    fn define_block_parameters(
+        &self,
+        frame: &mut Frame,
+        block: BasicBlockId,
+        arguments: Vec<InterpreterValue>,
+    ) -> InterpreterResult<()> {
+        let function = frame.function;
+        let parameters = function.dfg.block_parameters(block);
+        if parameters.len() != arguments.len() {
+            return Err(invalid(format!(
+                "{block} expects {} arguments but was given {}",
+                parameters.len(),
+                arguments.len()
+            )));
+        }
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            frame.define(*parameter, argument);
+        }
+        Ok(())
+    }
+
+    fn execute_instruction(
+        &mut self,
+        frame: &mut Frame,
+        instruction_id: InstructionId,
+    ) -> InterpreterResult<()> {
+        let function = frame.function;
+        let dfg = &function.dfg;
+        let results = match &dfg[instruction_id] {
+            Instruction::Binary(binary) => {
+                vec![evaluate_binary(frame, binary)?]
+            }
+            Instruction::Cast(value, typ) => {
+                let (value, _) = frame.lookup_numeric(*value)?;
+                let Type::Numeric(typ) = typ else {
+                    return Err(invalid(format!("cannot cast to non-numeric type {typ}")));
+                };
+                vec![cast(value, *typ)]
+            }
+            Instruction::Not(value) => {
+                let (value, typ) = frame.lookup_numeric(*value)?;
+                let bit_size = match typ {
+                    NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => {
+                        bit_size
+                    }
+                    NumericType::NativeField => {
+                        return Err(invalid("`not` cannot be applied to a field".to_owned()));
+                    }
+                };
+                let max = (BigUint::from(1u32) << bit_size) - 1u32;
+                let value = to_field(max - (to_biguint(value) % (BigUint::from(1u32) << bit_size)));
+                vec![InterpreterValue::Numeric { value, typ }]
+            }
+            Instruction::Truncate { value, bit_size, .. } => {
+                let (value, typ) = frame.lookup_numeric(*value)?;
+                vec![InterpreterValue::Numeric { value: truncate(value, *bit_size), typ }]
+            }
+            Instruction::Constrain(lhs, rhs, message) => {
+                if frame.lookup(*lhs)? != frame.lookup(*rhs)? {
+                    return Err(InterpreterError::FailedConstraint { message: message.clone() });
+                }
+                Vec::new()
+            }
+            Instruction::RangeCheck { value, max_bit_size, assert_message } => {
+                let (value, _) = frame.lookup_numeric(*value)?;
+                if value.num_bits() > *max_bit_size {
+                    let message = assert_message.clone();
+                    return Err(InterpreterError::FailedConstraint { message });
+                }
+                Vec::new()
+            }
+            Instruction::Call { func, arguments } => {
+                let arguments = try_vecmap(arguments, |argument| frame.lookup(*argument))?;
+                self.execute_call(frame, instruction_id, *func, arguments)?
+            }
+            Instruction::Allocate => vec![InterpreterValue::Reference(Rc::new(RefCell::new(None)))],
+            Instruction::Load { address } => {
+                let value = frame.lookup_reference(*address)?.borrow().clone();
+                vec![value.ok_or(InterpreterError::UninitializedReference)?]
+            }
+            Instruction::Store { address, value } => {
+                let value = frame.lookup(*value)?;
+                *frame.lookup_reference(*address)?.borrow_mut() = Some(value);
+                Vec::new()
+            }
+            Instruction::EnableSideEffects { condition } => {
+                let (condition, _) = frame.lookup_numeric(*condition)?;
+                frame.side_effects_enabled = !condition.is_zero();
+                Vec::new()
+            }
+            Instruction::ArrayGet { array, index } => {
+                let (elements, _) = frame.lookup_array(*array)?;
+                let index = frame.lookup_index(*index)?;
+                match elements.get(index) {
+                    Some(element) => vec![element.clone()],
+                    None if !frame.side_effects_enabled => {
+                        vec![InterpreterValue::zero(&frame.result_type(instruction_id, 0))?]
+                    }
+                    None => {
+                        return Err(InterpreterError::IndexOutOfBounds {
+                            index,
+                            length: elements.len(),
+                        });
+                    }
+                }
+            }
+            Instruction::ArraySet { array, index, value } => {
+                let (mut elements, typ) = frame.lookup_array(*array)?;
+                let index = frame.lookup_index(*index)?;
+                let value = frame.lookup(*value)?;
+                if index < elements.len() {
+                    elements.set(index, value);
+                } else if frame.side_effects_enabled {
+                    let length = elements.len();
+                    return Err(InterpreterError::IndexOutOfBounds { index, length });
+                }
+                vec![InterpreterValue::Array { elements, typ }]
+            }
+            Instruction::IncrementRc { .. } => Vec::new(),
+        };
+
+        let result_ids = dfg.instruction_results(instruction_id);
+        if result_ids.len() != results.len() {
+            return Err(invalid(format!(
+                "instruction {instruction_id} has {} results but produced {}",
+                result_ids.len(),
+                results.len()
+            )));
+        }
+        for (id, value) in result_ids.iter().zip(results) {
+            frame.define(*id, value);
+        }
+        Ok(())
+    }
+
+    fn execute_call(
+        &mut self,
+        frame: &Frame,
+        instruction: InstructionId,
+        func: ValueId,
+        arguments: Vec<InterpreterValue>,
+    ) -> InterpreterResult<Vec<InterpreterValue>> {
+        match frame.lookup(func)? {
+            InterpreterValue::Function(id) => {
+                let is_brillig = self
+                    .ssa
+                    .functions
+                    .get(&id)
+                    .map_or(false, |function| function.runtime() == RuntimeType::Brillig);
+
+                // Brillig calls made from ACIR are skipped when side effects are disabled,
+                // and produce zeroed outputs instead.
+                let caller_is_acir = frame.function.runtime() == RuntimeType::Acir;
+                if is_brillig && caller_is_acir && !frame.side_effects_enabled {
+                    let results = frame.function.dfg.instruction_results(instruction);
+                    return try_vecmap(results, |result| {
+                        InterpreterValue::zero(&frame.function.dfg.type_of_value(*result))
+                    });
+                }
+                self.call_function(id, arguments)
+            }
+            InterpreterValue::Intrinsic(intrinsic) => {
+                intrinsics::call_intrinsic(frame, instruction, intrinsic, arguments)
+            }
+            InterpreterValue::ForeignFunction(name) => match name.as_str() {
+                // Printing has no effect on the program's results.
+                "print" => Ok(Vec::new()),
+                _ => Err(InterpreterError::Unsupported(format!("foreign function `{name}`"))),
+            },
+            other => Err(invalid(format!("attempted to call {other}"))),
+        }
+    }
+}
+
+fn evaluate_binary(frame: &Frame, binary: &Binary) -> InterpreterResult<InterpreterValue> {
+    let (lhs, typ) = frame.lookup_numeric(binary.lhs)?;
+    let (rhs, _) = frame.lookup_numeric(binary.rhs)?;
+
+    let numeric = |value| Ok(InterpreterValue::Numeric { value, typ });
+    match binary.operator {
+        // Integer arithmetic happens in the field; wrapping and overflow checks are explicit in the SSA.
+        BinaryOp::Add => numeric(lhs + rhs),
+        BinaryOp::Sub => numeric(lhs - rhs),
+        BinaryOp::Mul => numeric(lhs * rhs),
+        BinaryOp::Eq => Ok(InterpreterValue::bool(lhs == rhs)),
+        BinaryOp::Div | BinaryOp::Mod if rhs.is_zero() => {
+            if frame.side_effects_enabled {
+                Err(InterpreterError::DivisionByZero)
+            } else {
+                numeric(FieldElement::zero())
+            }
+        }
+        operator => match typ {
+            NumericType::NativeField => match operator {
+                BinaryOp::Div => numeric(lhs / rhs),
+                BinaryOp::Lt => Ok(InterpreterValue::bool(lhs < rhs)),
+                _ => Err(invalid(format!("`{operator}` cannot be applied to fields"))),
+            },
+            NumericType::Unsigned { .. } => {
+                let (lhs, rhs) = (to_biguint(lhs), to_biguint(rhs));
+                match operator {
+                    BinaryOp::Div => numeric(to_field(lhs / rhs)),
+                    BinaryOp::Mod => numeric(to_field(lhs % rhs)),
+                    BinaryOp::Lt => Ok(InterpreterValue::bool(lhs < rhs)),
+                    BinaryOp::And => numeric(to_field(lhs & rhs)),
+                    BinaryOp::Or => numeric(to_field(lhs | rhs)),
+                    BinaryOp::Xor => numeric(to_field(lhs ^ rhs)),
+                    BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Eq => {
+                        unreachable!("handled above")
+                    }
+                }
+            }
+            NumericType::Signed { bit_size } => {
+                if bit_size >= 127 {
+                    return Err(InterpreterError::Unsupported(format!("i{bit_size} arithmetic")));
+                }
+                let lhs = to_signed(lhs, bit_size);
+                let rhs = to_signed(rhs, bit_size);
+                let result = match operator {
+                    BinaryOp::Div => lhs.wrapping_div(rhs),
+                    BinaryOp::Mod => lhs.wrapping_rem(rhs),
+                    BinaryOp::Lt => return Ok(InterpreterValue::bool(lhs < rhs)),
+                    BinaryOp::And => lhs & rhs,
+                    BinaryOp::Or => lhs | rhs,
+                    BinaryOp::Xor => lhs ^ rhs,
+                    BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Eq => {
+                        unreachable!("handled above")
+                    }
+                };
+                numeric(from_signed(result, bit_size))
+            }
+        },
+    }
+}
+
+fn to_biguint(value: FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&value.to_be_bytes())
+}
+
+fn to_field(value: BigUint) -> FieldElement {
+    FieldElement::from_be_bytes_reduce(&value.to_bytes_be())
+}
+
+/// Interprets the lowest `bit_size` bits of `value` as a two's complement integer.
+fn to_signed(value: FieldElement, bit_size: u32) -> i128 {
+    let value = value.to_u128() & ((1u128 << bit_size) - 1);
+    if value < 1u128 << (bit_size - 1) {
+        value as i128
+    } else {
+        value as i128 - (1i128 << bit_size)
+    }
+}
+
+/// Encodes `value` as a `bit_size` bit two's complement integer.
+fn from_signed(value: i128, bit_size: u32) -> FieldElement {
+    let modulus = 1i128 << bit_size;
+    FieldElement::from(value.rem_euclid(modulus) as u128)
+}
+
+/// Casts `value` to `typ`, reducing it modulo the type's maximum value.
+fn cast(value: FieldElement, typ: NumericType) -> InterpreterValue {
+    let value = match typ {
+        NumericType::NativeField => value,
+        NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => {
+            to_field(to_biguint(value) % (BigUint::from(1u32) << bit_size))
+        }
+    };
+    InterpreterValue::Numeric { value, typ }
+}
+
+/// Reduces `value` modulo `2^bit_size`.
+///
+/// Field elements greater than half of the field modulus are treated as negative numbers,
+/// as ACIR generation does for truncations of subtractions.
+fn truncate(value: FieldElement, bit_size: u32) -> FieldElement {
+    let modulus = BigUint::from(1u32) << bit_size;
+    if to_biguint(value) > FieldElement::modulus() / 2u32 {
+        let negated = to_biguint(-value) % &modulus;
+        to_field((&modulus - negated) % modulus)
+    } else {
+        to_field(to_biguint(value) % modulus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, str::FromStr};
+
+    use acvm::FieldElement;
+
+    use crate::ssa::{
+        ir::types::{NumericType, Type},
+        ssa_gen::Ssa,
+    };
+
+    use super::{InterpreterError, InterpreterValue};
+
+    fn field(value: u128) -> InterpreterValue {
+        InterpreterValue::Numeric { value: value.into(), typ: NumericType::NativeField }
+    }
+
+    fn u8(value: u128) -> InterpreterValue {
+        InterpreterValue::Numeric {
+            value: value.into(),
+            typ: NumericType::Unsigned { bit_size: 8 },
+        }
+    }
+
+    fn interpret(
+        src: &str,
+        inputs: Vec<InterpreterValue>,
+    ) -> Result<Vec<InterpreterValue>, InterpreterError> {
+        Ssa::from_str(src).unwrap_or_else(|error| panic!("{error}")).interpret(inputs)
+    }
+
+    #[test]
+    fn executes_loops_and_calls() {
+        // Sums `double(i)` for `i` in `0..v0`.
+        let src = "
+acir fn main f0 {
+  b0(v0: u32):
+    jmp b1(u32 0, Field 0)
+  b1(v1: u32, v2: Field):
+    v3 = lt v1, v0
+    jmpif v3 then: b2, else: b3
+  b2():
+    v4 = cast v1 as Field
+    v5 = call f1(v4) -> Field
+    v6 = add v2, v5
+    v7 = add v1, u32 1
+    jmp b1(v7, v6)
+  b3():
+    return v2
+}
+brillig fn double f1 {
+  b0(v0: Field):
+    v1 = mul v0, Field 2
+    return v1
+}";
+        let input = InterpreterValue::Numeric {
+            value: FieldElement::from(4u128),
+            typ: NumericType::Unsigned { bit_size: 32 },
+        };
+        assert_eq!(interpret(src, vec![input]), Ok(vec![field(12)]));
+    }
+
+    #[test]
+    fn executes_references_and_arrays() {
+        let src = "
+acir fn main f0 {
+  b0(v0: [u8; 2], v1: u32):
+    v2 = allocate -> &mut [u8; 2]
+    store v0 at v2
+    v3 = load v2 -> [u8; 2]
+    v4 = array_set v3, index v1, value u8 7
+    store v4 at v2
+    v5 = load v2 -> [u8; 2]
+    v6 = array_get v5, index u32 0 -> u8
+    return v6, v5
+}";
+        let array = InterpreterValue::Array {
+            elements: vec![u8(1), u8(2)].into(),
+            typ: Type::Array(Rc::new(vec![Type::unsigned(8)]), 2),
+        };
+        let index = |index: u128| InterpreterValue::Numeric {
+            value: index.into(),
+            typ: NumericType::Unsigned { bit_size: 32 },
+        };
+
+        let results = interpret(src, vec![array.clone(), index(0)]).unwrap();
+        assert_eq!(results[0], u8(7));
+
+        let results = interpret(src, vec![array.clone(), index(1)]).unwrap();
+        assert_eq!(results[0], u8(1));
+
+        let error = interpret(src, vec![array, index(2)]).unwrap_err();
+        assert_eq!(error, InterpreterError::IndexOutOfBounds { index: 2, length: 2 });
+    }
+
+    #[test]
+    fn integer_overflow_is_caught_by_range_checks() {
+        let src = "
+acir fn main f0 {
+  b0(v0: u8, v1: u8):
+    v2 = sub v0, v1
+    range_check v2 to 8 bits 'attempt to subtract with overflow'
+    v3 = truncate v2 to 8 bits, max_bit_size: 9
+    return v3
+}";
+        assert_eq!(interpret(src, vec![u8(5), u8(3)]), Ok(vec![u8(2)]));
+
+        let message = Some("attempt to subtract with overflow".to_owned());
+        assert_eq!(
+            interpret(src, vec![u8(3), u8(5)]),
+            Err(InterpreterError::FailedConstraint { message })
+        );
+    }
+
+    #[test]
+    fn truncates_negative_values_as_twos_complement() {
+        let minus_two = -FieldElement::from(2u128);
+        assert_eq!(super::truncate(minus_two, 8), FieldElement::from(254u128));
+        assert_eq!(super::truncate(FieldElement::from(300u128), 8), FieldElement::from(44u128));
+    }
+}
@@ -0,0 +1,192 @@
+//! Differential testing of SSA passes.
+//!
+//! The unoptimized program is executed on a handful of generated inputs and the results recorded.
+//! After each pass the program is executed on the same inputs again, and any difference in behavior
+//! is attributed to that pass.
+use acvm::FieldElement;
+use iter_extended::vecmap;
+
+use crate::{
+    errors::InternalError,
+    ssa::{
+        ir::{
+            dfg::CallStack,
+            types::{NumericType, Type},
+        },
+        ssa_gen::Ssa,
+    },
+};
+
+use super::{InterpreterResult, InterpreterValue};
+
+/// The number of sets of inputs the program is executed on after each pass.
+const INPUT_SETS: u64 = 4;
+
+/// The recorded behavior of the unoptimized program on a number of generated inputs.
+pub(crate) struct DifferentialCheck {
+    runs: Vec<(Vec<InterpreterValue>, InterpreterResult<Vec<InterpreterValue>>)>,
+}
+
+impl DifferentialCheck {
+    /// Executes the unoptimized `ssa` on generated inputs, recording its behavior.
+    pub(crate) fn new(ssa: &Ssa) -> Self {
+        let main = ssa.main();
+        let parameter_types =
+            vecmap(main.parameters(), |parameter| main.dfg.type_of_value(*parameter));
+
+        let runs = (0..INPUT_SETS)
+            .filter_map(|seed| {
+                let mut generator = InputGenerator::new(seed);
+                let inputs: Vec<_> = parameter_types
+                    .iter()
+                    .map(|typ| generator.generate(typ))
+                    .collect::<Option<_>>()?;
+                let result = ssa.interpret(inputs.clone());
+
+                // Nothing can be learned from inputs on which the program's behavior is unknown.
+                match &result {
+                    Err(error) if error.is_inconclusive() => None,
+                    _ => Some((inputs, result)),
+                }
+            })
+            .collect();
+
+        DifferentialCheck { runs }
+    }
+
+    /// Executes `ssa` on each set of recorded inputs, returning an error naming `pass`
+    /// if the program no longer behaves as it did before optimization.
+    pub(crate) fn check(&self, ssa: &Ssa, pass: &str) -> Result<(), InternalError> {
+        for (inputs, expected) in &self.runs {
+            let actual = ssa.interpret(inputs.clone());
+            if matches!(&actual, Err(error) if error.is_inconclusive()) {
+                continue;
+            }
+
+            if !same_behavior(expected, &actual) {
+                let inputs = vecmap(inputs, ToString::to_string).join(", ");
+                let message = format!(
+                    "SSA pass `{pass}` changed the behavior of the program on inputs ({inputs}): expected {}, found {}",
+                    describe(expected),
+                    describe(&actual)
+                );
+                return Err(InternalError::General { message, call_stack: CallStack::new() });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Programs behave the same if they return the same values or if they both fail.
+///
+/// Passes may change which of a program's assertions fails first, so the failures themselves are not compared.
+fn same_behavior(
+    expected: &InterpreterResult<Vec<InterpreterValue>>,
+    actual: &InterpreterResult<Vec<InterpreterValue>>,
+) -> bool {
+    match (expected, actual) {
+        (Ok(expected), Ok(actual)) => expected == actual,
+        (Err(expected), Err(actual)) => {
+            expected == actual || (expected.is_program_failure() && actual.is_program_failure())
+        }
+        _ => false,
+    }
+}
+
+fn describe(result: &InterpreterResult<Vec<InterpreterValue>>) -> String {
+    match result {
+        Ok(values) => format!("({})", vecmap(values, ToString::to_string).join(", ")),
+        Err(error) => error.to_string(),
+    }
+}
+
+/// Generates deterministic inputs for a program's main function.
+///
+/// The first set of inputs is all zeros and the second all ones, as these commonly exercise edge
+/// cases such as division by zero. Further sets of inputs are pseudo-random.
+struct InputGenerator {
+    seed: u64,
+    state: u64,
+}
+
+impl InputGenerator {
+    fn new(seed: u64) -> Self {
+        // The xorshift state must be non-zero.
+        InputGenerator { seed, state: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1 }
+    }
+
+    /// Returns a value of type `typ`, or `None` if values of this type cannot be passed to main.
+    fn generate(&mut self, typ: &Type) -> Option<InterpreterValue> {
+        match typ {
+            Type::Numeric(typ) => {
+                Some(InterpreterValue::Numeric { value: self.numeric(*typ), typ: *typ })
+            }
+            Type::Array(element_types, length) => {
+                let mut elements = im::Vector::new();
+                for _ in 0..*length {
+                    for element_type in element_types.iter() {
+                        elements.push_back(self.generate(element_type)?);
+                    }
+                }
+                Some(InterpreterValue::Array { elements, typ: typ.clone() })
+            }
+            Type::Slice(_) | Type::Reference(_) | Type::Function => None,
+        }
+    }
+
+    fn numeric(&mut self, typ: NumericType) -> FieldElement {
+        match self.seed {
+            0 => FieldElement::zero(),
+            1 => FieldElement::one(),
+            _ => {
+                let bit_size = match typ {
+                    NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => {
+                        bit_size.min(64)
+                    }
+                    NumericType::NativeField => 64,
+                };
+                let mask = if bit_size == 64 { u64::MAX } else { (1 << bit_size) - 1 };
+                FieldElement::from((self.next() & mask) as u128)
+            }
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::ssa::ssa_gen::Ssa;
+
+    use super::DifferentialCheck;
+
+    #[test]
+    fn reports_pass_which_changes_behavior() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field, v1: Field):
+    v2 = add v0, v1
+    return v2
+}";
+        let miscompiled = "
+acir fn main f0 {
+  b0(v0: Field, v1: Field):
+    v2 = sub v0, v1
+    return v2
+}";
+        let ssa = Ssa::from_str(src).unwrap();
+        let check = DifferentialCheck::new(&ssa);
+        assert!(check.check(&ssa, "Nothing").is_ok());
+
+        let miscompiled = Ssa::from_str(miscompiled).unwrap();
+        let error = check.check(&miscompiled, "Miscompilation").unwrap_err();
+        assert!(error.to_string().contains("Miscompilation"), "{error}");
+    }
+}
@@ -0,0 +1,270 @@
+use acvm::{acir::BlackBoxFunc, BlackBoxResolutionError, FieldElement};
+use iter_extended::vecmap;
+
+use crate::ssa::ir::{
+    instruction::{Endian, InstructionId, Intrinsic},
+    types::{NumericType, Type},
+};
+
+use super::{
+    cast, invalid, to_biguint, Frame, InterpreterError, InterpreterResult, InterpreterValue,
+};
+
+/// Executes a call to `intrinsic`, returning its results.
+pub(super) fn call_intrinsic(
+    frame: &Frame,
+    instruction: InstructionId,
+    intrinsic: Intrinsic,
+    arguments: Vec<InterpreterValue>,
+) -> InterpreterResult<Vec<InterpreterValue>> {
+    let result_type = |position| frame.result_type(instruction, position);
+    let invalid_arguments = || invalid(format!("invalid arguments to {intrinsic}"));
+
+    match intrinsic {
+        Intrinsic::ArrayLen => match arguments.as_slice() {
+            // Slices are passed alongside their length
+            [length, InterpreterValue::Array { typ: Type::Slice(_), .. }] => {
+                Ok(vec![length.clone()])
+            }
+            [InterpreterValue::Array { typ: Type::Array(_, length), .. }] => {
+                Ok(vec![numeric(FieldElement::from(*length as u128), &result_type(0))?])
+            }
+            _ => Err(invalid_arguments()),
+        },
+        Intrinsic::AssertConstant => Ok(Vec::new()),
+        Intrinsic::StrAsBytes => Ok(arguments),
+        Intrinsic::AsField | Intrinsic::FromField => {
+            let Some(InterpreterValue::Numeric { value, .. }) = arguments.first() else {
+                return Err(invalid_arguments());
+            };
+            let Type::Numeric(typ) = result_type(0) else {
+                return Err(invalid_arguments());
+            };
+            Ok(vec![cast(*value, typ)])
+        }
+        Intrinsic::SlicePushBack => {
+            let (length, mut elements, typ) = slice_arguments(&arguments, intrinsic)?;
+            elements.extend(arguments[2..].iter().cloned());
+            Ok(vec![add_to_length(length, 1)?, InterpreterValue::Array { elements, typ }])
+        }
+        Intrinsic::SlicePushFront => {
+            let (length, mut elements, typ) = slice_arguments(&arguments, intrinsic)?;
+            for element in arguments[2..].iter().rev() {
+                elements.push_front(element.clone());
+            }
+            Ok(vec![add_to_length(length, 1)?, InterpreterValue::Array { elements, typ }])
+        }
+        Intrinsic::SlicePopBack => {
+            let (length, mut elements, typ) = slice_arguments(&arguments, intrinsic)?;
+            let popped = pop_elements(&mut elements, &typ, |elements| elements.pop_back())?;
+            let mut results =
+                vec![add_to_length(length, -1)?, InterpreterValue::Array { elements, typ }];
+            results.extend(popped.into_iter().rev());
+            Ok(results)
+        }
+        Intrinsic::SlicePopFront => {
+            let (length, mut elements, typ) = slice_arguments(&arguments, intrinsic)?;
+            let mut results = pop_elements(&mut elements, &typ, |elements| elements.pop_front())?;
+            results.push(add_to_length(length, -1)?);
+            results.push(InterpreterValue::Array { elements, typ });
+            Ok(results)
+        }
+        Intrinsic::SliceInsert => {
+            let (length, mut elements, typ) = slice_arguments(&arguments, intrinsic)?;
+            let new_elements = arguments.get(3..).ok_or_else(invalid_arguments)?;
+            let index = slice_index(&arguments, new_elements.len())?;
+            if index > elements.len() {
+                return Err(InterpreterError::IndexOutOfBounds { index, length: elements.len() });
+            }
+            for (offset, element) in new_elements.iter().enumerate() {
+                elements.insert(index + offset, element.clone());
+            }
+            Ok(vec![add_to_length(length, 1)?, InterpreterValue::Array { elements, typ }])
+        }
+        Intrinsic::SliceRemove => {
+            let (length, mut elements, typ) = slice_arguments(&arguments, intrinsic)?;
+            let element_size = typ.element_size();
+            let index = slice_index(&arguments, element_size)?;
+            if index + element_size > elements.len() {
+                return Err(InterpreterError::IndexOutOfBounds { index, length: elements.len() });
+            }
+            let removed = vecmap(0..element_size, |_| elements.remove(index));
+            let mut results =
+                vec![add_to_length(length, -1)?, InterpreterValue::Array { elements, typ }];
+            results.extend(removed);
+            Ok(results)
+        }
+        Intrinsic::ToBits(endian) => {
+            let (value, limb_count) = match arguments.as_slice() {
+                [value, limb_count] => (numeric_value(value)?, numeric_value(limb_count)?),
+                _ => return Err(invalid_arguments()),
+            };
+            to_radix(value, 2, limb_count, endian, &result_type(0), &result_type(1))
+        }
+        Intrinsic::ToRadix(endian) => {
+            let (value, radix, limb_count) = match arguments.as_slice() {
+                [value, radix, limb_count] => {
+                    (numeric_value(value)?, numeric_value(radix)?, numeric_value(limb_count)?)
+                }
+                _ => return Err(invalid_arguments()),
+            };
+            let radix = radix.try_to_u64().filter(|radix| (2..=256).contains(radix));
+            let radix = radix.ok_or_else(invalid_arguments)? as u32;
+            to_radix(value, radix, limb_count, endian, &result_type(0), &result_type(1))
+        }
+        Intrinsic::Sort => {
+            let Some(InterpreterValue::Array { elements, typ }) = arguments.first() else {
+                return Err(invalid_arguments());
+            };
+            let mut sorted = elements
+                .iter()
+                .cloned()
+                .map(|element| -> InterpreterResult<_> {
+                    let key = to_biguint(numeric_value(&element)?);
+                    Ok((key, element))
+                })
+                .collect::<InterpreterResult<Vec<_>>>()?;
+            sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let elements = sorted.into_iter().map(|(_, element)| element).collect();
+            Ok(vec![InterpreterValue::Array { elements, typ: typ.clone() }])
+        }
+        Intrinsic::BlackBox(function) => call_black_box(function, &arguments, &result_type(0)),
+    }
+}
+
+/// Splits the `(length, contents)` pair at the start of the arguments to a slice intrinsic.
+fn slice_arguments(
+    arguments: &[InterpreterValue],
+    intrinsic: Intrinsic,
+) -> InterpreterResult<(InterpreterValue, im::Vector<InterpreterValue>, Type)> {
+    match arguments {
+        [length, InterpreterValue::Array { elements, typ }, ..] => {
+            Ok((length.clone(), elements.clone(), typ.clone()))
+        }
+        _ => Err(invalid(format!("invalid arguments to {intrinsic}"))),
+    }
+}
+
+/// Returns the flattened index at which a slice element is inserted or removed.
+fn slice_index(arguments: &[InterpreterValue], element_size: usize) -> InterpreterResult<usize> {
+    let index = arguments.get(2).ok_or_else(|| invalid("missing slice index".to_owned()))?;
+    let index = numeric_value(index)?
+        .try_to_u64()
+        .ok_or_else(|| InterpreterError::Unsupported("slice index does not fit in a u64".into()))?;
+    Ok(index as usize * element_size)
+}
+
+/// Removes one (possibly composite) element from a slice using `pop`,
+/// returning its flattened values in the order they were removed.
+fn pop_elements(
+    elements: &mut im::Vector<InterpreterValue>,
+    typ: &Type,
+    mut pop: impl FnMut(&mut im::Vector<InterpreterValue>) -> Option<InterpreterValue>,
+) -> InterpreterResult<Vec<InterpreterValue>> {
+    let length = elements.len();
+    let popped = (0..typ.element_size())
+        .map(|_| pop(elements))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(InterpreterError::IndexOutOfBounds { index: length, length })?;
+    Ok(popped)
+}
+
+fn add_to_length(length: InterpreterValue, change: i8) -> InterpreterResult<InterpreterValue> {
+    let InterpreterValue::Numeric { value, typ } = length else {
+        return Err(invalid(format!("expected a slice length, found {length}")));
+    };
+    let value = if change < 0 {
+        value - FieldElement::from(change.unsigned_abs() as u128)
+    } else {
+        value + FieldElement::from(change as u128)
+    };
+    Ok(InterpreterValue::Numeric { value, typ })
+}
+
+fn numeric_value(value: &InterpreterValue) -> InterpreterResult<FieldElement> {
+    match value {
+        InterpreterValue::Numeric { value, .. } => Ok(*value),
+        other => Err(invalid(format!("expected a numeric value, found {other}"))),
+    }
+}
+
+fn numeric(value: FieldElement, typ: &Type) -> InterpreterResult<InterpreterValue> {
+    match typ {
+        Type::Numeric(typ) => Ok(InterpreterValue::Numeric { value, typ: *typ }),
+        other => Err(invalid(format!("expected a numeric type, found {other}"))),
+    }
+}
+
+/// Decomposes `value` into `limb_count` digits of the given `radix`, failing if it does not fit.
+fn to_radix(
+    value: FieldElement,
+    radix: u32,
+    limb_count: FieldElement,
+    endian: Endian,
+    length_type: &Type,
+    slice_type: &Type,
+) -> InterpreterResult<Vec<InterpreterValue>> {
+    let limb_count = limb_count.to_u128() as usize;
+    let mut digits = to_biguint(value).to_radix_le(radix);
+    if digits.len() > limb_count && digits.iter().skip(limb_count).any(|digit| *digit != 0) {
+        return Err(InterpreterError::FailedConstraint { message: None });
+    }
+    digits.resize(limb_count, 0);
+    if endian == Endian::Big {
+        digits.reverse();
+    }
+
+    let element_type = match slice_type {
+        Type::Slice(element_types) | Type::Array(element_types, _) if element_types.len() == 1 => {
+            &element_types[0]
+        }
+        other => return Err(invalid(format!("unexpected radix decomposition type {other}"))),
+    };
+    let elements = digits
+        .into_iter()
+        .map(|digit| numeric(FieldElement::from(digit as u128), element_type))
+        .collect::<InterpreterResult<_>>()?;
+
+    let length = numeric(FieldElement::from(limb_count as u128), length_type)?;
+    Ok(vec![length, InterpreterValue::Array { elements, typ: slice_type.clone() }])
+}
+
+fn call_black_box(
+    function: BlackBoxFunc,
+    arguments: &[InterpreterValue],
+    result_type: &Type,
+) -> InterpreterResult<Vec<InterpreterValue>> {
+    let hash_function: fn(&[u8]) -> Result<[u8; 32], BlackBoxResolutionError> = match function {
+        BlackBoxFunc::SHA256 => acvm::blackbox_solver::sha256,
+        BlackBoxFunc::Blake2s => acvm::blackbox_solver::blake2s,
+        BlackBoxFunc::Keccak256 => acvm::blackbox_solver::keccak256,
+        other => {
+            return Err(InterpreterError::Unsupported(format!("black box function {other}")));
+        }
+    };
+
+    let Some(InterpreterValue::Array { elements, .. }) = arguments.first() else {
+        return Err(invalid(format!("invalid arguments to {function}")));
+    };
+    let mut bytes = elements
+        .iter()
+        .map(|element| numeric_value(element).map(|byte| byte.to_u128() as u8))
+        .collect::<InterpreterResult<Vec<_>>>()?;
+    if function == BlackBoxFunc::Keccak256 {
+        // Keccak256 is also passed the number of bytes of its input to hash
+        let length = arguments.get(1).map(numeric_value).transpose()?;
+        let length = length.map_or(bytes.len(), |length| length.to_u128() as usize);
+        bytes.truncate(length);
+    }
+
+    let hash = hash_function(&bytes).map_err(|error| {
+        InterpreterError::Unsupported(format!("black box function {function} failed: {error}"))
+    })?;
+
+    let element_type = Type::Numeric(NumericType::Unsigned { bit_size: 8 });
+    let elements = hash
+        .into_iter()
+        .map(|byte| numeric(FieldElement::from(byte as u128), &element_type))
+        .collect::<InterpreterResult<_>>()?;
+    Ok(vec![InterpreterValue::Array { elements, typ: result_type.clone() }])
+}
@@ -14,7 +14,7 @@ use super::{
 };
 
 use acvm::FieldElement;
-use fxhash::FxHashMap as HashMap;
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use iter_extended::vecmap;
 use noirc_errors::Location;
 
@@ -22,7 +22,7 @@ use noirc_errors::Location;
 /// its blocks, instructions, and values. This struct is largely responsible for
 /// owning most data in a function and handing out Ids to this data that can be
 /// shared without worrying about ownership.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct DataFlowGraph {
     /// All of the instructions in a function
     instructions: DenseMap<Instruction>,
@@ -81,6 +81,19 @@ pub(crate) struct DataFlowGraph {
     /// may not have a corresponding location.
     locations: HashMap<InstructionId, CallStack>,
 
+    /// The call stacks of the array operations within each loop which has been unrolled.
+    ///
+    /// Unrolling leaves no trace of a loop in the SSA, so this is recorded for ACIR generation
+    /// to warn about array operations which are repeated by each iteration of a loop.
+    loop_array_call_stacks: HashSet<CallStack>,
+
+    /// The call stacks of calls to functions marked `#[no_inline]` which were inlined into an
+    /// ACIR function anyway, along with the name of each function.
+    ///
+    /// ACIR has no function calls, so this is recorded for ACIR generation to warn that the
+    /// attribute had no effect.
+    inlined_no_inline_calls: Vec<(String, CallStack)>,
+
     pub(crate) data_bus: DataBus,
 }
 
@@ -427,6 +440,27 @@ impl DataFlowGraph {
         self.locations.entry(instruction).or_default().push_back(location);
     }
 
+    /// Records that the array operation with the given call stack was within an unrolled loop.
+    pub(crate) fn add_loop_array_call_stack(&mut self, call_stack: CallStack) {
+        self.loop_array_call_stacks.insert(call_stack);
+    }
+
+    /// Returns true if the array operation with the given call stack was within an unrolled loop.
+    pub(crate) fn is_loop_array_call_stack(&self, call_stack: &CallStack) -> bool {
+        self.loop_array_call_stacks.contains(call_stack)
+    }
+
+    /// Records that a call to the `#[no_inline]` function with the given name was inlined.
+    pub(crate) fn add_inlined_no_inline_call(&mut self, name: String, call_stack: CallStack) {
+        self.inlined_no_inline_calls.push((name, call_stack));
+    }
+
+    /// Returns each call to a `#[no_inline]` function which was inlined, along with the name of
+    /// the function it called.
+    pub(crate) fn inlined_no_inline_calls(&self) -> &[(String, CallStack)] {
+        &self.inlined_no_inline_calls
+    }
+
     pub(crate) fn get_value_call_stack(&self, value: ValueId) -> CallStack {
         match &self.values[self.resolve(value)] {
             Value::Instruction { instruction, .. } => self.get_call_stack(*instruction),
@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
 use iter_extended::vecmap;
+use noirc_frontend::monomorphization::ast::InlineType;
 
 use super::basic_block::BasicBlockId;
 use super::dfg::DataFlowGraph;
@@ -23,7 +24,7 @@ pub(crate) enum RuntimeType {
 /// All functions outside of the current function are seen as external.
 /// To reference external functions its FunctionId can be used but this
 /// cannot be checked for correctness until inlining is performed.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Function {
     /// The first basic block in the function
     entry_block: BasicBlockId,
@@ -35,6 +36,9 @@ pub(crate) struct Function {
 
     runtime: RuntimeType,
 
+    /// Whether calls to this function should be inlined where the caller's runtime allows otherwise.
+    inline_type: InlineType,
+
     /// The DataFlowGraph holds the majority of data pertaining to the function
     /// including its blocks, instructions, and values.
     pub(crate) dfg: DataFlowGraph,
@@ -47,7 +51,19 @@ impl Function {
     pub(crate) fn new(name: String, id: FunctionId) -> Self {
         let mut dfg = DataFlowGraph::default();
         let entry_block = dfg.make_block();
-        Self { name, id, entry_block, dfg, runtime: RuntimeType::Acir }
+        Self {
+            name,
+            id,
+            entry_block,
+            dfg,
+            runtime: RuntimeType::Acir,
+            inline_type: InlineType::default(),
+        }
+    }
+
+    /// Creates a copy of `another` with the given id.
+    pub(crate) fn clone_with_id(id: FunctionId, another: &Function) -> Self {
+        Self { id, ..another.clone() }
     }
 
     /// The name of the function.
@@ -71,6 +87,16 @@ impl Function {
         self.runtime = runtime;
     }
 
+    /// Whether calls to this function should be inlined.
+    pub(crate) fn inline_type(&self) -> InlineType {
+        self.inline_type
+    }
+
+    /// Set whether calls to this function should be inlined.
+    pub(crate) fn set_inline_type(&mut self, inline_type: InlineType) {
+        self.inline_type = inline_type;
+    }
+
     /// Retrieves the entry block of a function.
     ///
     /// A function's entry block contains the instructions
@@ -5,6 +5,7 @@ use std::{
 };
 
 use iter_extended::vecmap;
+use noirc_frontend::monomorphization::ast::InlineType;
 
 use super::{
     basic_block::BasicBlockId,
@@ -15,7 +16,11 @@ use super::{
 
 /// Helper function for Function's Display impl to pretty-print the function with the given formatter.
 pub(crate) fn display_function(function: &Function, f: &mut Formatter) -> Result {
-    writeln!(f, "{} fn {} {} {{", function.runtime(), function.name(), function.id())?;
+    match function.inline_type() {
+        InlineType::Inline => write!(f, "{}", function.runtime())?,
+        InlineType::NoInline => write!(f, "{}({})", function.runtime(), function.inline_type())?,
+    }
+    writeln!(f, " fn {} {} {{", function.name(), function.id())?;
     display_block_with_successors(function, function.entry_block(), &mut HashSet::new(), f)?;
     write!(f, "}}")
 }
@@ -68,13 +73,12 @@ fn value(function: &Function, id: ValueId) -> String {
         }
         Value::Function(id) => id.to_string(),
         Value::Intrinsic(intrinsic) => intrinsic.to_string(),
-        Value::Array { array, .. } => {
+        Value::Array { array, typ } => {
             let elements = vecmap(array, |element| value(function, *element));
-            format!("[{}]", elements.join(", "))
-        }
-        Value::Param { .. } | Value::Instruction { .. } | Value::ForeignFunction(_) => {
-            id.to_string()
+            format!("[{}] of {typ}", elements.join(", "))
         }
+        Value::ForeignFunction(name) => name.clone(),
+        Value::Param { .. } | Value::Instruction { .. } => id.to_string(),
     }
 }
 
@@ -133,50 +137,84 @@ pub(crate) fn display_instruction(
         write!(f, "{} = ", value_list(function, results))?;
     }
 
+    let instruction_data = &function.dfg[instruction];
+    display_instruction_data(function, instruction_data, f)?;
+
+    // The types of these results cannot be inferred from the instruction itself
+    if instruction_data.requires_ctrl_typevars() && !results.is_empty() {
+        let types = vecmap(results, |result| function.dfg.type_of_value(*result).to_string());
+        if let [typ] = types.as_slice() {
+            write!(f, " -> {typ}")?;
+        } else {
+            write!(f, " -> ({})", types.join(", "))?;
+        }
+    }
+    writeln!(f)
+}
+
+/// Returns a single instruction as it would be displayed within its function, without indentation
+pub(crate) fn instruction_to_string(function: &Function, instruction: InstructionId) -> String {
+    struct DisplayInstruction<'f>(&'f Function, InstructionId);
+
+    impl std::fmt::Display for DisplayInstruction<'_> {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            display_instruction(self.0, self.1, f)
+        }
+    }
+
+    DisplayInstruction(function, instruction).to_string().trim().to_owned()
+}
+
+/// Display an instruction without its results or a trailing newline
+fn display_instruction_data(
+    function: &Function,
+    instruction: &Instruction,
+    f: &mut Formatter,
+) -> Result {
     let show = |id| value(function, id);
 
-    match &function.dfg[instruction] {
+    match instruction {
         Instruction::Binary(binary) => {
-            writeln!(f, "{} {}, {}", binary.operator, show(binary.lhs), show(binary.rhs))
+            write!(f, "{} {}, {}", binary.operator, show(binary.lhs), show(binary.rhs))
         }
-        Instruction::Cast(lhs, typ) => writeln!(f, "cast {} as {typ}", show(*lhs)),
-        Instruction::Not(rhs) => writeln!(f, "not {}", show(*rhs)),
+        Instruction::Cast(lhs, typ) => write!(f, "cast {} as {typ}", show(*lhs)),
+        Instruction::Not(rhs) => write!(f, "not {}", show(*rhs)),
         Instruction::Truncate { value, bit_size, max_bit_size } => {
             let value = show(*value);
-            writeln!(f, "truncate {value} to {bit_size} bits, max_bit_size: {max_bit_size}",)
+            write!(f, "truncate {value} to {bit_size} bits, max_bit_size: {max_bit_size}",)
         }
         Instruction::Constrain(lhs, rhs, message) => match message {
-            Some(message) => writeln!(f, "constrain {} == {} '{message}'", show(*lhs), show(*rhs)),
-            None => writeln!(f, "constrain {} == {}", show(*lhs), show(*rhs)),
+            Some(message) => write!(f, "constrain {} == {} '{message}'", show(*lhs), show(*rhs)),
+            None => write!(f, "constrain {} == {}", show(*lhs), show(*rhs)),
         },
         Instruction::Call { func, arguments } => {
-            writeln!(f, "call {}({})", show(*func), value_list(function, arguments))
+            write!(f, "call {}({})", show(*func), value_list(function, arguments))
         }
-        Instruction::Allocate => writeln!(f, "allocate"),
-        Instruction::Load { address } => writeln!(f, "load {}", show(*address)),
+        Instruction::Allocate => write!(f, "allocate"),
+        Instruction::Load { address } => write!(f, "load {}", show(*address)),
         Instruction::Store { address, value } => {
-            writeln!(f, "store {} at {}", show(*value), show(*address))
+            write!(f, "store {} at {}", show(*value), show(*address))
         }
         Instruction::EnableSideEffects { condition } => {
-            writeln!(f, "enable_side_effects {}", show(*condition))
+            write!(f, "enable_side_effects {}", show(*condition))
         }
         Instruction::ArrayGet { array, index } => {
-            writeln!(f, "array_get {}, index {}", show(*array), show(*index))
+            write!(f, "array_get {}, index {}", show(*array), show(*index))
         }
         Instruction::ArraySet { array, index, value } => {
-            writeln!(
-                f,
-                "array_set {}, index {}, value {}",
-                show(*array),
-                show(*index),
-                show(*value)
-            )
+            write!(f, "array_set {}, index {}, value {}", show(*array), show(*index), show(*value))
         }
         Instruction::IncrementRc { value } => {
-            writeln!(f, "inc_rc {}", show(*value))
+            write!(f, "inc_rc {}", show(*value))
         }
-        Instruction::RangeCheck { value, max_bit_size, .. } => {
-            writeln!(f, "range_check {} to {} bits", show(*value), *max_bit_size,)
+        Instruction::RangeCheck { value, max_bit_size, assert_message } => {
+            let value = show(*value);
+            match assert_message {
+                Some(message) => {
+                    write!(f, "range_check {value} to {max_bit_size} bits '{message}'")
+                }
+                None => write!(f, "range_check {value} to {max_bit_size} bits"),
+            }
         }
     }
 }
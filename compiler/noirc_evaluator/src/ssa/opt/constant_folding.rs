@@ -7,12 +7,17 @@
 //! - Check whether any input values have been constrained to be equal to a value of a simpler form
 //!   by a [constrain instruction][Instruction::Constrain]. If so, replace the input value with the simpler form.
 //! - Check whether the instruction is [pure][Instruction::is_pure()]
-//!   and there exists a duplicate instruction earlier in the same block.
+//!   and there exists a duplicate instruction earlier in the same block or in a block which dominates it.
 //!   If so, the instruction can be replaced with the results of this previous instruction.
+//! - Check whether the instruction is a [constrain][Instruction::Constrain] or [range check][Instruction::RangeCheck]
+//!   which is implied by a check earlier in the same block or in a block which dominates it. If so, it is removed.
 //!
 //! These operations are done in parallel so that they can each benefit from each other
 //! without the need for multiple passes.
 //!
+//! Blocks are visited in reverse post order, which guarantees that each block is visited after
+//! all of the blocks which dominate it.
+//!
 //! Other passes perform a certain amount of constant folding automatically as they insert instructions
 //! into the [`DataFlowGraph`] but this pass can become needed if [`DataFlowGraph::set_value`] or
 //! [`DataFlowGraph::set_value_from_id`] are used on a value which enables instructions dependent on the value to
@@ -20,16 +25,16 @@
 //!
 //! This is the only pass which removes duplicated pure [`Instruction`]s however and so is needed when
 //! different blocks are merged, i.e. after the [`flatten_cfg`][super::flatten_cfg] pass.
-use std::collections::HashSet;
-
 use iter_extended::vecmap;
 
 use crate::ssa::{
     ir::{
         basic_block::BasicBlockId,
         dfg::{DataFlowGraph, InsertInstructionResult},
+        dom::DominatorTree,
         function::Function,
         instruction::{Instruction, InstructionId},
+        post_order::PostOrder,
         value::ValueId,
     },
     ssa_gen::Ssa,
@@ -50,58 +55,69 @@ impl Ssa {
 }
 
 /// The structure of this pass is simple:
-/// Go through each block and re-insert all instructions.
+/// Go through each reachable block in reverse post order and re-insert all instructions.
 fn constant_fold(function: &mut Function) {
-    let mut context = Context::default();
-    context.block_queue.push(function.entry_block());
+    let mut post_order = PostOrder::with_function(function).into_vec();
+    post_order.reverse();
 
-    while let Some(block) = context.block_queue.pop() {
-        if context.visited_blocks.contains(&block) {
-            continue;
-        }
-
-        context.visited_blocks.insert(block);
+    let mut context = Context::new(function);
+    for block in post_order {
         context.fold_constants_in_block(function, block);
     }
 }
 
-#[derive(Default)]
 struct Context {
-    /// Maps pre-folded ValueIds to the new ValueIds obtained by re-inserting the instruction.
-    visited_blocks: HashSet<BasicBlockId>,
-    block_queue: Vec<BasicBlockId>,
+    dom_tree: DominatorTree,
+
+    /// Cache of instructions without any side-effects along with their outputs and the blocks
+    /// they were found in. The outputs may be reused by any block dominated by one of these blocks.
+    cached_instruction_results: HashMap<Instruction, Vec<(BasicBlockId, Vec<ValueId>)>>,
+
+    /// The blocks containing each constrain instruction which has been seen so far.
+    /// Assertion messages are removed from these constraints so that identical checks are found
+    /// regardless of their messages.
+    constraints: HashMap<Instruction, Vec<BasicBlockId>>,
+
+    /// The blocks containing a range check of each value, along with the bit size it was checked against.
+    range_checks: HashMap<ValueId, Vec<(BasicBlockId, u32)>>,
 }
 
 impl Context {
+    fn new(function: &Function) -> Self {
+        Context {
+            dom_tree: DominatorTree::with_function(function),
+            cached_instruction_results: HashMap::default(),
+            constraints: HashMap::default(),
+            range_checks: HashMap::default(),
+        }
+    }
+
     fn fold_constants_in_block(&mut self, function: &mut Function, block: BasicBlockId) {
         let instructions = function.dfg[block].take_instructions();
 
-        // Cache of instructions without any side-effects along with their outputs.
-        let mut cached_instruction_results: HashMap<Instruction, Vec<ValueId>> = HashMap::default();
-
         for instruction_id in instructions {
-            Self::fold_constants_into_instruction(
-                &mut function.dfg,
-                block,
-                instruction_id,
-                &mut cached_instruction_results,
-            );
+            self.fold_constants_into_instruction(&mut function.dfg, block, instruction_id);
         }
-        self.block_queue.extend(function.dfg[block].successors());
     }
 
     fn fold_constants_into_instruction(
+        &mut self,
         dfg: &mut DataFlowGraph,
         block: BasicBlockId,
         id: InstructionId,
-        instruction_result_cache: &mut HashMap<Instruction, Vec<ValueId>>,
     ) {
         let instruction = Self::resolve_instruction(id, dfg);
         let old_results = dfg.instruction_results(id).to_vec();
 
-        // If a copy of this instruction exists earlier in the block, then reuse the previous results.
-        if let Some(cached_results) = instruction_result_cache.get(&instruction) {
-            Self::replace_result_ids(dfg, &old_results, cached_results);
+        // If a copy of this instruction exists earlier in the block or in a dominating block,
+        // then reuse the previous results.
+        if let Some(cached_results) = self.get_cached_results(&instruction, block) {
+            Self::replace_result_ids(dfg, &old_results, &cached_results);
+            return;
+        }
+
+        // Checks which have already been performed on every path to this instruction can be removed.
+        if self.is_implied_by_earlier_check(&instruction, block) {
             return;
         }
 
@@ -110,7 +126,7 @@ impl Context {
 
         Self::replace_result_ids(dfg, &old_results, &new_results);
 
-        Self::cache_instruction(instruction, new_results, dfg, instruction_result_cache);
+        self.cache_instruction(instruction, new_results, block, dfg);
     }
 
     /// Fetches an [`Instruction`] by its [`InstructionId`] and fully resolves its inputs.
@@ -151,16 +167,70 @@ impl Context {
         new_results
     }
 
+    /// Returns the results of a copy of `instruction` in a block which dominates `block`, if any.
+    fn get_cached_results(
+        &mut self,
+        instruction: &Instruction,
+        block: BasicBlockId,
+    ) -> Option<Vec<ValueId>> {
+        let cached = self.cached_instruction_results.get(instruction)?;
+        let (_, results) = cached
+            .iter()
+            .find(|(cached_block, _)| self.dom_tree.dominates(*cached_block, block))?;
+        Some(results.clone())
+    }
+
+    /// Returns true if `instruction` is a check which is implied by a check
+    /// in `block` or in a block which dominates it.
+    fn is_implied_by_earlier_check(
+        &mut self,
+        instruction: &Instruction,
+        block: BasicBlockId,
+    ) -> bool {
+        let dom_tree = &mut self.dom_tree;
+        match instruction {
+            Instruction::Constrain(lhs, rhs, _) => {
+                let constraint = Instruction::Constrain(*lhs, *rhs, None);
+                let blocks = self.constraints.get(&constraint);
+                blocks.map_or(false, |blocks| {
+                    blocks.iter().any(|earlier| dom_tree.dominates(*earlier, block))
+                })
+            }
+            Instruction::RangeCheck { value, max_bit_size, .. } => {
+                // A value which fits in fewer bits also fits in `max_bit_size` bits
+                let checks = self.range_checks.get(value);
+                checks.map_or(false, |checks| {
+                    checks.iter().any(|(earlier, bit_size)| {
+                        bit_size <= max_bit_size && dom_tree.dominates(*earlier, block)
+                    })
+                })
+            }
+            _ => false,
+        }
+    }
+
     fn cache_instruction(
+        &mut self,
         instruction: Instruction,
         instruction_results: Vec<ValueId>,
+        block: BasicBlockId,
         dfg: &DataFlowGraph,
-        instruction_result_cache: &mut HashMap<Instruction, Vec<ValueId>>,
     ) {
-        // If the instruction doesn't have side-effects, cache the results so we can reuse them if
-        // the same instruction appears again later in the block.
-        if instruction.is_pure(dfg) {
-            instruction_result_cache.insert(instruction, instruction_results);
+        match instruction {
+            Instruction::Constrain(lhs, rhs, _) => {
+                let constraint = Instruction::Constrain(lhs, rhs, None);
+                self.constraints.entry(constraint).or_default().push(block);
+            }
+            Instruction::RangeCheck { value, max_bit_size, .. } => {
+                self.range_checks.entry(value).or_default().push((block, max_bit_size));
+            }
+            // If the instruction doesn't have side-effects, cache the results so we can reuse them if
+            // the same instruction appears again later in the block or in a block it dominates.
+            instruction if instruction.is_pure(dfg) => {
+                let cached = self.cached_instruction_results.entry(instruction).or_default();
+                cached.push((block, instruction_results));
+            }
+            _ => (),
         }
     }
 
@@ -189,6 +259,7 @@ mod test {
             types::Type,
             value::{Value, ValueId},
         },
+        parser::assert_ssa_pass,
     };
 
     #[test]
@@ -334,4 +405,78 @@ mod test {
 
         assert_eq!(instruction, &Instruction::Cast(ValueId::test_new(0), Type::unsigned(32)));
     }
+
+    #[test]
+    fn deduplicates_instructions_in_dominated_blocks() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: u1, v1: Field):
+    v2 = mul v1, v1
+    jmpif v0 then: b1, else: b2
+  b1():
+    v3 = mul v1, v1
+    v4 = add v3, Field 1
+    jmp b3(v4)
+  b2():
+    v5 = add v1, Field 1
+    jmp b3(v5)
+  b3(v6: Field):
+    v7 = add v1, Field 1
+    v8 = add v6, v7
+    return v2, v8
+}";
+        // `v3` is a copy of `v2` in the dominating entry block. `v7` is a copy of `v5`, but it
+        // cannot be replaced as `b2` does not dominate `b3`.
+        let expected = "
+brillig fn main f0 {
+  b0(v0: u1, v1: Field):
+    v2 = mul v1, v1
+    jmpif v0 then: b1, else: b2
+  b1():
+    v4 = add v2, Field 1
+    jmp b3(v4)
+  b2():
+    v5 = add v1, Field 1
+    jmp b3(v5)
+  b3(v6: Field):
+    v7 = add v1, Field 1
+    v8 = add v6, v7
+    return v2, v8
+}";
+        assert_ssa_pass("fold_constants", src, expected);
+    }
+
+    #[test]
+    fn removes_checks_implied_by_dominating_checks() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: u1, v1: Field, v2: Field):
+    constrain v1 == v2 'first'
+    range_check v1 to 8 bits
+    jmpif v0 then: b1, else: b2
+  b1():
+    constrain v1 == v2 'second'
+    range_check v1 to 16 bits
+    range_check v2 to 8 bits
+    jmp b2()
+  b2():
+    range_check v1 to 4 bits
+    return
+}";
+        // Only checks which are at least as strict as those already performed remain.
+        let expected = "
+brillig fn main f0 {
+  b0(v0: u1, v1: Field, v2: Field):
+    constrain v1 == v2 'first'
+    range_check v1 to 8 bits
+    jmpif v0 then: b1, else: b2
+  b1():
+    range_check v2 to 8 bits
+    jmp b2()
+  b2():
+    range_check v1 to 4 bits
+    return
+}";
+        assert_ssa_pass("fold_constants", src, expected);
+    }
 }
@@ -2,9 +2,10 @@
 //! The purpose of this pass is to inline the instructions of each function call
 //! within the function caller. If all function calls are known, there will only
 //! be a single function remaining when the pass finishes.
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use iter_extended::{btree_map, vecmap};
+use noirc_frontend::monomorphization::ast::InlineType;
 
 use crate::ssa::{
     function_builder::FunctionBuilder,
@@ -23,6 +24,10 @@ use fxhash::FxHashMap as HashMap;
 /// frames at any point in time.
 const RECURSION_LIMIT: u32 = 1000;
 
+/// Functions with at least this many instructions which are called from more than one
+/// place are called from Brillig functions rather than being inlined into them.
+const INLINE_INSTRUCTION_LIMIT: usize = 500;
+
 impl Ssa {
     /// Inline all functions within the IR.
     ///
@@ -36,8 +41,15 @@ impl Ssa {
     /// changes. This is because if the function's id later becomes known by a later
     /// pass, we would need to re-run all of inlining anyway to inline it, so we might
     /// as well save the work for later instead of performing it twice.
+    ///
+    /// ACIR has no way to call a function, so every function called from an ACIR function is
+    /// inlined into it. Brillig functions may call each other, so calls from Brillig functions to
+    /// functions which should not be inlined are instead redirected to a Brillig copy of the
+    /// function, which is kept as an entry point of its own.
     #[tracing::instrument(level = "trace", skip(self))]
     pub(crate) fn inline_functions(mut self) -> Ssa {
+        self.redirect_brillig_calls_to_uninlined_functions();
+
         self.functions = btree_map(get_entry_point_functions(&self), |entry_point| {
             let new_function = InlineContext::new(&self, entry_point).inline_all(&self);
             (entry_point, new_function)
@@ -45,6 +57,98 @@ impl Ssa {
 
         self
     }
+
+    /// Redirects each call from a Brillig function to an ACIR function which should not be
+    /// inlined to a Brillig copy of that function.
+    fn redirect_brillig_calls_to_uninlined_functions(&mut self) {
+        let uninlined_functions = self.uninlined_functions();
+        if uninlined_functions.is_empty() {
+            return;
+        }
+
+        let mut brillig_copies: HashMap<FunctionId, FunctionId> = HashMap::default();
+        let mut callers: Vec<FunctionId> = self
+            .functions
+            .iter()
+            .filter(|(_, function)| function.runtime() == RuntimeType::Brillig)
+            .map(|(id, _)| *id)
+            .collect();
+
+        // Copies may call other uninlined functions in turn, so they are redirected as well
+        while let Some(caller) = callers.pop() {
+            let function = &self.functions[&caller];
+            let mut callees = BTreeMap::new();
+            for (call, callee) in called_functions(function) {
+                if let Instruction::Call { func, .. } = &function.dfg[call] {
+                    callees.insert(*func, callee);
+                }
+            }
+
+            for (func, callee) in callees {
+                if !uninlined_functions.contains(&callee) {
+                    continue;
+                }
+                let copy_id = match brillig_copies.get(&callee) {
+                    Some(copy_id) => *copy_id,
+                    None => {
+                        let copy_id = self.next_id.next();
+                        let mut copy = Function::clone_with_id(copy_id, &self.functions[&callee]);
+                        copy.set_runtime(RuntimeType::Brillig);
+                        self.functions.insert(copy_id, copy);
+                        brillig_copies.insert(callee, copy_id);
+                        callers.push(copy_id);
+                        copy_id
+                    }
+                };
+                let dfg = &mut self.functions.get_mut(&caller).expect("caller exists").dfg;
+                let copy = dfg.import_function(copy_id);
+                dfg.set_value_from_id(func, copy);
+            }
+        }
+    }
+
+    /// Returns the ACIR functions which should not be inlined into Brillig functions. These are
+    /// the functions marked `#[no_inline]`, along with any functions which are large enough and
+    /// called from enough places that inlining them would duplicate a significant amount of code.
+    fn uninlined_functions(&self) -> BTreeSet<FunctionId> {
+        let mut call_counts: HashMap<FunctionId, usize> = HashMap::default();
+        for function in self.functions.values() {
+            for (_, callee) in called_functions(function) {
+                *call_counts.entry(callee).or_default() += 1;
+            }
+        }
+
+        self.functions
+            .iter()
+            .filter(|(id, function)| {
+                let call_count = call_counts.get(*id).copied().unwrap_or_default();
+                function.runtime() == RuntimeType::Acir
+                    && match function.inline_type() {
+                        InlineType::NoInline => true,
+                        InlineType::Inline => {
+                            call_count > 1
+                                && function.dfg.num_instructions() >= INLINE_INSTRUCTION_LIMIT
+                        }
+                    }
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+/// Returns each call to a known function within `function`, along with the function it calls.
+fn called_functions(function: &Function) -> Vec<(InstructionId, FunctionId)> {
+    let mut calls = Vec::new();
+    for block in function.reachable_blocks() {
+        for instruction in function.dfg[block].instructions() {
+            if let Instruction::Call { func, .. } = &function.dfg[*instruction] {
+                if let Value::Function(callee) = &function.dfg[*func] {
+                    calls.push((*instruction, *callee));
+                }
+            }
+        }
+    }
+    calls
 }
 
 /// The context for the function inlining pass.
@@ -115,7 +219,9 @@ impl InlineContext {
     /// that could not be inlined calling it.
     fn new(ssa: &Ssa, entry_point: FunctionId) -> InlineContext {
         let source = &ssa.functions[&entry_point];
-        let builder = FunctionBuilder::new(source.name().to_owned(), entry_point, source.runtime());
+        let mut builder =
+            FunctionBuilder::new(source.name().to_owned(), entry_point, source.runtime());
+        builder.current_function.set_inline_type(source.inline_type());
         Self { builder, recursion_level: 0, entry_point, call_stack: CallStack::new() }
     }
 
@@ -371,6 +477,17 @@ impl<'function> PerFunctionContext<'function> {
         let old_results = self.source_function.dfg.instruction_results(call_id);
         let arguments = vecmap(arguments, |arg| self.translate_value(*arg));
 
+        // ACIR has no function calls, so `#[no_inline]` has no effect on calls from it
+        let callee = &ssa.functions[&function];
+        if callee.inline_type() == InlineType::NoInline
+            && self.context.builder.current_function.runtime() == RuntimeType::Acir
+        {
+            let mut call_stack = self.context.call_stack.clone();
+            call_stack.append(self.source_function.dfg.get_call_stack(call_id));
+            let dfg = &mut self.context.builder.current_function.dfg;
+            dfg.add_inlined_no_inline_call(callee.name().to_owned(), call_stack);
+        }
+
         let mut call_stack = self.source_function.dfg.get_call_stack(call_id);
         let has_location = !call_stack.is_empty();
 
@@ -508,7 +625,10 @@ impl<'function> PerFunctionContext<'function> {
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
     use acvm::FieldElement;
+    use iter_extended::vecmap;
 
     use crate::ssa::{
         function_builder::FunctionBuilder,
@@ -519,6 +639,8 @@ mod test {
             map::Id,
             types::Type,
         },
+        parser::assert_ssa_pass,
+        ssa_gen::Ssa,
     };
 
     #[test]
@@ -787,4 +909,77 @@ mod test {
         let main = ssa.main();
         assert_eq!(main.reachable_blocks().len(), 4);
     }
+
+    #[test]
+    fn brillig_calls_to_no_inline_functions_are_kept() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field):
+    v1 = call f1(v0) -> Field
+    v2 = call f2(v1) -> Field
+    return v2
+}
+brillig fn helper f1 {
+  b0(v0: Field):
+    v1 = call f2(v0) -> Field
+    v2 = call f2(v1) -> Field
+    return v2
+}
+acir(no_inline) fn double f2 {
+  b0(v0: Field):
+    v1 = add v0, v0
+    return v1
+}";
+        // `double` is still inlined into `main`, as ACIR functions cannot call other functions,
+        // but `helper` calls a Brillig copy of it.
+        let expected = "
+acir fn main f0 {
+  b0(v0: Field):
+    v1 = call f1(v0) -> Field
+    v2 = add v1, v1
+    return v2
+}
+brillig fn helper f1 {
+  b0(v0: Field):
+    v1 = call f3(v0) -> Field
+    v2 = call f3(v1) -> Field
+    return v2
+}
+brillig(no_inline) fn double f3 {
+  b0(v0: Field):
+    v1 = add v0, v0
+    return v1
+}";
+        assert_ssa_pass("inline_functions", src, expected);
+    }
+
+    #[test]
+    fn acir_calls_to_no_inline_functions_are_recorded() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field):
+    v1 = call f1(v0) -> Field
+    v2 = call f2(v1) -> Field
+    return v2
+}
+brillig fn helper f1 {
+  b0(v0: Field):
+    v1 = call f2(v0) -> Field
+    return v1
+}
+acir(no_inline) fn double f2 {
+  b0(v0: Field):
+    v1 = add v0, v0
+    return v1
+}";
+        // Only the call from `main` is inlined, so only it is recorded to warn about
+        let ssa = Ssa::from_str(src).unwrap_or_else(|error| panic!("{error}")).inline_functions();
+        let names = vecmap(ssa.main().dfg.inlined_no_inline_calls(), |(name, _)| name.as_str());
+        assert_eq!(names, vec!["double"]);
+        for function in ssa.functions.values() {
+            if function.id() != ssa.main_id {
+                assert!(function.dfg.inlined_no_inline_calls().is_empty());
+            }
+        }
+    }
 }
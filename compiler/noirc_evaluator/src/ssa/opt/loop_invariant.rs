@@ -0,0 +1,401 @@
+//! Loop Invariant Code Motion (LICM) pass: hoists instructions whose operands do not change
+//! between iterations of a loop into the loop's pre-header, so that they are only executed once.
+//!
+//! ACIR functions have their loops unrolled, so this is only useful for Brillig functions.
+//! Since a hoisted instruction is executed even if the loop body never is, only instructions
+//! which can never fail are hoisted. Anything which may halt execution, such as a constraint or
+//! a division by a non-constant value, is left in the loop.
+use std::collections::HashSet;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, TerminatorInstruction},
+        post_order::PostOrder,
+        types::Type,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+use super::unrolling::{find_blocks_in_loop, Loop};
+
+impl Ssa {
+    /// Hoists loop invariant instructions out of the loops of each Brillig function.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn loop_invariant_code_motion(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                hoist_loop_invariants(function);
+            }
+        }
+        self
+    }
+}
+
+fn hoist_loop_invariants(function: &mut Function) {
+    let cfg = ControlFlowGraph::with_function(function);
+    let post_order = PostOrder::with_function(function);
+    let mut dom_tree = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+
+    let mut loops = Vec::new();
+    for block in post_order.as_slice() {
+        for predecessor in cfg.predecessors(*block) {
+            if dom_tree.is_reachable(predecessor) && dom_tree.dominates(*block, predecessor) {
+                // predecessor -> block is the back-edge of a loop
+                loops.push(find_blocks_in_loop(*block, predecessor, &cfg));
+            }
+        }
+    }
+
+    // Handle inner loops first so that their invariants, once hoisted into the body of
+    // an outer loop, may be hoisted again if they are also invariant in the outer loop.
+    loops.sort_by_key(|loop_| loop_.blocks.len());
+
+    // Blocks are visited in reverse post order so that each instruction is visited
+    // after any instructions in the loop which it may depend upon.
+    let reverse_post_order: Vec<_> = post_order.as_slice().iter().rev().copied().collect();
+
+    for loop_ in loops {
+        if let Some(pre_header) = get_pre_header(function, &cfg, &loop_) {
+            let blocks = reverse_post_order.iter().filter(|block| loop_.blocks.contains(block));
+            hoist_from_loop(function, &loop_, blocks.copied().collect(), pre_header);
+        }
+    }
+}
+
+/// Returns the block which all entries into the loop come from, if it only ever jumps to the
+/// loop header. Instructions hoisted into it will then only be executed if the loop is reached.
+fn get_pre_header(
+    function: &Function,
+    cfg: &ControlFlowGraph,
+    loop_: &Loop,
+) -> Option<BasicBlockId> {
+    let mut entries = cfg.predecessors(loop_.header).filter(|block| !loop_.blocks.contains(block));
+    let pre_header = entries.next()?;
+    if entries.next().is_some() {
+        return None;
+    }
+
+    match function.dfg[pre_header].terminator() {
+        Some(TerminatorInstruction::Jmp { destination, .. }) if *destination == loop_.header => {
+            Some(pre_header)
+        }
+        _ => None,
+    }
+}
+
+/// Moves each instruction in `blocks` which is invariant in the loop to the end of `pre_header`.
+fn hoist_from_loop(
+    function: &mut Function,
+    loop_: &Loop,
+    blocks: Vec<BasicBlockId>,
+    pre_header: BasicBlockId,
+) {
+    // The results of each instruction which is left in the loop
+    let mut defined_in_loop = HashSet::new();
+    for block in &blocks {
+        for instruction in function.dfg[*block].instructions() {
+            defined_in_loop.extend(function.dfg.instruction_results(*instruction).iter().copied());
+        }
+    }
+
+    for block in blocks {
+        let mut hoisted = Vec::new();
+        for instruction_id in function.dfg[block].instructions() {
+            let instruction = &function.dfg[*instruction_id];
+            let mut is_invariant = true;
+            instruction.for_each_value(|value| {
+                is_invariant &= is_loop_invariant(function, loop_, &defined_in_loop, value);
+            });
+
+            if is_invariant && can_hoist(function, *instruction_id) {
+                for result in function.dfg.instruction_results(*instruction_id) {
+                    defined_in_loop.remove(result);
+                }
+                hoisted.push(*instruction_id);
+            }
+        }
+
+        if !hoisted.is_empty() {
+            function.dfg[block]
+                .instructions_mut()
+                .retain(|instruction| !hoisted.contains(instruction));
+            for instruction in hoisted {
+                function.dfg[pre_header].insert_instruction(instruction);
+            }
+        }
+    }
+}
+
+/// A value is invariant in a loop if it is not computed by any instruction or block parameter within it.
+fn is_loop_invariant(
+    function: &Function,
+    loop_: &Loop,
+    defined_in_loop: &HashSet<ValueId>,
+    value: ValueId,
+) -> bool {
+    let value = function.dfg.resolve(value);
+    match &function.dfg[value] {
+        Value::Instruction { .. } => !defined_in_loop.contains(&value),
+        Value::Param { block, .. } => !loop_.blocks.contains(block),
+        Value::Array { array, .. } => array
+            .iter()
+            .all(|element| is_loop_invariant(function, loop_, defined_in_loop, *element)),
+        Value::NumericConstant { .. }
+        | Value::Function(_)
+        | Value::Intrinsic(_)
+        | Value::ForeignFunction(_) => true,
+    }
+}
+
+/// Returns true if `instruction` always succeeds and its results only depend upon its operands.
+fn can_hoist(function: &Function, instruction: InstructionId) -> bool {
+    let dfg = &function.dfg;
+
+    // Brillig may mutate an array in place if it is not referenced elsewhere, so each
+    // iteration of the loop must create its own copy of any array it writes to.
+    let returns_array = dfg
+        .instruction_results(instruction)
+        .iter()
+        .any(|result| matches!(dfg.type_of_value(*result), Type::Array(..) | Type::Slice(_)));
+    if returns_array {
+        return false;
+    }
+
+    let instruction = &dfg[instruction];
+    match instruction {
+        // Divisions are only safe to hoist if they are by a non-zero constant
+        Instruction::Binary(_) => !instruction.has_side_effects(dfg),
+        Instruction::Cast(..) | Instruction::Not(_) | Instruction::Truncate { .. } => true,
+
+        // Array reads are only hoisted if they can never be out of bounds
+        Instruction::ArrayGet { array, index } => {
+            let length = match dfg.type_of_value(*array) {
+                Type::Array(element_types, length) => element_types.len() * length,
+                _ => return false,
+            };
+            dfg.get_numeric_constant(*index).map_or(false, |index| index.to_u128() < length as u128)
+        }
+
+        // Slice intrinsics, which may fail if the slice is too short, are excluded as they return arrays
+        Instruction::Call { func, .. } => match dfg[*func] {
+            Value::Intrinsic(intrinsic) => !intrinsic.has_side_effects(),
+            _ => false,
+        },
+
+        // These either interact with memory or may halt execution
+        Instruction::Constrain(..)
+        | Instruction::RangeCheck { .. }
+        | Instruction::EnableSideEffects { .. }
+        | Instruction::IncrementRc { .. }
+        | Instruction::Allocate
+        | Instruction::Load { .. }
+        | Instruction::Store { .. }
+        | Instruction::ArraySet { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use acvm::{
+        acir::brillig::RegisterIndex,
+        brillig_vm::{brillig::Value, Registers, VMStatus, VM},
+        FieldElement,
+    };
+    use iter_extended::vecmap;
+
+    use crate::{
+        brillig::{
+            brillig_gen::brillig_fn::FunctionContext, brillig_ir::tests::DummyBlackBoxSolver,
+            brillig_ir::BrilligContext,
+        },
+        ssa::{parser::assert_ssa_pass, ssa_gen::Ssa},
+    };
+
+    /// Executes the Brillig `main` function of `ssa` on `inputs`, returning its
+    /// first return value and the number of opcodes executed.
+    fn execute(ssa: &Ssa, inputs: &[u128]) -> (FieldElement, usize) {
+        let brillig = ssa.to_brillig(false, false);
+        let main = ssa.main();
+        let mut entry_point = BrilligContext::new_entry_point_artifact(
+            FunctionContext::parameters(main).unwrap(),
+            FunctionContext::return_values(main).unwrap(),
+            FunctionContext::function_id_to_function_label(main.id()),
+        );
+        while let Some(label) = entry_point.first_unresolved_function_call() {
+            let artifact = brillig.find_by_function_label(label).expect("function should exist");
+            entry_point.link_with(artifact);
+        }
+        let bytecode = entry_point.finish().byte_code;
+
+        let registers = Registers { inner: vecmap(inputs, |input| Value::from(*input)) };
+        let mut vm = VM::new(registers, Vec::new(), &bytecode, Vec::new(), &DummyBlackBoxSolver);
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+        (vm.get_registers().get(RegisterIndex::from(0)).to_field(), vm.step_count())
+    }
+
+    // fn main(x: Field, y: Field) -> Field {
+    //     let mut sum = 0;
+    //     for i in 0..10 {
+    //         sum += x * y + i as Field;
+    //     }
+    //     sum
+    // }
+    const SUM_LOOP: &str = "
+brillig fn main f0 {
+  b0(v0: Field, v1: Field):
+    jmp b1(u32 0, Field 0)
+  b1(v2: u32, v3: Field):
+    v4 = lt v2, u32 10
+    jmpif v4 then: b2, else: b3
+  b2():
+    v5 = mul v0, v1
+    v6 = cast v2 as Field
+    v7 = add v5, v6
+    v8 = add v3, v7
+    v9 = add v2, u32 1
+    jmp b1(v9, v8)
+  b3():
+    return v3
+}";
+
+    #[test]
+    fn hoists_invariant_instructions() {
+        let expected = "
+brillig fn main f0 {
+  b0(v0: Field, v1: Field):
+    v5 = mul v0, v1
+    jmp b1(u32 0, Field 0)
+  b1(v2: u32, v3: Field):
+    v4 = lt v2, u32 10
+    jmpif v4 then: b2, else: b3
+  b2():
+    v6 = cast v2 as Field
+    v7 = add v5, v6
+    v8 = add v3, v7
+    v9 = add v2, u32 1
+    jmp b1(v9, v8)
+  b3():
+    return v3
+}";
+        assert_ssa_pass("loop_invariant_code_motion", SUM_LOOP, expected);
+    }
+
+    #[test]
+    fn hoisting_reduces_executed_opcodes() {
+        let ssa = Ssa::from_str(SUM_LOOP).unwrap();
+        let (expected_result, steps_before) = execute(&ssa, &[3, 4]);
+        assert_eq!(expected_result, FieldElement::from(165_u128));
+
+        let ssa = ssa.loop_invariant_code_motion();
+        let (result, steps_after) = execute(&ssa, &[3, 4]);
+        assert_eq!(result, expected_result);
+        assert!(
+            steps_after < steps_before,
+            "expected fewer than {steps_before} opcodes to be executed, found {steps_after}"
+        );
+    }
+
+    #[test]
+    fn hoists_chains_of_invariants_out_of_nested_loops() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: Field):
+    jmp b1(u32 0)
+  b1(v1: u32):
+    v2 = lt v1, u32 4
+    jmpif v2 then: b2, else: b3
+  b2():
+    jmp b4(u32 0)
+  b3():
+    return
+  b4(v3: u32):
+    v4 = lt v3, u32 4
+    jmpif v4 then: b5, else: b6
+  b5():
+    v5 = mul v0, v0
+    v6 = add v5, Field 1
+    v7 = cast v1 as Field
+    v8 = add v5, v7
+    v9 = add v3, u32 1
+    jmp b4(v9)
+  b6():
+    v10 = add v1, u32 1
+    jmp b1(v10)
+}";
+        // `v5` and `v6` are invariant in both loops, whereas `v8` depends upon the outer loop's index.
+        let expected = "
+brillig fn main f0 {
+  b0(v0: Field):
+    v5 = mul v0, v0
+    v6 = add v5, Field 1
+    jmp b1(u32 0)
+  b1(v1: u32):
+    v2 = lt v1, u32 4
+    jmpif v2 then: b2, else: b3
+  b2():
+    v7 = cast v1 as Field
+    v8 = add v5, v7
+    jmp b4(u32 0)
+  b3():
+    return
+  b4(v3: u32):
+    v4 = lt v3, u32 4
+    jmpif v4 then: b5, else: b6
+  b5():
+    v9 = add v3, u32 1
+    jmp b4(v9)
+  b6():
+    v10 = add v1, u32 1
+    jmp b1(v10)
+}";
+        assert_ssa_pass("loop_invariant_code_motion", src, expected);
+    }
+
+    #[test]
+    fn does_not_hoist_instructions_which_may_fail() {
+        // The loop may never execute, in which case neither the division
+        // nor the constraint should cause the program to fail.
+        let src = "
+brillig fn main f0 {
+  b0(v0: u32, v1: u32):
+    jmp b1(u32 0)
+  b1(v2: u32):
+    v3 = lt v2, v1
+    jmpif v3 then: b2, else: b3
+  b2():
+    v4 = div v0, v1
+    constrain v0 == u32 5
+    v5 = add v2, u32 1
+    jmp b1(v5)
+  b3():
+    return
+}";
+        assert_ssa_pass("loop_invariant_code_motion", src, src);
+    }
+
+    #[test]
+    fn does_not_modify_acir_functions() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field):
+    jmp b1(u32 0)
+  b1(v1: u32):
+    v2 = lt v1, u32 4
+    jmpif v2 then: b2, else: b3
+  b2():
+    v3 = mul v0, v0
+    v4 = add v1, u32 1
+    jmp b1(v4)
+  b3():
+    return
+}";
+        assert_ssa_pass("loop_invariant_code_motion", src, src);
+    }
+}
@@ -11,6 +11,8 @@ mod die;
 mod fill_internal_slices;
 pub(crate) mod flatten_cfg;
 mod inlining;
+mod loop_invariant;
 mod mem2reg;
+mod range_analysis;
 mod simplify_cfg;
 mod unrolling;
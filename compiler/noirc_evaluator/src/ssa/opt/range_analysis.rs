@@ -0,0 +1,303 @@
+//! This file contains a pass which removes [truncations][Instruction::Truncate] and
+//! [range checks][Instruction::RangeCheck] which are known to be unnecessary.
+//!
+//! Integer arithmetic is checked conservatively when generating SSA: every operation which may
+//! overflow is followed by a range check or truncation, even if its operands are small enough that
+//! it never can. This pass tracks an upper bound on the number of bits of each value, from:
+//! - constants,
+//! - the parameters of `main`, which are range constrained by ACIR generation,
+//! - the results of [binary][Instruction::Binary] operations, casts and truncations, given bounds on their operands,
+//! - range checks earlier in the same block, which bound the value they check from then on.
+//!
+//! A truncation of a value which already fits into the truncated bit size is replaced by the value itself,
+//! and a range check of a value which already fits into the checked bit size is removed.
+//!
+//! Values are treated as integers less than the field modulus, so bounds only hold while they
+//! cannot wrap around it. This is only valid for ACIR functions, as Brillig performs integer
+//! arithmetic modulo the bit size of its operands.
+use acvm::FieldElement;
+use fxhash::FxHashMap as HashMap;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::DataFlowGraph,
+        function::{Function, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction, InstructionId},
+        post_order::PostOrder,
+        types::{NumericType, Type},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+/// The largest number of bits a value may have while being guaranteed to be less than the field modulus.
+const MAX_BITS: u32 = FieldElement::max_num_bits() - 1;
+
+impl Ssa {
+    /// Removes truncations and range checks of values which are known to
+    /// already fit within the bit size they are truncated or checked to.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn remove_redundant_range_checks(mut self) -> Ssa {
+        let main_id = self.main_id;
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Acir {
+                let is_main = function.id() == main_id;
+                Context::new(function, is_main).remove_redundant_range_checks(function);
+            }
+        }
+        self
+    }
+}
+
+struct Context {
+    /// An upper bound on the number of bits of each value computed by an instruction, or of the
+    /// parameters of `main`. These bounds hold everywhere the value is used.
+    max_bits: HashMap<ValueId, u32>,
+}
+
+impl Context {
+    fn new(function: &Function, is_main: bool) -> Self {
+        let mut max_bits = HashMap::default();
+
+        // The parameters of `main` are range constrained when they are converted into witnesses
+        if is_main {
+            for parameter in function.parameters() {
+                if let Type::Numeric(
+                    NumericType::Unsigned { bit_size } | NumericType::Signed { bit_size },
+                ) = function.dfg.type_of_value(*parameter)
+                {
+                    max_bits.insert(*parameter, bit_size);
+                }
+            }
+        }
+
+        Context { max_bits }
+    }
+
+    fn remove_redundant_range_checks(&mut self, function: &mut Function) {
+        // Blocks are visited in reverse post order, so that the definitions of all
+        // values used by a block, other than its parameters, have been visited first.
+        let mut blocks = PostOrder::with_function(function).into_vec();
+        blocks.reverse();
+
+        for block in blocks {
+            self.remove_redundant_range_checks_in_block(&mut function.dfg, block);
+        }
+    }
+
+    fn remove_redundant_range_checks_in_block(
+        &mut self,
+        dfg: &mut DataFlowGraph,
+        block: BasicBlockId,
+    ) {
+        // Bounds on values established by range checks in this block, which only
+        // hold for the instructions following the range check.
+        let mut checked_bits = HashMap::default();
+
+        let instructions = dfg[block].take_instructions();
+        let mut remaining_instructions = Vec::with_capacity(instructions.len());
+
+        for instruction_id in instructions {
+            let bits = |value| self.value_bits(dfg, &checked_bits, value);
+
+            match &dfg[instruction_id] {
+                Instruction::Truncate { value, bit_size, .. } => {
+                    let (value, bit_size) = (*value, *bit_size);
+                    let result = dfg.instruction_results(instruction_id)[0];
+                    if bits(value).map_or(false, |bits| bits <= bit_size) {
+                        dfg.set_value_from_id(result, value);
+                        continue;
+                    }
+                    let result_bits = bits(value).map_or(bit_size, |bits| bits.min(bit_size));
+                    self.max_bits.insert(result, result_bits);
+                }
+                Instruction::RangeCheck { value, max_bit_size, .. } => {
+                    let (value, max_bit_size) = (*value, *max_bit_size);
+                    if bits(value).map_or(false, |bits| bits <= max_bit_size) {
+                        continue;
+                    }
+                    checked_bits.insert(dfg.resolve(value), max_bit_size);
+                }
+                instruction => {
+                    if let Some(result_bits) =
+                        self.instruction_bits(dfg, &checked_bits, instruction)
+                    {
+                        let results = dfg.instruction_results(instruction_id);
+                        if result_bits <= MAX_BITS && results.len() == 1 {
+                            self.max_bits.insert(results[0], result_bits);
+                        }
+                    }
+                }
+            }
+
+            remaining_instructions.push(instruction_id);
+        }
+
+        *dfg[block].instructions_mut() = remaining_instructions;
+    }
+
+    /// Returns an upper bound on the number of bits of the result of `instruction`, if one is known.
+    fn instruction_bits(
+        &self,
+        dfg: &DataFlowGraph,
+        checked_bits: &HashMap<ValueId, u32>,
+        instruction: &Instruction,
+    ) -> Option<u32> {
+        let bits = |value| self.value_bits(dfg, checked_bits, value);
+
+        match instruction {
+            Instruction::Binary(Binary { lhs, rhs, operator }) => {
+                let is_unsigned =
+                    matches!(dfg.type_of_value(*lhs), Type::Numeric(NumericType::Unsigned { .. }));
+                match operator {
+                    BinaryOp::Eq | BinaryOp::Lt => Some(1),
+                    BinaryOp::Add => Some(bits(*lhs)?.max(bits(*rhs)?) + 1),
+                    BinaryOp::Mul => {
+                        let (lhs, rhs) = (bits(*lhs)?, bits(*rhs)?);
+                        // Multiplying by a boolean either leaves a value unchanged or zeroes it
+                        if lhs.min(rhs) <= 1 {
+                            Some(lhs.max(rhs))
+                        } else {
+                            Some(lhs + rhs)
+                        }
+                    }
+                    // Signed and field division and remainder do not preserve these bounds
+                    BinaryOp::Div if is_unsigned => bits(*lhs),
+                    BinaryOp::Mod if is_unsigned => match (bits(*lhs), bits(*rhs)) {
+                        (Some(lhs), Some(rhs)) => Some(lhs.min(rhs)),
+                        (lhs, rhs) => lhs.or(rhs),
+                    },
+                    BinaryOp::And => match (bits(*lhs), bits(*rhs)) {
+                        (Some(lhs), Some(rhs)) => Some(lhs.min(rhs)),
+                        (lhs, rhs) => lhs.or(rhs),
+                    },
+                    BinaryOp::Or | BinaryOp::Xor => Some(bits(*lhs)?.max(bits(*rhs)?)),
+                    BinaryOp::Sub | BinaryOp::Div | BinaryOp::Mod => None,
+                }
+            }
+            Instruction::Cast(value, typ) => {
+                let value_bits = bits(*value);
+                match (dfg.type_of_value(*value), typ) {
+                    // Casting into a smaller integer type truncates the value
+                    (
+                        Type::Numeric(incoming),
+                        Type::Numeric(
+                            NumericType::Unsigned { bit_size } | NumericType::Signed { bit_size },
+                        ),
+                    ) if incoming_bit_size(incoming) > *bit_size => {
+                        Some(value_bits.map_or(*bit_size, |bits| bits.min(*bit_size)))
+                    }
+                    _ => value_bits,
+                }
+            }
+            Instruction::Not(value) => match dfg.type_of_value(*value) {
+                // `!x` is computed as `2^bit_size - 1 - x`, which only fits if `x` does
+                Type::Numeric(NumericType::Unsigned { bit_size }) => {
+                    bits(*value).filter(|bits| *bits <= bit_size).map(|_| bit_size)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns an upper bound on the number of bits of `value`, if one is known.
+    fn value_bits(
+        &self,
+        dfg: &DataFlowGraph,
+        checked_bits: &HashMap<ValueId, u32>,
+        value: ValueId,
+    ) -> Option<u32> {
+        let value = dfg.resolve(value);
+        let known_bits = match &dfg[value] {
+            Value::NumericConstant { constant, .. } => Some(constant.num_bits()),
+            _ => self.max_bits.get(&value).copied(),
+        };
+        let checked_bits = checked_bits.get(&value).copied();
+        let bits = match (known_bits, checked_bits) {
+            (Some(known), Some(checked)) => Some(known.min(checked)),
+            (known, checked) => known.or(checked),
+        };
+        bits.filter(|bits| *bits <= MAX_BITS)
+    }
+}
+
+fn incoming_bit_size(typ: NumericType) -> u32 {
+    match typ {
+        NumericType::Unsigned { bit_size } | NumericType::Signed { bit_size } => bit_size,
+        NumericType::NativeField => FieldElement::max_num_bits(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::parser::assert_ssa_pass;
+
+    #[test]
+    fn removes_checks_of_values_which_already_fit() {
+        let src = "
+acir fn main f0 {
+  b0(v0: u8, v1: u8):
+    v2 = add v0, v1
+    range_check v2 to 16 bits
+    range_check v2 to 8 bits 'attempt to add with overflow'
+    v3 = mul v0, v1
+    v4 = truncate v3 to 16 bits, max_bit_size: 16
+    v5 = truncate v3 to 8 bits, max_bit_size: 16
+    return v2, v4, v5
+}";
+        // `v2` has at most 9 bits and `v3` at most 16 bits.
+        let expected = "
+acir fn main f0 {
+  b0(v0: u8, v1: u8):
+    v2 = add v0, v1
+    range_check v2 to 8 bits 'attempt to add with overflow'
+    v3 = mul v0, v1
+    v5 = truncate v3 to 8 bits, max_bit_size: 16
+    return v2, v3, v5
+}";
+        assert_ssa_pass("remove_redundant_range_checks", src, expected);
+    }
+
+    #[test]
+    fn uses_earlier_range_checks() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field, v1: u1):
+    v2 = truncate v0 to 32 bits, max_bit_size: 254
+    range_check v0 to 32 bits
+    v3 = truncate v0 to 32 bits, max_bit_size: 254
+    v4 = mul v0, v1
+    range_check v4 to 32 bits
+    v5 = sub v0, Field 1
+    range_check v5 to 32 bits
+    return v2, v3
+}";
+        // Multiplying by the boolean `v1` does not increase the size of `v0`,
+        // but subtracting from it may cause it to wrap around the field.
+        let expected = "
+acir fn main f0 {
+  b0(v0: Field, v1: u1):
+    v2 = truncate v0 to 32 bits, max_bit_size: 254
+    range_check v0 to 32 bits
+    v4 = mul v0, v1
+    v5 = sub v0, Field 1
+    range_check v5 to 32 bits
+    return v2, v0
+}";
+        assert_ssa_pass("remove_redundant_range_checks", src, expected);
+    }
+
+    #[test]
+    fn does_not_modify_brillig_functions() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: u8, v1: u8):
+    v2 = add v0, v1
+    range_check v2 to 16 bits
+    return v2
+}";
+        assert_ssa_pass("remove_redundant_range_checks", src, src);
+    }
+}
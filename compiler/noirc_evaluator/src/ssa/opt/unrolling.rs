@@ -24,7 +24,7 @@ use crate::{
             dom::DominatorTree,
             function::{Function, RuntimeType},
             function_inserter::FunctionInserter,
-            instruction::TerminatorInstruction,
+            instruction::{Instruction, TerminatorInstruction},
             post_order::PostOrder,
             value::ValueId,
         },
@@ -55,14 +55,14 @@ impl Ssa {
     }
 }
 
-struct Loop {
+pub(super) struct Loop {
     /// The header block of a loop is the block which dominates all the
     /// other blocks in the loop.
-    header: BasicBlockId,
+    pub(super) header: BasicBlockId,
 
     /// The start of the back_edge n -> d is the block n at the end of
     /// the loop that jumps back to the header block d which restarts the loop.
-    back_edge_start: BasicBlockId,
+    pub(super) back_edge_start: BasicBlockId,
 
     /// All the blocks contained within the loop, including `header` and `back_edge_start`.
     pub(crate) blocks: HashSet<BasicBlockId>,
@@ -148,7 +148,7 @@ impl Loops {
 
 /// Return each block that is in a loop starting in the given header block.
 /// Expects back_edge_start -> header to be the back edge of the loop.
-fn find_blocks_in_loop(
+pub(super) fn find_blocks_in_loop(
     header: BasicBlockId,
     back_edge_start: BasicBlockId,
     cfg: &ControlFlowGraph,
@@ -187,15 +187,39 @@ fn unroll_loop(
     let mut unroll_into = get_pre_header(cfg, loop_);
     let mut jump_value = get_induction_variable(function, unroll_into)?;
 
+    let array_call_stacks = array_call_stacks_in_loop(function, loop_);
+
     while let Some(context) = unroll_loop_header(function, loop_, unroll_into, jump_value)? {
         let (last_block, last_value) = context.unroll_loop_iteration();
         unroll_into = last_block;
         jump_value = last_value;
     }
 
+    for call_stack in array_call_stacks {
+        function.dfg.add_loop_array_call_stack(call_stack);
+    }
     Ok(())
 }
 
+/// Returns the call stacks of the array operations within the loop, which are repeated
+/// by each iteration of the loop once it is unrolled.
+fn array_call_stacks_in_loop(function: &Function, loop_: &Loop) -> Vec<CallStack> {
+    let mut call_stacks = Vec::new();
+    for block in &loop_.blocks {
+        for instruction in function.dfg[*block].instructions() {
+            let is_array_operation = matches!(
+                function.dfg[*instruction],
+                Instruction::ArrayGet { .. } | Instruction::ArraySet { .. }
+            );
+            let call_stack = function.dfg.get_call_stack(*instruction);
+            if is_array_operation && !call_stack.is_empty() {
+                call_stacks.push(call_stack);
+            }
+        }
+    }
+    call_stacks
+}
+
 /// The loop pre-header is the block that comes before the loop begins. Generally a header block
 /// is expected to have 2 predecessors: the pre-header and the final block of the loop which jumps
 /// back to the beginning.
@@ -0,0 +1,930 @@
+//! Parser for the textual SSA form produced by [`Ssa`]'s `Display` implementation.
+//!
+//! This allows optimization passes to be tested against SSA written out by hand rather
+//! than built up instruction by instruction through the `FunctionBuilder`.
+//!
+//! Values, blocks and functions are numbered in the order in which they are created while parsing,
+//! so the ids written in the source are only used to link definitions to their uses. Two programs
+//! which differ only in their numbering therefore parse to the same [`Ssa`].
+//!
+//! Instructions are inserted exactly as written, without being simplified.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    iter::Peekable,
+    rc::Rc,
+    str::{CharIndices, FromStr},
+};
+
+use acvm::FieldElement;
+use noirc_frontend::monomorphization::ast::InlineType;
+
+use super::{
+    ir::{
+        basic_block::BasicBlockId,
+        function::{Function, FunctionId, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction, Intrinsic, TerminatorInstruction},
+        map::Id,
+        types::Type,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+/// An error encountered while parsing SSA, along with the (one-indexed) line it was found on.
+#[derive(Debug)]
+pub(crate) struct SsaParseError {
+    line: usize,
+    message: String,
+}
+
+impl Display for SsaParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+type ParseResult<T> = Result<T, String>;
+
+impl FromStr for Ssa {
+    type Err = SsaParseError;
+
+    /// Parses SSA in the format it is printed in.
+    ///
+    /// The first function in `src` is taken to be the program's entry point.
+    fn from_str(src: &str) -> Result<Ssa, SsaParseError> {
+        let lines: Vec<(usize, &str)> = src
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, strip_comment(line).trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+
+        // Functions may be called before they are defined, so assign all function ids up front.
+        let mut function_ids = HashMap::new();
+        for (line_number, line) in &lines {
+            if line.contains(" fn ") && line.ends_with('{') {
+                let header = parse_function_header(line)
+                    .map_err(|message| SsaParseError { line: *line_number, message })?;
+                let id = Id::test_new(function_ids.len());
+                if function_ids.insert(header.id.to_owned(), id).is_some() {
+                    let message = format!("function {} is defined more than once", header.id);
+                    return Err(SsaParseError { line: *line_number, message });
+                }
+            }
+        }
+
+        let mut functions = Vec::new();
+        let mut lines = lines.into_iter();
+        while let Some((line_number, line)) = lines.next() {
+            let error = |message| SsaParseError { line: line_number, message };
+            let header = parse_function_header(line).map_err(error)?;
+
+            let mut body = Vec::new();
+            loop {
+                match lines.next() {
+                    Some((_, "}")) => break,
+                    Some(line) => body.push(line),
+                    None => {
+                        return Err(error(format!("function {} is never closed", header.id)));
+                    }
+                }
+            }
+
+            let function_id = function_ids[header.id];
+            let mut parser = FunctionParser::new(header, function_id, &function_ids);
+            parser.parse_body(&body)?;
+            functions.push(parser.function);
+        }
+
+        if functions.is_empty() {
+            return Err(SsaParseError {
+                line: 1,
+                message: "expected at least one function".into(),
+            });
+        }
+        Ok(Ssa::new(functions))
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find("//") {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+struct FunctionHeader<'a> {
+    runtime: RuntimeType,
+    inline_type: InlineType,
+    name: &'a str,
+    id: &'a str,
+}
+
+/// Parses a function header of the form `acir fn main f0 {`, where the runtime
+/// may be followed by `(no_inline)`.
+fn parse_function_header(line: &str) -> ParseResult<FunctionHeader> {
+    let expected = || format!("expected a function header, found `{line}`");
+
+    let (runtime, rest) = line.split_once(" fn ").ok_or_else(expected)?;
+    let (runtime, inline_type) = match runtime.strip_suffix("(no_inline)") {
+        Some(runtime) => (runtime, InlineType::NoInline),
+        None => (runtime, InlineType::Inline),
+    };
+    let runtime = match runtime {
+        "acir" => RuntimeType::Acir,
+        "brillig" => RuntimeType::Brillig,
+        other => return Err(format!("unknown runtime `{other}`")),
+    };
+    let rest = rest.strip_suffix('{').ok_or_else(expected)?.trim_end();
+    let (name, id) = rest.rsplit_once(' ').ok_or_else(expected)?;
+    if !is_id(id, 'f') {
+        return Err(expected());
+    }
+    Ok(FunctionHeader { runtime, inline_type, name: name.trim(), id })
+}
+
+/// Returns true if `text` is `prefix` followed by a number, such as `v12` for `prefix` `v`.
+fn is_id(text: &str, prefix: char) -> bool {
+    text.strip_prefix(prefix)
+        .map_or(false, |index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+struct FunctionParser<'a> {
+    function: Function,
+    function_ids: &'a HashMap<String, FunctionId>,
+    blocks: HashMap<String, BasicBlockId>,
+    values: HashMap<String, ValueId>,
+}
+
+impl<'a> FunctionParser<'a> {
+    fn new(
+        header: FunctionHeader,
+        id: FunctionId,
+        function_ids: &'a HashMap<String, FunctionId>,
+    ) -> Self {
+        let mut function = Function::new(header.name.to_owned(), id);
+        function.set_runtime(header.runtime);
+        function.set_inline_type(header.inline_type);
+        Self { function, function_ids, blocks: HashMap::new(), values: HashMap::new() }
+    }
+
+    fn parse_body(&mut self, body: &[(usize, &str)]) -> Result<(), SsaParseError> {
+        // Blocks may be jumped to before they are defined, so create them all up front.
+        // The first block is the function's entry block.
+        for (line_number, line) in body {
+            if let Some((name, _)) = block_header(line) {
+                let block = if self.blocks.is_empty() {
+                    self.function.entry_block()
+                } else {
+                    self.function.dfg.make_block()
+                };
+                if self.blocks.insert(name.to_owned(), block).is_some() {
+                    let message = format!("block {name} is defined more than once");
+                    return Err(SsaParseError { line: *line_number, message });
+                }
+            }
+        }
+
+        let mut current_block = None;
+        for (line_number, line) in body {
+            let result = match block_header(line) {
+                Some((name, parameters)) => {
+                    let block = self.blocks[name];
+                    current_block = Some(block);
+                    self.parse_block_parameters(block, parameters)
+                }
+                None => match current_block {
+                    Some(block) => self.parse_instruction(block, line),
+                    None => Err(format!("expected a block header, found `{line}`")),
+                },
+            };
+            result.map_err(|message| SsaParseError { line: *line_number, message })?;
+        }
+
+        if current_block.is_none() {
+            let message = format!("function {} has no blocks", self.function.name());
+            let line = body.first().map_or(0, |(line_number, _)| *line_number);
+            return Err(SsaParseError { line, message });
+        }
+        Ok(())
+    }
+
+    fn parse_block_parameters(&mut self, block: BasicBlockId, parameters: &str) -> ParseResult<()> {
+        let mut tokens = Tokens::new(parameters)?;
+        while !tokens.is_empty() {
+            let name = tokens.expect_value_name()?;
+            tokens.expect(Token::Colon)?;
+            let typ = tokens.parse_type()?;
+            let parameter = self.function.dfg.add_block_parameter(block, typ);
+            self.define_value(name, parameter)?;
+            if !tokens.is_empty() {
+                tokens.expect(Token::Comma)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_instruction(&mut self, block: BasicBlockId, line: &str) -> ParseResult<()> {
+        if line == "(no terminator instruction)" {
+            return Ok(());
+        }
+
+        let mut tokens = Tokens::new(line)?;
+
+        let mut results = Vec::new();
+        if tokens.peek_is(|token| matches!(token, Token::Ident(name) if is_id(name, 'v'))) {
+            loop {
+                results.push(tokens.expect_value_name()?);
+                if !tokens.eat(&Token::Comma) {
+                    break;
+                }
+            }
+            tokens.expect(Token::Equals)?;
+        }
+
+        let keyword = tokens.expect_ident()?;
+        if let Some(terminator) = self.parse_terminator(&keyword, &mut tokens)? {
+            if !results.is_empty() {
+                return Err(format!("`{keyword}` does not produce any results"));
+            }
+            tokens.expect_end()?;
+            self.function.dfg.set_block_terminator(block, terminator);
+            return Ok(());
+        }
+
+        let (instruction, ctrl_typevars) = self.parse_instruction_data(&keyword, &mut tokens)?;
+        tokens.expect_end()?;
+
+        let instruction = self.function.dfg.make_instruction(instruction, ctrl_typevars);
+        self.function.dfg[block].insert_instruction(instruction);
+
+        let result_values = self.function.dfg.instruction_results(instruction).to_vec();
+        if result_values.len() != results.len() {
+            return Err(format!(
+                "`{keyword}` produces {} results but {} were named",
+                result_values.len(),
+                results.len()
+            ));
+        }
+        for (name, value) in results.into_iter().zip(result_values) {
+            self.define_value(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Parses the remainder of a terminator instruction, returning `None` if `keyword` does not begin one.
+    fn parse_terminator(
+        &mut self,
+        keyword: &str,
+        tokens: &mut Tokens,
+    ) -> ParseResult<Option<TerminatorInstruction>> {
+        let terminator = match keyword {
+            "jmp" => {
+                let destination = self.parse_block(tokens)?;
+                tokens.expect(Token::LeftParen)?;
+                let arguments = self.parse_value_list(tokens, Token::RightParen)?;
+                TerminatorInstruction::Jmp {
+                    destination,
+                    arguments,
+                    call_stack: Default::default(),
+                }
+            }
+            "jmpif" => {
+                let condition = self.parse_value(tokens)?;
+                tokens.expect_keyword("then")?;
+                tokens.expect(Token::Colon)?;
+                let then_destination = self.parse_block(tokens)?;
+                tokens.expect(Token::Comma)?;
+                tokens.expect_keyword("else")?;
+                tokens.expect(Token::Colon)?;
+                let else_destination = self.parse_block(tokens)?;
+                TerminatorInstruction::JmpIf { condition, then_destination, else_destination }
+            }
+            "return" => {
+                let mut return_values = Vec::new();
+                while !tokens.is_empty() {
+                    if !return_values.is_empty() {
+                        tokens.expect(Token::Comma)?;
+                    }
+                    return_values.push(self.parse_value(tokens)?);
+                }
+                TerminatorInstruction::Return { return_values, call_stack: Default::default() }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(terminator))
+    }
+
+    /// Parses the remainder of an instruction beginning with `keyword`,
+    /// along with the types of its results if they can't be inferred.
+    fn parse_instruction_data(
+        &mut self,
+        keyword: &str,
+        tokens: &mut Tokens,
+    ) -> ParseResult<(Instruction, Option<Vec<Type>>)> {
+        let instruction = match keyword {
+            "add" | "sub" | "mul" | "div" | "eq" | "mod" | "lt" | "and" | "or" | "xor" => {
+                let operator = match keyword {
+                    "add" => BinaryOp::Add,
+                    "sub" => BinaryOp::Sub,
+                    "mul" => BinaryOp::Mul,
+                    "div" => BinaryOp::Div,
+                    "eq" => BinaryOp::Eq,
+                    "mod" => BinaryOp::Mod,
+                    "lt" => BinaryOp::Lt,
+                    "and" => BinaryOp::And,
+                    "or" => BinaryOp::Or,
+                    _ => BinaryOp::Xor,
+                };
+                let lhs = self.parse_value(tokens)?;
+                tokens.expect(Token::Comma)?;
+                let rhs = self.parse_value(tokens)?;
+                Instruction::Binary(Binary { lhs, operator, rhs })
+            }
+            "cast" => {
+                let value = self.parse_value(tokens)?;
+                tokens.expect_keyword("as")?;
+                Instruction::Cast(value, tokens.parse_type()?)
+            }
+            "not" => Instruction::Not(self.parse_value(tokens)?),
+            "truncate" => {
+                let value = self.parse_value(tokens)?;
+                tokens.expect_keyword("to")?;
+                let bit_size = tokens.expect_u32()?;
+                tokens.expect_keyword("bits")?;
+                tokens.expect(Token::Comma)?;
+                tokens.expect_keyword("max_bit_size")?;
+                tokens.expect(Token::Colon)?;
+                let max_bit_size = tokens.expect_u32()?;
+                Instruction::Truncate { value, bit_size, max_bit_size }
+            }
+            "constrain" => {
+                let lhs = self.parse_value(tokens)?;
+                tokens.expect(Token::DoubleEquals)?;
+                let rhs = self.parse_value(tokens)?;
+                Instruction::Constrain(lhs, rhs, tokens.parse_message())
+            }
+            "range_check" => {
+                let value = self.parse_value(tokens)?;
+                tokens.expect_keyword("to")?;
+                let max_bit_size = tokens.expect_u32()?;
+                tokens.expect_keyword("bits")?;
+                Instruction::RangeCheck {
+                    value,
+                    max_bit_size,
+                    assert_message: tokens.parse_message(),
+                }
+            }
+            "call" => {
+                let func = self.parse_value(tokens)?;
+                tokens.expect(Token::LeftParen)?;
+                let arguments = self.parse_value_list(tokens, Token::RightParen)?;
+                let result_types = if tokens.eat(&Token::Arrow) {
+                    tokens.parse_result_types()?
+                } else {
+                    Vec::new()
+                };
+                return Ok((Instruction::Call { func, arguments }, Some(result_types)));
+            }
+            "allocate" => {
+                tokens.expect(Token::Arrow)?;
+                return Ok((Instruction::Allocate, Some(tokens.parse_result_types()?)));
+            }
+            "load" => {
+                let address = self.parse_value(tokens)?;
+                tokens.expect(Token::Arrow)?;
+                return Ok((Instruction::Load { address }, Some(tokens.parse_result_types()?)));
+            }
+            "store" => {
+                let value = self.parse_value(tokens)?;
+                tokens.expect_keyword("at")?;
+                let address = self.parse_value(tokens)?;
+                Instruction::Store { address, value }
+            }
+            "enable_side_effects" => {
+                Instruction::EnableSideEffects { condition: self.parse_value(tokens)? }
+            }
+            "array_get" => {
+                let array = self.parse_value(tokens)?;
+                tokens.expect(Token::Comma)?;
+                tokens.expect_keyword("index")?;
+                let index = self.parse_value(tokens)?;
+                tokens.expect(Token::Arrow)?;
+                let result_types = tokens.parse_result_types()?;
+                return Ok((Instruction::ArrayGet { array, index }, Some(result_types)));
+            }
+            "array_set" => {
+                let array = self.parse_value(tokens)?;
+                tokens.expect(Token::Comma)?;
+                tokens.expect_keyword("index")?;
+                let index = self.parse_value(tokens)?;
+                tokens.expect(Token::Comma)?;
+                tokens.expect_keyword("value")?;
+                let value = self.parse_value(tokens)?;
+                Instruction::ArraySet { array, index, value }
+            }
+            "inc_rc" => Instruction::IncrementRc { value: self.parse_value(tokens)? },
+            other => return Err(format!("unknown instruction `{other}`")),
+        };
+        Ok((instruction, None))
+    }
+
+    fn parse_block(&self, tokens: &mut Tokens) -> ParseResult<BasicBlockId> {
+        let name = tokens.expect_ident()?;
+        self.blocks.get(&name).copied().ok_or_else(|| format!("unknown block `{name}`"))
+    }
+
+    /// Parses a comma separated list of values, consuming the `terminator` which closes it.
+    fn parse_value_list(
+        &mut self,
+        tokens: &mut Tokens,
+        terminator: Token,
+    ) -> ParseResult<Vec<ValueId>> {
+        let mut values = Vec::new();
+        while !tokens.eat(&terminator) {
+            if !values.is_empty() {
+                tokens.expect(Token::Comma)?;
+            }
+            values.push(self.parse_value(tokens)?);
+        }
+        Ok(values)
+    }
+
+    /// Parses a value, which may be:
+    /// - a reference to a previously defined value, e.g. `v3`
+    /// - a numeric constant, e.g. `u32 5`
+    /// - an array constant, e.g. `[Field 1, Field 2] of [Field; 2]`
+    /// - a function, e.g. `f1`
+    /// - an intrinsic or foreign function, referred to by name
+    fn parse_value(&mut self, tokens: &mut Tokens) -> ParseResult<ValueId> {
+        if tokens.eat(&Token::LeftBracket) {
+            let elements = self.parse_value_list(tokens, Token::RightBracket)?;
+            tokens.expect_keyword("of")?;
+            let typ = tokens.parse_type()?;
+            return Ok(self.function.dfg.make_array(elements.into(), typ));
+        }
+
+        let name = tokens.expect_ident()?;
+        if let Some(typ) = numeric_type(&name) {
+            let constant = tokens.expect_number()?;
+            let constant = parse_field_element(&constant)
+                .ok_or_else(|| format!("invalid numeric constant `{constant}`"))?;
+            return Ok(self.function.dfg.make_constant(constant, typ));
+        }
+        if is_id(&name, 'v') {
+            return self
+                .values
+                .get(&name)
+                .copied()
+                .ok_or_else(|| format!("unknown value `{name}`"));
+        }
+        if is_id(&name, 'f') {
+            let function =
+                self.function_ids.get(&name).ok_or_else(|| format!("unknown function `{name}`"))?;
+            return Ok(self.function.dfg.import_function(*function));
+        }
+        match Intrinsic::lookup(&name) {
+            Some(intrinsic) => Ok(self.function.dfg.import_intrinsic(intrinsic)),
+            None => Ok(self.function.dfg.import_foreign_function(&name)),
+        }
+    }
+
+    fn define_value(&mut self, name: String, value: ValueId) -> ParseResult<()> {
+        if self.values.insert(name.clone(), value).is_some() {
+            return Err(format!("value `{name}` is defined more than once"));
+        }
+        Ok(())
+    }
+}
+
+/// Splits a block header of the form `b0(v0: Field, v1: u32):` into the block's name and parameters.
+fn block_header(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_suffix("):")?;
+    let (name, parameters) = line.split_once('(')?;
+    is_id(name, 'b').then_some((name, parameters))
+}
+
+/// Returns the numeric type named `name`, e.g. `Field` or `u32`.
+fn numeric_type(name: &str) -> Option<Type> {
+    if name == "Field" {
+        return Some(Type::field());
+    }
+    let parse_bit_size = |bit_size: &str| {
+        bit_size.chars().all(|c| c.is_ascii_digit()).then(|| bit_size.parse().ok()).flatten()
+    };
+    if let Some(bit_size) = name.strip_prefix('u').and_then(parse_bit_size) {
+        return Some(Type::unsigned(bit_size));
+    }
+    name.strip_prefix('i').and_then(parse_bit_size).map(Type::signed)
+}
+
+/// Parses a field element in the format of its `Display` implementation, which abbreviates
+/// multiples of powers of two, e.g. `2⁶⁴` or `-2³²×3`.
+fn parse_field_element(text: &str) -> Option<FieldElement> {
+    let (is_negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+
+    let power_of_two = |power: &str| {
+        let exponent = parse_superscript(power.strip_prefix('2')?)?;
+        Some((0..exponent).fold(FieldElement::one(), |value, _| value * FieldElement::from(2u128)))
+    };
+    let value = match text.split_once('×') {
+        Some((power, multiplier)) => power_of_two(power)? * FieldElement::try_from_str(multiplier)?,
+        None => power_of_two(text).or_else(|| FieldElement::try_from_str(text))?,
+    };
+
+    Some(if is_negative { -value } else { value })
+}
+
+/// Parses a number written in superscript digits, e.g. `⁶⁴`.
+fn parse_superscript(text: &str) -> Option<u32> {
+    if text.is_empty() {
+        return None;
+    }
+    text.chars().try_fold(0, |number, c| {
+        let digit = "⁰¹²³⁴⁵⁶⁷⁸⁹".chars().position(|superscript| superscript == c)?;
+        Some(number * 10 + digit as u32)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    Semicolon,
+    Equals,
+    DoubleEquals,
+    Ampersand,
+    Arrow,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(text) | Token::Number(text) => write!(f, "{text}"),
+            Token::Str(text) => write!(f, "'{text}'"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::Semicolon => write!(f, ";"),
+            Token::Equals => write!(f, "="),
+            Token::DoubleEquals => write!(f, "=="),
+            Token::Ampersand => write!(f, "&"),
+            Token::Arrow => write!(f, "->"),
+        }
+    }
+}
+
+/// The tokens making up a single line of SSA.
+struct Tokens {
+    tokens: std::vec::IntoIter<Token>,
+    peeked: Option<Token>,
+}
+
+impl Tokens {
+    fn new(line: &str) -> ParseResult<Self> {
+        let mut tokens = Vec::new();
+        let mut chars = line.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '(' => Token::LeftParen,
+                ')' => Token::RightParen,
+                '[' => Token::LeftBracket,
+                ']' => Token::RightBracket,
+                ',' => Token::Comma,
+                ':' => Token::Colon,
+                ';' => Token::Semicolon,
+                '&' => Token::Ampersand,
+                '=' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::DoubleEquals,
+                '=' => Token::Equals,
+                '-' if chars.next_if(|(_, c)| *c == '>').is_some() => Token::Arrow,
+                '\'' => {
+                    let end = line[start + 1..]
+                        .find('\'')
+                        .ok_or_else(|| "unterminated string".to_owned())?;
+                    let text = &line[start + 1..start + 1 + end];
+                    // Skip over the string's contents and closing quote.
+                    for _ in 0..text.chars().count() + 1 {
+                        chars.next();
+                    }
+                    Token::Str(text.to_owned())
+                }
+                '-' => Token::Number(format!("-{}", take_word(line, start + 1, &mut chars))),
+                c if c.is_numeric() => Token::Number(take_word(line, start, &mut chars)),
+                c if c.is_alphabetic() || c == '_' => {
+                    Token::Ident(take_word(line, start, &mut chars))
+                }
+                other => return Err(format!("unexpected character `{other}`")),
+            };
+            tokens.push(token);
+        }
+        Ok(Tokens { tokens: tokens.into_iter(), peeked: None })
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        self.peeked.take().or_else(|| self.tokens.next())
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        if self.peeked.is_none() {
+            self.peeked = self.tokens.next();
+        }
+        self.peeked.as_ref()
+    }
+
+    fn peek_is(&mut self, predicate: impl FnOnce(&Token) -> bool) -> bool {
+        self.peek().map_or(false, predicate)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.peek().is_none()
+    }
+
+    /// Consumes the next token if it is `token`, returning whether it was consumed.
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek_is(|next| next == token);
+        if matches {
+            self.next();
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: Token) -> ParseResult<()> {
+        match self.next() {
+            Some(next) if next == token => Ok(()),
+            Some(next) => Err(format!("expected `{token}`, found `{next}`")),
+            None => Err(format!("expected `{token}`, found end of line")),
+        }
+    }
+
+    fn expect_end(&mut self) -> ParseResult<()> {
+        match self.next() {
+            Some(next) => Err(format!("expected end of line, found `{next}`")),
+            None => Ok(()),
+        }
+    }
+
+    fn expect_ident(&mut self) -> ParseResult<String> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(name),
+            Some(next) => Err(format!("expected an identifier, found `{next}`")),
+            None => Err("expected an identifier, found end of line".to_owned()),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        self.expect(Token::Ident(keyword.to_owned()))
+    }
+
+    fn expect_value_name(&mut self) -> ParseResult<String> {
+        let name = self.expect_ident()?;
+        if is_id(&name, 'v') {
+            Ok(name)
+        } else {
+            Err(format!("expected a value such as `v0`, found `{name}`"))
+        }
+    }
+
+    fn expect_number(&mut self) -> ParseResult<String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(number),
+            Some(next) => Err(format!("expected a number, found `{next}`")),
+            None => Err("expected a number, found end of line".to_owned()),
+        }
+    }
+
+    fn expect_u32(&mut self) -> ParseResult<u32> {
+        let number = self.expect_number()?;
+        number.parse().map_err(|_| format!("expected an integer, found `{number}`"))
+    }
+
+    /// Parses an optional assertion message, e.g. `'overflow'`.
+    fn parse_message(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Str(_)) => match self.next() {
+                Some(Token::Str(message)) => Some(message),
+                _ => unreachable!("a string was peeked"),
+            },
+            _ => None,
+        }
+    }
+
+    fn parse_type(&mut self) -> ParseResult<Type> {
+        if self.eat(&Token::Ampersand) {
+            self.expect_keyword("mut")?;
+            return Ok(Type::Reference(Rc::new(self.parse_type()?)));
+        }
+
+        if self.eat(&Token::LeftBracket) {
+            let mut element_types = vec![self.parse_type()?];
+            while self.eat(&Token::Comma) {
+                element_types.push(self.parse_type()?);
+            }
+            let length = if self.eat(&Token::Semicolon) {
+                let length = self.expect_number()?;
+                Some(length.parse().map_err(|_| format!("invalid array length `{length}`"))?)
+            } else {
+                None
+            };
+            self.expect(Token::RightBracket)?;
+            return Ok(match length {
+                Some(length) => Type::Array(Rc::new(element_types), length),
+                None => Type::Slice(Rc::new(element_types)),
+            });
+        }
+
+        let name = self.expect_ident()?;
+        if name == "function" {
+            return Ok(Type::Function);
+        }
+        numeric_type(&name).ok_or_else(|| format!("unknown type `{name}`"))
+    }
+
+    /// Parses the types following `->`, which are parenthesized unless there is exactly one.
+    fn parse_result_types(&mut self) -> ParseResult<Vec<Type>> {
+        if !self.eat(&Token::LeftParen) {
+            return Ok(vec![self.parse_type()?]);
+        }
+        let mut types = Vec::new();
+        while !self.eat(&Token::RightParen) {
+            if !types.is_empty() {
+                self.expect(Token::Comma)?;
+            }
+            types.push(self.parse_type()?);
+        }
+        Ok(types)
+    }
+}
+
+/// Consumes the rest of the word beginning at `start`, returning it.
+fn take_word(line: &str, start: usize, chars: &mut Peekable<CharIndices>) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '×';
+    let mut end = line.len();
+    while let Some((index, c)) = chars.peek() {
+        if !is_word_char(*c) {
+            end = *index;
+            break;
+        }
+        chars.next();
+    }
+    line[start..end].to_owned()
+}
+
+/// Runs the SSA pass named `pass` over `src`, asserting that the result is equivalent to `expected`.
+///
+/// Both programs are normalized by parsing them, so they needn't use the same value numbering.
+#[track_caller]
+pub(crate) fn assert_ssa_pass(pass: &str, src: &str, expected: &str) {
+    let ssa = Ssa::from_str(src).unwrap_or_else(|error| panic!("invalid source SSA: {error}"));
+    let ssa = match pass {
+        "defunctionalize" => ssa.defunctionalize(),
+        "inline_functions" => ssa.inline_functions(),
+        "loop_invariant_code_motion" => ssa.loop_invariant_code_motion(),
+        "mem2reg" => ssa.mem2reg(),
+        "evaluate_assert_constant" => ssa.evaluate_assert_constant().expect("pass failed"),
+        "unroll_loops" => ssa.unroll_loops().expect("pass failed"),
+        "simplify_cfg" => ssa.simplify_cfg(),
+        "flatten_cfg" => ssa.flatten_cfg(),
+        "fold_constants" => ssa.fold_constants(),
+        "remove_redundant_range_checks" => ssa.remove_redundant_range_checks(),
+        "dead_instruction_elimination" => ssa.dead_instruction_elimination(),
+        "fill_internal_slices" => ssa.fill_internal_slices(),
+        other => panic!("unknown SSA pass `{other}`"),
+    };
+
+    let normalize = |src: &str| match Ssa::from_str(src) {
+        Ok(ssa) => ssa.to_string(),
+        Err(error) => panic!("invalid SSA: {error}\n{src}"),
+    };
+    let actual = normalize(&ssa.to_string());
+    let expected = normalize(expected);
+    assert_eq!(actual, expected, "\nafter {pass}:\n{actual}\nexpected:\n{expected}");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::ssa::ssa_gen::Ssa;
+
+    use super::{assert_ssa_pass, parse_field_element};
+    use acvm::FieldElement;
+
+    fn parse(src: &str) -> Ssa {
+        Ssa::from_str(src).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    #[test]
+    fn round_trips_control_flow() {
+        let src = "
+acir fn main f0 {
+  b0(v0: u1, v1: Field):
+    jmpif v0 then: b1, else: b2
+  b1():
+    v3 = add v1, Field 1
+    return v3
+  b2():
+    jmp b3(Field 2)
+  b3(v5: Field):
+    return v5
+}";
+        assert_eq!(parse(src).to_string().trim(), src.trim());
+    }
+
+    #[test]
+    fn round_trips_instructions() {
+        let src = "
+brillig fn main f0 {
+  b0(v0: u32, v1: [Field; 2], v2: [u8]):
+    v3 = allocate -> &mut Field
+    store Field 5 at v3
+    v5 = load v3 -> Field
+    v6 = cast v0 as Field
+    v7 = not v0
+    v9 = truncate v6 to 8 bits, max_bit_size: 254
+    constrain v5 == v9 'values differ'
+    range_check v6 to 32 bits
+    range_check v5 to 16 bits 'too large'
+    enable_side_effects u1 1
+    v13 = array_get v1, index u32 1 -> Field
+    v15 = array_set v1, index u32 0, value v13
+    inc_rc v15
+    v17 = lt v0, u32 -1
+    v21, v22 = call f1(v0, [Field 1, Field 2⁶⁴] of [Field; 2]) -> (Field, u32)
+    v23, v24 = call to_le_bits(v5, u32 8) -> (u32, [u1])
+    call print(v0)
+    v27 = eq v6, Field -2³²×3
+    return v15, v7
+}
+acir fn foo f1 {
+  b0(v0: u32, v1: [Field; 2]):
+    return Field 0, v0
+}";
+        // Values are renumbered while parsing, so only the second printing is expected to be stable.
+        let printed = parse(src).to_string();
+        assert_eq!(parse(&printed).to_string(), printed);
+        for line in
+            ["store Field 5 at", "range_check", "'too large'", "call print(", "of [Field; 2]"]
+        {
+            assert!(printed.contains(line), "expected `{line}` in:\n{printed}");
+        }
+    }
+
+    #[test]
+    fn parses_abbreviated_field_elements() {
+        let two_to_the = |power| {
+            (0..power).fold(FieldElement::one(), |value, _| value * FieldElement::from(2u128))
+        };
+        assert_eq!(parse_field_element("2⁶⁴"), Some(two_to_the(64)));
+        assert_eq!(
+            parse_field_element("-2³²×3"),
+            Some(-(two_to_the(32) * FieldElement::from(3u128)))
+        );
+        assert_eq!(parse_field_element("25"), Some(FieldElement::from(25u128)));
+        assert_eq!(parse_field_element("2"), Some(FieldElement::from(2u128)));
+        assert_eq!(parse_field_element("twelve"), None);
+    }
+
+    #[test]
+    fn reports_undefined_values() {
+        let src = "
+acir fn main f0 {
+  b0():
+    return v3
+}";
+        let error = Ssa::from_str(src).err().expect("expected a parse error");
+        assert_eq!(error.to_string(), "line 4: unknown value `v3`");
+    }
+
+    #[test]
+    fn runs_named_pass() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field):
+    v1 = add v0, Field 1
+    v2 = mul v0, Field 2
+    return v1
+}";
+        let expected = "
+acir fn main f0 {
+  b0(v0: Field):
+    v1 = add v0, Field 1
+    return v1
+}";
+        assert_ssa_pass("dead_instruction_elimination", src, expected);
+    }
+}
@@ -113,6 +113,7 @@ impl<'a> FunctionContext<'a> {
         } else {
             self.builder.new_function(func.name.clone(), id);
         }
+        self.builder.current_function.set_inline_type(func.inline_type);
         self.add_parameters_to_scope(&func.parameters);
     }
 
@@ -0,0 +1,378 @@
+//! Structural validation of SSA.
+//!
+//! Optimization passes are expected to leave the IR well-formed. A pass which doesn't will often
+//! not cause an error until much later, if at all, so this validator can be run between passes
+//! to catch the offending pass as soon as possible.
+use std::collections::HashMap;
+
+use crate::errors::InternalError;
+
+use super::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::CallStack,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, TerminatorInstruction},
+        printer::instruction_to_string,
+        types::Type,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Checks that each function in the program is well-formed, returning an error describing
+    /// the first problem found, which is attributed to the pass named `pass`.
+    ///
+    /// If `memory_promoted` is true, ACIR functions must not contain any `Allocate`, `Load` or
+    /// `Store` instructions, as these should have been removed by mem2reg before ACIR generation.
+    pub(crate) fn validate(&self, pass: &str, memory_promoted: bool) -> Result<(), InternalError> {
+        for function in self.functions.values() {
+            let check_memory = memory_promoted && function.runtime() == RuntimeType::Acir;
+            Validator::new(self, function, check_memory).validate().map_err(|error| {
+                let location = match error.instruction {
+                    Some(instruction) => {
+                        format!("`{}`", instruction_to_string(function, instruction))
+                    }
+                    None => format!("the terminator of {}", error.block),
+                };
+                let message = format!(
+                    "SSA validation failed after {pass}: {} at {location} in {} {}",
+                    error.message,
+                    function.name(),
+                    function.id(),
+                );
+                let call_stack = error.instruction.map_or_else(CallStack::new, |instruction| {
+                    function.dfg.get_call_stack(instruction)
+                });
+                InternalError::General { message, call_stack }
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem found in a function, along with the instruction it was found in.
+/// If `instruction` is `None`, the problem is in the terminator of `block`.
+struct ValidationError {
+    block: BasicBlockId,
+    instruction: Option<InstructionId>,
+    message: String,
+}
+
+/// The position of a value's definition or use within a function.
+#[derive(Copy, Clone)]
+struct Position {
+    block: BasicBlockId,
+    /// Block parameters are at index 0, each instruction at 1 plus its index in the block,
+    /// and the terminator after all instructions.
+    index: usize,
+}
+
+struct Validator<'f> {
+    ssa: &'f Ssa,
+    function: &'f Function,
+    check_memory: bool,
+    dom_tree: DominatorTree,
+    definitions: HashMap<ValueId, Position>,
+}
+
+impl<'f> Validator<'f> {
+    fn new(ssa: &'f Ssa, function: &'f Function, check_memory: bool) -> Self {
+        let dom_tree = DominatorTree::with_function(function);
+        Validator { ssa, function, check_memory, dom_tree, definitions: HashMap::new() }
+    }
+
+    fn validate(mut self) -> Result<(), ValidationError> {
+        let dfg = &self.function.dfg;
+        let blocks = self.function.reachable_blocks();
+
+        for block in &blocks {
+            for parameter in dfg.block_parameters(*block) {
+                self.definitions.insert(*parameter, Position { block: *block, index: 0 });
+            }
+            for (index, instruction) in dfg[*block].instructions().iter().enumerate() {
+                for result in dfg.instruction_results(*instruction) {
+                    let position = Position { block: *block, index: index + 1 };
+                    self.definitions.insert(*result, position);
+                }
+            }
+        }
+
+        for block in blocks {
+            let instructions = dfg[block].instructions();
+            for (index, instruction) in instructions.iter().enumerate() {
+                let position = Position { block, index: index + 1 };
+                self.validate_instruction(*instruction, position).map_err(|message| {
+                    ValidationError { block, instruction: Some(*instruction), message }
+                })?;
+            }
+
+            let position = Position { block, index: instructions.len() + 1 };
+            self.validate_terminator(block, position).map_err(|message| ValidationError {
+                block,
+                instruction: None,
+                message,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn validate_instruction(
+        &mut self,
+        instruction: InstructionId,
+        position: Position,
+    ) -> Result<(), String> {
+        let dfg = &self.function.dfg;
+        let instruction = &dfg[instruction];
+
+        let mut operands = Vec::new();
+        instruction.for_each_value(|value| operands.push(value));
+        for operand in operands {
+            self.validate_use(operand, position)?;
+        }
+
+        match instruction {
+            Instruction::Binary(binary) => {
+                self.expect_same_types(binary.lhs, binary.rhs)?;
+                if !matches!(dfg.type_of_value(binary.lhs), Type::Numeric(_)) {
+                    return Err(format!("`{}` must be applied to numeric values", binary.operator));
+                }
+            }
+            Instruction::Constrain(lhs, rhs, _) => self.expect_same_types(*lhs, *rhs)?,
+            Instruction::ArrayGet { array, index } => {
+                self.expect_array_access(*array, *index)?;
+            }
+            Instruction::ArraySet { array, index, value } => {
+                let element_types = self.expect_array_access(*array, *index)?;
+                let value_type = dfg.type_of_value(*value);
+                if !element_types.contains(&value_type) {
+                    return Err(format!(
+                        "a value of type {value_type} is stored in an array of type {}",
+                        dfg.type_of_value(*array)
+                    ));
+                }
+            }
+            Instruction::Call { func, .. } => {
+                if let Value::Function(id) = &dfg[dfg.resolve(*func)] {
+                    if !self.ssa.functions.contains_key(id) {
+                        return Err(format!("call to {id}, which is not in the program"));
+                    }
+                }
+            }
+            Instruction::Allocate | Instruction::Load { .. } | Instruction::Store { .. }
+                if self.check_memory =>
+            {
+                return Err("references remain in an ACIR function after mem2reg".to_owned());
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn validate_terminator(
+        &mut self,
+        block: BasicBlockId,
+        position: Position,
+    ) -> Result<(), String> {
+        let dfg = &self.function.dfg;
+        let Some(terminator) = dfg[block].terminator() else {
+            return Err(format!("{block} has no terminator"));
+        };
+
+        let mut operands = Vec::new();
+        terminator.for_each_value(|value| operands.push(value));
+        for operand in operands {
+            self.validate_use(operand, position)?;
+        }
+
+        match terminator {
+            TerminatorInstruction::Jmp { destination, arguments, .. } => {
+                self.expect_block_arguments(*destination, arguments)
+            }
+            TerminatorInstruction::JmpIf { condition, then_destination, else_destination } => {
+                let condition_type = dfg.type_of_value(*condition);
+                if condition_type != Type::bool() {
+                    return Err(format!("jmpif condition has type {condition_type}"));
+                }
+                self.expect_block_arguments(*then_destination, &[])?;
+                self.expect_block_arguments(*else_destination, &[])
+            }
+            TerminatorInstruction::Return { .. } => Ok(()),
+        }
+    }
+
+    /// Checks that `value` is defined at a point which dominates its use at `position`.
+    fn validate_use(&mut self, value: ValueId, position: Position) -> Result<(), String> {
+        let dfg = &self.function.dfg;
+        let value = dfg.resolve(value);
+        match &dfg[value] {
+            Value::Param { .. } | Value::Instruction { .. } => {
+                let Some(definition) = self.definitions.get(&value).copied() else {
+                    return Err(format!("{value} is used but never defined"));
+                };
+                let dominates = if definition.block == position.block {
+                    definition.index < position.index
+                } else {
+                    self.dom_tree.dominates(definition.block, position.block)
+                };
+                if dominates {
+                    Ok(())
+                } else {
+                    Err(format!("{value} is used before it is defined in {}", definition.block))
+                }
+            }
+            // The elements of array constants are uses of their own
+            Value::Array { array, .. } => {
+                for element in array.clone() {
+                    self.validate_use(element, position)?;
+                }
+                Ok(())
+            }
+            Value::NumericConstant { .. }
+            | Value::Function(_)
+            | Value::Intrinsic(_)
+            | Value::ForeignFunction(_) => Ok(()),
+        }
+    }
+
+    fn expect_same_types(&self, lhs: ValueId, rhs: ValueId) -> Result<(), String> {
+        let dfg = &self.function.dfg;
+        let (lhs_type, rhs_type) = (dfg.type_of_value(lhs), dfg.type_of_value(rhs));
+        if lhs_type == rhs_type {
+            Ok(())
+        } else {
+            Err(format!("operands have mismatched types {lhs_type} and {rhs_type}"))
+        }
+    }
+
+    /// Checks that `array` is an array or slice indexed by a numeric `index`, returning its element types.
+    fn expect_array_access(&self, array: ValueId, index: ValueId) -> Result<Vec<Type>, String> {
+        let dfg = &self.function.dfg;
+        let element_types = match dfg.type_of_value(array) {
+            Type::Array(element_types, _) | Type::Slice(element_types) => element_types,
+            other => return Err(format!("{array} is indexed but has type {other}")),
+        };
+        let index_type = dfg.type_of_value(index);
+        if !matches!(index_type, Type::Numeric(_)) {
+            return Err(format!("array index {index} has type {index_type}"));
+        }
+        Ok(element_types.to_vec())
+    }
+
+    fn expect_block_arguments(
+        &self,
+        destination: BasicBlockId,
+        arguments: &[ValueId],
+    ) -> Result<(), String> {
+        let dfg = &self.function.dfg;
+        let parameters = dfg.block_parameters(destination);
+        if parameters.len() != arguments.len() {
+            return Err(format!(
+                "{destination} expects {} arguments but is passed {}",
+                parameters.len(),
+                arguments.len()
+            ));
+        }
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            let (parameter_type, argument_type) =
+                (dfg.type_of_value(*parameter), dfg.type_of_value(*argument));
+            if parameter_type != argument_type {
+                return Err(format!(
+                    "{destination} expects an argument of type {parameter_type} but is passed {argument_type}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::ssa::ssa_gen::Ssa;
+
+    fn validate(src: &str, memory_promoted: bool) -> Result<(), String> {
+        let ssa = Ssa::from_str(src).unwrap_or_else(|error| panic!("{error}"));
+        ssa.validate("Test Pass", memory_promoted).map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn accepts_well_formed_ssa() {
+        let src = "
+acir fn main f0 {
+  b0(v0: u1, v1: Field):
+    jmpif v0 then: b1, else: b2
+  b1():
+    v3 = add v1, Field 1
+    jmp b3(v3)
+  b2():
+    jmp b3(v1)
+  b3(v2: Field):
+    return v2
+}";
+        assert_eq!(validate(src, true), Ok(()));
+    }
+
+    #[test]
+    fn rejects_use_in_non_dominated_block() {
+        let src = "
+acir fn main f0 {
+  b0(v0: u1, v1: Field):
+    jmpif v0 then: b1, else: b2
+  b1():
+    v3 = add v1, Field 1
+    jmp b3(v1)
+  b2():
+    jmp b3(v3)
+  b3(v2: Field):
+    return v2
+}";
+        let error = validate(src, false).unwrap_err();
+        assert!(error.contains("SSA validation failed after Test Pass"), "{error}");
+        assert!(error.contains("used before it is defined"), "{error}");
+    }
+
+    #[test]
+    fn rejects_mismatched_jump_arguments() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field):
+    jmp b1(v0, v0)
+  b1(v1: Field):
+    return v1
+}";
+        let error = validate(src, false).unwrap_err();
+        assert!(error.contains("b1 expects 1 arguments but is passed 2"), "{error}");
+    }
+
+    #[test]
+    fn rejects_mismatched_binary_operands() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field, v1: u32):
+    v2 = add v0, v1
+    return v2
+}";
+        let error = validate(src, false).unwrap_err();
+        assert!(error.contains("mismatched types Field and u32"), "{error}");
+        assert!(error.contains("v2 = add v0, v1"), "{error}");
+    }
+
+    #[test]
+    fn rejects_references_in_acir_after_mem2reg() {
+        let src = "
+acir fn main f0 {
+  b0(v0: Field):
+    v1 = allocate -> &mut Field
+    store v0 at v1
+    v2 = load v1 -> Field
+    return v2
+}";
+        assert_eq!(validate(src, false), Ok(()));
+        let error = validate(src, true).unwrap_err();
+        assert!(error.contains("references remain"), "{error}");
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::{token::SecondaryAttribute, Ident, UnresolvedGenerics, UnresolvedType};
+use crate::{token::SecondaryAttribute, Ident, ItemVisibility, UnresolvedGenerics, UnresolvedType};
 use iter_extended::vecmap;
 use noirc_errors::Span;
 
@@ -9,20 +9,30 @@ use noirc_errors::Span;
 pub struct NoirStruct {
     pub name: Ident,
     pub attributes: Vec<SecondaryAttribute>,
+    pub visibility: ItemVisibility,
     pub generics: UnresolvedGenerics,
-    pub fields: Vec<(Ident, UnresolvedType)>,
+    pub fields: Vec<StructField>,
     pub span: Span,
 }
 
+/// A single field of a struct: `visibility name: typ`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructField {
+    pub visibility: ItemVisibility,
+    pub name: Ident,
+    pub typ: UnresolvedType,
+}
+
 impl NoirStruct {
     pub fn new(
         name: Ident,
         attributes: Vec<SecondaryAttribute>,
+        visibility: ItemVisibility,
         generics: Vec<Ident>,
-        fields: Vec<(Ident, UnresolvedType)>,
+        fields: Vec<StructField>,
         span: Span,
     ) -> NoirStruct {
-        NoirStruct { name, attributes, generics, fields, span }
+        NoirStruct { name, attributes, visibility, generics, fields, span }
     }
 }
 
@@ -31,12 +41,18 @@ impl Display for NoirStruct {
         let generics = vecmap(&self.generics, |generic| generic.to_string());
         let generics = if generics.is_empty() { "".into() } else { generics.join(", ") };
 
-        writeln!(f, "struct {}{} {{", self.name, generics)?;
+        writeln!(f, "{}struct {}{} {{", self.visibility, self.name, generics)?;
 
-        for (name, typ) in self.fields.iter() {
-            writeln!(f, "    {name}: {typ},")?;
+        for field in self.fields.iter() {
+            writeln!(f, "    {field},")?;
         }
 
         write!(f, "}}")
     }
 }
+
+impl Display for StructField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}: {}", self.visibility, self.name, self.typ)
+    }
+}
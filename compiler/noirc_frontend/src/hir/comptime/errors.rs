@@ -0,0 +1,114 @@
+use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic, Location};
+use thiserror::Error;
+
+/// An error which occurred while evaluating a `comptime` block.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InterpreterError {
+    #[error("`{name}` is not known at compile-time")]
+    NonComptimeVarReferenced { name: String, location: Location },
+    #[error("`{name}` cannot be called from a comptime block used in a type")]
+    CallBeforeResolution { name: String, location: Location },
+    #[error("`{name}` cannot be evaluated at compile-time")]
+    UnsupportedFunction { name: String, location: Location },
+    #[error("{item} cannot be evaluated at compile-time")]
+    Unsupported { item: &'static str, location: Location },
+    #[error("Expected a value of type {expected}")]
+    TypeMismatch { expected: &'static str, location: Location },
+    #[error("Attempt to {operation} with overflow")]
+    IntegerOverflow { operation: &'static str, location: Location },
+    #[error("Attempt to divide by zero")]
+    DivisionByZero { location: Location },
+    #[error("Index out of bounds, the length is {length} but the index is {index}")]
+    IndexOutOfBounds { index: u128, length: usize, location: Location },
+    #[error("Failed assertion{}", message.as_ref().map(|message| format!(": '{message}'")).unwrap_or_default())]
+    AssertionFailed { message: Option<String>, location: Location },
+    #[error("Integers of {bit_size} bits cannot be evaluated at compile-time")]
+    IntegerTooLarge { bit_size: u32, location: Location },
+    #[error("Too many nested function calls in comptime block")]
+    RecursionLimitReached { location: Location },
+    #[error("{item} cannot be the result of a comptime block")]
+    CannotInline { item: &'static str, location: Location },
+    /// The program already failed to resolve or type check, which has been reported elsewhere
+    #[error("Encountered an error expression")]
+    ErrorExpression { location: Location },
+}
+
+impl InterpreterError {
+    pub fn location(&self) -> Location {
+        match self {
+            InterpreterError::NonComptimeVarReferenced { location, .. }
+            | InterpreterError::CallBeforeResolution { location, .. }
+            | InterpreterError::UnsupportedFunction { location, .. }
+            | InterpreterError::Unsupported { location, .. }
+            | InterpreterError::TypeMismatch { location, .. }
+            | InterpreterError::IntegerOverflow { location, .. }
+            | InterpreterError::DivisionByZero { location }
+            | InterpreterError::IndexOutOfBounds { location, .. }
+            | InterpreterError::AssertionFailed { location, .. }
+            | InterpreterError::IntegerTooLarge { location, .. }
+            | InterpreterError::RecursionLimitReached { location }
+            | InterpreterError::CannotInline { location, .. }
+            | InterpreterError::ErrorExpression { location } => *location,
+        }
+    }
+
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            InterpreterError::NonComptimeVarReferenced { .. } => "E0701",
+            InterpreterError::CallBeforeResolution { .. } => "E0702",
+            InterpreterError::UnsupportedFunction { .. } => "E0703",
+            InterpreterError::Unsupported { .. } => "E0704",
+            InterpreterError::TypeMismatch { .. } => "E0705",
+            InterpreterError::IntegerOverflow { .. } => "E0706",
+            InterpreterError::DivisionByZero { .. } => "E0707",
+            InterpreterError::IndexOutOfBounds { .. } => "E0708",
+            InterpreterError::AssertionFailed { .. } => "E0709",
+            InterpreterError::IntegerTooLarge { .. } => "E0710",
+            InterpreterError::RecursionLimitReached { .. } => "E0711",
+            InterpreterError::CannotInline { .. } => "E0712",
+            InterpreterError::ErrorExpression { .. } => "E0713",
+        }
+    }
+
+    /// Errors in expressions which already failed to resolve or type check are not reported again
+    pub fn is_reported_elsewhere(&self) -> bool {
+        matches!(self, InterpreterError::ErrorExpression { .. })
+    }
+}
+
+impl From<InterpreterError> for Diagnostic {
+    fn from(error: InterpreterError) -> Diagnostic {
+        let message = error.to_string();
+        let span = error.location().span;
+        let code = error.code();
+        let secondary = match error {
+            InterpreterError::NonComptimeVarReferenced { .. } => {
+                "Only values defined inside of the comptime block and globals can be used. Comptime blocks are evaluated once rather than for each instantiation of a generic function, so they cannot use its numeric generics".to_string()
+            }
+            InterpreterError::CallBeforeResolution { .. } => {
+                "Comptime blocks used as array lengths or numeric generics are evaluated before the functions of their crate are resolved, so they may only call functions from dependencies and builtin methods such as `len`".to_string()
+            }
+            InterpreterError::UnsupportedFunction { .. } => {
+                "Only functions written in Noir and some builtins can be called at compile-time".to_string()
+            }
+            InterpreterError::TypeMismatch { .. } => {
+                "This is likely a bug in the compiler, please report it".to_string()
+            }
+            InterpreterError::IntegerTooLarge { .. } => {
+                "Use a smaller integer type or a Field".to_string()
+            }
+            _ => String::new(),
+        };
+        let mut diagnostic = Diagnostic::simple_error(message, secondary, span);
+        diagnostic.code = Some(code);
+        diagnostic
+    }
+}
+
+impl From<InterpreterError> for FileDiagnostic {
+    fn from(error: InterpreterError) -> FileDiagnostic {
+        let file = error.location().file;
+        Diagnostic::from(error).in_file(file)
+    }
+}
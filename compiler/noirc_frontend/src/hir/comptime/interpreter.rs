@@ -0,0 +1,924 @@
+use std::collections::HashMap;
+
+use acvm::FieldElement;
+use iter_extended::try_vecmap;
+use noirc_errors::Location;
+
+use crate::graph::CrateId;
+use crate::hir_def::expr::{
+    HirArrayLiteral, HirBlockExpression, HirCallExpression, HirCastExpression,
+    HirConstructorExpression, HirExpression, HirIdent, HirIfExpression, HirIndexExpression,
+    HirInfixExpression, HirLambda, HirLiteral, HirMemberAccess, HirMethodCallExpression,
+    HirPrefixExpression,
+};
+use crate::hir_def::stmt::{
+    HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirPattern, HirStatement,
+};
+use crate::node_interner::{
+    DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, TraitImplKind,
+    TraitMethodId,
+};
+use crate::token::FunctionAttribute;
+use crate::{BinaryOpKind, FunctionKind, Signedness, Type, TypeBinding, TypeBindings, UnaryOp};
+
+use super::errors::InterpreterError;
+use super::value::Value;
+
+/// The maximum number of nested function calls within a comptime block.
+const MAX_CALL_DEPTH: usize = 128;
+
+/// The largest integer bit size which fits in the `i128` used to evaluate integers.
+const MAX_INTEGER_BIT_SIZE: u32 = 126;
+
+type IResult<T> = Result<T, InterpreterError>;
+
+/// Evaluates HIR expressions at compile-time.
+///
+/// The interpreter can be used before type checking, in which case integer literals
+/// are evaluated as fields since their types are not yet known.
+pub struct Interpreter<'interner> {
+    interner: &'interner NodeInterner,
+
+    /// The values of the local variables in each scope of the current function.
+    /// Each function call starts with new scopes since functions cannot refer to
+    /// the variables of their caller.
+    scopes: Vec<HashMap<DefinitionId, Value>>,
+
+    call_depth: usize,
+
+    /// The crate whose types are being resolved, when evaluating a comptime block used as an
+    /// array length or numeric generic. The functions of this crate cannot be called yet.
+    resolving_crate: Option<CrateId>,
+}
+
+impl<'interner> Interpreter<'interner> {
+    pub fn new(interner: &'interner NodeInterner) -> Self {
+        Self { interner, scopes: vec![HashMap::new()], call_depth: 0, resolving_crate: None }
+    }
+
+    /// Creates an interpreter for comptime blocks evaluated while resolving the types of
+    /// the given crate, such as those used as array lengths.
+    pub fn new_for_types(interner: &'interner NodeInterner, crate_id: CrateId) -> Self {
+        Self { resolving_crate: Some(crate_id), ..Self::new(interner) }
+    }
+
+    /// Evaluates the given expression
+    pub fn evaluate(&mut self, id: ExprId) -> IResult<Value> {
+        let location = self.interner.expr_location(&id);
+        match self.interner.expression(&id) {
+            HirExpression::Ident(ident) => self.evaluate_ident(ident, id),
+            HirExpression::Literal(literal) => self.evaluate_literal(literal, id, location),
+            HirExpression::Block(block) => self.evaluate_block(block),
+            HirExpression::Prefix(prefix) => self.evaluate_prefix(prefix, location),
+            HirExpression::Infix(infix) => self.evaluate_infix(infix),
+            HirExpression::Index(index) => self.evaluate_index(index, location),
+            HirExpression::Constructor(constructor) => self.evaluate_constructor(constructor),
+            HirExpression::MemberAccess(access) => self.evaluate_member_access(access, location),
+            HirExpression::Call(call) => self.evaluate_call(call, id),
+            HirExpression::MethodCall(method_call) => self.evaluate_method_call(method_call, id),
+            HirExpression::Cast(cast) => self.evaluate_cast(cast, location),
+            HirExpression::If(if_expr) => self.evaluate_if(if_expr, location),
+            HirExpression::Tuple(fields) => {
+                Ok(Value::Tuple(try_vecmap(fields, |field| self.evaluate(field))?))
+            }
+            HirExpression::Lambda(lambda) => self.evaluate_lambda(lambda),
+            HirExpression::TraitMethodReference(method) => {
+                self.evaluate_trait_method(method, id, location)
+            }
+            HirExpression::Error => Err(InterpreterError::ErrorExpression { location }),
+        }
+    }
+
+    fn evaluate_ident(&mut self, ident: HirIdent, id: ExprId) -> IResult<Value> {
+        let interner = self.interner;
+        match &interner.definition(ident.id).kind {
+            DefinitionKind::Function(function) => Ok(Value::Function(*function, id)),
+            DefinitionKind::Global(expression) => self.evaluate(*expression),
+            DefinitionKind::Local(_) => self.lookup(ident),
+            DefinitionKind::GenericType(type_variable) => match &*type_variable.borrow() {
+                TypeBinding::Bound(binding) => binding
+                    .evaluate_to_u64()
+                    .map(|value| Value::Field(FieldElement::from(value as u128)))
+                    .ok_or_else(|| self.not_comptime(ident)),
+                TypeBinding::Unbound(_) => Err(self.not_comptime(ident)),
+            },
+        }
+    }
+
+    fn lookup(&self, ident: HirIdent) -> IResult<Value> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&ident.id))
+            .cloned()
+            .ok_or_else(|| self.not_comptime(ident))
+    }
+
+    fn lookup_mut(&mut self, ident: HirIdent) -> IResult<&mut Value> {
+        match self.scopes.iter().rposition(|scope| scope.contains_key(&ident.id)) {
+            Some(index) => Ok(self.scopes[index].get_mut(&ident.id).expect("Scope contains ident")),
+            None => Err(self.not_comptime(ident)),
+        }
+    }
+
+    fn not_comptime(&self, ident: HirIdent) -> InterpreterError {
+        let name = self.interner.definition_name(ident.id).to_owned();
+        InterpreterError::NonComptimeVarReferenced { name, location: ident.location }
+    }
+
+    fn evaluate_literal(
+        &mut self,
+        literal: HirLiteral,
+        id: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        match literal {
+            HirLiteral::Unit => Ok(Value::Unit),
+            HirLiteral::Bool(value) => Ok(Value::Bool(value)),
+            HirLiteral::Integer(value, negative) => {
+                let typ = self.interner.id_type(id).follow_bindings();
+                evaluate_integer_literal(value, negative, &typ, location)
+            }
+            HirLiteral::Str(value) => Ok(Value::String(value)),
+            HirLiteral::FmtStr(..) => {
+                Err(InterpreterError::Unsupported { item: "Format strings", location })
+            }
+            HirLiteral::Array(HirArrayLiteral::Standard(elements)) => {
+                Ok(Value::Array(try_vecmap(elements, |element| self.evaluate(element))?))
+            }
+            HirLiteral::Array(HirArrayLiteral::Repeated { repeated_element, length }) => {
+                let element = self.evaluate(repeated_element)?;
+                let length = length.evaluate_to_u64().ok_or(InterpreterError::Unsupported {
+                    item: "Arrays with a generic length",
+                    location,
+                })?;
+                Ok(Value::Array(vec![element; length as usize]))
+            }
+        }
+    }
+
+    fn evaluate_block(&mut self, block: HirBlockExpression) -> IResult<Value> {
+        self.scopes.push(HashMap::new());
+        let mut result = Value::Unit;
+        for statement in block.statements() {
+            result = self.evaluate_statement(*statement)?;
+        }
+        self.scopes.pop();
+        Ok(result)
+    }
+
+    fn evaluate_prefix(
+        &mut self,
+        prefix: HirPrefixExpression,
+        location: Location,
+    ) -> IResult<Value> {
+        let rhs = self.evaluate(prefix.rhs)?;
+        match (prefix.operator, rhs) {
+            (UnaryOp::Minus, Value::Field(value)) => Ok(Value::Field(-value)),
+            (UnaryOp::Minus, Value::Integer(value, signedness, bit_size)) => {
+                checked_integer(value.checked_neg(), signedness, bit_size, "negate", location)
+            }
+            (UnaryOp::Not, Value::Bool(value)) => Ok(Value::Bool(!value)),
+            (UnaryOp::Not, Value::Integer(value, signedness, bit_size)) => {
+                Ok(Value::Integer(truncate(!value, signedness, bit_size), signedness, bit_size))
+            }
+            (UnaryOp::MutableReference | UnaryOp::Dereference { .. }, _) => {
+                Err(InterpreterError::Unsupported { item: "References", location })
+            }
+            (UnaryOp::Minus, _) => {
+                Err(InterpreterError::TypeMismatch { expected: "integer or field", location })
+            }
+            (UnaryOp::Not, _) => {
+                Err(InterpreterError::TypeMismatch { expected: "integer or bool", location })
+            }
+        }
+    }
+
+    fn evaluate_infix(&mut self, infix: HirInfixExpression) -> IResult<Value> {
+        let lhs = self.evaluate(infix.lhs)?;
+        let rhs = self.evaluate(infix.rhs)?;
+        evaluate_binary(infix.operator.kind, lhs, rhs, infix.operator.location)
+    }
+
+    fn evaluate_index(&mut self, index: HirIndexExpression, location: Location) -> IResult<Value> {
+        let collection = self.evaluate(index.collection)?;
+        let index = self.evaluate(index.index)?;
+        match collection {
+            Value::Array(mut elements) => {
+                let index = array_index(&index, elements.len(), location)?;
+                Ok(elements.swap_remove(index))
+            }
+            _ => Err(InterpreterError::TypeMismatch { expected: "array", location }),
+        }
+    }
+
+    fn evaluate_constructor(&mut self, constructor: HirConstructorExpression) -> IResult<Value> {
+        let fields = try_vecmap(constructor.fields, |(name, field)| {
+            Ok((name.0.contents, self.evaluate(field)?))
+        })?;
+        Ok(Value::Struct(fields))
+    }
+
+    fn evaluate_member_access(
+        &mut self,
+        access: HirMemberAccess,
+        location: Location,
+    ) -> IResult<Value> {
+        let object = self.evaluate(access.lhs)?;
+        member(object, &access.rhs.0.contents, location)
+    }
+
+    fn evaluate_call(&mut self, call: HirCallExpression, id: ExprId) -> IResult<Value> {
+        let function = self.evaluate(call.func)?;
+        let arguments = try_vecmap(call.arguments, |argument| self.evaluate(argument))?;
+        match function {
+            Value::Function(function, function_expr) => {
+                self.call_function(function, function_expr, arguments, id, call.location)
+            }
+            Value::Closure(lambda, captures) => {
+                let parameters = lambda.parameters.into_iter().map(|(pattern, _)| pattern);
+                let parameters = captures
+                    .into_iter()
+                    .map(|(id, value)| {
+                        (HirPattern::Identifier(HirIdent { location: call.location, id }), value)
+                    })
+                    .chain(parameters.zip(arguments))
+                    .collect();
+                self.call_body(parameters, lambda.body, call.location)
+            }
+            _ => Err(InterpreterError::TypeMismatch {
+                expected: "function",
+                location: call.location,
+            }),
+        }
+    }
+
+    /// Method calls are only evaluated before type checking, which otherwise turns them into
+    /// function calls. Without types, the method is found from the type of the object's value,
+    /// so only methods on primitive types such as `len` on arrays can be called.
+    fn evaluate_method_call(
+        &mut self,
+        method_call: HirMethodCallExpression,
+        id: ExprId,
+    ) -> IResult<Value> {
+        let location = method_call.location;
+        let object = self.evaluate(method_call.object)?;
+        let method_name = &method_call.method.0.contents;
+        let function = value_type(&object)
+            .and_then(|typ| self.interner.lookup_primitive_method(&typ, method_name))
+            .ok_or(InterpreterError::Unsupported {
+                item: "Methods on non-primitive types before type checking",
+                location,
+            })?;
+
+        let mut arguments = vec![object];
+        for argument in method_call.arguments {
+            arguments.push(self.evaluate(argument)?);
+        }
+        self.call_function(function, id, arguments, id, location)
+    }
+
+    fn call_function(
+        &mut self,
+        function: FuncId,
+        function_expr: ExprId,
+        arguments: Vec<Value>,
+        call: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        let interner = self.interner;
+        let name = || interner.function_name(&function).to_owned();
+
+        // Functions are only resolved after the types of the crate they are in. Functions of
+        // that crate which happen to be resolved already are rejected as well, so that whether
+        // a program compiles does not depend on the order its functions are declared in.
+        let is_resolving = self.resolving_crate == Some(interner.function_module(function).krate);
+        let meta = match interner.try_function_meta(&function) {
+            Some(meta) if !(is_resolving && meta.kind == FunctionKind::Normal) => meta,
+            _ => return Err(InterpreterError::CallBeforeResolution { name: name(), location }),
+        };
+
+        match meta.kind {
+            FunctionKind::Normal => (),
+            FunctionKind::Builtin => return self.call_builtin(function, arguments, call, location),
+            FunctionKind::LowLevel | FunctionKind::Oracle => {
+                return Err(InterpreterError::UnsupportedFunction { name: name(), location })
+            }
+        }
+
+        // Bind the generics of the function to the types it is called with while evaluating it,
+        // so that literals and trait methods within it can be resolved to their actual types.
+        let bindings: TypeBindings = interner
+            .try_get_instantiation_bindings(function_expr)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .map(|(id, (var, binding))| (*id, (var.clone(), binding.follow_bindings())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (var, binding) in bindings.values() {
+            var.force_bind(binding.clone());
+        }
+
+        let parameters = meta.parameters.0.into_iter().map(|(pattern, ..)| pattern);
+        let body = *interner.function(&function).as_expr();
+        let result = self.call_body(parameters.zip(arguments).collect(), body, location);
+
+        for (id, (var, _)) in bindings {
+            var.unbind(id);
+        }
+        result
+    }
+
+    /// Evaluates `body` in a new set of scopes in which only the given parameters are defined.
+    fn call_body(
+        &mut self,
+        parameters: Vec<(HirPattern, Value)>,
+        body: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(InterpreterError::RecursionLimitReached { location });
+        }
+
+        let caller_scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+        self.call_depth += 1;
+
+        let mut result = Ok(());
+        for (pattern, argument) in parameters {
+            result = result.and_then(|_| self.define_pattern(&pattern, argument, location));
+        }
+        let result = result.and_then(|_| self.evaluate(body));
+
+        self.call_depth -= 1;
+        self.scopes = caller_scopes;
+        result
+    }
+
+    fn call_builtin(
+        &mut self,
+        function: FuncId,
+        arguments: Vec<Value>,
+        call: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        let interner = self.interner;
+        let attribute = interner.function_attributes(&function).function.clone();
+        let builtin = attribute.and_then(FunctionAttribute::builtin);
+
+        match (builtin.as_deref(), arguments.as_slice()) {
+            (Some("array_len"), [Value::Array(elements)]) => {
+                Ok(Value::Field(FieldElement::from(elements.len())))
+            }
+            (Some("as_field"), [value]) => cast(value.clone(), &Type::FieldElement, location),
+            (Some("from_field"), [value @ Value::Field(_)]) => {
+                cast(value.clone(), &interner.id_type(call), location)
+            }
+            (Some("assert_constant"), _) => Ok(Value::Unit),
+            (Some("modulus_num_bits"), []) => {
+                Ok(Value::Field(FieldElement::from(FieldElement::max_num_bits() as u128)))
+            }
+            _ => {
+                let name = interner.function_name(&function).to_owned();
+                Err(InterpreterError::UnsupportedFunction { name, location })
+            }
+        }
+    }
+
+    fn evaluate_cast(
+        &mut self,
+        cast_expr: HirCastExpression,
+        location: Location,
+    ) -> IResult<Value> {
+        let value = self.evaluate(cast_expr.lhs)?;
+        cast(value, &cast_expr.r#type, location)
+    }
+
+    fn evaluate_if(&mut self, if_expr: HirIfExpression, location: Location) -> IResult<Value> {
+        match self.evaluate(if_expr.condition)? {
+            Value::Bool(true) => self.evaluate(if_expr.consequence),
+            Value::Bool(false) => match if_expr.alternative {
+                Some(alternative) => self.evaluate(alternative),
+                None => Ok(Value::Unit),
+            },
+            _ => Err(InterpreterError::TypeMismatch { expected: "bool", location }),
+        }
+    }
+
+    fn evaluate_lambda(&mut self, lambda: HirLambda) -> IResult<Value> {
+        let captures = try_vecmap(&lambda.captures, |capture| {
+            Ok((capture.ident.id, self.lookup(capture.ident)?))
+        })?;
+        Ok(Value::Closure(lambda, captures))
+    }
+
+    fn evaluate_trait_method(
+        &mut self,
+        method: TraitMethodId,
+        id: ExprId,
+        location: Location,
+    ) -> IResult<Value> {
+        let interner = self.interner;
+        let impl_id = match interner.get_selected_impl_for_ident(id) {
+            Some(TraitImplKind::Normal(impl_id)) => Some(impl_id),
+            Some(TraitImplKind::Assumed { object_type }) => {
+                match interner.lookup_trait_implementation(&object_type, method.trait_id) {
+                    Ok(TraitImplKind::Normal(impl_id)) => Some(impl_id),
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
+        match impl_id {
+            Some(impl_id) => {
+                let function = interner.get_trait_implementation(impl_id).borrow().methods
+                    [method.method_index];
+                Ok(Value::Function(function, id))
+            }
+            None => Err(InterpreterError::Unsupported { item: "Generic trait methods", location }),
+        }
+    }
+
+    fn evaluate_statement(&mut self, statement: StmtId) -> IResult<Value> {
+        match self.interner.statement(&statement) {
+            HirStatement::Let(let_statement) => {
+                let value = self.evaluate(let_statement.expression)?;
+                let location = self.interner.expr_location(&let_statement.expression);
+                self.define_pattern(&let_statement.pattern, value, location)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::Constrain(constrain) => self.evaluate_constrain(constrain),
+            HirStatement::Assign(assign) => self.evaluate_assign(assign),
+            HirStatement::For(for_loop) => self.evaluate_for(for_loop),
+            HirStatement::Expression(expression) => self.evaluate(expression),
+            HirStatement::Semi(expression) => {
+                self.evaluate(expression)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::Error => {
+                Err(InterpreterError::ErrorExpression { location: Location::dummy() })
+            }
+        }
+    }
+
+    fn define_pattern(
+        &mut self,
+        pattern: &HirPattern,
+        value: Value,
+        location: Location,
+    ) -> IResult<()> {
+        match pattern {
+            HirPattern::Identifier(ident) => {
+                let scope = self.scopes.last_mut().expect("There is always at least one scope");
+                scope.insert(ident.id, value);
+                Ok(())
+            }
+            HirPattern::Mutable(pattern, _) => self.define_pattern(pattern, value, location),
+            HirPattern::Tuple(patterns, _) => match value {
+                Value::Tuple(fields) if fields.len() == patterns.len() => {
+                    for (pattern, field) in patterns.iter().zip(fields) {
+                        self.define_pattern(pattern, field, location)?;
+                    }
+                    Ok(())
+                }
+                _ => Err(InterpreterError::TypeMismatch { expected: "tuple", location }),
+            },
+            HirPattern::Struct(_, patterns, _) => {
+                for (field_name, pattern) in patterns {
+                    let field = member(value.clone(), &field_name.0.contents, location)?;
+                    self.define_pattern(pattern, field, location)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn evaluate_constrain(&mut self, constrain: HirConstrainStatement) -> IResult<Value> {
+        let HirConstrainStatement(expression, _, message) = constrain;
+        let location = self.interner.expr_location(&expression);
+        match self.evaluate(expression)? {
+            Value::Bool(true) => Ok(Value::Unit),
+            Value::Bool(false) => Err(InterpreterError::AssertionFailed { message, location }),
+            _ => Err(InterpreterError::TypeMismatch { expected: "bool", location }),
+        }
+    }
+
+    fn evaluate_assign(&mut self, assign: HirAssignStatement) -> IResult<Value> {
+        let value = self.evaluate(assign.expression)?;
+        let location = self.interner.expr_location(&assign.expression);
+        self.store_lvalue(assign.lvalue, value, location)?;
+        Ok(Value::Unit)
+    }
+
+    fn store_lvalue(&mut self, lvalue: HirLValue, value: Value, location: Location) -> IResult<()> {
+        match lvalue {
+            HirLValue::Ident(ident, _) => {
+                *self.lookup_mut(ident)? = value;
+                Ok(())
+            }
+            HirLValue::Index { array, index, .. } => {
+                let index = self.evaluate(index)?;
+                let mut array_value = self.evaluate_lvalue(&array, location)?;
+                match &mut array_value {
+                    Value::Array(elements) => {
+                        let index = array_index(&index, elements.len(), location)?;
+                        elements[index] = value;
+                    }
+                    _ => {
+                        return Err(InterpreterError::TypeMismatch { expected: "array", location })
+                    }
+                }
+                self.store_lvalue(*array, array_value, location)
+            }
+            HirLValue::MemberAccess { object, field_name, .. } => {
+                let mut object_value = self.evaluate_lvalue(&object, location)?;
+                let field_name = &field_name.0.contents;
+                let field = match &mut object_value {
+                    Value::Tuple(fields) => {
+                        field_name.parse::<usize>().ok().and_then(|index| fields.get_mut(index))
+                    }
+                    Value::Struct(fields) => fields
+                        .iter_mut()
+                        .find(|(name, _)| name == field_name)
+                        .map(|(_, field)| field),
+                    _ => None,
+                };
+                match field {
+                    Some(field) => *field = value,
+                    None => {
+                        let expected = "struct or tuple";
+                        return Err(InterpreterError::TypeMismatch { expected, location });
+                    }
+                }
+                self.store_lvalue(*object, object_value, location)
+            }
+            HirLValue::Dereference { .. } => {
+                Err(InterpreterError::Unsupported { item: "References", location })
+            }
+        }
+    }
+
+    fn evaluate_lvalue(&mut self, lvalue: &HirLValue, location: Location) -> IResult<Value> {
+        match lvalue {
+            HirLValue::Ident(ident, _) => self.lookup(*ident),
+            HirLValue::Index { array, index, .. } => {
+                let array = self.evaluate_lvalue(array, location)?;
+                let index = self.evaluate(*index)?;
+                match array {
+                    Value::Array(mut elements) => {
+                        let index = array_index(&index, elements.len(), location)?;
+                        Ok(elements.swap_remove(index))
+                    }
+                    _ => Err(InterpreterError::TypeMismatch { expected: "array", location }),
+                }
+            }
+            HirLValue::MemberAccess { object, field_name, .. } => {
+                let object = self.evaluate_lvalue(object, location)?;
+                member(object, &field_name.0.contents, location)
+            }
+            HirLValue::Dereference { .. } => {
+                Err(InterpreterError::Unsupported { item: "References", location })
+            }
+        }
+    }
+
+    fn evaluate_for(&mut self, for_loop: HirForStatement) -> IResult<Value> {
+        let location = self.interner.expr_location(&for_loop.start_range);
+        let start = self.evaluate(for_loop.start_range)?;
+        let end = self.evaluate(for_loop.end_range)?;
+
+        let (start_index, end_index) = match (start.to_u128(), end.to_u128()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Err(InterpreterError::TypeMismatch { expected: "loop bounds", location }),
+        };
+
+        for index in start_index..end_index {
+            let index = match start {
+                Value::Integer(_, signedness, bit_size) => {
+                    Value::Integer(index as i128, signedness, bit_size)
+                }
+                _ => Value::Field(FieldElement::from(index)),
+            };
+            self.scopes.push(HashMap::from([(for_loop.identifier.id, index)]));
+            self.evaluate(for_loop.block)?;
+            self.scopes.pop();
+        }
+        Ok(Value::Unit)
+    }
+}
+
+fn evaluate_integer_literal(
+    value: FieldElement,
+    negative: bool,
+    typ: &Type,
+    location: Location,
+) -> IResult<Value> {
+    match typ {
+        Type::Integer(signedness, bit_size) => {
+            let value = value.try_into_u128().and_then(|value| i128::try_from(value).ok());
+            let value = if negative { value.and_then(i128::checked_neg) } else { value };
+            checked_integer(value, *signedness, *bit_size, "create an integer", location)
+        }
+        _ => Ok(Value::Field(if negative { -value } else { value })),
+    }
+}
+
+/// Returns an integer value if `value` fits within the given integer type
+fn checked_integer(
+    value: Option<i128>,
+    signedness: Signedness,
+    bit_size: u32,
+    operation: &'static str,
+    location: Location,
+) -> IResult<Value> {
+    if bit_size > MAX_INTEGER_BIT_SIZE {
+        return Err(InterpreterError::IntegerTooLarge { bit_size, location });
+    }
+    let (min, max) = match signedness {
+        Signedness::Unsigned => (0, (1i128 << bit_size) - 1),
+        Signedness::Signed => (-(1i128 << (bit_size - 1)), (1i128 << (bit_size - 1)) - 1),
+    };
+    match value {
+        Some(value) if (min..=max).contains(&value) => {
+            Ok(Value::Integer(value, signedness, bit_size))
+        }
+        _ => Err(InterpreterError::IntegerOverflow { operation, location }),
+    }
+}
+
+/// Wraps `value` around to fit within the given integer type
+fn truncate(value: i128, signedness: Signedness, bit_size: u32) -> i128 {
+    let modulus = 1i128 << bit_size;
+    let value = value.rem_euclid(modulus);
+    match signedness {
+        Signedness::Signed if value >= modulus / 2 => value - modulus,
+        _ => value,
+    }
+}
+
+fn evaluate_binary(
+    operator: BinaryOpKind,
+    lhs: Value,
+    rhs: Value,
+    location: Location,
+) -> IResult<Value> {
+    if matches!(operator, BinaryOpKind::Equal | BinaryOpKind::NotEqual) {
+        let equal = values_equal(&lhs, &rhs)
+            .ok_or(InterpreterError::TypeMismatch { expected: "comparable value", location })?;
+        return Ok(Value::Bool(equal == (operator == BinaryOpKind::Equal)));
+    }
+
+    match (lhs, rhs) {
+        (Value::Bool(lhs), Value::Bool(rhs)) => match operator {
+            BinaryOpKind::And => Ok(Value::Bool(lhs & rhs)),
+            BinaryOpKind::Or => Ok(Value::Bool(lhs | rhs)),
+            BinaryOpKind::Xor => Ok(Value::Bool(lhs ^ rhs)),
+            _ => Err(InterpreterError::TypeMismatch { expected: "integer or field", location }),
+        },
+        (Value::Field(lhs), Value::Field(rhs)) => {
+            evaluate_field_binary(operator, lhs, rhs, location)
+        }
+        (Value::Integer(lhs, signedness, bit_size), rhs) => {
+            let rhs = integer_operand(rhs, signedness, bit_size, location)?;
+            evaluate_integer_binary(operator, lhs, rhs, signedness, bit_size, location)
+        }
+        (lhs, Value::Integer(rhs, signedness, bit_size)) => {
+            let lhs = integer_operand(lhs, signedness, bit_size, location)?;
+            evaluate_integer_binary(operator, lhs, rhs, signedness, bit_size, location)
+        }
+        _ => Err(InterpreterError::TypeMismatch { expected: "integer, field or bool", location }),
+    }
+}
+
+/// Returns the value of an operand of a binary operation on integers of the given type.
+/// Fields are accepted since literals are evaluated as fields when their type is not known.
+fn integer_operand(
+    value: Value,
+    signedness: Signedness,
+    bit_size: u32,
+    location: Location,
+) -> IResult<i128> {
+    match value {
+        Value::Integer(value, ..) => Ok(value),
+        Value::Field(value) => {
+            match evaluate_integer_literal(
+                value,
+                false,
+                &Type::Integer(signedness, bit_size),
+                location,
+            )? {
+                Value::Integer(value, ..) => Ok(value),
+                _ => unreachable!("Integer literals evaluate to integers"),
+            }
+        }
+        _ => Err(InterpreterError::TypeMismatch { expected: "integer", location }),
+    }
+}
+
+fn evaluate_field_binary(
+    operator: BinaryOpKind,
+    lhs: FieldElement,
+    rhs: FieldElement,
+    location: Location,
+) -> IResult<Value> {
+    let value = match operator {
+        BinaryOpKind::Add => lhs + rhs,
+        BinaryOpKind::Subtract => lhs - rhs,
+        BinaryOpKind::Multiply => lhs * rhs,
+        BinaryOpKind::Divide if rhs.is_zero() => {
+            return Err(InterpreterError::DivisionByZero { location })
+        }
+        BinaryOpKind::Divide => lhs / rhs,
+        BinaryOpKind::Less => return Ok(Value::Bool(lhs < rhs)),
+        BinaryOpKind::LessEqual => return Ok(Value::Bool(lhs <= rhs)),
+        BinaryOpKind::Greater => return Ok(Value::Bool(lhs > rhs)),
+        BinaryOpKind::GreaterEqual => return Ok(Value::Bool(lhs >= rhs)),
+        // The remaining operators are only valid on integers, but may be applied to
+        // fields when the types of literals are not yet known.
+        _ => {
+            let (lhs, rhs) = match (lhs.try_into_u128(), rhs.try_into_u128()) {
+                (Some(lhs), Some(rhs)) => (lhs, rhs),
+                _ => return Err(InterpreterError::TypeMismatch { expected: "integer", location }),
+            };
+            let value = match operator {
+                BinaryOpKind::Modulo if rhs == 0 => {
+                    return Err(InterpreterError::DivisionByZero { location })
+                }
+                BinaryOpKind::Modulo => lhs % rhs,
+                BinaryOpKind::And => lhs & rhs,
+                BinaryOpKind::Or => lhs | rhs,
+                BinaryOpKind::Xor => lhs ^ rhs,
+                BinaryOpKind::ShiftRight => lhs.checked_shr(rhs as u32).unwrap_or(0),
+                _ => {
+                    let shift = FieldElement::from(2u128).pow(&FieldElement::from(rhs));
+                    return Ok(Value::Field(FieldElement::from(lhs) * shift));
+                }
+            };
+            FieldElement::from(value)
+        }
+    };
+    Ok(Value::Field(value))
+}
+
+fn evaluate_integer_binary(
+    operator: BinaryOpKind,
+    lhs: i128,
+    rhs: i128,
+    signedness: Signedness,
+    bit_size: u32,
+    location: Location,
+) -> IResult<Value> {
+    let checked =
+        |value, operation| checked_integer(value, signedness, bit_size, operation, location);
+    match operator {
+        BinaryOpKind::Add => checked(lhs.checked_add(rhs), "add"),
+        BinaryOpKind::Subtract => checked(lhs.checked_sub(rhs), "subtract"),
+        BinaryOpKind::Multiply => checked(lhs.checked_mul(rhs), "multiply"),
+        BinaryOpKind::Divide | BinaryOpKind::Modulo if rhs == 0 => {
+            Err(InterpreterError::DivisionByZero { location })
+        }
+        BinaryOpKind::Divide => checked(lhs.checked_div(rhs), "divide"),
+        BinaryOpKind::Modulo => checked(lhs.checked_rem(rhs), "calculate the remainder"),
+        BinaryOpKind::Less => Ok(Value::Bool(lhs < rhs)),
+        BinaryOpKind::LessEqual => Ok(Value::Bool(lhs <= rhs)),
+        BinaryOpKind::Greater => Ok(Value::Bool(lhs > rhs)),
+        BinaryOpKind::GreaterEqual => Ok(Value::Bool(lhs >= rhs)),
+        BinaryOpKind::And => checked(Some(lhs & rhs), "and"),
+        BinaryOpKind::Or => checked(Some(lhs | rhs), "or"),
+        BinaryOpKind::Xor => checked(Some(lhs ^ rhs), "xor"),
+        BinaryOpKind::ShiftLeft => {
+            let value = if rhs < 0 || rhs >= bit_size as i128 {
+                0
+            } else {
+                truncate(lhs.wrapping_shl(rhs as u32), signedness, bit_size)
+            };
+            Ok(Value::Integer(value, signedness, bit_size))
+        }
+        BinaryOpKind::ShiftRight => {
+            let value = lhs >> rhs.clamp(0, 127);
+            Ok(Value::Integer(value, signedness, bit_size))
+        }
+        BinaryOpKind::Equal | BinaryOpKind::NotEqual => {
+            unreachable!("Equality is checked for all values in evaluate_binary")
+        }
+    }
+}
+
+/// Returns whether two values are equal, or None if they cannot be compared
+fn values_equal(lhs: &Value, rhs: &Value) -> Option<bool> {
+    let all_equal = |lhs: &[Value], rhs: &[Value]| {
+        if lhs.len() != rhs.len() {
+            return Some(false);
+        }
+        lhs.iter()
+            .zip(rhs)
+            .try_fold(true, |equal, (lhs, rhs)| Some(equal && values_equal(lhs, rhs)?))
+    };
+
+    match (lhs, rhs) {
+        (Value::Unit, Value::Unit) => Some(true),
+        (Value::Bool(lhs), Value::Bool(rhs)) => Some(lhs == rhs),
+        (Value::Field(lhs), Value::Field(rhs)) => Some(lhs == rhs),
+        (Value::Integer(lhs, ..), Value::Integer(rhs, ..)) => Some(lhs == rhs),
+        (Value::Integer(integer, ..), Value::Field(field))
+        | (Value::Field(field), Value::Integer(integer, ..)) => {
+            Some(*integer >= 0 && field.try_into_u128() == Some(*integer as u128))
+        }
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs == rhs),
+        (Value::Array(lhs), Value::Array(rhs)) | (Value::Tuple(lhs), Value::Tuple(rhs)) => {
+            all_equal(lhs, rhs)
+        }
+        (Value::Struct(lhs), Value::Struct(rhs)) => {
+            lhs.iter().try_fold(true, |equal, (name, lhs)| {
+                let rhs = rhs.iter().find(|(field, _)| field == name).map(|(_, rhs)| rhs)?;
+                Some(equal && values_equal(lhs, rhs)?)
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the type of a value of a primitive type. Elements and fields whose type cannot be
+/// determined, such as the elements of an empty array, have an error type which unifies with
+/// any other type.
+fn value_type(value: &Value) -> Option<Type> {
+    let element_type = |value: &Value| value_type(value).unwrap_or(Type::Error);
+    match value {
+        Value::Unit => Some(Type::Unit),
+        Value::Bool(_) => Some(Type::Bool),
+        Value::Field(_) => Some(Type::FieldElement),
+        Value::Integer(_, signedness, bit_size) => Some(Type::Integer(*signedness, *bit_size)),
+        Value::String(value) => Some(Type::String(Box::new(Type::Constant(value.len() as u64)))),
+        Value::Array(elements) => {
+            let length = Box::new(Type::Constant(elements.len() as u64));
+            let element_type = elements.first().map_or(Type::Error, element_type);
+            Some(Type::Array(length, Box::new(element_type)))
+        }
+        Value::Tuple(fields) => Some(Type::Tuple(fields.iter().map(element_type).collect())),
+        Value::Struct(_) | Value::Function(..) | Value::Closure(..) => None,
+    }
+}
+
+fn array_index(index: &Value, length: usize, location: Location) -> IResult<usize> {
+    let index = index
+        .to_u128()
+        .ok_or(InterpreterError::TypeMismatch { expected: "array index", location })?;
+    if index >= length as u128 {
+        return Err(InterpreterError::IndexOutOfBounds { index, length, location });
+    }
+    Ok(index as usize)
+}
+
+/// Returns the field of a struct or tuple with the given name
+fn member(object: Value, name: &str, location: Location) -> IResult<Value> {
+    let field = match object {
+        Value::Tuple(mut fields) => name
+            .parse::<usize>()
+            .ok()
+            .filter(|index| *index < fields.len())
+            .map(|index| fields.swap_remove(index)),
+        Value::Struct(fields) => {
+            fields.into_iter().find(|(field, _)| field == name).map(|(_, field)| field)
+        }
+        _ => None,
+    };
+    field.ok_or(InterpreterError::TypeMismatch { expected: "struct or tuple", location })
+}
+
+/// Casts a value to a numeric type, wrapping it around if it does not fit
+fn cast(value: Value, typ: &Type, location: Location) -> IResult<Value> {
+    let typ = typ.follow_bindings();
+    match typ {
+        Type::Integer(signedness, bit_size) => {
+            if bit_size > MAX_INTEGER_BIT_SIZE {
+                return Err(InterpreterError::IntegerTooLarge { bit_size, location });
+            }
+            let value = match value {
+                Value::Integer(value, ..) => value,
+                Value::Bool(value) => value as i128,
+                Value::Field(value) => {
+                    // Only the lowest 128 bits can remain after truncation
+                    let bytes = value.to_be_bytes();
+                    let low_bytes = bytes[bytes.len() - 16..].try_into().expect("16 bytes");
+                    u128::from_be_bytes(low_bytes) as i128
+                }
+                _ => return Err(InterpreterError::TypeMismatch { expected: "numeric", location }),
+            };
+            Ok(Value::Integer(truncate(value, signedness, bit_size), signedness, bit_size))
+        }
+        Type::Bool => Err(InterpreterError::TypeMismatch { expected: "numeric", location }),
+        // Any other type is either a field or an unbound integer literal type defaulting to one
+        _ => match value {
+            Value::Field(value) => Ok(Value::Field(value)),
+            Value::Bool(value) => Ok(Value::Field(FieldElement::from(value))),
+            // Signed integers are represented by their two's complement in a field
+            Value::Integer(value, _, bit_size) => {
+                let value = truncate(value, Signedness::Unsigned, bit_size);
+                Ok(Value::Field(FieldElement::from(value)))
+            }
+            _ => Err(InterpreterError::TypeMismatch { expected: "numeric", location }),
+        },
+    }
+}
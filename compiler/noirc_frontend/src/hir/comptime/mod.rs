@@ -0,0 +1,34 @@
+//! Evaluation of `comptime` blocks.
+//!
+//! Each `comptime` block is evaluated by an interpreter over the HIR once its crate has been
+//! type checked, and is then replaced with the literal expression of its result. Globals
+//! initialized by a `comptime` block may also be evaluated while resolving types, when they
+//! are used as array lengths or numeric generics.
+mod errors;
+mod interpreter;
+mod value;
+
+pub use errors::InterpreterError;
+pub use interpreter::Interpreter;
+pub use value::Value;
+
+use crate::node_interner::NodeInterner;
+
+/// Evaluates each `comptime` block resolved since the last call, replacing each
+/// with the literal expression of its result.
+pub(crate) fn evaluate_comptime_expressions(interner: &mut NodeInterner) -> Vec<InterpreterError> {
+    let mut errors = Vec::new();
+    for expr_id in interner.take_comptime_expressions() {
+        let location = interner.expr_location(&expr_id);
+        let typ = interner.id_type(expr_id);
+        let value = Interpreter::new(interner).evaluate(expr_id);
+        let result = value.and_then(|value| value.into_hir_expression(interner, &typ, location));
+
+        match result {
+            Ok(expression) => interner.replace_expr(&expr_id, expression),
+            Err(error) if error.is_reported_elsewhere() => (),
+            Err(error) => errors.push(error),
+        }
+    }
+    errors
+}
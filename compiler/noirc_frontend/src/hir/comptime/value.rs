@@ -0,0 +1,126 @@
+use acvm::FieldElement;
+use iter_extended::try_vecmap;
+use noirc_errors::Location;
+
+use crate::hir_def::expr::{
+    HirArrayLiteral, HirConstructorExpression, HirExpression, HirLambda, HirLiteral,
+};
+use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner};
+use crate::{Ident, Signedness, Type};
+
+use super::errors::InterpreterError;
+
+/// A value produced by evaluating an expression at compile-time.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Field(FieldElement),
+    /// An integer of the given signedness and bit size. Signed integers hold
+    /// their actual value rather than their two's complement representation.
+    Integer(i128, Signedness, u32),
+    String(String),
+    Array(Vec<Value>),
+    Tuple(Vec<Value>),
+    /// The fields of a struct in the order they were constructed in.
+    Struct(Vec<(String, Value)>),
+    /// A function along with the expression referring to it, which holds its
+    /// instantiation bindings and selected trait impl.
+    Function(FuncId, ExprId),
+    /// A lambda along with the values of the variables it captures.
+    Closure(HirLambda, Vec<(DefinitionId, Value)>),
+}
+
+impl Value {
+    /// Converts an integer or field value into a `u128`, if it is non-negative and small enough.
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        match self {
+            Value::Field(value) => value.try_into_u128(),
+            Value::Integer(value, ..) => u128::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Converts this value into the equivalent HIR expression of the given type.
+    pub(crate) fn into_hir_expression(
+        self,
+        interner: &mut NodeInterner,
+        typ: &Type,
+        location: Location,
+    ) -> Result<HirExpression, InterpreterError> {
+        let typ = typ.follow_bindings();
+        let expression = match self {
+            Value::Unit => HirExpression::Literal(HirLiteral::Unit),
+            Value::Bool(value) => HirExpression::Literal(HirLiteral::Bool(value)),
+            Value::Field(value) => HirExpression::Literal(HirLiteral::Integer(value, false)),
+            Value::Integer(value, ..) => {
+                let field = FieldElement::from(value.unsigned_abs());
+                HirExpression::Literal(HirLiteral::Integer(field, value < 0))
+            }
+            Value::String(value) => HirExpression::Literal(HirLiteral::Str(value)),
+            Value::Array(elements) => {
+                let element_type = match &typ {
+                    Type::Array(_, element_type) => element_type.as_ref(),
+                    _ => {
+                        return Err(InterpreterError::TypeMismatch { expected: "array", location })
+                    }
+                };
+                let elements = try_vecmap(elements, |element| {
+                    element.into_expr_id(interner, element_type, location)
+                })?;
+                HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements)))
+            }
+            Value::Tuple(fields) => {
+                let field_types = match &typ {
+                    Type::Tuple(field_types) => field_types,
+                    _ => {
+                        return Err(InterpreterError::TypeMismatch { expected: "tuple", location })
+                    }
+                };
+                let fields = try_vecmap(fields.into_iter().zip(field_types), |(field, typ)| {
+                    field.into_expr_id(interner, typ, location)
+                })?;
+                HirExpression::Tuple(fields)
+            }
+            Value::Struct(mut fields) => {
+                let (struct_type, struct_generics) = match &typ {
+                    Type::Struct(struct_type, generics) => (struct_type.clone(), generics.clone()),
+                    _ => {
+                        return Err(InterpreterError::TypeMismatch { expected: "struct", location })
+                    }
+                };
+                let field_types = struct_type.borrow().get_fields(&struct_generics);
+                let fields = try_vecmap(field_types, |(name, typ)| {
+                    let index = fields.iter().position(|(field, _)| *field == name);
+                    let index = index
+                        .ok_or(InterpreterError::TypeMismatch { expected: "struct", location })?;
+                    let (_, value) = fields.swap_remove(index);
+                    let value = value.into_expr_id(interner, &typ, location)?;
+                    Ok((Ident::new(name, location.span), value))
+                })?;
+                HirExpression::Constructor(HirConstructorExpression {
+                    r#type: struct_type,
+                    struct_generics,
+                    fields,
+                })
+            }
+            Value::Function(..) | Value::Closure(..) => {
+                return Err(InterpreterError::CannotInline { item: "Functions", location })
+            }
+        };
+        Ok(expression)
+    }
+
+    fn into_expr_id(
+        self,
+        interner: &mut NodeInterner,
+        typ: &Type,
+        location: Location,
+    ) -> Result<ExprId, InterpreterError> {
+        let expression = self.into_hir_expression(interner, typ, location)?;
+        let id = interner.push_expr(expression);
+        interner.push_expr_location(id, location.span, location.file);
+        interner.push_expr_type(&id, typ.follow_bindings());
+        Ok(id)
+    }
+}
@@ -1,7 +1,10 @@
 use super::dc_mod::collect_defs;
+use super::derive::{check_derived_impls, DerivedTraitImpl};
 use super::errors::{DefCollectorErrorKind, DuplicateType};
 use crate::graph::CrateId;
+use crate::hir::comptime::{evaluate_comptime_expressions, InterpreterError};
 use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
+use crate::hir::resolution::dead_code::find_dead_code;
 use crate::hir::resolution::errors::ResolverError;
 
 use crate::hir::resolution::import::{resolve_imports, ImportDirective};
@@ -19,7 +22,7 @@ use crate::node_interner::{FuncId, NodeInterner, StmtId, StructId, TraitId, Type
 
 use crate::parser::{ParserError, SortedModule};
 use crate::{
-    ExpressionKind, Ident, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait,
+    Expression, ExpressionKind, Ident, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait,
     NoirTypeAlias, Path, PathKind, Type, UnresolvedGenerics, UnresolvedTraitConstraint,
     UnresolvedType,
 };
@@ -45,6 +48,7 @@ impl UnresolvedFunctions {
 
     pub fn resolve_trait_bounds_trait_ids(
         &mut self,
+        interner: &mut NodeInterner,
         def_maps: &BTreeMap<CrateId, CrateDefMap>,
         crate_id: CrateId,
     ) -> Vec<DefCollectorErrorKind> {
@@ -54,10 +58,13 @@ impl UnresolvedFunctions {
             let module = ModuleId { krate: crate_id, local_id: *local_id };
 
             for bound in &mut func.def.where_clause {
-                match resolve_trait_by_path(def_maps, module, bound.trait_bound.trait_path.clone())
-                {
-                    Ok(trait_id) => {
+                let path = bound.trait_bound.trait_path.clone();
+                match resolve_trait_by_path(interner, def_maps, module, path) {
+                    Ok((trait_id, warning)) => {
                         bound.trait_bound.trait_id = Some(trait_id);
+                        if let Some(warning) = warning {
+                            errors.push(DefCollectorErrorKind::PathResolutionError(warning));
+                        }
                     }
                     Err(err) => {
                         errors.push(err);
@@ -95,6 +102,8 @@ pub struct UnresolvedTraitImpl {
     pub methods: UnresolvedFunctions,
     pub generics: UnresolvedGenerics,
     pub where_clause: Vec<UnresolvedTraitConstraint>,
+    pub associated_types: Vec<(Ident, UnresolvedType)>,
+    pub associated_constants: Vec<(Ident, UnresolvedType, Expression)>,
 }
 
 #[derive(Clone)]
@@ -123,6 +132,7 @@ pub struct DefCollector {
     pub(crate) collected_globals: Vec<UnresolvedGlobal>,
     pub(crate) collected_impls: ImplMap,
     pub(crate) collected_traits_impls: Vec<UnresolvedTraitImpl>,
+    pub(crate) collected_derives: Vec<DerivedTraitImpl>,
 }
 
 /// Maps the type and the module id in which the impl is defined to the functions contained in that
@@ -141,6 +151,7 @@ pub enum CompilationError {
     DefinitionError(DefCollectorErrorKind),
     ResolverError(ResolverError),
     TypeError(TypeCheckError),
+    InterpreterError(InterpreterError),
 }
 
 impl From<CompilationError> for CustomDiagnostic {
@@ -150,6 +161,7 @@ impl From<CompilationError> for CustomDiagnostic {
             CompilationError::DefinitionError(error) => error.into(),
             CompilationError::ResolverError(error) => error.into(),
             CompilationError::TypeError(error) => error.into(),
+            CompilationError::InterpreterError(error) => error.into(),
         }
     }
 }
@@ -177,6 +189,12 @@ impl From<TypeCheckError> for CompilationError {
     }
 }
 
+impl From<InterpreterError> for CompilationError {
+    fn from(value: InterpreterError) -> Self {
+        CompilationError::InterpreterError(value)
+    }
+}
+
 impl DefCollector {
     fn new(def_map: CrateDefMap) -> DefCollector {
         DefCollector {
@@ -189,6 +207,7 @@ impl DefCollector {
             collected_impls: HashMap::new(),
             collected_globals: vec![],
             collected_traits_impls: vec![],
+            collected_derives: vec![],
         }
     }
 
@@ -270,12 +289,25 @@ impl DefCollector {
                 let error = DefCollectorErrorKind::PathResolutionError(error);
                 (error.into(), file_id)
             }));
+
+            // Imports of items which aren't visible from the importing module still resolve,
+            // but are reported as warnings
+            for resolved_import in &resolved {
+                if let Some(error) = resolved_import.error.clone() {
+                    let file_id = current_def_map.file_id(resolved_import.module_scope);
+                    let error = DefCollectorErrorKind::PathResolutionError(error);
+                    errors.push((error.into(), file_id));
+                }
+            }
         };
 
         // Populate module namespaces according to the imports used
         let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
         for resolved_import in resolved {
             let name = resolved_import.name;
+            if resolved_import.allow_dead_code {
+                current_def_map.modules[resolved_import.module_scope.0].allow_dead_import(&name);
+            }
             for ns in resolved_import.resolved_namespace.iter_defs() {
                 let result = current_def_map.modules[resolved_import.module_scope.0].import(
                     name.clone(),
@@ -298,7 +330,8 @@ impl DefCollector {
         // Each function uses its own resolver with a newly created ScopeForest, and must be resolved again to be within a function's scope
         //
         // Additionally, we must resolve integer globals before structs since structs may refer to
-        // the values of integer globals as numeric generics.
+        // the values of integer globals as numeric generics. Globals initialized by a comptime
+        // block are resolved alongside them so that they can be used as numeric generics too.
         let (literal_globals, other_globals) =
             filter_literal_globals(def_collector.collected_globals);
 
@@ -359,17 +392,38 @@ impl DefCollector {
             &mut errors,
         );
 
+        // Now that every trait impl is known, check the fields of structs with derived impls
+        errors.extend(check_derived_impls(context, crate_id, def_collector.collected_derives));
+
         errors.extend(resolved_globals.errors);
 
         for macro_processor in macro_processors {
-            macro_processor.process_typed_ast(&crate_id, context);
+            let macro_errors = macro_processor.process_typed_ast(&crate_id, context);
+            errors.extend(macro_errors.into_iter().map(|(error, file_id)| {
+                (DefCollectorErrorKind::MacroError(error).into(), file_id)
+            }));
         }
-        errors.extend(type_check_globals(&mut context.def_interner, resolved_globals.globals));
+        errors.extend(type_check_globals(
+            &mut context.def_interner,
+            &context.def_maps,
+            resolved_globals.globals,
+        ));
 
         // Type check all of the functions in the crate
-        errors.extend(type_check_functions(&mut context.def_interner, file_func_ids));
-        errors.extend(type_check_functions(&mut context.def_interner, file_method_ids));
-        errors.extend(type_check_functions(&mut context.def_interner, file_trait_impls_ids));
+        let interner = &mut context.def_interner;
+        let def_maps = &context.def_maps;
+        errors.extend(type_check_functions(interner, def_maps, file_func_ids));
+        errors.extend(type_check_functions(interner, def_maps, file_method_ids));
+        errors.extend(type_check_functions(interner, def_maps, file_trait_impls_ids));
+
+        // Dependencies are not expected to use all of their items, so only the root crate
+        // is checked for dead code.
+        if crate_id.is_root() {
+            errors.extend(find_dead_code(context, crate_id));
+        }
+
+        // Evaluate comptime blocks now that the types of their results are known
+        errors.extend(evaluate_comptime(&mut context.def_interner, &errors));
         errors
     }
 }
@@ -389,11 +443,12 @@ fn inject_prelude(
         Path { segments: segments.clone(), kind: crate::PathKind::Dep, span: Span::default() };
 
     if !crate_id.is_stdlib() {
-        if let Ok(module_def) = path_resolver::resolve_path(
+        if let Ok(resolution) = path_resolver::resolve_path(
             &context.def_maps,
             ModuleId { krate: crate_id, local_id: crate_root },
             path,
         ) {
+            let module_def = resolution.module_def_id;
             let module_id = module_def.as_module().expect("std::prelude should be a module");
             let prelude = context.module(module_id).scope().names();
 
@@ -408,6 +463,7 @@ fn inject_prelude(
                         path: Path { segments, kind: PathKind::Dep, span: Span::default() },
                         alias: None,
                         is_prelude: true,
+                        allow_dead_code: false,
                     },
                 );
             }
@@ -423,18 +479,39 @@ fn filter_literal_globals(
 ) -> (Vec<UnresolvedGlobal>, Vec<UnresolvedGlobal>) {
     globals.into_iter().partition(|global| match &global.stmt_def.expression.kind {
         ExpressionKind::Literal(literal) => !matches!(literal, Literal::Array(_)),
+        ExpressionKind::Comptime(_) => true,
         _ => false,
     })
 }
 
+/// Evaluates the comptime blocks of the crate, unless it already failed to compile since
+/// the blocks may contain expressions which failed to resolve or type check.
+fn evaluate_comptime(
+    interner: &mut NodeInterner,
+    errors: &[(CompilationError, FileId)],
+) -> Vec<(CompilationError, FileId)> {
+    let has_errors =
+        errors.iter().any(|(error, _)| CustomDiagnostic::from(error.clone()).is_error());
+    if has_errors {
+        interner.take_comptime_expressions();
+        return Vec::new();
+    }
+
+    vecmap(evaluate_comptime_expressions(interner), |error| {
+        let file = error.location().file;
+        (error.into(), file)
+    })
+}
+
 fn type_check_globals(
     interner: &mut NodeInterner,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
     global_ids: Vec<(FileId, StmtId)>,
 ) -> Vec<(CompilationError, fm::FileId)> {
     global_ids
         .iter()
         .flat_map(|(file_id, stmt_id)| {
-            TypeChecker::check_global(stmt_id, interner)
+            TypeChecker::check_global(stmt_id, interner, def_maps)
                 .iter()
                 .cloned()
                 .map(|e| (e.into(), *file_id))
@@ -445,12 +522,13 @@ fn type_check_globals(
 
 fn type_check_functions(
     interner: &mut NodeInterner,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
     file_func_ids: Vec<(FileId, FuncId)>,
 ) -> Vec<(CompilationError, fm::FileId)> {
     file_func_ids
         .iter()
         .flat_map(|(file, func)| {
-            type_check_func(interner, *func)
+            type_check_func(interner, def_maps, *func)
                 .iter()
                 .cloned()
                 .map(|e| (e.into(), *file))
@@ -465,14 +543,24 @@ pub(crate) fn check_methods_signatures(
     impl_methods: &Vec<(FileId, FuncId)>,
     trait_id: TraitId,
     trait_impl_generic_count: usize,
+    associated_types: &[(Ident, Type)],
     errors: &mut Vec<(CompilationError, FileId)>,
 ) {
     let self_type = resolver.get_self_type().expect("trait impl must have a Self type").clone();
+    let impl_generics = resolver.get_generics().to_vec();
 
     // Temporarily bind the trait's Self type to self_type so we can type check
     let the_trait = resolver.interner.get_trait_mut(trait_id);
     the_trait.self_type_typevar.bind(self_type);
 
+    // Likewise for its associated types and constants
+    let mut associated_item_count = 0;
+    for (name, _, type_var) in the_trait.associated_items() {
+        let typ = associated_types.iter().find(|(item, _)| item == name);
+        type_var.bind(typ.map_or(Type::Error, |(_, typ)| typ.clone()));
+        associated_item_count += 1;
+    }
+
     // Temporarily take the trait's methods so we can use both them and a mutable reference
     // to the interner within the loop.
     let trait_methods = std::mem::take(&mut the_trait.methods);
@@ -491,12 +579,28 @@ pub(crate) fn check_methods_signatures(
         {
             let impl_function_type = impl_method.typ.instantiate(resolver.interner);
 
+            // The implicit generics for associated types in the method's own where clause
+            // are not specified by users either.
+            let implicit_generic_count = impl_method
+                .trait_constraints
+                .iter()
+                .flat_map(|constraint| &constraint.associated_types)
+                .filter(|(_, typ)| match typ {
+                    Type::NamedGeneric(type_var, _) => {
+                        !impl_generics.iter().any(|(_, generic, _)| generic == type_var)
+                    }
+                    _ => false,
+                })
+                .count();
+
             let impl_method_generic_count =
-                impl_method.typ.generic_count() - trait_impl_generic_count;
+                impl_method.typ.generic_count() - trait_impl_generic_count - implicit_generic_count;
 
             // We subtract 1 here to account for the implicit generic `Self` type that is on all
             // traits (and thus trait methods) but is not required (or allowed) for users to specify.
-            let trait_method_generic_count = trait_method.generics().len() - 1;
+            // The trait's associated types and constants are implicit generics as well.
+            let trait_method_generic_count =
+                trait_method.generics().len() - 1 - associated_item_count;
 
             if impl_method_generic_count != trait_method_generic_count {
                 let error = DefCollectorErrorKind::MismatchTraitImplementationNumGenerics {
@@ -561,4 +665,7 @@ pub(crate) fn check_methods_signatures(
     let the_trait = resolver.interner.get_trait_mut(trait_id);
     the_trait.set_methods(trait_methods);
     the_trait.self_type_typevar.unbind(the_trait.self_type_typevar_id);
+    for (_, type_var_id, type_var) in the_trait.associated_items() {
+        type_var.unbind(type_var_id);
+    }
 }
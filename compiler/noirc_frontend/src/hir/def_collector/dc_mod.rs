@@ -7,10 +7,11 @@ use noirc_errors::Location;
 use crate::{
     graph::CrateId,
     hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait},
-    node_interner::{FunctionModifiers, TraitId, TypeAliasId},
-    parser::{SortedModule, SortedSubModule},
-    FunctionDefinition, Ident, LetStatement, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
-    NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
+    node_interner::{FunctionModifiers, TraitId},
+    parser::{ModuleDeclaration, SortedModule, SortedSubModule},
+    token::SecondaryAttribute,
+    FunctionDefinition, Ident, ItemVisibility, LetStatement, NoirFunction, NoirStruct, NoirTrait,
+    NoirTraitImpl, NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
 };
 
 use super::{
@@ -18,9 +19,11 @@ use super::{
         CompilationError, DefCollector, UnresolvedFunctions, UnresolvedGlobal, UnresolvedTraitImpl,
         UnresolvedTypeAlias,
     },
+    derive::{derive_trait_impls, DerivedTraitImpl},
     errors::{DefCollectorErrorKind, DuplicateType},
 };
 use crate::hir::def_map::{parse_file, LocalModuleId, ModuleData, ModuleId};
+use crate::hir::resolution::dead_code::DEAD_CODE_LINT;
 use crate::hir::resolution::import::ImportDirective;
 use crate::hir::Context;
 
@@ -59,6 +62,7 @@ pub fn collect_defs(
             path: import.path,
             alias: import.alias,
             is_prelude: false,
+            allow_dead_code: SecondaryAttribute::allows(&import.attributes, DEAD_CODE_LINT),
         });
     }
 
@@ -83,19 +87,19 @@ impl<'a> ModCollector<'a> {
     fn collect_globals(
         &mut self,
         context: &mut Context,
-        globals: Vec<LetStatement>,
+        globals: Vec<(LetStatement, ItemVisibility, Vec<SecondaryAttribute>)>,
     ) -> Vec<(CompilationError, fm::FileId)> {
         let mut errors = vec![];
-        for global in globals {
+        for (global, visibility, attributes) in globals {
             let name = global.pattern.name_ident().clone();
 
             // First create dummy function in the DefInterner
             // So that we can get a StmtId
-            let stmt_id = context.def_interner.push_empty_global();
+            let stmt_id = context.def_interner.push_empty_global(attributes);
 
             // Add the statement to the scope so its path can be looked up later
-            let result =
-                self.def_collector.def_map.modules[self.module_id.0].declare_global(name, stmt_id);
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .declare_global(name, visibility, stmt_id);
 
             if let Err((first_def, second_def)) = result {
                 let err = DefCollectorErrorKind::Duplicate {
@@ -159,6 +163,18 @@ impl<'a> ModCollector<'a> {
                 context.def_interner.push_function(*func_id, &noir_function.def, module, location);
             }
 
+            let mut associated_types = Vec::new();
+            let mut associated_constants = Vec::new();
+            for item in trait_impl.items {
+                match item {
+                    TraitImplItem::Type { name, alias } => associated_types.push((name, alias)),
+                    TraitImplItem::Constant(name, typ, value) => {
+                        associated_constants.push((name, typ, value));
+                    }
+                    TraitImplItem::Function(_) => (),
+                }
+            }
+
             let unresolved_trait_impl = UnresolvedTraitImpl {
                 file_id: self.file_id,
                 module_id: self.module_id,
@@ -168,6 +184,8 @@ impl<'a> ModCollector<'a> {
                 generics: trait_impl.impl_generics,
                 where_clause: trait_impl.where_clause,
                 trait_id: None, // will be filled later
+                associated_types,
+                associated_constants,
             };
 
             self.def_collector.collected_traits_impls.push(unresolved_trait_impl);
@@ -219,6 +237,7 @@ impl<'a> ModCollector<'a> {
             }
 
             let name = function.name_ident().clone();
+            let visibility = function.def.visibility;
             let func_id = context.def_interner.push_empty_fn();
 
             // First create dummy function in the DefInterner
@@ -236,7 +255,7 @@ impl<'a> ModCollector<'a> {
 
             // Add function to scope/ns of the module
             let result = self.def_collector.def_map.modules[self.module_id.0]
-                .declare_function(name, func_id);
+                .declare_function(name, visibility, func_id);
 
             if let Err((first_def, second_def)) = result {
                 let error = DefCollectorErrorKind::Duplicate {
@@ -263,6 +282,7 @@ impl<'a> ModCollector<'a> {
         let mut definition_errors = vec![];
         for struct_definition in types {
             let name = struct_definition.name.clone();
+            let visibility = struct_definition.visibility;
 
             let unresolved = UnresolvedStruct {
                 file_id: self.file_id,
@@ -271,7 +291,13 @@ impl<'a> ModCollector<'a> {
             };
 
             // Create the corresponding module for the struct namespace
-            let id = match self.push_child_module(&name, self.file_id, false, false) {
+            let id = match self.push_child_module(
+                &name,
+                ItemVisibility::Public,
+                self.file_id,
+                false,
+                false,
+            ) {
                 Ok(local_id) => {
                     context.def_interner.new_struct(&unresolved, krate, local_id, self.file_id)
                 }
@@ -282,8 +308,8 @@ impl<'a> ModCollector<'a> {
             };
 
             // Add the struct to scope so its path can be looked up later
-            let result =
-                self.def_collector.def_map.modules[self.module_id.0].declare_struct(name, id);
+            let result = self.def_collector.def_map.modules[self.module_id.0]
+                .declare_struct(name, visibility, id);
 
             if let Err((first_def, second_def)) = result {
                 let error = DefCollectorErrorKind::Duplicate {
@@ -294,6 +320,21 @@ impl<'a> ModCollector<'a> {
                 definition_errors.push((error.into(), self.file_id));
             }
 
+            // Collect any impls requested by `#[derive(...)]` alongside the handwritten ones
+            let (derived_impls, derive_errors) = derive_trait_impls(&unresolved.struct_def, krate);
+            definition_errors
+                .extend(derive_errors.into_iter().map(|error| (error.into(), self.file_id)));
+
+            for derived_impl in &derived_impls {
+                self.def_collector.collected_derives.push(DerivedTraitImpl {
+                    file_id: self.file_id,
+                    module_id: self.module_id,
+                    struct_id: id,
+                    trait_path: derived_impl.trait_name.clone(),
+                });
+            }
+            definition_errors.extend(self.collect_trait_impls(context, derived_impls, krate));
+
             // And store the TypeId -> StructType mapping somewhere it is reachable
             self.def_collector.collected_types.insert(id, unresolved);
         }
@@ -351,7 +392,13 @@ impl<'a> ModCollector<'a> {
             let name = trait_definition.name.clone();
 
             // Create the corresponding module for the trait namespace
-            let trait_id = match self.push_child_module(&name, self.file_id, false, false) {
+            let trait_id = match self.push_child_module(
+                &name,
+                ItemVisibility::Public,
+                self.file_id,
+                false,
+                false,
+            ) {
                 Ok(local_id) => TraitId(ModuleId { krate, local_id }),
                 Err(error) => {
                     errors.push((error.into(), self.file_id));
@@ -360,8 +407,11 @@ impl<'a> ModCollector<'a> {
             };
 
             // Add the trait to scope so its path can be looked up later
-            let result =
-                self.def_collector.def_map.modules[self.module_id.0].declare_trait(name, trait_id);
+            let result = self.def_collector.def_map.modules[self.module_id.0].declare_trait(
+                name,
+                trait_definition.visibility,
+                trait_id,
+            );
 
             if let Err((first_def, second_def)) = result {
                 let error = DefCollectorErrorKind::Duplicate {
@@ -380,6 +430,8 @@ impl<'a> ModCollector<'a> {
             };
 
             let mut method_ids = HashMap::new();
+            let mut associated_types = HashMap::new();
+            let mut associated_constants = HashMap::new();
             for trait_item in &trait_definition.items {
                 match trait_item {
                     TraitItem::Function {
@@ -395,10 +447,11 @@ impl<'a> ModCollector<'a> {
 
                         let modifiers = FunctionModifiers {
                             name: name.to_string(),
-                            visibility: crate::FunctionVisibility::Public,
+                            visibility: crate::ItemVisibility::Public,
                             // TODO(Maddiaa): Investigate trait implementations with attributes see: https://github.com/noir-lang/noir/issues/2629
                             attributes: crate::token::Attributes::empty(),
                             is_unconstrained: false,
+                            is_comptime: false,
                             contract_function_type: None,
                             is_internal: None,
                         };
@@ -409,7 +462,7 @@ impl<'a> ModCollector<'a> {
                             .push_function_definition(func_id, modifiers, trait_id.0, location);
 
                         match self.def_collector.def_map.modules[trait_id.0.local_id.0]
-                            .declare_function(name.clone(), func_id)
+                            .declare_function(name.clone(), ItemVisibility::Public, func_id)
                         {
                             Ok(()) => {
                                 if let Some(body) = body {
@@ -440,32 +493,27 @@ impl<'a> ModCollector<'a> {
                         }
                     }
                     TraitItem::Constant { name, .. } => {
-                        let stmt_id = context.def_interner.push_empty_global();
-
-                        if let Err((first_def, second_def)) = self.def_collector.def_map.modules
-                            [trait_id.0.local_id.0]
-                            .declare_global(name.clone(), stmt_id)
-                        {
+                        if let Some(first_def) = associated_constants.get(&name.0.contents) {
                             let error = DefCollectorErrorKind::Duplicate {
                                 typ: DuplicateType::TraitAssociatedConst,
-                                first_def,
-                                second_def,
+                                first_def: (*first_def).clone(),
+                                second_def: name.clone(),
                             };
                             errors.push((error.into(), self.file_id));
+                        } else {
+                            associated_constants.insert(name.0.contents.clone(), name);
                         }
                     }
                     TraitItem::Type { name } => {
-                        // TODO(nickysn or alexvitkov): implement context.def_interner.push_empty_type_alias and get an id, instead of using TypeAliasId::dummy_id()
-                        if let Err((first_def, second_def)) = self.def_collector.def_map.modules
-                            [trait_id.0.local_id.0]
-                            .declare_type_alias(name.clone(), TypeAliasId::dummy_id())
-                        {
+                        if let Some(first_def) = associated_types.get(&name.0.contents) {
                             let error = DefCollectorErrorKind::Duplicate {
                                 typ: DuplicateType::TraitAssociatedType,
-                                first_def,
-                                second_def,
+                                first_def: (*first_def).clone(),
+                                second_def: name.clone(),
                             };
                             errors.push((error.into(), self.file_id));
+                        } else {
+                            associated_types.insert(name.0.contents.clone(), name);
                         }
                     }
                 }
@@ -494,7 +542,9 @@ impl<'a> ModCollector<'a> {
     ) -> Vec<(CompilationError, FileId)> {
         let mut errors: Vec<(CompilationError, FileId)> = vec![];
         for submodule in submodules {
-            match self.push_child_module(&submodule.name, file_id, true, submodule.is_contract) {
+            let name = &submodule.name;
+            let visibility = submodule.visibility;
+            match self.push_child_module(name, visibility, file_id, true, submodule.is_contract) {
                 Ok(child) => {
                     errors.extend(collect_defs(
                         self.def_collector,
@@ -519,9 +569,10 @@ impl<'a> ModCollector<'a> {
     fn parse_module_declaration(
         &mut self,
         context: &mut Context,
-        mod_name: &Ident,
+        mod_decl: &ModuleDeclaration,
         crate_id: CrateId,
     ) -> Vec<(CompilationError, FileId)> {
+        let mod_name = &mod_decl.ident;
         let mut errors: Vec<(CompilationError, FileId)> = vec![];
         let child_file_id =
             match find_module(&context.file_manager, self.file_id, &mod_name.0.contents) {
@@ -563,7 +614,7 @@ impl<'a> ModCollector<'a> {
         );
 
         // Add module into def collector and get a ModuleId
-        match self.push_child_module(mod_name, child_file_id, true, false) {
+        match self.push_child_module(mod_name, mod_decl.visibility, child_file_id, true, false) {
             Ok(child_mod_id) => {
                 errors.extend(collect_defs(
                     self.def_collector,
@@ -586,6 +637,7 @@ impl<'a> ModCollector<'a> {
     fn push_child_module(
         &mut self,
         mod_name: &Ident,
+        visibility: ItemVisibility,
         file_id: FileId,
         add_to_parent_scope: bool,
         is_contract: bool,
@@ -613,9 +665,11 @@ impl<'a> ModCollector<'a> {
                 local_id: LocalModuleId(module_id),
             };
 
-            if let Err((first_def, second_def)) =
-                modules[self.module_id.0].declare_child_module(mod_name.to_owned(), mod_id)
-            {
+            if let Err((first_def, second_def)) = modules[self.module_id.0].declare_child_module(
+                mod_name.to_owned(),
+                visibility,
+                mod_id,
+            ) {
                 let err = DefCollectorErrorKind::Duplicate {
                     typ: DuplicateType::Module,
                     first_def,
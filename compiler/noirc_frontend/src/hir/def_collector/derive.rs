@@ -0,0 +1,420 @@
+//! Generates the trait impls requested by `#[derive(...)]` attributes on structs.
+//!
+//! Derived impls are built as ordinary AST trait impls during definition collection, so they
+//! are resolved and type checked exactly like handwritten ones. Once every trait impl in the
+//! crate is known, `check_derived_impls` verifies that each field of a deriving struct
+//! implements the derived trait, pointing at the offending field rather than at generated code.
+use fm::FileId;
+use iter_extended::vecmap;
+use noirc_errors::{Span, Spanned};
+
+use crate::graph::CrateId;
+use crate::hir::def_map::{LocalModuleId, ModuleId};
+use crate::hir::resolution::resolve_trait_by_path;
+use crate::hir::Context;
+use crate::node_interner::StructId;
+use crate::token::SecondaryAttribute;
+use crate::{
+    AssignStatement, BinaryOpKind, BlockExpression, CallExpression, ConstructorExpression,
+    Expression, ExpressionKind, FunctionDefinition, FunctionReturnType, Ident, IfExpression,
+    InfixExpression, LValue, LetStatement, Literal, MemberAccessExpression, NoirFunction,
+    NoirStruct, NoirTraitImpl, Path, PathKind, Pattern, Statement, StatementKind, TraitBound,
+    TraitImplItem, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData,
+};
+
+use super::dc_crate::CompilationError;
+use super::errors::DefCollectorErrorKind;
+
+/// The traits which can be derived, along with the stdlib module each is declared in.
+const DERIVABLE_TRAITS: [(&str, &str); 4] =
+    [("Eq", "ops"), ("Default", "default"), ("Ord", "cmp"), ("Hash", "hash")];
+
+/// A trait impl generated for a struct by `#[derive(...)]`
+pub(crate) struct DerivedTraitImpl {
+    pub(crate) file_id: FileId,
+    pub(crate) module_id: LocalModuleId,
+    pub(crate) struct_id: StructId,
+    pub(crate) trait_path: Path,
+}
+
+/// Returns the trait impls requested by the `#[derive(...)]` attributes of the given struct.
+pub(crate) fn derive_trait_impls(
+    struct_def: &NoirStruct,
+    krate: CrateId,
+) -> (Vec<NoirTraitImpl>, Vec<DefCollectorErrorKind>) {
+    let mut impls = Vec::new();
+    let mut errors = Vec::new();
+    let span = struct_def.name.span();
+
+    for attribute in &struct_def.attributes {
+        if let SecondaryAttribute::Derive(trait_names) = attribute {
+            for trait_name in trait_names {
+                match DERIVABLE_TRAITS.iter().find(|(name, _)| name == trait_name) {
+                    Some((name, module)) => {
+                        let deriver = Deriver { struct_def, krate, span };
+                        impls.push(deriver.derive(name, module));
+                    }
+                    None => {
+                        let trait_name = trait_name.clone();
+                        errors.push(DefCollectorErrorKind::UnsupportedDerive { trait_name, span });
+                    }
+                }
+            }
+        }
+    }
+
+    (impls, errors)
+}
+
+/// Checks that the type of every field of a struct implements each trait derived for it.
+///
+/// Fields whose type is one of the struct's generics are skipped: the derived impl requires
+/// those to implement the trait in its where clause instead.
+pub(crate) fn check_derived_impls(
+    context: &mut Context,
+    crate_id: CrateId,
+    derived_impls: Vec<DerivedTraitImpl>,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors: Vec<(CompilationError, FileId)> = Vec::new();
+
+    for derived in derived_impls {
+        let module = ModuleId { krate: crate_id, local_id: derived.module_id };
+        let trait_path = derived.trait_path;
+        let interner = &mut context.def_interner;
+
+        // If the trait failed to resolve, that error was already issued for the impl itself
+        let trait_id =
+            match resolve_trait_by_path(interner, &context.def_maps, module, trait_path.clone()) {
+                Ok((trait_id, _)) => trait_id,
+                Err(_) => continue,
+            };
+
+        let the_struct = interner.get_struct(derived.struct_id);
+        let the_struct = the_struct.borrow();
+
+        // Instantiating the struct's generics leaves them as unbound type variables,
+        // which any impl is allowed to match.
+        let generic_args = the_struct.instantiate(interner);
+        let field_types = the_struct.get_fields(&generic_args);
+
+        for ((_, field_name, _), (_, field_type)) in the_struct.fields().iter().zip(field_types) {
+            if interner.try_lookup_trait_implementation(&field_type, trait_id).is_err() {
+                let error = DefCollectorErrorKind::DerivedTraitNotImplementedForField {
+                    trait_name: trait_path.last_segment().0.contents,
+                    struct_name: the_struct.name.0.contents.clone(),
+                    field_name: field_name.clone(),
+                    field_type,
+                };
+                errors.push((error.into(), derived.file_id));
+            }
+        }
+    }
+
+    errors
+}
+
+struct Deriver<'a> {
+    struct_def: &'a NoirStruct,
+    krate: CrateId,
+    span: Span,
+}
+
+impl<'a> Deriver<'a> {
+    fn derive(&self, trait_name: &str, module: &str) -> NoirTraitImpl {
+        let trait_path = self.stdlib_path(&[module, trait_name]);
+
+        let method = match trait_name {
+            "Eq" => self.derive_eq(&trait_path),
+            "Default" => self.derive_default(&trait_path),
+            "Ord" => self.derive_ord(&trait_path),
+            "Hash" => self.derive_hash(&trait_path),
+            _ => unreachable!("{trait_name} is not a derivable trait"),
+        };
+
+        let generics = &self.struct_def.generics;
+        let struct_path = Path::from_ident(self.struct_def.name.clone());
+        let generic_types =
+            vecmap(generics, |generic| self.named_type(Path::from_ident(generic.clone())));
+        let object_type =
+            self.unresolved_type(UnresolvedTypeData::Named(struct_path, generic_types));
+
+        // Each generic used as the type of a field must implement the trait as well
+        let where_clause = generics
+            .iter()
+            .filter(|generic| {
+                self.struct_def.fields.iter().any(|field| mentions_generic(&field.typ, generic))
+            })
+            .map(|generic| UnresolvedTraitConstraint {
+                typ: self.named_type(Path::from_ident(generic.clone())),
+                trait_bound: TraitBound {
+                    trait_path: trait_path.clone(),
+                    trait_id: None,
+                    trait_generics: Vec::new(),
+                },
+            })
+            .collect();
+
+        NoirTraitImpl {
+            impl_generics: generics.clone(),
+            trait_name: trait_path,
+            trait_generics: Vec::new(),
+            object_type,
+            where_clause,
+            items: vec![TraitImplItem::Function(method)],
+        }
+    }
+
+    /// `fn eq(self, other: Self) -> bool { Eq::eq(self.a, other.a) & Eq::eq(self.b, other.b) }`
+    fn derive_eq(&self, trait_path: &Path) -> NoirFunction {
+        let body = self
+            .struct_def
+            .fields
+            .iter()
+            .map(|field| self.call_on_fields(trait_path, "eq", &field.name))
+            .reduce(|lhs, rhs| {
+                let operator = Spanned::from(self.span, BinaryOpKind::And);
+                let infix = InfixExpression { lhs, operator, rhs };
+                self.expression(ExpressionKind::Infix(Box::new(infix)))
+            })
+            .unwrap_or_else(|| self.expression(ExpressionKind::Literal(Literal::Bool(true))));
+
+        let return_type = self.unresolved_type(UnresolvedTypeData::Bool);
+        self.binary_method("eq", vec![self.statement(StatementKind::Expression(body))], return_type)
+    }
+
+    /// `fn default() -> Self { Name { a: Default::default(), b: Default::default() } }`
+    fn derive_default(&self, trait_path: &Path) -> NoirFunction {
+        let default = self.method_path(trait_path, "default");
+        let fields = vecmap(&self.struct_def.fields, |field| {
+            (field.name.clone(), self.call(default.clone(), Vec::new(), field.name.span()))
+        });
+
+        let type_name = Path::from_ident(self.struct_def.name.clone());
+        let constructor = ConstructorExpression { type_name, fields };
+        let body = self.expression(ExpressionKind::Constructor(Box::new(constructor)));
+        let body = BlockExpression(vec![self.statement(StatementKind::Expression(body))]);
+
+        let name = Ident::new("default".to_string(), self.span);
+        let return_type = FunctionReturnType::Ty(self.self_type());
+        let def = FunctionDefinition::normal(&name, &Vec::new(), &[], &body, &[], &return_type);
+        NoirFunction::normal(def)
+    }
+
+    /// Compares each field in order, returning the first result which isn't `Ordering::equal()`:
+    ///
+    /// ```noir
+    /// fn cmp(self, other: Self) -> Ordering {
+    ///     let mut result = Ord::cmp(self.a, other.a);
+    ///     if Eq::eq(result, Ordering::equal()) {
+    ///         result = Ord::cmp(self.b, other.b);
+    ///     }
+    ///     result
+    /// }
+    /// ```
+    fn derive_ord(&self, trait_path: &Path) -> NoirFunction {
+        let ordering = self.stdlib_path(&["cmp", "Ordering"]);
+        let equal = self.call(self.method_path(&ordering, "equal"), Vec::new(), self.span);
+        let result = Ident::new("result".to_string(), self.span);
+
+        let mut fields = self.struct_def.fields.iter();
+        let body = match fields.next() {
+            None => vec![self.statement(StatementKind::Expression(equal))],
+            Some(first) if self.struct_def.fields.len() == 1 => {
+                let cmp = self.call_on_fields(trait_path, "cmp", &first.name);
+                vec![self.statement(StatementKind::Expression(cmp))]
+            }
+            Some(first) => {
+                let pattern =
+                    Pattern::Mutable(Box::new(Pattern::Identifier(result.clone())), self.span);
+                let r#type = UnresolvedType { typ: UnresolvedTypeData::Unspecified, span: None };
+                let expression = self.call_on_fields(trait_path, "cmp", &first.name);
+                let mut statements = vec![self.statement(StatementKind::Let(LetStatement {
+                    pattern,
+                    r#type,
+                    expression,
+                }))];
+
+                let eq = self.method_path(&self.stdlib_path(&["ops", "Eq"]), "eq");
+                for field in fields {
+                    let result_variable = self.variable(Path::from_ident(result.clone()));
+                    let condition =
+                        self.call(eq.clone(), vec![result_variable, equal.clone()], self.span);
+
+                    let expression = self.call_on_fields(trait_path, "cmp", &field.name);
+                    let lvalue = LValue::Ident(result.clone());
+                    let assign = self
+                        .statement(StatementKind::Assign(AssignStatement { lvalue, expression }));
+                    let consequence =
+                        self.expression(ExpressionKind::Block(BlockExpression(vec![assign])));
+
+                    let if_expression = IfExpression { condition, consequence, alternative: None };
+                    let if_expression =
+                        self.expression(ExpressionKind::If(Box::new(if_expression)));
+                    statements.push(self.statement(StatementKind::Semi(if_expression)));
+                }
+
+                let result = self.variable(Path::from_ident(result));
+                statements.push(self.statement(StatementKind::Expression(result)));
+                statements
+            }
+        };
+
+        let return_type = self.named_type(ordering);
+        self.binary_method("cmp", body, return_type)
+    }
+
+    /// Writes each field into the hasher in declaration order:
+    ///
+    /// ```noir
+    /// fn hash(self, state: &mut Hasher) {
+    ///     Hash::hash(self.a, state);
+    ///     Hash::hash(self.b, state);
+    /// }
+    /// ```
+    ///
+    /// A struct without fields hashes like `()`.
+    fn derive_hash(&self, trait_path: &Path) -> NoirFunction {
+        let hash = self.method_path(trait_path, "hash");
+        let state = Ident::new("state".to_string(), self.span);
+
+        let mut values = vecmap(&self.struct_def.fields, |field| {
+            (self.field_of("self", &field.name), field.name.span())
+        });
+        if values.is_empty() {
+            values.push((self.expression(ExpressionKind::Literal(Literal::Unit)), self.span));
+        }
+
+        let statements = vecmap(values, |(value, span)| {
+            let state = self.variable(Path::from_ident(state.clone()));
+            let call = self.call(hash.clone(), vec![value, state], span);
+            self.statement(StatementKind::Semi(call))
+        });
+        let body = BlockExpression(statements);
+
+        let hasher = self.named_type(self.stdlib_path(&["hash", "Hasher"]));
+        let hasher = self.unresolved_type(UnresolvedTypeData::MutableReference(Box::new(hasher)));
+        let name = Ident::new("hash".to_string(), self.span);
+        let parameters =
+            [(Ident::new("self".to_string(), self.span), self.self_type()), (state, hasher)];
+        let return_type = FunctionReturnType::Default(self.span);
+        let def =
+            FunctionDefinition::normal(&name, &Vec::new(), &parameters, &body, &[], &return_type);
+        NoirFunction::normal(def)
+    }
+
+    /// Creates a method taking `self` and `other: Self`
+    fn binary_method(
+        &self,
+        name: &str,
+        body: Vec<Statement>,
+        return_type: UnresolvedType,
+    ) -> NoirFunction {
+        let name = Ident::new(name.to_string(), self.span);
+        let parameters = [
+            (Ident::new("self".to_string(), self.span), self.self_type()),
+            (Ident::new("other".to_string(), self.span), self.self_type()),
+        ];
+        let body = BlockExpression(body);
+        let return_type = FunctionReturnType::Ty(return_type);
+        let def =
+            FunctionDefinition::normal(&name, &Vec::new(), &parameters, &body, &[], &return_type);
+        NoirFunction::normal(def)
+    }
+
+    /// `Trait::method(self.field, other.field)`, spanning the field's declaration so that
+    /// any error in the generated call points at the field it came from.
+    fn call_on_fields(&self, trait_path: &Path, method: &str, field: &Ident) -> Expression {
+        let method = self.method_path(trait_path, method);
+        let arguments = vec![self.field_of("self", field), self.field_of("other", field)];
+        self.call(method, arguments, field.span())
+    }
+
+    fn field_of(&self, variable: &str, field: &Ident) -> Expression {
+        let span = field.span();
+        let lhs = self.variable(Path::from_single(variable.to_string(), span));
+        let member_access = MemberAccessExpression { lhs, rhs: field.clone() };
+        Expression::new(ExpressionKind::MemberAccess(Box::new(member_access)), span)
+    }
+
+    fn call(&self, function: Path, arguments: Vec<Expression>, span: Span) -> Expression {
+        let func = Box::new(Expression::new(ExpressionKind::Variable(function), span));
+        Expression::new(ExpressionKind::Call(Box::new(CallExpression { func, arguments })), span)
+    }
+
+    fn variable(&self, path: Path) -> Expression {
+        self.expression(ExpressionKind::Variable(path))
+    }
+
+    fn expression(&self, kind: ExpressionKind) -> Expression {
+        Expression::new(kind, self.span)
+    }
+
+    fn statement(&self, kind: StatementKind) -> Statement {
+        Statement { kind, span: self.span }
+    }
+
+    fn method_path(&self, path: &Path, method: &str) -> Path {
+        let mut path = path.clone();
+        path.segments.push(Ident::new(method.to_string(), self.span));
+        path
+    }
+
+    /// Returns a path to an item in the stdlib, as seen from the crate deriving the trait
+    fn stdlib_path(&self, segments: &[&str]) -> Path {
+        let mut segments = vecmap(segments, |segment| Ident::new(segment.to_string(), self.span));
+
+        let kind = if self.krate.is_stdlib() {
+            PathKind::Crate
+        } else {
+            segments.insert(0, Ident::new("std".to_string(), self.span));
+            PathKind::Dep
+        };
+
+        Path { segments, kind, span: self.span }
+    }
+
+    fn self_type(&self) -> UnresolvedType {
+        self.named_type(Path::from_single("Self".to_string(), self.span))
+    }
+
+    fn named_type(&self, path: Path) -> UnresolvedType {
+        self.unresolved_type(UnresolvedTypeData::Named(path, Vec::new()))
+    }
+
+    fn unresolved_type(&self, typ: UnresolvedTypeData) -> UnresolvedType {
+        UnresolvedType { typ, span: Some(self.span) }
+    }
+}
+
+/// True if `generic` is used as a type anywhere within `typ`. Uses as an array or string
+/// length are ignored since those generics are numeric.
+fn mentions_generic(typ: &UnresolvedType, generic: &Ident) -> bool {
+    match &typ.typ {
+        UnresolvedTypeData::Named(path, args) => {
+            let is_generic = path.kind == PathKind::Plain
+                && path.segments.len() == 1
+                && path.segments[0] == *generic;
+            is_generic || args.iter().any(|arg| mentions_generic(arg, generic))
+        }
+        UnresolvedTypeData::Array(_, element)
+        | UnresolvedTypeData::FormatString(_, element)
+        | UnresolvedTypeData::Parenthesized(element)
+        | UnresolvedTypeData::MutableReference(element) => mentions_generic(element, generic),
+        UnresolvedTypeData::Tuple(elements) | UnresolvedTypeData::TraitAsType(_, elements) => {
+            elements.iter().any(|element| mentions_generic(element, generic))
+        }
+        UnresolvedTypeData::Function(args, ret, env) => {
+            args.iter().any(|arg| mentions_generic(arg, generic))
+                || mentions_generic(ret, generic)
+                || mentions_generic(env, generic)
+        }
+        UnresolvedTypeData::FieldElement
+        | UnresolvedTypeData::Integer(..)
+        | UnresolvedTypeData::Bool
+        | UnresolvedTypeData::Expression(_)
+        | UnresolvedTypeData::String(_)
+        | UnresolvedTypeData::Unit
+        | UnresolvedTypeData::Unspecified
+        | UnresolvedTypeData::Error => false,
+    }
+}
@@ -65,6 +65,10 @@ pub enum DefCollectorErrorKind {
     TraitNotFound { trait_path: Path },
     #[error("Missing Trait method implementation")]
     TraitMissingMethod { trait_name: Ident, method_name: Ident, trait_impl_span: Span },
+    #[error("Associated item is not defined in trait")]
+    AssociatedItemNotInTrait { trait_name: Ident, item_name: Ident },
+    #[error("Missing Trait associated item")]
+    TraitMissingAssociatedItem { trait_name: Ident, item_name: Ident, trait_impl_span: Span },
     #[error("Module is already part of the crate")]
     ModuleAlreadyPartOfCrate { mod_name: Ident, span: Span },
     #[error("Module was originally declared here")]
@@ -73,6 +77,15 @@ pub enum DefCollectorErrorKind {
         "Either the type or the trait must be from the same crate as the trait implementation"
     )]
     TraitImplOrphaned { span: Span },
+    #[error("Trait cannot be derived")]
+    UnsupportedDerive { trait_name: String, span: Span },
+    #[error("Field type does not implement the derived trait")]
+    DerivedTraitNotImplementedForField {
+        trait_name: String,
+        struct_name: String,
+        field_name: Ident,
+        field_type: crate::Type,
+    },
     #[error("macro error : {0:?}")]
     MacroError(MacroError),
 }
@@ -89,6 +102,37 @@ impl DefCollectorErrorKind {
     pub fn into_file_diagnostic(self, file: fm::FileId) -> FileDiagnostic {
         Diagnostic::from(self).in_file(file)
     }
+
+    /// The stable error code of this error, if one has been assigned.
+    ///
+    /// Errors reported by macro processors have no code since they are not defined by the compiler.
+    pub fn code(&self) -> Option<&'static str> {
+        let code = match self {
+            DefCollectorErrorKind::Duplicate { .. } => "E0401",
+            DefCollectorErrorKind::UnresolvedModuleDecl { .. } => "E0402",
+            DefCollectorErrorKind::PathResolutionError(error) => error.code(),
+            DefCollectorErrorKind::NonStructTypeInImpl { .. } => "E0403",
+            DefCollectorErrorKind::MutableReferenceInTraitImpl { .. } => "E0404",
+            DefCollectorErrorKind::OverlappingImpl { .. }
+            | DefCollectorErrorKind::OverlappingImplNote { .. } => "E0405",
+            DefCollectorErrorKind::ForeignImpl { .. } => "E0406",
+            DefCollectorErrorKind::MismatchTraitImplementationNumParameters { .. } => "E0407",
+            DefCollectorErrorKind::MismatchTraitImplementationNumGenerics { .. } => "E0408",
+            DefCollectorErrorKind::MethodNotInTrait { .. } => "E0409",
+            DefCollectorErrorKind::NotATrait { .. } => "E0410",
+            DefCollectorErrorKind::TraitNotFound { .. } => "E0411",
+            DefCollectorErrorKind::TraitMissingMethod { .. } => "E0412",
+            DefCollectorErrorKind::AssociatedItemNotInTrait { .. } => "E0413",
+            DefCollectorErrorKind::TraitMissingAssociatedItem { .. } => "E0414",
+            DefCollectorErrorKind::ModuleAlreadyPartOfCrate { .. }
+            | DefCollectorErrorKind::ModuleOriginallyDefined { .. } => "E0415",
+            DefCollectorErrorKind::TraitImplOrphaned { .. } => "E0416",
+            DefCollectorErrorKind::UnsupportedDerive { .. } => "E0417",
+            DefCollectorErrorKind::DerivedTraitNotImplementedForField { .. } => "E0418",
+            DefCollectorErrorKind::MacroError(_) => return None,
+        };
+        Some(code)
+    }
 }
 
 impl fmt::Display for DuplicateType {
@@ -110,7 +154,8 @@ impl fmt::Display for DuplicateType {
 
 impl From<DefCollectorErrorKind> for Diagnostic {
     fn from(error: DefCollectorErrorKind) -> Diagnostic {
-        match error {
+        let code = error.code();
+        let mut diagnostic = match error {
             DefCollectorErrorKind::Duplicate { typ, first_def, second_def } => {
                 let primary_message = format!(
                     "Duplicate definitions of {} with name {} found",
@@ -223,6 +268,23 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                     trait_impl_span,
                 )
             }
+            DefCollectorErrorKind::AssociatedItemNotInTrait { trait_name, item_name } => {
+                let primary_message = format!("`{item_name}` is not an associated type or constant of trait `{trait_name}`");
+                Diagnostic::simple_error(primary_message, String::new(), item_name.span())
+            }
+            DefCollectorErrorKind::TraitMissingAssociatedItem {
+                trait_name,
+                item_name,
+                trait_impl_span,
+            } => {
+                let primary_message =
+                    format!("`{item_name}` from trait `{trait_name}` is not defined");
+                Diagnostic::simple_error(
+                    primary_message,
+                    format!("Please define {item_name} here"),
+                    trait_impl_span,
+                )
+            }
             DefCollectorErrorKind::NotATrait { not_a_trait_name } => {
                 let span = not_a_trait_name.span();
                 Diagnostic::simple_error(
@@ -246,9 +308,28 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                 "Either the type or the trait must be from the same crate as the trait implementation".into(),
                 span,
             ),
+            DefCollectorErrorKind::UnsupportedDerive { trait_name, span } => {
+                Diagnostic::simple_error(
+                    format!("`{trait_name}` cannot be derived"),
+                    "Only `Eq`, `Default`, `Ord` and `Hash` can be derived".into(),
+                    span,
+                )
+            }
+            DefCollectorErrorKind::DerivedTraitNotImplementedForField {
+                trait_name,
+                struct_name,
+                field_name,
+                field_type,
+            } => Diagnostic::simple_error(
+                format!("Cannot derive `{trait_name}` for `{struct_name}`"),
+                format!("`{field_type}` does not implement `{trait_name}`"),
+                field_name.span(),
+            ),
             DefCollectorErrorKind::MacroError(macro_error) => {
                 Diagnostic::simple_error(macro_error.primary_message, macro_error.secondary_message.unwrap_or_default(), macro_error.span.unwrap_or_default())
             },
-        }
+        };
+        diagnostic.code = code;
+        diagnostic
     }
 }
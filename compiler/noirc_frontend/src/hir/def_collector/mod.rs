@@ -19,4 +19,5 @@
 //! These passes are performed sequentially (along with type checking afterward) in dc_crate.
 pub mod dc_crate;
 pub mod dc_mod;
+pub(crate) mod derive;
 pub mod errors;
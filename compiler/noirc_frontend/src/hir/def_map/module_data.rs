@@ -1,10 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use noirc_errors::Location;
 
 use crate::{
     node_interner::{FuncId, StmtId, StructId, TraitId, TypeAliasId},
-    Ident,
+    Ident, ItemVisibility,
 };
 
 use super::{ItemScope, LocalModuleId, ModuleDefId, ModuleId, PerNs};
@@ -27,6 +27,9 @@ pub struct ModuleData {
 
     /// True if this module is a `contract Foo { ... }` module containing contract functions
     pub is_contract: bool,
+
+    /// Names of the imports marked `#[allow(dead_code)]` in this module
+    dead_imports_allowed: HashSet<String>,
 }
 
 impl ModuleData {
@@ -38,6 +41,7 @@ impl ModuleData {
             definitions: ItemScope::default(),
             location,
             is_contract,
+            dead_imports_allowed: HashSet::new(),
         }
     }
 
@@ -45,30 +49,50 @@ impl ModuleData {
         &self.scope
     }
 
+    pub(crate) fn definitions(&self) -> &ItemScope {
+        &self.definitions
+    }
+
+    pub(crate) fn allow_dead_import(&mut self, name: &Ident) {
+        self.dead_imports_allowed.insert(name.0.contents.clone());
+    }
+
+    /// True if the import with the given name is marked `#[allow(dead_code)]`
+    pub(crate) fn allows_dead_import(&self, name: &Ident) -> bool {
+        self.dead_imports_allowed.contains(&name.0.contents)
+    }
+
     fn declare(
         &mut self,
         name: Ident,
+        visibility: ItemVisibility,
         item_id: ModuleDefId,
         trait_id: Option<TraitId>,
     ) -> Result<(), (Ident, Ident)> {
-        self.scope.add_definition(name.clone(), item_id, trait_id)?;
+        self.scope.add_definition(name.clone(), visibility, item_id, trait_id)?;
 
         // definitions is a subset of self.scope so it is expected if self.scope.define_func_def
         // returns without error, so will self.definitions.define_func_def.
-        self.definitions.add_definition(name, item_id, trait_id)
+        self.definitions.add_definition(name, visibility, item_id, trait_id)
     }
 
-    pub fn declare_function(&mut self, name: Ident, id: FuncId) -> Result<(), (Ident, Ident)> {
-        self.declare(name, id.into(), None)
+    pub fn declare_function(
+        &mut self,
+        name: Ident,
+        visibility: ItemVisibility,
+        id: FuncId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.declare(name, visibility, id.into(), None)
     }
 
+    /// Trait methods are always public, as they are visible wherever the trait is
     pub fn declare_trait_function(
         &mut self,
         name: Ident,
         id: FuncId,
         trait_id: TraitId,
     ) -> Result<(), (Ident, Ident)> {
-        self.declare(name, id.into(), Some(trait_id))
+        self.declare(name, ItemVisibility::Public, id.into(), Some(trait_id))
     }
 
     pub fn remove_function(&mut self, name: &Ident) {
@@ -76,45 +100,64 @@ impl ModuleData {
         self.definitions.remove_definition(name);
     }
 
-    pub fn declare_global(&mut self, name: Ident, id: StmtId) -> Result<(), (Ident, Ident)> {
-        self.declare(name, id.into(), None)
+    pub fn declare_global(
+        &mut self,
+        name: Ident,
+        visibility: ItemVisibility,
+        id: StmtId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.declare(name, visibility, id.into(), None)
     }
 
-    pub fn declare_struct(&mut self, name: Ident, id: StructId) -> Result<(), (Ident, Ident)> {
-        self.declare(name, ModuleDefId::TypeId(id), None)
+    pub fn declare_struct(
+        &mut self,
+        name: Ident,
+        visibility: ItemVisibility,
+        id: StructId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.declare(name, visibility, ModuleDefId::TypeId(id), None)
     }
 
+    /// Type aliases have no visibility modifier and are always public
     pub fn declare_type_alias(
         &mut self,
         name: Ident,
         id: TypeAliasId,
     ) -> Result<(), (Ident, Ident)> {
-        self.declare(name, id.into(), None)
+        self.declare(name, ItemVisibility::Public, id.into(), None)
     }
 
-    pub fn declare_trait(&mut self, name: Ident, id: TraitId) -> Result<(), (Ident, Ident)> {
-        self.declare(name, ModuleDefId::TraitId(id), None)
+    pub fn declare_trait(
+        &mut self,
+        name: Ident,
+        visibility: ItemVisibility,
+        id: TraitId,
+    ) -> Result<(), (Ident, Ident)> {
+        self.declare(name, visibility, ModuleDefId::TraitId(id), None)
     }
 
     pub fn declare_child_module(
         &mut self,
         name: Ident,
+        visibility: ItemVisibility,
         child_id: ModuleId,
     ) -> Result<(), (Ident, Ident)> {
-        self.declare(name, child_id.into(), None)
+        self.declare(name, visibility, child_id.into(), None)
     }
 
     pub fn find_func_with_name(&self, name: &Ident) -> Option<FuncId> {
         self.scope.find_func_with_name(name)
     }
 
+    /// Imported names are re-exported from this module, so they are always public.
+    /// The visibility of the imported item itself is checked when resolving the import.
     pub fn import(
         &mut self,
         name: Ident,
         id: ModuleDefId,
         is_prelude: bool,
     ) -> Result<(), (Ident, Ident)> {
-        self.scope.add_item_to_namespace(name, id, None, is_prelude)
+        self.scope.add_item_to_namespace(name, ItemVisibility::Public, id, None, is_prelude)
     }
 
     pub fn find_name(&self, name: &Ident) -> PerNs {
@@ -1,3 +1,4 @@
+pub mod comptime;
 pub mod def_collector;
 pub mod def_map;
 pub mod resolution;
@@ -6,6 +7,7 @@ pub mod type_check;
 
 use crate::graph::{CrateGraph, CrateId};
 use crate::hir_def::function::FuncMeta;
+use crate::macros_api::MacroProcessor;
 use crate::node_interner::{FuncId, NodeInterner, StructId};
 use def_map::{Contract, CrateDefMap};
 use fm::FileManager;
@@ -30,6 +32,10 @@ pub struct Context<'file_manager> {
     /// A map of each file that already has been visited from a prior `mod foo;` declaration.
     /// This is used to issue an error if a second `mod foo;` is declared to the same file.
     pub visited_files: BTreeMap<fm::FileId, Location>,
+
+    /// Macro plugins registered by tools built on top of the compiler. These can be selected
+    /// by name alongside the plugins built into the compiler.
+    macro_processors: Vec<&'static dyn MacroProcessor>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -47,6 +53,7 @@ impl Context<'_> {
             visited_files: BTreeMap::new(),
             crate_graph: CrateGraph::default(),
             file_manager: Cow::Owned(file_manager),
+            macro_processors: Vec::new(),
         }
     }
 
@@ -57,9 +64,21 @@ impl Context<'_> {
             visited_files: BTreeMap::new(),
             crate_graph: CrateGraph::default(),
             file_manager: Cow::Borrowed(file_manager),
+            macro_processors: Vec::new(),
         }
     }
 
+    /// Registers a macro plugin to be run on each crate compiled with this context,
+    /// in addition to the plugins built into the compiler.
+    pub fn register_macro_processor(&mut self, macro_processor: &'static dyn MacroProcessor) {
+        self.macro_processors.push(macro_processor);
+    }
+
+    /// The macro plugins registered with [Context::register_macro_processor].
+    pub fn macro_processors(&self) -> &[&'static dyn MacroProcessor] {
+        &self.macro_processors
+    }
+
     /// Returns the CrateDefMap for a given CrateId.
     /// It is perfectly valid for the compiler to look
     /// up a CrateDefMap and it is not available.
@@ -0,0 +1,553 @@
+//! Dead code analysis.
+//!
+//! Once a crate has been resolved and type checked, every item reachable from the crate's
+//! entry points is found by walking the HIR of each reachable function and global. Entry points
+//! are `main`, functions with a primary attribute such as `#[test]`, `pub` items, items within
+//! contracts and items marked with `#[allow(dead_code)]`. A warning is then issued for each
+//! remaining function, global and struct, for each trait impl of an unused struct, for each
+//! private struct field which is never read, and for each import which is never used unless it
+//! is marked `#[allow(dead_code)]`.
+use std::collections::{HashMap, HashSet};
+
+use fm::FileId;
+use iter_extended::vecmap;
+
+use crate::graph::CrateId;
+use crate::hir::def_collector::dc_crate::CompilationError;
+use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId};
+use crate::hir::Context;
+use crate::hir_def::expr::{HirArrayLiteral, HirExpression, HirLiteral};
+use crate::hir_def::stmt::{HirLValue, HirPattern, HirStatement};
+use crate::node_interner::{
+    DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, StructId,
+};
+use crate::token::SecondaryAttribute;
+use crate::{FunctionKind, Ident, ItemVisibility, Type};
+
+use super::errors::ResolverError;
+
+pub(crate) const DEAD_CODE_LINT: &str = "dead_code";
+
+/// Finds the unused items of the given crate, returning a warning for each of them.
+pub(crate) fn find_dead_code(
+    context: &Context,
+    crate_id: CrateId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut analysis = DeadCodeAnalysis::new(&context.def_interner, &context.def_maps[&crate_id]);
+    analysis.collect_items();
+    analysis.find_live_items();
+    analysis.into_warnings()
+}
+
+/// An item which is reported if it is never found to be live
+struct Candidate {
+    name: Ident,
+    item: ModuleDefId,
+    file: FileId,
+}
+
+struct DeadCodeAnalysis<'a> {
+    interner: &'a NodeInterner,
+    def_map: &'a CrateDefMap,
+
+    /// Functions, globals and structs which are reported if they are not live
+    candidates: Vec<Candidate>,
+
+    /// Structs whose private fields are reported if they are never read
+    structs: Vec<StructId>,
+
+    /// Imports which are reported if their name is never used in their module
+    imports: Vec<(Ident, ModuleId, FileId)>,
+
+    /// The trait impls for each struct of this crate. Their methods become live along
+    /// with the struct, and the impls are reported if the struct is not live.
+    trait_impls: Vec<(StructId, Ident, Type, FileId)>,
+
+    /// Methods which become live along with the struct they are defined on
+    struct_methods: HashMap<StructId, Vec<FuncId>>,
+
+    /// Maps the definition of each global to the global's let statement
+    global_definitions: HashMap<DefinitionId, StmtId>,
+
+    live: HashSet<ModuleDefId>,
+
+    /// Live items whose contents have not been visited yet
+    worklist: Vec<ModuleDefId>,
+
+    /// Each struct field which is read by live code
+    read_fields: HashSet<(StructId, String)>,
+}
+
+impl<'a> DeadCodeAnalysis<'a> {
+    fn new(interner: &'a NodeInterner, def_map: &'a CrateDefMap) -> Self {
+        let global_definitions = interner
+            .get_all_globals()
+            .into_keys()
+            .map(|stmt_id| (interner.let_statement(&stmt_id).ident().id, stmt_id))
+            .collect();
+
+        Self {
+            interner,
+            def_map,
+            candidates: Vec::new(),
+            structs: Vec::new(),
+            imports: Vec::new(),
+            trait_impls: Vec::new(),
+            struct_methods: HashMap::new(),
+            global_definitions,
+            live: HashSet::new(),
+            worklist: Vec::new(),
+            read_fields: HashSet::new(),
+        }
+    }
+
+    /// Collects the items of the crate, marking each entry point as live
+    fn collect_items(&mut self) {
+        let interner = self.interner;
+        let def_map = self.def_map;
+        let main_function = def_map.main_function();
+        let crate_files: HashSet<FileId> =
+            def_map.modules().iter().map(|(_, module)| module.location.file).collect();
+
+        // The struct or trait owning each method module
+        let mut method_modules = HashMap::new();
+        for (_, module) in def_map.modules().iter() {
+            for (item, _, _) in module.definitions().types().values().flat_map(|s| s.values()) {
+                match item {
+                    ModuleDefId::TypeId(struct_id) if struct_id.krate() == def_map.krate => {
+                        method_modules.insert(struct_id.local_module_id(), Some(*struct_id));
+                    }
+                    ModuleDefId::TraitId(trait_id) if trait_id.0.krate == def_map.krate => {
+                        method_modules.insert(trait_id.0.local_id, None);
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        for (index, module) in def_map.modules().iter() {
+            let local_id = LocalModuleId(index);
+            let module_id = ModuleId { krate: def_map.krate, local_id };
+            let file = module.location.file;
+            let in_contract = self.in_contract(local_id);
+            let definitions = module.definitions();
+
+            for (name, scope) in definitions.types().iter().chain(definitions.values()) {
+                for (item, visibility, _) in scope.values() {
+                    let is_root = in_contract || *visibility == ItemVisibility::Public;
+
+                    match (item, method_modules.get(&local_id)) {
+                        // Trait methods are never reported
+                        (ModuleDefId::FunctionId(func_id), Some(None)) => {
+                            self.mark_live(ModuleDefId::FunctionId(*func_id));
+                        }
+                        // Public methods are live as long as their struct is
+                        (ModuleDefId::FunctionId(func_id), Some(Some(struct_id)))
+                            if *visibility == ItemVisibility::Public && !in_contract =>
+                        {
+                            self.struct_methods.entry(*struct_id).or_default().push(*func_id);
+                        }
+                        (ModuleDefId::FunctionId(func_id), _) => {
+                            let attributes = interner.function_attributes(func_id);
+                            let is_entry_point = Some(*func_id) == main_function
+                                || attributes.function.is_some()
+                                || attributes.allows(DEAD_CODE_LINT);
+                            self.add_item(name, *item, file, is_root || is_entry_point);
+                        }
+                        (ModuleDefId::GlobalId(stmt_id), _) => {
+                            let attributes = interner.global_attributes(stmt_id);
+                            let allowed = SecondaryAttribute::allows(attributes, DEAD_CODE_LINT);
+                            self.add_item(name, *item, file, is_root || allowed);
+                        }
+                        (ModuleDefId::TypeId(struct_id), _) => {
+                            let attributes = interner.struct_attributes(struct_id);
+                            let allowed = SecondaryAttribute::allows(attributes, DEAD_CODE_LINT);
+                            self.add_item(name, *item, file, is_root || allowed);
+                            if !in_contract && !allowed {
+                                self.structs.push(*struct_id);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            if in_contract {
+                continue;
+            }
+
+            // Any name in scope which isn't defined in this module was imported
+            let scope = module.scope();
+            for (name, imported) in scope.types().iter().chain(scope.values()) {
+                let is_prelude = imported.values().any(|(_, _, is_prelude)| *is_prelude);
+                let is_definition = definitions.types().contains_key(name)
+                    || definitions.values().contains_key(name);
+                let already_added = self.imports.iter().any(|(import, id, _)| {
+                    *id == module_id && import.0.contents == name.0.contents
+                });
+
+                let allowed = module.allows_dead_import(name);
+
+                if !is_prelude && !is_definition && !already_added && !allowed {
+                    self.imports.push((name.clone(), module_id, file));
+                }
+            }
+        }
+
+        for trait_impl in interner.trait_implementations() {
+            let trait_impl = trait_impl.borrow();
+            if !crate_files.contains(&trait_impl.file) {
+                continue;
+            }
+
+            match trait_impl.typ.follow_bindings() {
+                Type::Struct(struct_type, _)
+                    if struct_type.borrow().id.krate() == def_map.krate =>
+                {
+                    let struct_id = struct_type.borrow().id;
+                    let methods = self.struct_methods.entry(struct_id).or_default();
+                    methods.extend(trait_impl.methods.iter().copied());
+
+                    let trait_name = trait_impl.ident.clone();
+                    let typ = trait_impl.typ.clone();
+                    self.trait_impls.push((struct_id, trait_name, typ, trait_impl.file));
+                }
+                _ => {
+                    for method in &trait_impl.methods {
+                        self.mark_live(ModuleDefId::FunctionId(*method));
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_item(&mut self, name: &Ident, item: ModuleDefId, file: FileId, is_root: bool) {
+        if is_root {
+            self.mark_live(item);
+        } else {
+            self.candidates.push(Candidate { name: name.clone(), item, file });
+        }
+    }
+
+    /// True if the given module is a contract or is within one
+    fn in_contract(&self, local_id: LocalModuleId) -> bool {
+        let module = &self.def_map.modules()[local_id.0];
+        module.is_contract || module.parent.map_or(false, |parent| self.in_contract(parent))
+    }
+
+    fn mark_live(&mut self, item: ModuleDefId) {
+        if self.live.insert(item) {
+            self.worklist.push(item);
+        }
+    }
+
+    fn find_live_items(&mut self) {
+        while let Some(item) = self.worklist.pop() {
+            match item {
+                ModuleDefId::FunctionId(func_id) => self.visit_function(func_id),
+                ModuleDefId::GlobalId(global_id) => self.visit_global(global_id),
+                ModuleDefId::TypeId(struct_id) => self.visit_struct(struct_id),
+                _ => (),
+            }
+        }
+    }
+
+    fn visit_function(&mut self, func_id: FuncId) {
+        if let Some(meta) = self.interner.try_function_meta(&func_id) {
+            self.visit_type(&meta.typ);
+
+            if meta.kind == FunctionKind::Normal {
+                let body = self.interner.function(&func_id);
+                self.visit_expression(*body.as_expr());
+            }
+        }
+    }
+
+    fn visit_global(&mut self, global_id: StmtId) {
+        let let_statement = self.interner.let_statement(&global_id);
+        self.visit_type(&let_statement.r#type);
+        self.visit_expression(let_statement.expression);
+    }
+
+    fn visit_struct(&mut self, struct_id: StructId) {
+        if struct_id.krate() != self.def_map.krate {
+            return;
+        }
+
+        let struct_type = self.interner.get_struct(struct_id);
+        let field_types = vecmap(struct_type.borrow().fields(), |(_, _, typ)| typ.clone());
+        for field_type in &field_types {
+            self.visit_type(field_type);
+        }
+
+        if let Some(methods) = self.struct_methods.get(&struct_id).cloned() {
+            for method in methods {
+                self.mark_live(ModuleDefId::FunctionId(method));
+            }
+        }
+    }
+
+    fn visit_type(&mut self, typ: &Type) {
+        match typ.follow_bindings() {
+            Type::Array(length, element) => {
+                self.visit_type(&length);
+                self.visit_type(&element);
+            }
+            Type::String(length) => self.visit_type(&length),
+            Type::FmtString(length, environment) => {
+                self.visit_type(&length);
+                self.visit_type(&environment);
+            }
+            Type::Struct(struct_type, generics) => {
+                self.mark_live(ModuleDefId::TypeId(struct_type.borrow().id));
+                for generic in &generics {
+                    self.visit_type(generic);
+                }
+            }
+            Type::Tuple(fields) => {
+                for field in &fields {
+                    self.visit_type(field);
+                }
+            }
+            Type::Function(arguments, return_type, environment) => {
+                for argument in &arguments {
+                    self.visit_type(argument);
+                }
+                self.visit_type(&return_type);
+                self.visit_type(&environment);
+            }
+            Type::MutableReference(element) => self.visit_type(&element),
+            Type::Forall(_, typ) => self.visit_type(&typ),
+            _ => (),
+        }
+    }
+
+    fn visit_expression(&mut self, expr_id: ExprId) {
+        self.visit_type(&self.interner.id_type(expr_id));
+
+        match self.interner.expression(&expr_id) {
+            HirExpression::Ident(ident) => self.visit_definition(ident.id),
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements))) => {
+                self.visit_expressions(&elements);
+            }
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Repeated {
+                repeated_element,
+                length,
+            })) => {
+                self.visit_expression(repeated_element);
+                self.visit_type(&length);
+            }
+            HirExpression::Literal(HirLiteral::FmtStr(_, elements)) => {
+                self.visit_expressions(&elements);
+            }
+            HirExpression::Literal(_) => (),
+            HirExpression::Block(block) => {
+                for statement in block.statements() {
+                    self.visit_statement(*statement);
+                }
+            }
+            HirExpression::Prefix(prefix) => self.visit_expression(prefix.rhs),
+            HirExpression::Infix(infix) => {
+                self.visit_expression(infix.lhs);
+                self.visit_expression(infix.rhs);
+            }
+            HirExpression::Index(index) => {
+                self.visit_expression(index.collection);
+                self.visit_expression(index.index);
+            }
+            HirExpression::Constructor(constructor) => {
+                self.mark_live(ModuleDefId::TypeId(constructor.r#type.borrow().id));
+                for (_, field) in &constructor.fields {
+                    self.visit_expression(*field);
+                }
+            }
+            HirExpression::MemberAccess(access) => {
+                self.visit_expression(access.lhs);
+                let lhs_type = self.interner.id_type(access.lhs);
+                self.read_field(&lhs_type, &access.rhs);
+            }
+            HirExpression::Call(call) => {
+                self.visit_expression(call.func);
+                self.visit_expressions(&call.arguments);
+            }
+            HirExpression::MethodCall(method_call) => {
+                self.visit_expression(method_call.object);
+                self.visit_expressions(&method_call.arguments);
+            }
+            HirExpression::Cast(cast) => {
+                self.visit_expression(cast.lhs);
+                self.visit_type(&cast.r#type);
+            }
+            HirExpression::If(if_expr) => {
+                self.visit_expression(if_expr.condition);
+                self.visit_expression(if_expr.consequence);
+                if let Some(alternative) = if_expr.alternative {
+                    self.visit_expression(alternative);
+                }
+            }
+            HirExpression::Tuple(fields) => self.visit_expressions(&fields),
+            HirExpression::Lambda(lambda) => {
+                for (pattern, typ) in &lambda.parameters {
+                    self.visit_pattern(pattern);
+                    self.visit_type(typ);
+                }
+                self.visit_type(&lambda.return_type);
+                self.visit_expression(lambda.body);
+            }
+            HirExpression::TraitMethodReference(_) | HirExpression::Error => (),
+        }
+    }
+
+    fn visit_expressions(&mut self, expressions: &[ExprId]) {
+        for expression in expressions {
+            self.visit_expression(*expression);
+        }
+    }
+
+    fn visit_definition(&mut self, definition_id: DefinitionId) {
+        if let Some(definition) = self.interner.try_definition(definition_id) {
+            match definition.kind {
+                DefinitionKind::Function(func_id) => {
+                    self.mark_live(ModuleDefId::FunctionId(func_id));
+                }
+                DefinitionKind::Global(_) => {
+                    if let Some(global_id) = self.global_definitions.get(&definition_id) {
+                        self.mark_live(ModuleDefId::GlobalId(*global_id));
+                    }
+                }
+                DefinitionKind::Local(_) | DefinitionKind::GenericType(_) => (),
+            }
+        }
+    }
+
+    fn visit_statement(&mut self, stmt_id: StmtId) {
+        match self.interner.statement(&stmt_id) {
+            HirStatement::Let(let_statement) => {
+                self.visit_pattern(&let_statement.pattern);
+                self.visit_type(&let_statement.r#type);
+                self.visit_expression(let_statement.expression);
+            }
+            HirStatement::Constrain(constrain) => self.visit_expression(constrain.0),
+            HirStatement::Assign(assign) => {
+                self.visit_lvalue(&assign.lvalue);
+                self.visit_expression(assign.expression);
+            }
+            HirStatement::For(for_loop) => {
+                self.visit_expression(for_loop.start_range);
+                self.visit_expression(for_loop.end_range);
+                self.visit_expression(for_loop.block);
+            }
+            HirStatement::Expression(expression) | HirStatement::Semi(expression) => {
+                self.visit_expression(expression);
+            }
+            HirStatement::Error => (),
+        }
+    }
+
+    fn visit_pattern(&mut self, pattern: &HirPattern) {
+        match pattern {
+            HirPattern::Identifier(_) => (),
+            HirPattern::Mutable(pattern, _) => self.visit_pattern(pattern),
+            HirPattern::Tuple(patterns, _) => {
+                for pattern in patterns {
+                    self.visit_pattern(pattern);
+                }
+            }
+            HirPattern::Struct(typ, fields, _) => {
+                self.visit_type(typ);
+                for (field_name, pattern) in fields {
+                    self.read_field(typ, field_name);
+                    self.visit_pattern(pattern);
+                }
+            }
+        }
+    }
+
+    /// Assigning to a field does not count as reading it, but any index is still visited
+    fn visit_lvalue(&mut self, lvalue: &HirLValue) {
+        match lvalue {
+            HirLValue::Ident(..) => (),
+            HirLValue::MemberAccess { object, .. } => self.visit_lvalue(object),
+            HirLValue::Index { array, index, .. } => {
+                self.visit_lvalue(array);
+                self.visit_expression(*index);
+            }
+            HirLValue::Dereference { lvalue, .. } => self.visit_lvalue(lvalue),
+        }
+    }
+
+    /// Records that the given field of a value of type `typ` was read.
+    /// Fields may be read through a mutable reference to a struct as well.
+    fn read_field(&mut self, typ: &Type, field_name: &Ident) {
+        match typ.follow_bindings() {
+            Type::Struct(struct_type, _) => {
+                let struct_id = struct_type.borrow().id;
+                self.read_fields.insert((struct_id, field_name.0.contents.clone()));
+            }
+            Type::MutableReference(element) => self.read_field(&element, field_name),
+            _ => (),
+        }
+    }
+
+    fn into_warnings(mut self) -> Vec<(CompilationError, FileId)> {
+        let mut warnings = Vec::new();
+
+        self.candidates.sort_by_key(|candidate| candidate.name.span().start());
+        for candidate in &self.candidates {
+            let is_used = self.live.contains(&candidate.item)
+                || match candidate.item {
+                    ModuleDefId::GlobalId(global_id) => {
+                        self.interner.is_global_used_in_type(global_id)
+                    }
+                    _ => false,
+                };
+
+            if !is_used {
+                let item_type = match candidate.item {
+                    ModuleDefId::TypeId(_) => "struct",
+                    item => item.as_str(),
+                };
+                let error =
+                    ResolverError::UnusedPrivateItem { ident: candidate.name.clone(), item_type };
+                warnings.push((error.into(), candidate.file));
+            }
+        }
+
+        for struct_id in &self.structs {
+            if !self.live.contains(&ModuleDefId::TypeId(*struct_id)) {
+                continue;
+            }
+
+            let struct_type = self.interner.get_struct(*struct_id);
+            let struct_type = struct_type.borrow();
+            for (visibility, field_name, _) in struct_type.fields() {
+                let is_read =
+                    self.read_fields.contains(&(*struct_id, field_name.0.contents.clone()));
+
+                if *visibility != ItemVisibility::Public && !is_read {
+                    let struct_name = struct_type.name.to_string();
+                    let field_name = field_name.clone();
+                    let error = ResolverError::UnusedStructField { struct_name, field_name };
+                    warnings.push((error.into(), struct_type.location.file));
+                }
+            }
+        }
+
+        for (struct_id, trait_name, typ, file) in self.trait_impls {
+            if !self.live.contains(&ModuleDefId::TypeId(struct_id)) {
+                let error = ResolverError::UnusedTraitImpl { trait_name, typ };
+                warnings.push((error.into(), file));
+            }
+        }
+
+        self.imports.sort_by_key(|(name, _, _)| name.span().start());
+        for (name, module_id, file) in self.imports {
+            if !self.interner.is_path_name_used(module_id, &name.0.contents) {
+                let error = ResolverError::UnusedPrivateItem { ident: name, item_type: "import" };
+                warnings.push((error.into(), file));
+            }
+        }
+
+        warnings
+    }
+}
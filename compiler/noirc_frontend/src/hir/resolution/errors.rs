@@ -2,7 +2,7 @@ pub use noirc_errors::Span;
 use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
 use thiserror::Error;
 
-use crate::{parser::ParserError, Ident, Type};
+use crate::{hir::comptime::InterpreterError, parser::ParserError, Ident, Type};
 
 use super::import::PathResolutionError;
 
@@ -20,6 +20,12 @@ pub enum ResolverError {
     DuplicateDefinition { name: String, first_span: Span, second_span: Span },
     #[error("Unused variable")]
     UnusedVariable { ident: Ident },
+    #[error("Unused private item")]
+    UnusedPrivateItem { ident: Ident, item_type: &'static str },
+    #[error("Unused struct field")]
+    UnusedStructField { struct_name: String, field_name: Ident },
+    #[error("Unused trait impl")]
+    UnusedTraitImpl { trait_name: Ident, typ: Type },
     #[error("Could not find variable in this scope")]
     VariableNotDeclared { name: String, span: Span },
     #[error("path is not an identifier")]
@@ -52,8 +58,6 @@ pub enum ResolverError {
     NoSuchNumericTypeVariable { path: crate::Path },
     #[error("Closures cannot capture mutable variables")]
     CapturedMutableVariable { span: Span },
-    #[error("Test functions are not allowed to have any parameters")]
-    TestFunctionHasParameters { span: Span },
     #[error("Only struct types can be used in constructor expressions")]
     NonStructUsedInConstructor { typ: Type, span: Span },
     #[error("Only struct types can have generics")]
@@ -82,12 +86,59 @@ pub enum ResolverError {
     NonCrateFunctionCalled { name: String, span: Span },
     #[error("Only sized types may be used in the entry point to a program")]
     InvalidTypeForEntryPoint { span: Span },
+    #[error("Comptime function {name} called outside of a comptime context")]
+    ComptimeFunctionCalledAtRuntime { name: String, span: Span },
+    #[error(transparent)]
+    ComptimeError(#[from] InterpreterError),
 }
 
 impl ResolverError {
     pub fn into_file_diagnostic(self, file: fm::FileId) -> FileDiagnostic {
         Diagnostic::from(self).in_file(file)
     }
+
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ResolverError::DuplicateDefinition { .. } => "E0101",
+            ResolverError::UnusedVariable { .. } => "E0102",
+            ResolverError::UnusedPrivateItem { .. } => "E0103",
+            ResolverError::UnusedStructField { .. } => "E0104",
+            ResolverError::UnusedTraitImpl { .. } => "E0105",
+            ResolverError::VariableNotDeclared { .. } => "E0106",
+            ResolverError::PathIsNotIdent { .. } => "E0107",
+            ResolverError::PathResolutionError(error) => error.code(),
+            ResolverError::Expected { .. } => "E0108",
+            ResolverError::DuplicateField { .. } => "E0109",
+            ResolverError::NoSuchField { .. } => "E0110",
+            ResolverError::MissingFields { .. } => "E0111",
+            ResolverError::UnnecessaryMut { .. } => "E0112",
+            ResolverError::UnnecessaryPub { .. } => "E0113",
+            ResolverError::NecessaryPub { .. } => "E0114",
+            ResolverError::DistinctNotAllowed { .. } => "E0115",
+            ResolverError::MissingRhsExpr { .. } => "E0116",
+            ResolverError::InvalidArrayLengthExpr { .. } => "E0117",
+            ResolverError::IntegerTooLarge { .. } => "E0118",
+            ResolverError::NoSuchNumericTypeVariable { .. } => "E0119",
+            ResolverError::CapturedMutableVariable { .. } => "E0120",
+            ResolverError::NonStructUsedInConstructor { .. } => "E0122",
+            ResolverError::NonStructWithGenerics { .. } => "E0123",
+            ResolverError::GenericsOnSelfType { .. } => "E0124",
+            ResolverError::IncorrectGenericCount { .. } => "E0125",
+            ResolverError::ParserError(error) => error.code(),
+            ResolverError::ContractFunctionTypeInNormalFunction { .. } => "E0126",
+            ResolverError::MutableReferenceToImmutableVariable { .. } => "E0127",
+            ResolverError::MutableReferenceToArrayElement { .. } => "E0128",
+            ResolverError::ContractFunctionInternalInNormalFunction { .. } => "E0129",
+            ResolverError::NumericConstantInFormatString { .. } => "E0130",
+            ResolverError::InvalidClosureEnvironment { .. } => "E0131",
+            ResolverError::PrivateFunctionCalled { .. } => "E0132",
+            ResolverError::NonCrateFunctionCalled { .. } => "E0133",
+            ResolverError::InvalidTypeForEntryPoint { .. } => "E0135",
+            ResolverError::ComptimeFunctionCalledAtRuntime { .. } => "E0136",
+            ResolverError::ComptimeError(error) => error.code(),
+        }
+    }
 }
 
 impl From<ResolverError> for Diagnostic {
@@ -95,7 +146,8 @@ impl From<ResolverError> for Diagnostic {
     /// ICEs will make the compiler panic, as they could affect the
     /// soundness of the generated program
     fn from(error: ResolverError) -> Diagnostic {
-        match error {
+        let code = error.code();
+        let mut diagnostic = match error {
             ResolverError::DuplicateDefinition { name, first_span, second_span } => {
                 let mut diag = Diagnostic::simple_error(
                     format!("duplicate definitions of {name} found"),
@@ -114,6 +166,27 @@ impl From<ResolverError> for Diagnostic {
                     ident.span(),
                 )
             }
+            ResolverError::UnusedPrivateItem { ident, item_type } => {
+                let name = &ident.0.contents;
+
+                Diagnostic::simple_warning(
+                    format!("{item_type} {name} is never used"),
+                    format!("unused {item_type}"),
+                    ident.span(),
+                )
+            }
+            ResolverError::UnusedStructField { struct_name, field_name } => {
+                Diagnostic::simple_warning(
+                    format!("field {field_name} of struct {struct_name} is never read"),
+                    "unused field".to_string(),
+                    field_name.span(),
+                )
+            }
+            ResolverError::UnusedTraitImpl { trait_name, typ } => Diagnostic::simple_warning(
+                format!("impl of {trait_name} for {typ} is never used"),
+                format!("{typ} is never used so this impl is never used either"),
+                trait_name.span(),
+            ),
             ResolverError::VariableNotDeclared { name, span } => Diagnostic::simple_error(
                 format!("cannot find `{name}` in this scope "),
                 "not found in this scope".to_string(),
@@ -239,11 +312,6 @@ impl From<ResolverError> for Diagnostic {
                 "Mutable variable".into(),
                 span,
             ),
-            ResolverError::TestFunctionHasParameters { span } => Diagnostic::simple_error(
-                "Test functions cannot have any parameters".into(),
-                "Try removing the parameters or moving the test into a wrapper function".into(),
-                span,
-            ),
             ResolverError::NonStructUsedInConstructor { typ, span } => Diagnostic::simple_error(
                 "Only struct types can be used in constructor expressions".into(),
                 format!("{typ} has no fields to construct it with"),
@@ -304,6 +372,12 @@ impl From<ResolverError> for Diagnostic {
             ResolverError::InvalidTypeForEntryPoint { span } => Diagnostic::simple_error(
                 "Only sized types may be used in the entry point to a program".to_string(),
                 "Slices, references, or any type containing them may not be used in main or a contract function".to_string(), span),
-        }
+            ResolverError::ComptimeFunctionCalledAtRuntime { name, span } => Diagnostic::simple_error(
+                format!("Comptime function `{name}` called outside of a comptime context"),
+                "Comptime functions may only be called from comptime blocks or other comptime functions".to_string(), span),
+            ResolverError::ComptimeError(error) => error.into(),
+        };
+        diagnostic.code = Some(code);
+        diagnostic
     }
 }
@@ -25,12 +25,13 @@ pub(crate) fn resolve_function_set(
     self_type: Option<Type>,
     trait_impl_id: Option<TraitImplId>,
     impl_generics: Vec<(Rc<String>, TypeVariable, Span)>,
+    self_associated_types: Vec<(String, Type)>,
     errors: &mut Vec<(CompilationError, FileId)>,
 ) -> Vec<(FileId, FuncId)> {
     let file_id = unresolved_functions.file_id;
 
     let where_clause_errors =
-        unresolved_functions.resolve_trait_bounds_trait_ids(def_maps, crate_id);
+        unresolved_functions.resolve_trait_bounds_trait_ids(interner, def_maps, crate_id);
     errors.extend(where_clause_errors.iter().cloned().map(|e| (e.into(), file_id)));
 
     vecmap(unresolved_functions.functions, |(mod_id, func_id, func)| {
@@ -43,6 +44,7 @@ pub(crate) fn resolve_function_set(
         // TypeVariables for the same generic, causing it to instantiate incorrectly.
         resolver.set_generics(impl_generics.clone());
         resolver.set_self_type(self_type.clone());
+        resolver.set_self_associated_types(self_associated_types.clone());
         resolver.set_trait_id(unresolved_functions.trait_id);
         resolver.set_trait_impl_id(trait_impl_id);
 
@@ -78,6 +80,7 @@ pub(crate) fn resolve_free_functions(
                 self_type.clone(),
                 None,
                 vec![], // no impl generics
+                vec![], // no associated types
                 errors,
             )
         })
@@ -1,11 +1,13 @@
 use iter_extended::partition_results;
-use noirc_errors::{CustomDiagnostic, Span};
+use noirc_errors::CustomDiagnostic;
 
 use crate::graph::CrateId;
 use std::collections::BTreeMap;
 
 use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId, PerNs};
-use crate::{Ident, Path, PathKind};
+use crate::{Ident, Path};
+
+use super::visibility::can_reference_module_id;
 
 #[derive(Debug, Clone)]
 pub struct ImportDirective {
@@ -13,14 +15,32 @@ pub struct ImportDirective {
     pub path: Path,
     pub alias: Option<Ident>,
     pub is_prelude: bool,
+    /// True if the import is marked `#[allow(dead_code)]`, so it is never reported as unused
+    pub allow_dead_code: bool,
+}
+
+struct NamespaceResolution {
+    namespace: PerNs,
+    error: Option<PathResolutionError>,
+}
+
+type NamespaceResolutionResult = Result<NamespaceResolution, PathResolutionError>;
+
+/// The result of successfully resolving a path. A path to an item which is not visible
+/// from the current module still resolves, but carries an `error` to be reported as a warning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathResolution {
+    pub module_def_id: ModuleDefId,
+    pub error: Option<PathResolutionError>,
 }
 
-pub type PathResolution = Result<PerNs, PathResolutionError>;
+pub type PathResolutionResult = Result<PathResolution, PathResolutionError>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PathResolutionError {
     Unresolved(Ident),
     ExternalContractUsed(Ident),
+    Private(Ident),
 }
 
 #[derive(Debug)]
@@ -32,11 +52,26 @@ pub struct ResolvedImport {
     // The module which we must add the resolved namespace to
     pub module_scope: LocalModuleId,
     pub is_prelude: bool,
+    pub allow_dead_code: bool,
+    // Any warning (such as a private item being imported) found while resolving the import
+    pub error: Option<PathResolutionError>,
+}
+
+impl PathResolutionError {
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PathResolutionError::Unresolved(_) => "E0601",
+            PathResolutionError::ExternalContractUsed(_) => "E0602",
+            PathResolutionError::Private(_) => "E0603",
+        }
+    }
 }
 
 impl From<PathResolutionError> for CustomDiagnostic {
     fn from(error: PathResolutionError) -> Self {
-        match error {
+        let code = error.code();
+        let mut diagnostic = match error {
             PathResolutionError::Unresolved(ident) => CustomDiagnostic::simple_error(
                 format!("Could not resolve '{ident}' in path"),
                 String::new(),
@@ -47,7 +82,15 @@ impl From<PathResolutionError> for CustomDiagnostic {
                 "Contracts may only be referenced from within a contract".to_string(),
                 ident.span(),
             ),
-        }
+            // This will be upgraded to an error in future versions
+            PathResolutionError::Private(ident) => CustomDiagnostic::simple_warning(
+                format!("{ident} is private and not visible from the current module"),
+                format!("{ident} is private"),
+                ident.span(),
+            ),
+        };
+        diagnostic.code = Some(code);
+        diagnostic
     }
 }
 
@@ -56,15 +99,13 @@ pub fn resolve_imports(
     imports_to_resolve: Vec<ImportDirective>,
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
 ) -> (Vec<ResolvedImport>, Vec<(PathResolutionError, LocalModuleId)>) {
-    let def_map = &def_maps[&crate_id];
-
     partition_results(imports_to_resolve, |import_directive| {
         let allow_contracts =
             allow_referencing_contracts(def_maps, crate_id, import_directive.module_id);
 
         let module_scope = import_directive.module_id;
-        let resolved_namespace =
-            resolve_path_to_ns(&import_directive, def_map, def_maps, allow_contracts)
+        let (resolved_namespace, error) =
+            resolve_import(crate_id, &import_directive, def_maps, allow_contracts)
                 .map_err(|error| (error, module_scope))?;
 
         let name = resolve_path_name(&import_directive);
@@ -73,10 +114,26 @@ pub fn resolve_imports(
             resolved_namespace,
             module_scope,
             is_prelude: import_directive.is_prelude,
+            allow_dead_code: import_directive.allow_dead_code,
+            error,
         })
     })
 }
 
+/// Resolves a single import directive from `crate_id`, returning the namespace it refers to
+/// along with any visibility warning.
+pub(super) fn resolve_import(
+    crate_id: CrateId,
+    import_directive: &ImportDirective,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+    allow_contracts: bool,
+) -> Result<(PerNs, Option<PathResolutionError>), PathResolutionError> {
+    let def_map = &def_maps[&crate_id];
+    let resolution =
+        resolve_path_to_ns(import_directive, crate_id, def_map, def_maps, allow_contracts)?;
+    Ok((resolution.namespace, resolution.error))
+}
+
 pub(super) fn allow_referencing_contracts(
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     krate: CrateId,
@@ -85,26 +142,43 @@ pub(super) fn allow_referencing_contracts(
     ModuleId { krate, local_id }.module(def_maps).is_contract
 }
 
-pub fn resolve_path_to_ns(
+/// Resolves the path of `import_directive` to a namespace. `importing_crate` is the crate
+/// containing `import_directive.module_id`, which is the module that visibility is checked from.
+/// For paths into dependencies, `def_map` may differ from the importing crate's def map.
+fn resolve_path_to_ns(
     import_directive: &ImportDirective,
+    importing_crate: CrateId,
     def_map: &CrateDefMap,
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     allow_contracts: bool,
-) -> PathResolution {
+) -> NamespaceResolutionResult {
     let import_path = &import_directive.path.segments;
 
     match import_directive.path.kind {
         crate::ast::PathKind::Crate => {
             // Resolve from the root of the crate
-            resolve_path_from_crate_root(def_map, import_path, def_maps, allow_contracts)
-        }
-        crate::ast::PathKind::Dep => {
-            resolve_external_dep(def_map, import_directive, def_maps, allow_contracts)
+            resolve_path_from_crate_root(
+                importing_crate,
+                import_directive.module_id,
+                def_map,
+                import_path,
+                def_maps,
+                allow_contracts,
+            )
         }
+        crate::ast::PathKind::Dep => resolve_external_dep(
+            importing_crate,
+            def_map,
+            import_directive,
+            def_maps,
+            allow_contracts,
+        ),
         crate::ast::PathKind::Plain => {
             // Plain paths are only used to import children modules. It's possible to allow import of external deps, but maybe this distinction is better?
             // In Rust they can also point to external Dependencies, if no children can be found with the specified name
             resolve_name_in_module(
+                importing_crate,
+                import_directive.module_id,
                 def_map,
                 import_path,
                 import_directive.module_id,
@@ -116,28 +190,43 @@ pub fn resolve_path_to_ns(
 }
 
 fn resolve_path_from_crate_root(
+    importing_crate: CrateId,
+    importing_module: LocalModuleId,
     def_map: &CrateDefMap,
     import_path: &[Ident],
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     allow_contracts: bool,
-) -> PathResolution {
-    resolve_name_in_module(def_map, import_path, def_map.root, def_maps, allow_contracts)
+) -> NamespaceResolutionResult {
+    resolve_name_in_module(
+        importing_crate,
+        importing_module,
+        def_map,
+        import_path,
+        def_map.root,
+        def_maps,
+        allow_contracts,
+    )
 }
 
 fn resolve_name_in_module(
+    importing_crate: CrateId,
+    importing_module: LocalModuleId,
     def_map: &CrateDefMap,
     import_path: &[Ident],
     starting_mod: LocalModuleId,
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     allow_contracts: bool,
-) -> PathResolution {
+) -> NamespaceResolutionResult {
+    let mut current_mod_id = ModuleId { krate: def_map.krate, local_id: starting_mod };
     let mut current_mod = &def_map.modules[starting_mod.0];
 
     // There is a possibility that the import path is empty
     // In that case, early return
     if import_path.is_empty() {
-        let mod_id = ModuleId { krate: def_map.krate, local_id: starting_mod };
-        return Ok(PerNs::types(mod_id.into()));
+        return Ok(NamespaceResolution {
+            namespace: PerNs::types(current_mod_id.into()),
+            error: None,
+        });
     }
 
     let mut import_path = import_path.iter();
@@ -147,6 +236,25 @@ fn resolve_name_in_module(
         return Err(PathResolutionError::Unresolved(first_segment.clone()));
     }
 
+    let mut warning = None;
+    let mut check_visibility = |ns: &PerNs, module: ModuleId, segment: &Ident| {
+        let visible = ns.iter_items().all(|(id, visibility, _)| {
+            // Function visibility is checked by the resolver where the function is referenced
+            matches!(id, ModuleDefId::FunctionId(_))
+                || can_reference_module_id(
+                    def_maps,
+                    importing_crate,
+                    importing_module,
+                    module,
+                    visibility,
+                )
+        });
+        if !visible && warning.is_none() {
+            warning = Some(PathResolutionError::Private(segment.clone()));
+        }
+    };
+    check_visibility(&current_ns, current_mod_id, first_segment);
+
     for segment in import_path {
         let typ = match current_ns.take_types() {
             None => return Err(PathResolutionError::Unresolved(segment.clone())),
@@ -164,6 +272,7 @@ fn resolve_name_in_module(
             ModuleDefId::GlobalId(_) => panic!("globals cannot be in the type namespace"),
         };
 
+        current_mod_id = new_module_id;
         current_mod = &def_maps[&new_module_id.krate].modules[new_module_id.local_id.0];
 
         // Check if namespace
@@ -176,10 +285,11 @@ fn resolve_name_in_module(
         if current_mod.is_contract && !allow_contracts {
             return Err(PathResolutionError::ExternalContractUsed(segment.clone()));
         }
+        check_visibility(&found_ns, current_mod_id, segment);
         current_ns = found_ns;
     }
 
-    Ok(current_ns)
+    Ok(NamespaceResolution { namespace: current_ns, error: warning })
 }
 
 fn resolve_path_name(import_directive: &ImportDirective) -> Ident {
@@ -190,11 +300,12 @@ fn resolve_path_name(import_directive: &ImportDirective) -> Ident {
 }
 
 fn resolve_external_dep(
+    importing_crate: CrateId,
     current_def_map: &CrateDefMap,
     directive: &ImportDirective,
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     allow_contracts: bool,
-) -> PathResolution {
+) -> NamespaceResolutionResult {
     // Use extern_prelude to get the dep
     //
     let path = &directive.path.segments;
@@ -209,19 +320,17 @@ fn resolve_external_dep(
     // Create an import directive for the dependency crate
     let path_without_crate_name = &path[1..]; // XXX: This will panic if the path is of the form `use dep::std` Ideal algorithm will not distinguish between crate and module
 
-    let path = Path {
-        segments: path_without_crate_name.to_vec(),
-        kind: PathKind::Plain,
-        span: Span::default(),
-    };
-    let dep_directive = ImportDirective {
-        module_id: dep_module.local_id,
-        path,
-        alias: directive.alias.clone(),
-        is_prelude: false,
-    };
-
     let dep_def_map = def_maps.get(&dep_module.krate).unwrap();
 
-    resolve_path_to_ns(&dep_directive, dep_def_map, def_maps, allow_contracts)
+    // Resolve the rest of the path from the root of the dependency, while still checking
+    // visibility relative to the module containing the original path.
+    resolve_name_in_module(
+        importing_crate,
+        directive.module_id,
+        dep_def_map,
+        path_without_crate_name,
+        dep_module.local_id,
+        def_maps,
+        allow_contracts,
+    )
 }
@@ -5,10 +5,12 @@
 //! redefined multiple times in the same scope. Once this is validated, it is linked
 //! to that definition via a matching DefinitionId. All references to the same definition
 //! will have the same DefinitionId.
+pub(crate) mod dead_code;
 pub mod errors;
 pub mod import;
 pub mod path_resolver;
 pub mod resolver;
+pub(crate) mod visibility;
 
 mod functions;
 mod globals;
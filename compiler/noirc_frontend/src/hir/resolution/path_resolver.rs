@@ -1,5 +1,6 @@
 use super::import::{
-    allow_referencing_contracts, resolve_path_to_ns, ImportDirective, PathResolutionError,
+    allow_referencing_contracts, resolve_import, ImportDirective, PathResolution,
+    PathResolutionResult,
 };
 use crate::Path;
 use std::collections::BTreeMap;
@@ -9,11 +10,13 @@ use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId};
 
 pub trait PathResolver {
     /// Resolve the given path returning the resolved ModuleDefId.
+    /// If the path resolves to an item which is not visible from the current module,
+    /// the returned PathResolution will also contain an error to be reported as a warning.
     fn resolve(
         &self,
         def_maps: &BTreeMap<CrateId, CrateDefMap>,
         path: Path,
-    ) -> Result<ModuleDefId, PathResolutionError>;
+    ) -> PathResolutionResult;
 
     fn local_module_id(&self) -> LocalModuleId;
 
@@ -36,7 +39,7 @@ impl PathResolver for StandardPathResolver {
         &self,
         def_maps: &BTreeMap<CrateId, CrateDefMap>,
         path: Path,
-    ) -> Result<ModuleDefId, PathResolutionError> {
+    ) -> PathResolutionResult {
         resolve_path(def_maps, self.module_id, path)
     }
 
@@ -55,17 +58,23 @@ pub fn resolve_path(
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     module_id: ModuleId,
     path: Path,
-) -> Result<ModuleDefId, PathResolutionError> {
+) -> PathResolutionResult {
     // lets package up the path into an ImportDirective and resolve it using that
-    let import =
-        ImportDirective { module_id: module_id.local_id, path, alias: None, is_prelude: false };
+    let import = ImportDirective {
+        module_id: module_id.local_id,
+        path,
+        alias: None,
+        is_prelude: false,
+        allow_dead_code: false,
+    };
     let allow_referencing_contracts =
         allow_referencing_contracts(def_maps, module_id.krate, module_id.local_id);
 
-    let def_map = &def_maps[&module_id.krate];
-    let ns = resolve_path_to_ns(&import, def_map, def_maps, allow_referencing_contracts)?;
+    let (ns, error) =
+        resolve_import(module_id.krate, &import, def_maps, allow_referencing_contracts)?;
 
     let function = ns.values.map(|(id, _, _)| id);
     let id = function.or_else(|| ns.types.map(|(id, _, _)| id));
-    Ok(id.expect("Found empty namespace"))
+    let module_def_id: ModuleDefId = id.expect("Found empty namespace");
+    Ok(PathResolution { module_def_id, error })
 }
@@ -19,13 +19,14 @@ use crate::hir_def::expr::{
 };
 
 use crate::hir_def::traits::{Trait, TraitConstraint};
-use crate::token::{Attributes, FunctionAttribute};
+use crate::token::Attributes;
 use regex::Regex;
 use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
 
 use crate::graph::CrateId;
-use crate::hir::def_map::{LocalModuleId, ModuleDefId, TryFromModuleDefId, MAIN_FUNCTION};
+use crate::hir::comptime::Interpreter;
+use crate::hir::def_map::{ModuleDefId, TryFromModuleDefId, MAIN_FUNCTION};
 use crate::hir_def::stmt::{HirAssignStatement, HirForStatement, HirLValue, HirPattern};
 use crate::node_interner::{
     DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId,
@@ -38,9 +39,9 @@ use crate::{
 };
 use crate::{
     ArrayLiteral, ContractFunctionType, Distinctness, ForRange, FunctionDefinition,
-    FunctionReturnType, FunctionVisibility, Generics, LValue, NoirStruct, NoirTypeAlias, Param,
-    Path, PathKind, Pattern, Shared, StructType, Type, TypeAliasType, TypeBinding, TypeVariable,
-    UnaryOp, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData,
+    FunctionReturnType, Generics, ItemVisibility, LValue, NoirStruct, NoirTypeAlias, Param, Path,
+    PathKind, Pattern, Shared, StructType, Type, TypeAliasType, TypeBinding, TypeVariable, UnaryOp,
+    UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData,
     UnresolvedTypeExpression, Visibility, ERROR_IDENT,
 };
 use fm::FileId;
@@ -56,6 +57,8 @@ use crate::hir_def::{
 };
 
 use super::errors::{PubPosition, ResolverError};
+use super::import::PathResolution;
+use super::visibility::can_reference_module_id;
 
 const SELF_TYPE_NAME: &str = "Self";
 
@@ -89,6 +92,11 @@ pub struct Resolver<'a> {
     /// Set to the current type if we're resolving an impl
     self_type: Option<Type>,
 
+    /// The associated types and constants that `Self::Name` refers to if we're resolving
+    /// a trait or a trait impl. Associated constants are represented by the numeric type
+    /// of their value, so that they may be used as array lengths.
+    self_associated_types: Vec<(String, Type)>,
+
     /// If we're currently resolving methods within a trait impl, this will be set
     /// to the corresponding trait impl ID.
     current_trait_impl: Option<TraitImplId>,
@@ -111,6 +119,10 @@ pub struct Resolver<'a> {
     /// that are captured. We do this in order to create the hidden environment
     /// parameter for the lambda function.
     lambda_stack: Vec<LambdaContext>,
+
+    /// True while resolving a `comptime` block or the body of a `comptime fn`.
+    /// Only code in these contexts may call comptime functions.
+    in_comptime_context: bool,
 }
 
 /// ResolverMetas are tagged onto each definition to track how many times they are used
@@ -144,12 +156,14 @@ impl<'a> Resolver<'a> {
             scopes: ScopeForest::default(),
             interner,
             self_type: None,
+            self_associated_types: Vec::new(),
             generics: Vec::new(),
             errors: Vec::new(),
             lambda_stack: Vec::new(),
             current_trait_impl: None,
             file,
             in_contract,
+            in_comptime_context: false,
         }
     }
 
@@ -157,6 +171,10 @@ impl<'a> Resolver<'a> {
         self.self_type = self_type;
     }
 
+    pub fn set_self_associated_types(&mut self, associated_types: Vec<(String, Type)>) {
+        self.self_associated_types = associated_types;
+    }
+
     pub fn set_trait_id(&mut self, trait_id: Option<TraitId>) {
         self.trait_id = trait_id;
     }
@@ -189,7 +207,9 @@ impl<'a> Resolver<'a> {
         self.resolve_local_globals();
 
         self.add_generics(&func.def.generics);
+        self.add_associated_generics(&func.def.where_clause);
         self.trait_bounds = func.def.where_clause.clone();
+        self.in_comptime_context = func.def.is_comptime;
 
         let (hir_func, func_meta) = self.intern_function(func, func_id);
         let func_scope_tree = self.scopes.end_function();
@@ -222,8 +242,9 @@ impl<'a> Resolver<'a> {
             is_open: false,
             is_internal: false,
             is_unconstrained: false,
-            visibility: FunctionVisibility::Public, // Trait functions are always public
-            generics: Vec::new(),                   // self.generics should already be set
+            is_comptime: false,
+            visibility: ItemVisibility::Public, // Trait functions are always public
+            generics: Vec::new(),               // self.generics should already be set
             parameters: vecmap(parameters, |(name, typ)| Param {
                 visibility: Visibility::Private,
                 pattern: Pattern::Identifier(name.clone()),
@@ -265,7 +286,12 @@ impl<'a> Resolver<'a> {
     fn check_for_unused_variables_in_local_scope(decl_map: Scope, unused_vars: &mut Vec<HirIdent>) {
         let unused_variables = decl_map.filter(|(variable_name, metadata)| {
             let has_underscore_prefix = variable_name.starts_with('_'); // XXX: This is used for development mode, and will be removed
-            metadata.warn_if_unused && metadata.num_times_used == 0 && !has_underscore_prefix
+                                                                        // Like in Rust, a method doesn't have to use `self`
+            let is_self = variable_name == "self";
+            metadata.warn_if_unused
+                && metadata.num_times_used == 0
+                && !has_underscore_prefix
+                && !is_self
         });
         unused_vars.extend(unused_variables.map(|(_, meta)| meta.ident));
     }
@@ -418,9 +444,10 @@ impl<'a> Resolver<'a> {
         &mut self,
         constraint: UnresolvedTraitConstraint,
     ) -> Option<TraitConstraint> {
-        let typ = self.resolve_type(constraint.typ);
+        let typ = self.resolve_type(constraint.typ.clone());
         let trait_id = self.lookup_trait_or_error(constraint.trait_bound.trait_path)?.id;
-        Some(TraitConstraint { typ, trait_id })
+        let associated_types = self.find_associated_generics(&constraint.typ, trait_id);
+        Some(TraitConstraint { typ, trait_id, associated_types })
     }
 
     /// Translates an UnresolvedType into a Type and appends any
@@ -602,15 +629,100 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        if let Some(typ) = self.lookup_associated_type(path) {
+            return Some(typ);
+        }
+
         // If we cannot find a local generic of the same name, try to look up a global
         match self.path_resolver.resolve(self.def_maps, path.clone()) {
-            Ok(ModuleDefId::GlobalId(id)) => {
+            Ok(PathResolution { module_def_id: ModuleDefId::GlobalId(id), error }) => {
+                if let Some(error) = error {
+                    self.push_err(ResolverError::PathResolutionError(error));
+                }
+                self.interner.add_path_reference(self.path_resolver.module_id(), path);
+                self.interner.add_global_used_in_type(id);
                 Some(Type::Constant(self.eval_global_as_array_length(id)))
             }
             _ => None,
         }
     }
 
+    /// Resolves `Self::Name` to an associated type or constant of the trait or trait impl being
+    /// resolved, and `T::Name` to the implicit generic for it when there is a `T: Trait` constraint.
+    fn lookup_associated_type(&self, path: &Path) -> Option<Type> {
+        if path.kind != PathKind::Plain || path.segments.len() != 2 {
+            return None;
+        }
+
+        let object = &path.segments[0].0.contents;
+        let item_name = &path.segments[1].0.contents;
+
+        if object == SELF_TYPE_NAME {
+            let (_, typ) = self.self_associated_types.iter().find(|(name, _)| name == item_name)?;
+            return Some(typ.clone());
+        }
+
+        let (name, type_var, _) = self.find_generic(&format!("{object}::{item_name}"))?;
+        Some(Type::NamedGeneric(type_var.clone(), name.clone()))
+    }
+
+    /// True if `path` is `Self::N` or `T::N` where `N` is an associated constant of the current
+    /// trait or of a trait in the current where clause.
+    fn lookup_associated_constant(&self, path: &Path) -> Option<Type> {
+        if self.is_associated_constant(path) {
+            self.lookup_associated_type(path)
+        } else {
+            None
+        }
+    }
+
+    fn is_associated_constant(&self, path: &Path) -> bool {
+        if path.kind != PathKind::Plain || path.segments.len() != 2 {
+            return false;
+        }
+
+        let object = &path.segments[0];
+        let item_name = &path.segments[1].0.contents;
+        let has_constant = |trait_id| {
+            self.interner.try_get_trait(trait_id).map_or(false, |the_trait| {
+                the_trait.constants.iter().any(|constant| &constant.name.0.contents == item_name)
+            })
+        };
+
+        if object.0.contents == SELF_TYPE_NAME {
+            return self.trait_id.map_or(false, has_constant);
+        }
+
+        self.trait_bounds.iter().any(|constraint| {
+            let constrains_object = matches!(
+                &constraint.typ.typ,
+                UnresolvedTypeData::Named(path, _) if path.segments.len() == 1 && path.last_segment() == *object
+            );
+            constrains_object && constraint.trait_bound.trait_id.map_or(false, has_constant)
+        })
+    }
+
+    /// Resolves a reference to an associated constant in an expression. Constants of the
+    /// current trait impl are replaced with their value, while constants of a generic type
+    /// refer to the numeric generic standing for them.
+    fn resolve_associated_constant(&mut self, path: Path, typ: Type) -> HirExpression {
+        match typ {
+            Type::Constant(value) => {
+                HirExpression::Literal(HirLiteral::Integer(u128::from(value).into(), false))
+            }
+            Type::NamedGeneric(type_var, name) => {
+                let location = Location::new(path.span(), self.file);
+                let definition = DefinitionKind::GenericType(type_var);
+                let id =
+                    self.interner.push_definition(name.to_string(), false, definition, location);
+                let typ = Type::polymorphic_integer(self.interner);
+                self.interner.push_definition_type(id, typ);
+                HirExpression::Ident(HirIdent { location, id })
+            }
+            _ => HirExpression::Error,
+        }
+    }
+
     fn resolve_array_size(
         &mut self,
         length: Option<UnresolvedTypeExpression>,
@@ -753,13 +865,15 @@ impl<'a> Resolver<'a> {
     pub fn resolve_struct_fields(
         mut self,
         unresolved: NoirStruct,
-    ) -> (Generics, Vec<(Ident, Type)>, Vec<ResolverError>) {
+    ) -> (Generics, Vec<(ItemVisibility, Ident, Type)>, Vec<ResolverError>) {
         let generics = self.add_generics(&unresolved.generics);
 
         // Check whether the struct definition has globals in the local module and add them to the scope
         self.resolve_local_globals();
 
-        let fields = vecmap(unresolved.fields, |(ident, typ)| (ident, self.resolve_type(typ)));
+        let fields = vecmap(unresolved.fields, |field| {
+            (field.visibility, field.name, self.resolve_type(field.typ))
+        });
 
         (generics, fields, self.errors)
     }
@@ -780,12 +894,87 @@ impl<'a> Resolver<'a> {
         &mut self,
         where_clause: &Vec<UnresolvedTraitConstraint>,
     ) -> Vec<TraitConstraint> {
-        vecmap(where_clause, |constraint| TraitConstraint {
-            typ: self.resolve_type(constraint.typ.clone()),
-            trait_id: constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id),
+        vecmap(where_clause, |constraint| {
+            let trait_id = constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id);
+            TraitConstraint {
+                typ: self.resolve_type(constraint.typ.clone()),
+                trait_id,
+                associated_types: self.find_associated_generics(&constraint.typ, trait_id),
+            }
         })
     }
 
+    /// Adds an implicit generic for each associated type and constant of the traits in the
+    /// given where clause, so that e.g. `T::Item` may be used when `T: Iterator`. These generics
+    /// are bound to the types given by the selected impl when the constraint is verified.
+    pub fn add_associated_generics(&mut self, where_clause: &[UnresolvedTraitConstraint]) {
+        for constraint in where_clause {
+            let generic = match &constraint.typ.typ {
+                UnresolvedTypeData::Named(path, args)
+                    if path.segments.len() == 1 && args.is_empty() =>
+                {
+                    path.last_segment()
+                }
+                _ => continue,
+            };
+
+            if self.find_generic(&generic.0.contents).is_none() {
+                continue;
+            }
+
+            let trait_id = match constraint.trait_bound.trait_id {
+                Some(trait_id) => trait_id,
+                None => match self.lookup(constraint.trait_bound.trait_path.clone()) {
+                    Ok(trait_id) => trait_id,
+                    Err(_) => continue,
+                },
+            };
+
+            let item_names = match self.interner.try_get_trait(trait_id) {
+                Some(the_trait) => vecmap(the_trait.associated_items(), |(name, ..)| name.clone()),
+                None => continue,
+            };
+
+            for item_name in item_names {
+                let name = format!("{generic}::{item_name}");
+                if self.find_generic(&name).is_none() {
+                    let id = self.interner.next_type_variable_id();
+                    let type_var = TypeVariable::unbound(id);
+                    self.generics.push((Rc::new(name), type_var, item_name.span()));
+                }
+            }
+        }
+    }
+
+    /// Returns the implicit generics added by `add_associated_generics` for the associated
+    /// types and constants of `typ: trait_id`, along with the name of each associated item.
+    fn find_associated_generics(
+        &self,
+        typ: &UnresolvedType,
+        trait_id: TraitId,
+    ) -> Vec<(String, Type)> {
+        let generic = match &typ.typ {
+            UnresolvedTypeData::Named(path, args)
+                if path.segments.len() == 1 && args.is_empty() =>
+            {
+                path.last_segment()
+            }
+            _ => return Vec::new(),
+        };
+
+        let the_trait = match self.interner.try_get_trait(trait_id) {
+            Some(the_trait) => the_trait,
+            None => return Vec::new(),
+        };
+
+        let items = the_trait.associated_items().filter_map(|(item_name, ..)| {
+            let (name, type_var, _) = self.find_generic(&format!("{generic}::{item_name}"))?;
+            let typ = Type::NamedGeneric(type_var.clone(), name.clone());
+            Some((item_name.0.contents.clone(), typ))
+        });
+        items.collect()
+    }
+
     /// Extract metadata from a NoirFunction
     /// to be used in analysis and intern the function parameters
     /// Prerequisite: self.add_generics() has already been called with the given
@@ -816,7 +1005,9 @@ impl<'a> Resolver<'a> {
                 });
             }
 
-            if self.is_entry_point_function(func) {
+            // Test functions with parameters are run against inputs generated from their ABI,
+            // so their parameters are restricted in the same way as those of entry points.
+            if self.is_entry_point_function(func) || attributes.is_test_function() {
                 self.verify_type_valid_for_program_input(&typ);
             }
 
@@ -852,14 +1043,6 @@ impl<'a> Resolver<'a> {
             self.push_err(ResolverError::DistinctNotAllowed { ident: func.name_ident().clone() });
         }
 
-        if matches!(attributes.function, Some(FunctionAttribute::Test { .. }))
-            && !parameters.is_empty()
-        {
-            self.push_err(ResolverError::TestFunctionHasParameters {
-                span: func.name_ident().span(),
-            });
-        }
-
         let mut typ = Type::Function(parameter_types, return_type, Box::new(Type::Unit));
 
         if !generics.is_empty() {
@@ -1141,53 +1324,33 @@ impl<'a> Resolver<'a> {
         &mut self,
         func: FuncId,
         span: Span,
-        visibility: FunctionVisibility,
+        visibility: ItemVisibility,
     ) {
         let function_module = self.interner.function_module(func);
         let current_module = self.path_resolver.module_id();
 
-        let same_crate = function_module.krate == current_module.krate;
-        let krate = function_module.krate;
-        let current_module = current_module.local_id;
+        if can_reference_module_id(
+            self.def_maps,
+            current_module.krate,
+            current_module.local_id,
+            function_module,
+            visibility,
+        ) {
+            return;
+        }
+
         let name = self.interner.function_name(&func).to_string();
         match visibility {
-            FunctionVisibility::Public => (),
-            FunctionVisibility::Private => {
-                if !same_crate
-                    || !self.module_descendent_of_target(
-                        krate,
-                        function_module.local_id,
-                        current_module,
-                    )
-                {
-                    self.errors.push(ResolverError::PrivateFunctionCalled { span, name });
-                }
+            ItemVisibility::Public => (),
+            ItemVisibility::Private => {
+                self.errors.push(ResolverError::PrivateFunctionCalled { span, name });
             }
-            FunctionVisibility::PublicCrate => {
-                if !same_crate {
-                    self.errors.push(ResolverError::NonCrateFunctionCalled { span, name });
-                }
+            ItemVisibility::PublicCrate => {
+                self.errors.push(ResolverError::NonCrateFunctionCalled { span, name });
             }
         }
     }
 
-    // Returns true if `current` is a (potentially nested) child module of `target`.
-    // This is also true if `current == target`.
-    fn module_descendent_of_target(
-        &self,
-        krate: CrateId,
-        target: LocalModuleId,
-        current: LocalModuleId,
-    ) -> bool {
-        if current == target {
-            return true;
-        }
-
-        self.def_maps[&krate].modules[current.0]
-            .parent
-            .map_or(false, |parent| self.module_descendent_of_target(krate, target, parent))
-    }
-
     fn resolve_local_variable(&mut self, hir_ident: HirIdent, var_scope_index: usize) {
         let mut transitive_capture_index: Option<usize> = None;
 
@@ -1250,7 +1413,10 @@ impl<'a> Resolver<'a> {
                 Literal::Unit => HirLiteral::Unit,
             }),
             ExpressionKind::Variable(path) => {
-                if let Some((hir_expr, object_type)) = self.resolve_trait_generic_path(&path) {
+                if let Some(typ) = self.lookup_associated_constant(&path) {
+                    self.resolve_associated_constant(path, typ)
+                } else if let Some((hir_expr, object_type)) = self.resolve_trait_generic_path(&path)
+                {
                     let expr_id = self.interner.push_expr(hir_expr);
                     self.interner.push_expr_location(expr_id, expr.span, self.file);
                     self.interner
@@ -1266,9 +1432,7 @@ impl<'a> Resolver<'a> {
                     if hir_ident.id != DefinitionId::dummy_id() {
                         match self.interner.definition(hir_ident.id).kind {
                             DefinitionKind::Function(id) => {
-                                if self.interner.function_visibility(id)
-                                    != FunctionVisibility::Public
-                                {
+                                if self.interner.function_visibility(id) != ItemVisibility::Public {
                                     let span = hir_ident.location.span;
                                     self.check_can_reference_function(
                                         id,
@@ -1276,6 +1440,16 @@ impl<'a> Resolver<'a> {
                                         self.interner.function_visibility(id),
                                     );
                                 }
+                                if self.interner.function_modifiers(&id).is_comptime
+                                    && !self.in_comptime_context
+                                {
+                                    let name = self.interner.function_name(&id).to_owned();
+                                    let span = hir_ident.location.span;
+                                    self.push_err(ResolverError::ComptimeFunctionCalledAtRuntime {
+                                        name,
+                                        span,
+                                    });
+                                }
                             }
                             DefinitionKind::Global(_) => {}
                             DefinitionKind::GenericType(_) => {
@@ -1415,6 +1589,15 @@ impl<'a> Resolver<'a> {
                 })
             }),
             ExpressionKind::Parenthesized(sub_expr) => return self.resolve_expression(*sub_expr),
+            ExpressionKind::Comptime(block_expr) => {
+                let block = Expression::new(ExpressionKind::Block(block_expr), expr.span);
+                let was_in_comptime_context =
+                    std::mem::replace(&mut self.in_comptime_context, true);
+                let expr_id = self.resolve_expression(block);
+                self.in_comptime_context = was_in_comptime_context;
+                self.interner.push_comptime_expression(expr_id);
+                return expr_id;
+            }
         };
 
         // If these lines are ever changed, make sure to change the early return
@@ -1621,7 +1804,18 @@ impl<'a> Resolver<'a> {
     }
 
     fn lookup_type_alias(&mut self, path: Path) -> Option<&TypeAliasType> {
-        self.lookup(path).ok().map(|id| self.interner.get_type_alias(id))
+        // Only report path errors if this is a type alias: otherwise the same path is
+        // resolved again as a struct and any error would be issued twice.
+        match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(PathResolution { module_def_id: ModuleDefId::TypeAliasId(id), error }) => {
+                if let Some(error) = error {
+                    self.push_err(ResolverError::PathResolutionError(error));
+                }
+                self.interner.add_path_reference(self.path_resolver.module_id(), &path);
+                Some(self.interner.get_type_alias(id))
+            }
+            _ => None,
+        }
     }
 
     // this resolves Self::some_static_method, inside an impl block (where we don't have a concrete self_type)
@@ -1645,21 +1839,33 @@ impl<'a> Resolver<'a> {
         None
     }
 
-    // this resolves TraitName::some_static_method
+    // this resolves TraitName::some_static_method, where the trait may be given by any path,
+    // e.g. `dep::std::ops::Eq::eq`
     fn resolve_trait_static_method(&mut self, path: &Path) -> Option<(HirExpression, Type)> {
-        if path.kind == PathKind::Plain && path.segments.len() == 2 {
-            let method = &path.segments[1];
+        if path.segments.len() < 2 {
+            return None;
+        }
 
-            let mut trait_path = path.clone();
-            trait_path.pop();
-            let trait_id = self.lookup(trait_path).ok()?;
-            let the_trait = self.interner.get_trait(trait_id);
+        let mut trait_path = path.clone();
+        let method = trait_path.pop();
 
-            let method = the_trait.find_method(method.0.contents.as_str())?;
-            let self_type = Type::type_variable(the_trait.self_type_typevar_id);
-            return Some((HirExpression::TraitMethodReference(method), self_type));
-        }
-        None
+        // Only report path errors if this is a trait: otherwise the full path is
+        // resolved again afterward and any error would be issued twice.
+        let trait_id = match self.path_resolver.resolve(self.def_maps, trait_path.clone()) {
+            Ok(PathResolution { module_def_id: ModuleDefId::TraitId(trait_id), error }) => {
+                if let Some(error) = error {
+                    self.push_err(ResolverError::PathResolutionError(error));
+                }
+                self.interner.add_path_reference(self.path_resolver.module_id(), &trait_path);
+                trait_id
+            }
+            _ => return None,
+        };
+
+        let the_trait = self.interner.get_trait(trait_id);
+        let method = the_trait.find_method(method.0.contents.as_str())?;
+        let self_type = Type::type_variable(the_trait.self_type_typevar_id);
+        Some((HirExpression::TraitMethodReference(method), self_type))
     }
 
     // this resolves a static trait method T::trait_method by iterating over the where clause
@@ -1680,8 +1886,9 @@ impl<'a> Resolver<'a> {
                     continue;
                 }
 
-                if let Ok(ModuleDefId::TraitId(trait_id)) =
-                    self.path_resolver.resolve(self.def_maps, trait_bound.trait_path.clone())
+                if let Ok(PathResolution {
+                    module_def_id: ModuleDefId::TraitId(trait_id), ..
+                }) = self.path_resolver.resolve(self.def_maps, trait_bound.trait_path.clone())
                 {
                     let the_trait = self.interner.get_trait(trait_id);
                     if let Some(method) =
@@ -1703,7 +1910,18 @@ impl<'a> Resolver<'a> {
     }
 
     fn resolve_path(&mut self, path: Path) -> Result<ModuleDefId, ResolverError> {
-        self.path_resolver.resolve(self.def_maps, path).map_err(ResolverError::PathResolutionError)
+        self.interner.add_path_reference(self.path_resolver.module_id(), &path);
+
+        let path_resolution = self
+            .path_resolver
+            .resolve(self.def_maps, path)
+            .map_err(ResolverError::PathResolutionError)?;
+
+        if let Some(error) = path_resolution.error {
+            self.push_err(ResolverError::PathResolutionError(error));
+        }
+
+        Ok(path_resolution.module_def_id)
     }
 
     fn resolve_block(&mut self, block_expr: BlockExpression) -> HirExpression {
@@ -1725,15 +1943,27 @@ impl<'a> Resolver<'a> {
 
         let length = stmt.expression;
         let span = self.interner.expr_span(&length);
+        self.eval_array_length(length, span).unwrap_or(0)
+    }
+
+    /// Resolves the value of an associated constant in a trait impl. This value must be known
+    /// at compile-time since associated constants may be used as array lengths.
+    pub fn resolve_associated_constant_value(&mut self, value: Expression) -> Type {
+        let span = value.span;
+        let expr_id = self.resolve_expression(value);
+        self.eval_array_length(expr_id, span).map_or(Type::Error, Type::Constant)
+    }
+
+    fn eval_array_length(&mut self, length: ExprId, span: Span) -> Option<u64> {
         let result = self.try_eval_array_length_id(length, span);
 
         match result.map(|length| length.try_into()) {
-            Ok(Ok(length_value)) => return length_value,
+            Ok(Ok(length_value)) => return Some(length_value),
             Ok(Err(_cast_err)) => self.push_err(ResolverError::IntegerTooLarge { span }),
             Err(Some(error)) => self.push_err(error),
             Err(None) => (),
         }
-        0
+        None
     }
 
     fn try_eval_array_length_id(
@@ -1741,6 +1971,18 @@ impl<'a> Resolver<'a> {
         rhs: ExprId,
         span: Span,
     ) -> Result<u128, Option<ResolverError>> {
+        if self.interner.is_comptime_expression(rhs) {
+            let crate_id = self.path_resolver.module_id().krate;
+            let value = Interpreter::new_for_types(self.interner, crate_id).evaluate(rhs);
+            return match value {
+                Ok(value) => {
+                    value.to_u128().ok_or(Some(ResolverError::InvalidArrayLengthExpr { span }))
+                }
+                Err(error) if error.is_reported_elsewhere() => Err(None),
+                Err(error) => Err(Some(error.into())),
+            };
+        }
+
         match self.interner.expression(&rhs) {
             HirExpression::Literal(HirLiteral::Integer(int, false)) => {
                 int.try_into_u128().ok_or(Some(ResolverError::IntegerTooLarge { span }))
@@ -1,8 +1,11 @@
-use std::collections::{BTreeMap, HashSet};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    rc::Rc,
+};
 
 use fm::FileId;
 use iter_extended::vecmap;
-use noirc_errors::Location;
+use noirc_errors::{Location, Span};
 
 use crate::{
     graph::CrateId,
@@ -14,15 +17,18 @@ use crate::{
             errors::{DefCollectorErrorKind, DuplicateType},
         },
         def_map::{CrateDefMap, ModuleDefId, ModuleId},
+        type_check::TypeCheckError,
         Context,
     },
     hir_def::traits::{TraitConstant, TraitFunction, TraitImpl, TraitType},
     node_interner::{FuncId, NodeInterner, TraitId},
-    Path, Shared, TraitItem, Type, TypeBinding, TypeVariableKind,
+    Ident, ItemVisibility, Path, Shared, TraitItem, Type, TypeBinding, TypeVariable,
+    TypeVariableKind,
 };
 
 use super::{
     functions, get_module_mut, get_struct_type,
+    import::{PathResolution, PathResolutionError},
     path_resolver::{PathResolver, StandardPathResolver},
     resolver::Resolver,
     take_errors,
@@ -42,9 +48,13 @@ pub(crate) fn resolve_traits(
     for (trait_id, unresolved_trait) in traits {
         // Resolve order
         // 1. Trait Types ( Trait constants can have a trait type, therefore types before constants)
-        let _ = resolve_trait_types(context, crate_id, &unresolved_trait);
+        let types = resolve_trait_types(context, &unresolved_trait);
+        context.def_interner.update_trait(trait_id, |trait_def| trait_def.types = types);
         // 2. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
-        let _ = resolve_trait_constants(context, crate_id, &unresolved_trait);
+        let (constants, errors) =
+            resolve_trait_constants(context, trait_id, crate_id, &unresolved_trait);
+        res.extend(errors);
+        context.def_interner.update_trait(trait_id, |trait_def| trait_def.constants = constants);
         // 3. Trait Methods
         let (methods, errors) =
             resolve_trait_methods(context, trait_id, crate_id, &unresolved_trait);
@@ -57,20 +67,69 @@ pub(crate) fn resolve_traits(
 }
 
 fn resolve_trait_types(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
-) -> (Vec<TraitType>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
+    context: &mut Context,
+    unresolved_trait: &UnresolvedTrait,
+) -> Vec<TraitType> {
+    let mut types = vec![];
+    for item in &unresolved_trait.trait_def.items {
+        if let TraitItem::Type { name } = item {
+            let type_var_id = context.def_interner.next_type_variable_id();
+            types.push(TraitType {
+                name: name.clone(),
+                span: name.span(),
+                type_var_id,
+                type_var: TypeVariable::unbound(type_var_id),
+            });
+        }
+    }
+    types
 }
+
 fn resolve_trait_constants(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
+    context: &mut Context,
+    trait_id: TraitId,
+    crate_id: CrateId,
+    unresolved_trait: &UnresolvedTrait,
 ) -> (Vec<TraitConstant>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
+    let interner = &mut context.def_interner;
+    let def_maps = &mut context.def_maps;
+
+    let path_resolver = StandardPathResolver::new(ModuleId {
+        local_id: unresolved_trait.module_id,
+        krate: crate_id,
+    });
+    let file = def_maps[&crate_id].file_id(unresolved_trait.module_id);
+
+    let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
+    resolver.set_self_associated_types(self_associated_types(resolver.interner, trait_id));
+
+    let mut constants = vec![];
+    for item in &unresolved_trait.trait_def.items {
+        if let TraitItem::Constant { name, typ, default_value } = item {
+            let type_var_id = resolver.interner.next_type_variable_id();
+            constants.push(TraitConstant {
+                name: name.clone(),
+                ty: resolver.resolve_type(typ.clone()),
+                default_value: default_value.clone(),
+                span: name.span(),
+                type_var_id,
+                type_var: TypeVariable::unbound(type_var_id),
+            });
+        }
+    }
+
+    let errors = vecmap(resolver.take_errors(), |error| (error.into(), file));
+    (constants, errors)
+}
+
+/// Returns what `Self::Name` refers to within the definition of the given trait for each of
+/// its associated types and constants.
+fn self_associated_types(interner: &NodeInterner, trait_id: TraitId) -> Vec<(String, Type)> {
+    let the_trait = interner.get_trait(trait_id);
+    vecmap(the_trait.associated_items(), |(name, _, type_var)| {
+        let generic_name = Rc::new(format!("Self::{name}"));
+        (name.0.contents.clone(), Type::NamedGeneric(type_var.clone(), generic_name))
+    })
 }
 
 fn resolve_trait_methods(
@@ -105,9 +164,11 @@ fn resolve_trait_methods(
             let self_type =
                 Type::TypeVariable(the_trait.self_type_typevar.clone(), TypeVariableKind::Normal);
 
+            let associated_types = self_associated_types(interner, trait_id);
             let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
             resolver.add_generics(generics);
             resolver.set_self_type(Some(self_type));
+            resolver.set_self_associated_types(associated_types);
 
             let func_id = unresolved_trait.method_ids[&name.0.contents];
             let (_, func_meta) = resolver.resolve_trait_function(
@@ -133,6 +194,11 @@ fn resolve_trait_methods(
             let the_trait = resolver.interner.get_trait(trait_id);
             generics.push((the_trait.self_type_typevar_id, the_trait.self_type_typevar.clone()));
 
+            // And over its associated types and constants, which depend on the Self type
+            for (_, type_var_id, type_var) in the_trait.associated_items() {
+                generics.push((type_var_id, type_var.clone()));
+            }
+
             let default_impl_list: Vec<_> = unresolved_trait
                 .fns_with_default_impl
                 .functions
@@ -265,8 +331,14 @@ fn collect_trait_impl(
     let unresolved_type = trait_impl.object_type.clone();
     let module = ModuleId { local_id: trait_impl.module_id, krate: crate_id };
     trait_impl.trait_id =
-        match resolve_trait_by_path(def_maps, module, trait_impl.trait_path.clone()) {
-            Ok(trait_id) => Some(trait_id),
+        match resolve_trait_by_path(interner, def_maps, module, trait_impl.trait_path.clone()) {
+            Ok((trait_id, warning)) => {
+                if let Some(warning) = warning {
+                    let warning = DefCollectorErrorKind::PathResolutionError(warning);
+                    errors.push((warning.into(), trait_impl.file_id));
+                }
+                Some(trait_id)
+            }
             Err(error) => {
                 errors.push((error.into(), trait_impl.file_id));
                 None
@@ -293,7 +365,8 @@ fn collect_trait_impl(
                 // be accessed with the `TypeName::method` syntax. We'll check later whether the
                 // object types in each method overlap or not. If they do, we issue an error.
                 // If not, that is specialization which is allowed.
-                if module.declare_function(method.name_ident().clone(), *method_id).is_err() {
+                let name = method.name_ident().clone();
+                if module.declare_function(name, ItemVisibility::Public, *method_id).is_err() {
                     module.remove_function(method.name_ident());
                 }
             }
@@ -343,15 +416,21 @@ fn check_trait_impl_crate_coherence(
     errors
 }
 
+/// Resolves the path to a trait, returning the trait's id along with
+/// a warning if the trait is not visible from `module`.
 pub(crate) fn resolve_trait_by_path(
+    interner: &mut NodeInterner,
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     module: ModuleId,
     path: Path,
-) -> Result<TraitId, DefCollectorErrorKind> {
+) -> Result<(TraitId, Option<PathResolutionError>), DefCollectorErrorKind> {
     let path_resolver = StandardPathResolver::new(module);
+    interner.add_path_reference(module, &path);
 
     match path_resolver.resolve(def_maps, path.clone()) {
-        Ok(ModuleDefId::TraitId(trait_id)) => Ok(trait_id),
+        Ok(PathResolution { module_def_id: ModuleDefId::TraitId(trait_id), error }) => {
+            Ok((trait_id, error))
+        }
         Ok(_) => Err(DefCollectorErrorKind::NotATrait { not_a_trait_name: path }),
         Err(_) => Err(DefCollectorErrorKind::TraitNotFound { trait_path: path }),
     }
@@ -366,7 +445,7 @@ pub(crate) fn resolve_trait_impls(
     let mut methods = Vec::<(FileId, FuncId)>::new();
 
     for trait_impl in traits {
-        let unresolved_type = trait_impl.object_type;
+        let unresolved_type = trait_impl.object_type.clone();
         let local_mod_id = trait_impl.module_id;
         let module_id = ModuleId { krate: crate_id, local_id: local_mod_id };
         let path_resolver = StandardPathResolver::new(module_id);
@@ -377,10 +456,27 @@ pub(crate) fn resolve_trait_impls(
             Resolver::new(interner, &path_resolver, &context.def_maps, trait_impl.file_id);
         resolver.add_generics(&trait_impl.generics);
         let self_type = resolver.resolve_type(unresolved_type.clone());
+        resolver.add_associated_generics(&trait_impl.where_clause);
         let generics = resolver.get_generics().to_vec();
 
         let impl_id = interner.next_trait_impl_id();
 
+        let associated_types = match trait_impl.trait_id {
+            Some(trait_id) => resolve_trait_impl_associated_types(
+                interner,
+                &path_resolver,
+                &context.def_maps,
+                &trait_impl,
+                trait_id,
+                self_type.clone(),
+                generics.clone(),
+                errors,
+            ),
+            None => Vec::new(),
+        };
+        let self_associated_types =
+            vecmap(&associated_types, |(name, typ)| (name.0.contents.clone(), typ.clone()));
+
         let mut impl_methods = functions::resolve_function_set(
             interner,
             crate_id,
@@ -389,6 +485,7 @@ pub(crate) fn resolve_trait_impls(
             Some(self_type.clone()),
             Some(impl_id),
             generics.clone(),
+            self_associated_types,
             errors,
         );
 
@@ -408,6 +505,7 @@ pub(crate) fn resolve_trait_impls(
         let mut new_resolver =
             Resolver::new(interner, &path_resolver, &context.def_maps, trait_impl.file_id);
 
+        let impl_generic_count = generics.len();
         new_resolver.set_generics(generics);
         new_resolver.set_self_type(Some(self_type.clone()));
 
@@ -416,7 +514,8 @@ pub(crate) fn resolve_trait_impls(
                 &mut new_resolver,
                 &impl_methods,
                 trait_id,
-                trait_impl.generics.len(),
+                impl_generic_count,
+                &associated_types,
                 errors,
             );
 
@@ -433,6 +532,7 @@ pub(crate) fn resolve_trait_impls(
                 file: trait_impl.file_id,
                 where_clause,
                 methods: vecmap(&impl_methods, |(_, func_id)| *func_id),
+                associated_types,
             });
 
             if let Err((prev_span, prev_file)) = interner.add_trait_implementation(
@@ -459,3 +559,119 @@ pub(crate) fn resolve_trait_impls(
 
     methods
 }
+
+/// Resolves the associated types and constants of a trait impl, in the order they are declared
+/// in the trait. Each one declared by the trait must be defined exactly once, unless the trait
+/// gives a default value for it.
+#[allow(clippy::too_many_arguments)]
+fn resolve_trait_impl_associated_types(
+    interner: &mut NodeInterner,
+    path_resolver: &StandardPathResolver,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+    trait_impl: &UnresolvedTraitImpl,
+    trait_id: TraitId,
+    self_type: Type,
+    generics: Vec<(Rc<String>, TypeVariable, Span)>,
+    errors: &mut Vec<(CompilationError, FileId)>,
+) -> Vec<(Ident, Type)> {
+    let file = trait_impl.file_id;
+    let mut resolver = Resolver::new(interner, path_resolver, def_maps, file);
+    resolver.set_generics(generics);
+    resolver.set_self_type(Some(self_type));
+
+    let the_trait = resolver.interner.get_trait(trait_id);
+    let trait_name = the_trait.name.clone();
+    let trait_types = vecmap(&the_trait.types, |typ| typ.name.clone());
+    let trait_constants = vecmap(&the_trait.constants, |constant| {
+        (constant.name.clone(), constant.ty.clone(), constant.default_value.clone())
+    });
+
+    let mut types: HashMap<String, (Ident, Type)> = HashMap::new();
+    let mut push_item = |items: &mut HashMap<_, _>, name: &Ident, typ, duplicate_type| {
+        if let Some((first_def, _)) = items.get(&name.0.contents) {
+            let error = DefCollectorErrorKind::Duplicate {
+                typ: duplicate_type,
+                first_def: Ident::clone(first_def),
+                second_def: name.clone(),
+            };
+            errors.push((error.into(), file));
+        } else {
+            items.insert(name.0.contents.clone(), (name.clone(), typ));
+        }
+    };
+
+    for (name, alias) in &trait_impl.associated_types {
+        if !trait_types.contains(name) {
+            let error = DefCollectorErrorKind::AssociatedItemNotInTrait {
+                trait_name: trait_name.clone(),
+                item_name: name.clone(),
+            };
+            errors.push((error.into(), file));
+            continue;
+        }
+
+        let typ = resolver.resolve_type(alias.clone());
+        push_item(&mut types, name, typ, DuplicateType::TraitAssociatedType);
+    }
+
+    let mut constants: HashMap<String, (Ident, Type)> = HashMap::new();
+    for (name, typ, value) in &trait_impl.associated_constants {
+        let expected_type = match trait_constants.iter().find(|(constant, ..)| constant == name) {
+            Some((_, expected_type, _)) => expected_type,
+            None => {
+                let error = DefCollectorErrorKind::AssociatedItemNotInTrait {
+                    trait_name: trait_name.clone(),
+                    item_name: name.clone(),
+                };
+                errors.push((error.into(), file));
+                continue;
+            }
+        };
+
+        let typ = resolver.resolve_type(typ.clone());
+        if typ != *expected_type {
+            let error = TypeCheckError::TypeMismatch {
+                expected_typ: expected_type.to_string(),
+                expr_typ: typ.to_string(),
+                expr_span: name.span(),
+            };
+            errors.push((error.into(), file));
+        }
+
+        let value = resolver.resolve_associated_constant_value(value.clone());
+        push_item(&mut constants, name, value, DuplicateType::TraitAssociatedConst);
+    }
+
+    let trait_impl_span = trait_impl.object_type.span.expect("type must have a span");
+    let mut missing_item = |item_name: &Ident| {
+        let error = DefCollectorErrorKind::TraitMissingAssociatedItem {
+            trait_name: trait_name.clone(),
+            item_name: item_name.clone(),
+            trait_impl_span,
+        };
+        errors.push((error.into(), file));
+        Type::Error
+    };
+
+    let mut associated_types = vecmap(trait_types, |name| {
+        let typ = match types.remove(&name.0.contents) {
+            Some((_, typ)) => typ,
+            None => missing_item(&name),
+        };
+        (name, typ)
+    });
+
+    for (name, _, default_value) in trait_constants {
+        let value = match (constants.remove(&name.0.contents), default_value) {
+            (Some((_, value)), _) => value,
+            (None, Some(default_value)) => {
+                resolver.resolve_associated_constant_value(default_value)
+            }
+            (None, None) => missing_item(&name),
+        };
+        associated_types.push((name, value));
+    }
+
+    errors.extend(take_errors(file, resolver));
+    associated_types
+}
@@ -0,0 +1,74 @@
+//! Visibility rules for items, struct fields and functions.
+//!
+//! Items without a visibility modifier are private: they may only be referenced from the
+//! module they are declared in and its descendants. `pub(crate)` items may be referenced
+//! anywhere within the same crate, and `pub` items from anywhere.
+use std::collections::BTreeMap;
+
+use crate::graph::CrateId;
+use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
+use crate::node_interner::StructId;
+use crate::ItemVisibility;
+
+/// Returns true if an item with the given visibility, declared in `target_module`, may be
+/// referenced from `current_module` of `current_crate`.
+pub(crate) fn can_reference_module_id(
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+    current_crate: CrateId,
+    current_module: LocalModuleId,
+    target_module: ModuleId,
+    visibility: ItemVisibility,
+) -> bool {
+    let same_crate = target_module.krate == current_crate;
+    match visibility {
+        ItemVisibility::Public => true,
+        ItemVisibility::PublicCrate => same_crate,
+        ItemVisibility::Private => {
+            same_crate
+                && module_descendent_of_target(
+                    &def_maps[&current_crate],
+                    target_module.local_id,
+                    current_module,
+                )
+        }
+    }
+}
+
+/// Returns true if a field of the given struct with the given visibility may be
+/// accessed from `current_module`.
+pub(crate) fn struct_field_is_visible(
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+    struct_id: StructId,
+    visibility: ItemVisibility,
+    current_module: ModuleId,
+) -> bool {
+    // Each struct has its own module holding its methods, whose parent is the module
+    // the struct was declared in. Private fields are visible from that parent module.
+    let struct_module = struct_id.module_id();
+    let declaring_module = struct_module.module(def_maps).parent.unwrap_or(struct_module.local_id);
+    let declaring_module = ModuleId { krate: struct_module.krate, local_id: declaring_module };
+
+    can_reference_module_id(
+        def_maps,
+        current_module.krate,
+        current_module.local_id,
+        declaring_module,
+        visibility,
+    )
+}
+
+// Returns true if `current` is a (potentially nested) child module of `target`.
+// This is also true if `current == target`.
+pub(crate) fn module_descendent_of_target(
+    def_map: &CrateDefMap,
+    target: LocalModuleId,
+    current: LocalModuleId,
+) -> bool {
+    if current == target {
+        return true;
+    }
+
+    def_map.modules[current.0]
+        .parent
+        .map_or(false, |parent| module_descendent_of_target(def_map, target, parent))
+}
@@ -115,17 +115,62 @@ pub enum TypeCheckError {
     NoMatchingImplFound { constraints: Vec<(Type, String)>, span: Span },
     #[error("Constraint for `{typ}: {trait_name}` is not needed, another matching impl is already in scope")]
     UnneededTraitConstraint { trait_name: String, typ: Type, span: Span },
+    #[error("Field {field_name} of {struct_name} is private")]
+    PrivateField { struct_name: String, field_name: String, span: Span },
 }
 
 impl TypeCheckError {
     pub fn add_context(self, ctx: &'static str) -> Self {
         TypeCheckError::Context { err: Box::new(self), ctx }
     }
+
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeCheckError::OpCannotBeUsed { .. } => "E0201",
+            TypeCheckError::OverflowingAssignment { .. } => "E0202",
+            TypeCheckError::TypeCannotBeUsed { .. } => "E0203",
+            TypeCheckError::TypeMismatch { .. } => "E0204",
+            TypeCheckError::TypeMismatchWithSource { .. } => "E0205",
+            TypeCheckError::ArityMisMatch { .. } => "E0206",
+            TypeCheckError::PublicReturnType { .. } => "E0207",
+            TypeCheckError::InvalidCast { .. } => "E0208",
+            TypeCheckError::ExpectedFunction { .. } => "E0209",
+            TypeCheckError::AccessUnknownMember { .. } => "E0210",
+            TypeCheckError::ParameterCountMismatch { .. } => "E0211",
+            TypeCheckError::UnsupportedCast { .. } => "E0212",
+            TypeCheckError::TupleIndexOutOfBounds { .. } => "E0213",
+            TypeCheckError::VariableMustBeMutable { .. } => "E0214",
+            TypeCheckError::UnresolvedMethodCall { .. } => "E0215",
+            TypeCheckError::InvalidComparisonOnField { .. } => "E0216",
+            TypeCheckError::IntegerSignedness { .. } => "E0217",
+            TypeCheckError::IntegerBitWidth { .. } => "E0218",
+            TypeCheckError::InvalidInfixOp { .. } => "E0219",
+            TypeCheckError::InvalidUnaryOp { .. } => "E0220",
+            TypeCheckError::InvalidBitwiseOperationOnField { .. } => "E0221",
+            TypeCheckError::IntegerTypeMismatch { .. } => "E0222",
+            TypeCheckError::IntegerAndFieldBinaryOperation { .. } => "E0223",
+            TypeCheckError::FieldModulo { .. } => "E0224",
+            TypeCheckError::FieldComparison { .. } => "E0225",
+            TypeCheckError::AmbiguousBitWidth { .. } => "E0226",
+            TypeCheckError::Context { err, .. } => err.code(),
+            TypeCheckError::NonHomogeneousArray { .. } => "E0227",
+            TypeCheckError::TypeAnnotationsNeeded { .. } => "E0228",
+            TypeCheckError::CallDeprecated { .. } => "E0229",
+            TypeCheckError::ResolverError(error) => error.code(),
+            TypeCheckError::UnusedResultError { .. } => "E0230",
+            TypeCheckError::TraitMethodParameterTypeMismatch { .. } => "E0231",
+            TypeCheckError::NoMatchingImplFound { .. } => "E0232",
+            TypeCheckError::UnneededTraitConstraint { .. } => "E0233",
+            TypeCheckError::PrivateField { .. } => "E0234",
+        }
+    }
 }
 
 impl From<TypeCheckError> for Diagnostic {
     fn from(error: TypeCheckError) -> Diagnostic {
-        match error {
+        let code = error.code();
+        let mut diagnostic = match error {
             TypeCheckError::TypeCannotBeUsed { typ, place, span } => Diagnostic::simple_error(
                 format!("The type {} cannot be used in a {}", &typ, place),
                 String::new(),
@@ -240,6 +285,7 @@ impl From<TypeCheckError> for Diagnostic {
                         }
 
                         diagnostic.add_secondary(format!("{actual} returned here"), expr_span);
+                        diagnostic.code = Some(code);
 
                         return diagnostic
                     },
@@ -275,6 +321,13 @@ impl From<TypeCheckError> for Diagnostic {
                 let msg = format!("Constraint for `{typ}: {trait_name}` is not needed, another matching impl is already in scope");
                 Diagnostic::simple_warning(msg, "Unnecessary trait constraint in where clause".into(), span)
             }
-        }
+            // This will be upgraded to an error in future versions
+            TypeCheckError::PrivateField { struct_name, field_name, span } => {
+                let msg = format!("field {field_name} of struct {struct_name} is private");
+                Diagnostic::simple_warning(msg, format!("{field_name} is not visible from the current module"), span)
+            }
+        };
+        diagnostic.code = Some(code);
+        diagnostic
     }
 }
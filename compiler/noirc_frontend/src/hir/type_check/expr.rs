@@ -2,13 +2,17 @@ use iter_extended::vecmap;
 use noirc_errors::Span;
 
 use crate::{
-    hir::{resolution::resolver::verify_mutable_reference, type_check::errors::Source},
+    hir::{
+        resolution::{resolver::verify_mutable_reference, visibility::struct_field_is_visible},
+        type_check::errors::Source,
+    },
     hir_def::{
         expr::{
             self, HirArrayLiteral, HirBinaryOp, HirExpression, HirLiteral, HirMethodCallExpression,
             HirMethodReference, HirPrefixExpression,
         },
-        types::Type,
+        traits::TraitConstraint,
+        types::{StructType, Type},
     },
     node_interner::{DefinitionKind, ExprId, FuncId, TraitId, TraitImplKind, TraitMethodId},
     BinaryOpKind, TypeBinding, TypeBindings, TypeVariableKind, UnaryOp,
@@ -61,6 +65,9 @@ impl<'interner> TypeChecker<'interner> {
                         let function = self.interner.function_meta(&function);
                         for mut constraint in function.trait_constraints.clone() {
                             constraint.typ = constraint.typ.substitute(&bindings);
+                            for (_, typ) in &mut constraint.associated_types {
+                                *typ = typ.substitute(&bindings);
+                            }
                             self.trait_constraints.push((constraint, *expr_id));
                         }
                     }
@@ -209,7 +216,13 @@ impl<'interner> TypeChecker<'interner> {
                         let ret = self.check_method_call(&function_id, method_ref, args, span);
 
                         if let Some(trait_id) = trait_id {
-                            self.verify_trait_constraint(&object_type, trait_id, function_id, span);
+                            self.verify_trait_constraint(
+                                &object_type,
+                                trait_id,
+                                function_id,
+                                &[],
+                                span,
+                            );
                         }
 
                         self.interner.replace_expr(expr_id, function_call);
@@ -297,13 +310,23 @@ impl<'interner> TypeChecker<'interner> {
                 let impl_kind = self.interner.get_selected_impl_for_ident(*expr_id);
                 if let Some(TraitImplKind::Assumed { object_type }) = impl_kind {
                     let the_trait = self.interner.get_trait(method.trait_id);
+                    let has_associated_items = the_trait.has_associated_items();
                     let object_type = object_type.substitute(&bindings);
                     bindings.insert(
                         the_trait.self_type_typevar_id,
                         (the_trait.self_type_typevar.clone(), object_type.clone()),
                     );
-                    self.interner
-                        .select_impl_for_ident(*expr_id, TraitImplKind::Assumed { object_type });
+                    self.interner.select_impl_for_ident(
+                        *expr_id,
+                        TraitImplKind::Assumed { object_type: object_type.clone() },
+                    );
+
+                    // The trait's associated types are only known once the impl is, so they
+                    // are bound after the rest of the function is checked.
+                    if has_associated_items {
+                        let constraint = TraitConstraint::new(object_type, method.trait_id);
+                        self.trait_constraints.push((constraint, *expr_id));
+                    }
                 }
 
                 self.interner.store_instantiation_bindings(*expr_id, bindings);
@@ -320,10 +343,21 @@ impl<'interner> TypeChecker<'interner> {
         object_type: &Type,
         trait_id: TraitId,
         function_ident_id: ExprId,
+        associated_types: &[(String, Type)],
         span: Span,
     ) {
         match self.interner.lookup_trait_implementation(object_type, trait_id) {
-            Ok(impl_kind) => self.interner.select_impl_for_ident(function_ident_id, impl_kind),
+            Ok(impl_kind) => {
+                self.bind_associated_types(
+                    object_type,
+                    trait_id,
+                    &impl_kind,
+                    function_ident_id,
+                    associated_types,
+                    span,
+                );
+                self.interner.select_impl_for_ident(function_ident_id, impl_kind);
+            }
             Err(erroring_constraints) => {
                 // Don't show any errors where try_get_trait returns None.
                 // This can happen if a trait is used that was never declared.
@@ -342,6 +376,46 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Unifies the associated types of the selected impl with the types expected by the caller.
+    /// These are the implicit `T::Item` generics of a function's where clause, along with any
+    /// associated types of the trait used in the signature of a trait method being called.
+    fn bind_associated_types(
+        &mut self,
+        object_type: &Type,
+        trait_id: TraitId,
+        impl_kind: &TraitImplKind,
+        function_ident_id: ExprId,
+        associated_types: &[(String, Type)],
+        span: Span,
+    ) {
+        let the_trait = self.interner.get_trait(trait_id);
+        if !the_trait.has_associated_items() {
+            return;
+        }
+
+        let mut expected_types = associated_types.to_vec();
+        if let Some(bindings) = self.interner.try_get_instantiation_bindings(function_ident_id) {
+            for (name, type_var_id, _) in the_trait.associated_items() {
+                if let Some((_, typ)) = bindings.get(&type_var_id) {
+                    expected_types.push((name.0.contents.clone(), typ.clone()));
+                }
+            }
+        }
+
+        let actual_types = self.interner.get_associated_types(object_type, trait_id, impl_kind);
+        for (name, expected) in expected_types {
+            if let Some((_, actual)) =
+                actual_types.iter().find(|(actual_name, _)| *actual_name == name)
+            {
+                self.unify(&expected, actual, || TypeCheckError::TypeMismatch {
+                    expected_typ: actual.to_string(),
+                    expr_typ: expected.to_string(),
+                    expr_span: span,
+                });
+            }
+        }
+    }
+
     /// Check if the given method type requires a mutable reference to the object type, and check
     /// if the given object type is already a mutable reference. If not, add one.
     /// This is used to automatically transform a method call: `foo.bar()` into a function
@@ -639,6 +713,7 @@ impl<'interner> TypeChecker<'interner> {
             // mismatch here as long as we continue typechecking the rest of the program to the best
             // of our ability.
             if param_name == arg_ident.0.contents {
+                self.check_field_visibility(&typ.borrow(), &param_name, arg_ident.span());
                 let arg_type = self.check_expression(&arg);
 
                 let span = self.interner.expr_span(expr_id);
@@ -684,6 +759,30 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Issue a warning if the given struct field is private and not visible
+    /// from the module of the function currently being type checked.
+    /// This covers field accesses as well as constructor expressions and patterns.
+    pub(super) fn check_field_visibility(
+        &mut self,
+        struct_type: &StructType,
+        field_name: &str,
+        span: Span,
+    ) {
+        if let (Some(func_id), Some(visibility)) =
+            (self.current_function, struct_type.field_visibility(field_name))
+        {
+            let current_module = self.interner.function_module(func_id);
+            let def_maps = self.def_maps;
+            if !struct_field_is_visible(def_maps, struct_type.id, visibility, current_module) {
+                self.errors.push(TypeCheckError::PrivateField {
+                    struct_name: struct_type.name.to_string(),
+                    field_name: field_name.to_string(),
+                    span,
+                });
+            }
+        }
+    }
+
     /// This will verify that an expression in the form `lhs.rhs_name` has the given field and will push
     /// a type error if it does not. If there is no error, the type of the struct/tuple field is returned
     /// along with the index of the field in question.
@@ -710,6 +809,7 @@ impl<'interner> TypeChecker<'interner> {
             Type::Struct(s, args) => {
                 let s = s.borrow();
                 if let Some((field, index)) = s.get_field(field_name, args) {
+                    self.check_field_visibility(&s, field_name, span);
                     return Some((field, index));
                 }
             }
@@ -13,7 +13,11 @@ mod stmt;
 
 pub use errors::TypeCheckError;
 
+use std::collections::BTreeMap;
+
 use crate::{
+    graph::CrateId,
+    hir::def_map::CrateDefMap,
     hir_def::{expr::HirExpression, stmt::HirStatement, traits::TraitConstraint},
     node_interner::{ExprId, FuncId, NodeInterner, StmtId},
     Type,
@@ -26,6 +30,7 @@ type TypeCheckFn = Box<dyn FnOnce() -> Result<(), TypeCheckError>>;
 pub struct TypeChecker<'interner> {
     delayed_type_checks: Vec<TypeCheckFn>,
     interner: &'interner mut NodeInterner,
+    def_maps: &'interner BTreeMap<CrateId, CrateDefMap>,
     errors: Vec<TypeCheckError>,
     current_function: Option<FuncId>,
 
@@ -38,7 +43,11 @@ pub struct TypeChecker<'interner> {
 
 /// Type checks a function and assigns the
 /// appropriate types to expressions in a side table
-pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<TypeCheckError> {
+pub fn type_check_func(
+    interner: &mut NodeInterner,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+    func_id: FuncId,
+) -> Vec<TypeCheckError> {
     let meta = interner.function_meta(&func_id);
     let declared_return_type = meta.return_type().clone();
     let can_ignore_ret = meta.can_ignore_return_type();
@@ -46,7 +55,7 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
     let function_body = interner.function(&func_id);
     let function_body_id = function_body.as_expr();
 
-    let mut type_checker = TypeChecker::new(interner);
+    let mut type_checker = TypeChecker::new(interner, def_maps);
     type_checker.current_function = Some(func_id);
 
     let meta = type_checker.interner.function_meta(&func_id);
@@ -57,7 +66,12 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
         let object = constraint.typ.clone();
         let trait_id = constraint.trait_id;
 
-        if !type_checker.interner.add_assumed_trait_implementation(object, trait_id) {
+        let associated_types = constraint.associated_types.clone();
+        if !type_checker.interner.add_assumed_trait_implementation(
+            object,
+            trait_id,
+            associated_types,
+        ) {
             if let Some(the_trait) = type_checker.interner.try_get_trait(trait_id) {
                 let trait_name = the_trait.name.to_string();
                 let typ = constraint.typ.clone();
@@ -87,7 +101,13 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
     // Verify any remaining trait constraints arising from the function body
     for (constraint, expr_id) in std::mem::take(&mut type_checker.trait_constraints) {
         let span = type_checker.interner.expr_span(&expr_id);
-        type_checker.verify_trait_constraint(&constraint.typ, constraint.trait_id, expr_id, span);
+        type_checker.verify_trait_constraint(
+            &constraint.typ,
+            constraint.trait_id,
+            expr_id,
+            &constraint.associated_types,
+            span,
+        );
     }
 
     errors.append(&mut type_checker.errors);
@@ -159,10 +179,14 @@ fn function_info(interner: &NodeInterner, function_body_id: &ExprId) -> (noirc_e
 }
 
 impl<'interner> TypeChecker<'interner> {
-    fn new(interner: &'interner mut NodeInterner) -> Self {
+    fn new(
+        interner: &'interner mut NodeInterner,
+        def_maps: &'interner BTreeMap<CrateId, CrateDefMap>,
+    ) -> Self {
         Self {
             delayed_type_checks: Vec::new(),
             interner,
+            def_maps,
             errors: Vec::new(),
             trait_constraints: Vec::new(),
             current_function: None,
@@ -178,10 +202,15 @@ impl<'interner> TypeChecker<'interner> {
         (body_type, std::mem::take(&mut self.delayed_type_checks))
     }
 
-    pub fn check_global(id: &StmtId, interner: &'interner mut NodeInterner) -> Vec<TypeCheckError> {
+    pub fn check_global(
+        id: &StmtId,
+        interner: &'interner mut NodeInterner,
+        def_maps: &'interner BTreeMap<CrateId, CrateDefMap>,
+    ) -> Vec<TypeCheckError> {
         let mut this = Self {
             delayed_type_checks: Vec::new(),
             interner,
+            def_maps,
             errors: Vec::new(),
             trait_constraints: Vec::new(),
             current_function: None,
@@ -231,7 +260,9 @@ mod test {
 
     use crate::graph::CrateId;
     use crate::hir::def_map::{ModuleData, ModuleId};
-    use crate::hir::resolution::import::PathResolutionError;
+    use crate::hir::resolution::import::{
+        PathResolution, PathResolutionError, PathResolutionResult,
+    };
     use crate::hir_def::expr::HirIdent;
     use crate::hir_def::stmt::HirLetStatement;
     use crate::hir_def::stmt::HirPattern::Identifier;
@@ -339,7 +370,7 @@ mod test {
         };
         interner.push_fn_meta(func_meta, func_id);
 
-        let errors = super::type_check_func(&mut interner, func_id);
+        let errors = super::type_check_func(&mut interner, &BTreeMap::new(), func_id);
         assert!(errors.is_empty());
     }
 
@@ -432,12 +463,13 @@ mod test {
             &self,
             _def_maps: &BTreeMap<CrateId, CrateDefMap>,
             path: Path,
-        ) -> Result<ModuleDefId, PathResolutionError> {
+        ) -> PathResolutionResult {
             // Not here that foo::bar and hello::foo::bar would fetch the same thing
             let name = path.segments.last().unwrap();
             self.0
                 .get(&name.0.contents)
                 .cloned()
+                .map(|module_def_id| PathResolution { module_def_id, error: None })
                 .ok_or_else(move || PathResolutionError::Unresolved(name.clone()))
         }
 
@@ -519,7 +551,8 @@ mod test {
         }
 
         // Type check section
-        let errors = super::type_check_func(&mut interner, func_ids.first().cloned().unwrap());
+        let errors =
+            super::type_check_func(&mut interner, &def_maps, func_ids.first().cloned().unwrap());
         assert_eq!(errors, vec![]);
     }
 }
@@ -126,6 +126,11 @@ impl<'interner> TypeChecker<'interner> {
                         if let Some((type_field, _)) =
                             struct_type.get_field(&field_name.0.contents, generics)
                         {
+                            self.check_field_visibility(
+                                &struct_type,
+                                &field_name.0.contents,
+                                field_name.span(),
+                            );
                             self.bind_pattern(field_pattern, type_field);
                         }
                     }
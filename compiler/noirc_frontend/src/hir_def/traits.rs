@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::{
     graph::CrateId,
     node_interner::{FuncId, TraitId, TraitMethodId},
-    Generics, Ident, NoirFunction, Type, TypeVariable, TypeVariableId,
+    Expression, Generics, Ident, NoirFunction, Type, TypeVariable, TypeVariableId,
 };
 use fm::FileId;
 use noirc_errors::Span;
@@ -18,18 +18,31 @@ pub struct TraitFunction {
     pub default_impl_module_id: crate::hir::def_map::LocalModuleId,
 }
 
+/// An associated constant of a trait, e.g. `let N: u32;`
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitConstant {
     pub name: Ident,
     pub ty: Type,
+    pub default_value: Option<Expression>,
     pub span: Span,
+
+    /// Associated constants may be used as numeric generics. All references to `Self::N`
+    /// within the trait resolve to this TypeVariable, which is bound to the value given
+    /// by an impl when checking that impl against the trait.
+    pub type_var_id: TypeVariableId,
+    pub type_var: TypeVariable,
 }
 
+/// An associated type of a trait, e.g. `type Item;`
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitType {
     pub name: Ident,
-    pub ty: Type,
     pub span: Span,
+
+    /// All references to `Self::Item` within the trait resolve to this TypeVariable,
+    /// which is bound to the type given by an impl when checking that impl against the trait.
+    pub type_var_id: TypeVariableId,
+    pub type_var: TypeVariable,
 }
 
 /// Represents a trait in the type system. Each instance of this struct
@@ -73,6 +86,10 @@ pub struct TraitImpl {
     pub file: FileId,
     pub methods: Vec<FuncId>, // methods[i] is the implementation of trait.methods[i] for Type typ
 
+    /// The associated types and constants of this impl, in the order they are declared in
+    /// the trait. Associated constants are stored as the `Type::Constant` of their value.
+    pub associated_types: Vec<(Ident, Type)>,
+
     /// The where clause, if present, contains each trait requirement which must
     /// be satisfied for this impl to be selected. E.g. in `impl Eq for [T] where T: Eq`,
     /// `where_clause` would contain the one `T: Eq` constraint. If there is no where clause,
@@ -85,11 +102,15 @@ pub struct TraitConstraint {
     pub typ: Type,
     pub trait_id: TraitId,
     // pub trait_generics: Generics, TODO
+    /// The implicit generics standing for each associated type and constant of the trait
+    /// in this constraint, e.g. `T::Item` for `T: Iterator`. These are bound to the types
+    /// of the selected impl when the constraint is verified.
+    pub associated_types: Vec<(String, Type)>,
 }
 
 impl TraitConstraint {
     pub fn new(typ: Type, trait_id: TraitId) -> Self {
-        Self { typ, trait_id }
+        Self { typ, trait_id, associated_types: Vec::new() }
     }
 }
 
@@ -118,6 +139,22 @@ impl Trait {
         }
         None
     }
+
+    /// Returns the name and type variable of each associated type and constant of this trait.
+    pub fn associated_items(
+        &self,
+    ) -> impl Iterator<Item = (&Ident, TypeVariableId, &TypeVariable)> {
+        let types = self.types.iter().map(|typ| (&typ.name, typ.type_var_id, &typ.type_var));
+        let constants = self
+            .constants
+            .iter()
+            .map(|constant| (&constant.name, constant.type_var_id, &constant.type_var));
+        types.chain(constants)
+    }
+
+    pub fn has_associated_items(&self) -> bool {
+        !self.types.is_empty() || !self.constants.is_empty()
+    }
 }
 
 impl std::fmt::Display for Trait {
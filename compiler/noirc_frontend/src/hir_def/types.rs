@@ -12,7 +12,7 @@ use iter_extended::vecmap;
 use noirc_errors::{Location, Span};
 use noirc_printable_type::PrintableType;
 
-use crate::{node_interner::StructId, Ident, Signedness};
+use crate::{node_interner::StructId, Ident, ItemVisibility, Signedness};
 
 use super::expr::{HirCallExpression, HirExpression, HirIdent};
 
@@ -163,7 +163,7 @@ pub struct StructType {
     /// Fields are ordered and private, they should only
     /// be accessed through get_field(), get_fields(), or instantiate()
     /// since these will handle applying generic arguments to fields as well.
-    fields: Vec<(Ident, Type)>,
+    fields: Vec<(ItemVisibility, Ident, Type)>,
 
     pub generics: Generics,
     pub location: Location,
@@ -193,7 +193,7 @@ impl StructType {
         name: Ident,
 
         location: Location,
-        fields: Vec<(Ident, Type)>,
+        fields: Vec<(ItemVisibility, Ident, Type)>,
         generics: Generics,
     ) -> StructType {
         StructType { id, fields, name, location, generics }
@@ -203,7 +203,7 @@ impl StructType {
     /// fields are resolved strictly after the struct itself is initially
     /// created. Therefore, this method is used to set the fields once they
     /// become known.
-    pub fn set_fields(&mut self, fields: Vec<(Ident, Type)>) {
+    pub fn set_fields(&mut self, fields: Vec<(ItemVisibility, Ident, Type)>) {
         assert!(self.fields.is_empty());
         self.fields = fields;
     }
@@ -212,12 +212,17 @@ impl StructType {
         self.fields.len()
     }
 
+    /// Returns the fields of this struct as they were declared, without applying any generics.
+    pub fn fields(&self) -> &[(ItemVisibility, Ident, Type)] {
+        &self.fields
+    }
+
     /// Returns the field matching the given field name, as well as its field index.
     pub fn get_field(&self, field_name: &str, generic_args: &[Type]) -> Option<(Type, usize)> {
         assert_eq!(self.generics.len(), generic_args.len());
 
-        self.fields.iter().enumerate().find(|(_, (name, _))| name.0.contents == field_name).map(
-            |(i, (_, typ))| {
+        self.fields.iter().enumerate().find(|(_, (_, name, _))| name.0.contents == field_name).map(
+            |(i, (_, _, typ))| {
                 let substitutions = self
                     .generics
                     .iter()
@@ -241,14 +246,22 @@ impl StructType {
             .map(|((old_id, old_var), new)| (*old_id, (old_var.clone(), new.clone())))
             .collect();
 
-        vecmap(&self.fields, |(name, typ)| {
+        vecmap(&self.fields, |(_, name, typ)| {
             let name = name.0.contents.clone();
             (name, typ.substitute(&substitutions))
         })
     }
 
     pub fn field_names(&self) -> BTreeSet<Ident> {
-        self.fields.iter().map(|(name, _)| name.clone()).collect()
+        self.fields.iter().map(|(_, name, _)| name.clone()).collect()
+    }
+
+    /// Returns the visibility of the field with the given name, if it exists
+    pub fn field_visibility(&self, field_name: &str) -> Option<ItemVisibility> {
+        self.fields
+            .iter()
+            .find(|(_, name, _)| name.0.contents == field_name)
+            .map(|(visibility, _, _)| *visibility)
     }
 
     /// True if the given index is the same index as a generic type of this struct
@@ -256,7 +269,7 @@ impl StructType {
     /// This is needed because we infer type kinds in Noir and don't have extensive kind checking.
     pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
         let target_id = self.generics[index_of_generic].0;
-        self.fields.iter().any(|(_, field)| field.contains_numeric_typevar(target_id))
+        self.fields.iter().any(|(_, _, field)| field.contains_numeric_typevar(target_id))
     }
 
     /// Instantiate this struct type, returning a Vec of the new generic args (in
@@ -1204,7 +1217,7 @@ impl Type {
             // only to have to call .into_iter again afterward. Trying to elide
             // collecting to a Vec leads to us dropping the temporary Ref before
             // the iterator is returned
-            Type::Struct(def, args) => vecmap(&def.borrow().fields, |(name, _)| {
+            Type::Struct(def, args) => vecmap(&def.borrow().fields, |(_, name, _)| {
                 let name = &name.0.contents;
                 let typ = def.borrow().get_field(name, args).unwrap().0;
                 (name.clone(), typ)
@@ -1591,7 +1604,7 @@ impl From<&Type> for PrintableType {
                 PrintableType::Struct { fields, name: struct_type.name.to_string() }
             }
             Type::TraitAsType(..) => unreachable!(),
-            Type::Tuple(_) => todo!("printing tuple types is not yet implemented"),
+            Type::Tuple(types) => PrintableType::Tuple { types: vecmap(types, |typ| typ.into()) },
             Type::TypeVariable(_, _) => unreachable!(),
             Type::NamedGeneric(..) => unreachable!(),
             Type::Forall(..) => unreachable!(),
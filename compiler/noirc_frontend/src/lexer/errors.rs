@@ -53,6 +53,21 @@ impl LexerErrorKind {
         }
     }
 
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexerErrorKind::UnexpectedCharacter { .. } => "E0501",
+            LexerErrorKind::NotADoubleChar { .. } => "E0502",
+            LexerErrorKind::InvalidIntegerLiteral { .. } => "E0503",
+            LexerErrorKind::MalformedFuncAttribute { .. } => "E0504",
+            LexerErrorKind::TooManyBits { .. } => "E0505",
+            LexerErrorKind::LogicalAnd { .. } => "E0506",
+            LexerErrorKind::UnterminatedBlockComment { .. } => "E0507",
+            LexerErrorKind::UnterminatedStringLiteral { .. } => "E0508",
+            LexerErrorKind::InvalidEscape { .. } => "E0509",
+        }
+    }
+
     fn parts(&self) -> (String, String, Span) {
         match self {
             LexerErrorKind::UnexpectedCharacter {
@@ -109,7 +124,9 @@ impl LexerErrorKind {
 impl From<LexerErrorKind> for Diagnostic {
     fn from(error: LexerErrorKind) -> Diagnostic {
         let (primary, secondary, span) = error.parts();
-        Diagnostic::simple_error(primary, secondary, span)
+        let mut diagnostic = Diagnostic::simple_error(primary, secondary, span);
+        diagnostic.code = Some(error.code());
+        diagnostic
     }
 }
 
@@ -681,6 +681,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn derive_attribute() {
+        let input = "#[derive(Eq, Default)]";
+        let mut lexer = Lexer::new(input);
+
+        let token = lexer.next_token().unwrap();
+        assert_eq!(
+            token.token(),
+            &Token::Attribute(Attribute::Secondary(crate::token::SecondaryAttribute::Derive(
+                vec!["Eq".to_string(), "Default".to_string()]
+            )))
+        );
+    }
+
     #[test]
     fn test_custom_gate_syntax() {
         let input = "#[foreign(sha256)]#[foreign(blake2s)]#[builtin(sum)]";
@@ -410,6 +410,16 @@ impl Attributes {
         matches!(self.function, Some(FunctionAttribute::Test(_)))
     }
 
+    /// Returns true if one of the secondary attributes is `no_inline`
+    pub fn is_no_inline(&self) -> bool {
+        self.secondary.iter().any(|attribute| attribute == &SecondaryAttribute::NoInline)
+    }
+
+    /// Returns true if one of the secondary attributes is `allow(lint)`
+    pub fn allows(&self, lint: &str) -> bool {
+        SecondaryAttribute::allows(&self.secondary, lint)
+    }
+
     /// True if these attributes mean the given function is an entry point function if it was
     /// defined within a contract. Note that this does not check if the function is actually part
     /// of a contract.
@@ -504,12 +514,27 @@ impl Attribute {
                 validate(name)?;
                 Attribute::Secondary(SecondaryAttribute::Field(name.to_string()))
             }
+            ["allow", name] => {
+                validate(name)?;
+                Attribute::Secondary(SecondaryAttribute::Allow(name.to_string()))
+            }
+            ["derive", names] => {
+                validate(names)?;
+                let names = names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(ToString::to_string)
+                    .collect();
+                Attribute::Secondary(SecondaryAttribute::Derive(names))
+            }
             // Secondary attributes
             ["deprecated"] => Attribute::Secondary(SecondaryAttribute::Deprecated(None)),
             ["contract_library_method"] => {
                 Attribute::Secondary(SecondaryAttribute::ContractLibraryMethod)
             }
             ["event"] => Attribute::Secondary(SecondaryAttribute::Event),
+            ["no_inline"] => Attribute::Secondary(SecondaryAttribute::NoInline),
             ["deprecated", name] => {
                 if !name.starts_with('"') && !name.ends_with('"') {
                     return Err(LexerErrorKind::MalformedFuncAttribute {
@@ -588,10 +613,27 @@ pub enum SecondaryAttribute {
     // the entry point.
     ContractLibraryMethod,
     Event,
+    // This is an attribute to specify that a function should be kept
+    // as a separate function rather than being inlined into its unconstrained
+    // callers. Constrained callers always inline it, as ACIR has no function calls.
+    NoInline,
     Field(String),
+    // Disables the warnings of the given lint, e.g. `#[allow(dead_code)]`
+    Allow(String),
+    // Generates impls of the given traits for a struct, e.g. `#[derive(Eq, Default)]`
+    Derive(Vec<String>),
     Custom(String),
 }
 
+impl SecondaryAttribute {
+    /// Returns true if the given attributes contain `allow(lint)`
+    pub fn allows(attributes: &[SecondaryAttribute], lint: &str) -> bool {
+        attributes.iter().any(
+            |attribute| matches!(attribute, SecondaryAttribute::Allow(allowed) if allowed == lint),
+        )
+    }
+}
+
 impl fmt::Display for SecondaryAttribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -602,7 +644,10 @@ impl fmt::Display for SecondaryAttribute {
             SecondaryAttribute::Custom(ref k) => write!(f, "#[{k}]"),
             SecondaryAttribute::ContractLibraryMethod => write!(f, "#[contract_library_method]"),
             SecondaryAttribute::Event => write!(f, "#[event]"),
+            SecondaryAttribute::NoInline => write!(f, "#[no_inline]"),
             SecondaryAttribute::Field(ref k) => write!(f, "#[field({k})]"),
+            SecondaryAttribute::Allow(ref k) => write!(f, "#[allow({k})]"),
+            SecondaryAttribute::Derive(ref traits) => write!(f, "#[derive({})]", traits.join(", ")),
         }
     }
 }
@@ -623,9 +668,13 @@ impl AsRef<str> for SecondaryAttribute {
         match self {
             SecondaryAttribute::Deprecated(Some(string)) => string,
             SecondaryAttribute::Deprecated(None) => "",
-            SecondaryAttribute::Custom(string) | SecondaryAttribute::Field(string) => string,
+            SecondaryAttribute::Custom(string)
+            | SecondaryAttribute::Field(string)
+            | SecondaryAttribute::Allow(string) => string,
             SecondaryAttribute::ContractLibraryMethod => "",
+            SecondaryAttribute::Derive(_) => "",
             SecondaryAttribute::Event => "",
+            SecondaryAttribute::NoInline => "",
         }
     }
 }
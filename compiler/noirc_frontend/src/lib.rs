@@ -58,22 +58,38 @@ pub mod macros_api {
         Pattern, Statement, UnresolvedType, UnresolvedTypeData, Visibility,
     };
     pub use crate::{
-        ForLoopStatement, ForRange, FunctionDefinition, FunctionVisibility, ImportStatement,
+        ForLoopStatement, ForRange, FunctionDefinition, ImportStatement, ItemVisibility,
         NoirStruct, Param, PrefixExpression, Signedness, StatementKind, StructType, Type, TypeImpl,
         UnaryOp,
     };
 
-    /// Methods to process the AST before and after type checking
+    /// A compiler plugin which transforms the source of each crate as it is compiled,
+    /// e.g. to generate the boilerplate required by a framework.
+    ///
+    /// Plugins are run on every crate in the crate graph, in the order they are given.
     pub trait MacroProcessor {
-        /// Function to manipulate the AST before type checking has been completed.
+        /// The name used to select this plugin, e.g. in the `macros` field of `Nargo.toml`.
+        fn name(&self) -> &'static str;
+
+        /// Function to manipulate the AST of a crate's root file once it has been parsed,
+        /// before any of its definitions are collected.
+        ///
+        /// Returning an error stops the compilation of the crate.
         fn process_untyped_ast(
             &self,
             ast: SortedModule,
             crate_id: &CrateId,
             context: &HirContext,
         ) -> Result<SortedModule, (MacroError, FileId)>;
-        /// Function to manipulate the AST after type checking has been completed.
-        /// The AST after type checking has been done is called the HIR.
-        fn process_typed_ast(&self, crate_id: &CrateId, context: &mut HirContext);
+
+        /// Function to manipulate the AST after name resolution, before type checking.
+        /// The AST after name resolution has been done is called the HIR.
+        ///
+        /// Any errors returned are reported alongside the crate's other errors.
+        fn process_typed_ast(
+            &self,
+            crate_id: &CrateId,
+            context: &mut HirContext,
+        ) -> Vec<(MacroError, FileId)>;
     }
 }
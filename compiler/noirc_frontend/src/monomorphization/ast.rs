@@ -204,6 +204,29 @@ pub struct Function {
 
     pub return_type: Type,
     pub unconstrained: bool,
+    pub inline_type: InlineType,
+    /// The location of the function's definition, which contains the locations of its body.
+    pub location: Location,
+}
+
+/// Whether calls to a function should be inlined into its callers.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InlineType {
+    /// The function is inlined into every caller.
+    #[default]
+    Inline,
+    /// The function is kept separate from its callers, marked with `#[no_inline]`.
+    /// ACIR has no way to call a function, so ACIR callers still inline it.
+    NoInline,
+}
+
+impl std::fmt::Display for InlineType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InlineType::Inline => write!(f, "inline"),
+            InlineType::NoInline => write!(f, "no_inline"),
+        }
+    }
 }
 
 /// Compared to hir_def::types::Type, this monomorphized Type has:
@@ -30,7 +30,7 @@ use crate::{
     Visibility,
 };
 
-use self::ast::{Definition, FuncId, Function, LocalId, Program};
+use self::ast::{Definition, FuncId, Function, InlineType, LocalId, Program};
 
 pub mod ast;
 pub mod printer;
@@ -237,12 +237,35 @@ impl<'interner> Monomorphizer<'interner> {
             _ => meta.return_type(),
         });
 
+        // The definition spans from the function's name to the end of its body
+        let body_location = self.interner.expr_location(&body_expr_id);
+        let location = if meta.location.file == body_location.file {
+            Location::new(meta.location.span.merge(body_location.span), body_location.file)
+        } else {
+            body_location
+        };
+
         let parameters = self.parameters(meta.parameters);
         let body = self.expr(body_expr_id);
         let unconstrained = modifiers.is_unconstrained
             || matches!(modifiers.contract_function_type, Some(ContractFunctionType::Open));
 
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = if modifiers.attributes.is_no_inline() {
+            InlineType::NoInline
+        } else {
+            InlineType::Inline
+        };
+
+        let function = ast::Function {
+            id,
+            name,
+            parameters,
+            body,
+            return_type,
+            unconstrained,
+            inline_type,
+            location,
+        };
         self.push_function(id, function);
     }
 
@@ -1146,14 +1169,19 @@ impl<'interner> Monomorphizer<'interner> {
 
     fn lambda(&mut self, lambda: HirLambda, expr: node_interner::ExprId) -> ast::Expression {
         if lambda.captures.is_empty() {
-            self.lambda_no_capture(lambda)
+            self.lambda_no_capture(lambda, expr)
         } else {
             let (setup, closure_variable) = self.lambda_with_setup(lambda, expr);
             ast::Expression::Block(vec![setup, closure_variable])
         }
     }
 
-    fn lambda_no_capture(&mut self, lambda: HirLambda) -> ast::Expression {
+    fn lambda_no_capture(
+        &mut self,
+        lambda: HirLambda,
+        expr: node_interner::ExprId,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&expr);
         let ret_type = self.convert_type(&lambda.return_type);
         let lambda_name = "lambda";
         let parameter_types = vecmap(&lambda.parameters, |(_, typ)| self.convert_type(typ));
@@ -1169,8 +1197,17 @@ impl<'interner> Monomorphizer<'interner> {
         let return_type = ret_type.clone();
         let name = lambda_name.to_owned();
         let unconstrained = false;
-
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = InlineType::default();
+        let function = ast::Function {
+            id,
+            name,
+            parameters,
+            body,
+            return_type,
+            unconstrained,
+            inline_type,
+            location,
+        };
         self.push_function(id, function);
 
         let typ =
@@ -1284,7 +1321,18 @@ impl<'interner> Monomorphizer<'interner> {
         parameters.append(&mut converted_parameters);
 
         let unconstrained = false;
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = InlineType::default();
+        let location = self.interner.expr_location(&expr);
+        let function = ast::Function {
+            id,
+            name,
+            parameters,
+            body,
+            return_type,
+            unconstrained,
+            inline_type,
+            location,
+        };
         self.push_function(id, function);
 
         let lambda_value =
@@ -1402,7 +1450,17 @@ impl<'interner> Monomorphizer<'interner> {
         let name = lambda_name.to_owned();
 
         let unconstrained = false;
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = InlineType::default();
+        let function = ast::Function {
+            id,
+            name,
+            parameters,
+            body,
+            return_type,
+            unconstrained,
+            inline_type,
+            location,
+        };
         self.push_function(id, function);
 
         ast::Expression::Ident(ast::Ident {
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use arena::{Arena, Index};
 use fm::FileId;
@@ -21,8 +21,8 @@ use crate::hir_def::{
 };
 use crate::token::{Attributes, SecondaryAttribute};
 use crate::{
-    ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared, TypeAliasType,
-    TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
+    ContractFunctionType, FunctionDefinition, Generics, ItemVisibility, Path, PathKind, Shared,
+    TypeAliasType, TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
 };
 
 /// An arbitrary number to limit the recursion depth when searching for trait impls.
@@ -51,6 +51,14 @@ pub struct NodeInterner {
     // Contains the source module each function was defined in
     function_modules: HashMap<FuncId, ModuleId>,
 
+    // Each name used as the first segment of a path, along with the module it was used in.
+    // This is used to warn about imports which are never used.
+    used_path_names: HashSet<(ModuleId, String)>,
+
+    // Globals used within types, such as array lengths. These are not referenced by any
+    // expression so they are tracked separately for the dead code analysis.
+    globals_used_in_types: HashSet<StmtId>,
+
     // Map each `Index` to it's own location
     id_to_location: HashMap<Index, Location>,
 
@@ -74,6 +82,8 @@ pub struct NodeInterner {
     structs: HashMap<StructId, Shared<StructType>>,
 
     struct_attributes: HashMap<StructId, StructAttributes>,
+
+    global_attributes: HashMap<StmtId, Vec<SecondaryAttribute>>,
     // Type Aliases map.
     //
     // Map type aliases to the actual type.
@@ -104,12 +114,23 @@ pub struct NodeInterner {
     /// by splitting it up by type.
     trait_implementation_map: HashMap<TraitId, Vec<(Type, TraitImplKind)>>,
 
+    /// The associated types of each `TraitImplKind::Assumed` impl in `trait_implementation_map`,
+    /// which are the implicit generics standing for them in the where clause they come from.
+    assumed_associated_types: HashMap<TraitId, Vec<(Type, Vec<(String, Type)>)>>,
+
     /// When impls are found during type checking, we tag the function call's Ident
     /// with the impl that was selected. For cases with where clauses, this may be
     /// an Assumed (but verified) impl. In this case the monomorphizer should have
     /// the context to get the concrete type of the object and select the correct impl itself.
     selected_trait_implementations: HashMap<ExprId, TraitImplKind>,
 
+    /// The `comptime` blocks which have been resolved but not yet evaluated, in the order
+    /// they were resolved.
+    comptime_expressions: Vec<ExprId>,
+
+    /// The same `comptime` blocks as `comptime_expressions`, to look them up by id.
+    comptime_expression_ids: HashSet<ExprId>,
+
     /// Map from ExprId (referring to a Function/Method call) to its corresponding TypeBindings,
     /// filled out during type checking from instantiated variables. Used during monomorphization
     /// to map call site types back onto function parameter types, and undo this binding as needed.
@@ -172,12 +193,15 @@ pub struct FunctionModifiers {
     pub name: String,
 
     /// Whether the function is `pub` or not.
-    pub visibility: FunctionVisibility,
+    pub visibility: ItemVisibility,
 
     pub attributes: Attributes,
 
     pub is_unconstrained: bool,
 
+    /// Whether the function may only be called at compile-time, from `comptime` code.
+    pub is_comptime: bool,
+
     /// This function's type in its contract.
     /// If this function is not in a contract, this is always 'Secret'.
     pub contract_function_type: Option<ContractFunctionType>,
@@ -195,9 +219,10 @@ impl FunctionModifiers {
     pub fn new() -> Self {
         Self {
             name: String::new(),
-            visibility: FunctionVisibility::Public,
+            visibility: ItemVisibility::Public,
             attributes: Attributes::empty(),
             is_unconstrained: false,
+            is_comptime: false,
             is_internal: None,
             contract_function_type: None,
         }
@@ -412,17 +437,23 @@ impl Default for NodeInterner {
             function_definition_ids: HashMap::new(),
             function_modifiers: HashMap::new(),
             function_modules: HashMap::new(),
+            used_path_names: HashSet::new(),
+            globals_used_in_types: HashSet::new(),
             func_id_to_trait: HashMap::new(),
             id_to_location: HashMap::new(),
             definitions: vec![],
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
             struct_attributes: HashMap::new(),
+            global_attributes: HashMap::new(),
             type_aliases: Vec::new(),
             traits: HashMap::new(),
             trait_implementations: Vec::new(),
             trait_implementation_map: HashMap::new(),
+            assumed_associated_types: HashMap::new(),
             selected_trait_implementations: HashMap::new(),
+            comptime_expressions: Vec::new(),
+            comptime_expression_ids: HashSet::new(),
             instantiation_bindings: HashMap::new(),
             field_indices: HashMap::new(),
             next_type_variable_id: std::cell::Cell::new(0),
@@ -610,8 +641,10 @@ impl NodeInterner {
     }
 
     /// Intern an empty global stmt. Used for collecting globals
-    pub fn push_empty_global(&mut self) -> StmtId {
-        self.push_stmt(HirStatement::Error)
+    pub fn push_empty_global(&mut self, attributes: Vec<SecondaryAttribute>) -> StmtId {
+        let stmt_id = self.push_stmt(HirStatement::Error);
+        self.global_attributes.insert(stmt_id, attributes);
+        stmt_id
     }
 
     pub fn update_global(&mut self, stmt_id: StmtId, hir_stmt: HirStatement) {
@@ -706,6 +739,7 @@ impl NodeInterner {
             visibility: function.visibility,
             attributes: function.attributes.clone(),
             is_unconstrained: function.is_unconstrained,
+            is_comptime: function.is_comptime,
             contract_function_type: Some(if function.is_open { Open } else { Secret }),
             is_internal: Some(function.is_internal),
         };
@@ -737,7 +771,7 @@ impl NodeInterner {
     ///
     /// The underlying function_visibilities map is populated during def collection,
     /// so this function can be called anytime afterward.
-    pub fn function_visibility(&self, func: FuncId) -> FunctionVisibility {
+    pub fn function_visibility(&self, func: FuncId) -> ItemVisibility {
         self.function_modifiers[&func].visibility
     }
 
@@ -746,6 +780,29 @@ impl NodeInterner {
         self.function_modules[&func]
     }
 
+    /// Records that the given path was used within `module`
+    pub fn add_path_reference(&mut self, module: ModuleId, path: &Path) {
+        if path.kind == PathKind::Plain {
+            if let Some(first_segment) = path.segments.first() {
+                self.used_path_names.insert((module, first_segment.0.contents.clone()));
+            }
+        }
+    }
+
+    /// True if a path starting with `name` was used within `module`
+    pub fn is_path_name_used(&self, module: ModuleId, name: &str) -> bool {
+        self.used_path_names.contains(&(module, name.to_string()))
+    }
+
+    /// Records that the given global was used within a type
+    pub fn add_global_used_in_type(&mut self, global: StmtId) {
+        self.globals_used_in_types.insert(global);
+    }
+
+    pub fn is_global_used_in_type(&self, global: StmtId) -> bool {
+        self.globals_used_in_types.contains(&global)
+    }
+
     /// Returns the interned HIR function corresponding to `func_id`
     //
     // Cloning HIR structures is cheap, so we return owned structures
@@ -793,6 +850,10 @@ impl NodeInterner {
         &self.struct_attributes[struct_id]
     }
 
+    pub fn global_attributes(&self, stmt_id: &StmtId) -> &[SecondaryAttribute] {
+        &self.global_attributes[stmt_id]
+    }
+
     /// Returns the interned statement corresponding to `stmt_id`
     pub fn statement(&self, stmt_id: &StmtId) -> HirStatement {
         let def =
@@ -945,6 +1006,28 @@ impl NodeInterner {
         &self.instantiation_bindings[&expr_id]
     }
 
+    pub fn try_get_instantiation_bindings(&self, expr_id: ExprId) -> Option<&TypeBindings> {
+        self.instantiation_bindings.get(&expr_id)
+    }
+
+    /// Records that the given expression is a `comptime` block to be evaluated
+    /// once its crate has been type checked.
+    pub fn push_comptime_expression(&mut self, expr_id: ExprId) {
+        self.comptime_expressions.push(expr_id);
+        self.comptime_expression_ids.insert(expr_id);
+    }
+
+    pub fn is_comptime_expression(&self, expr_id: ExprId) -> bool {
+        self.comptime_expression_ids.contains(&expr_id)
+    }
+
+    /// Returns the `comptime` blocks which have not been evaluated yet, in the order
+    /// they were resolved.
+    pub fn take_comptime_expressions(&mut self) -> Vec<ExprId> {
+        self.comptime_expression_ids.clear();
+        std::mem::take(&mut self.comptime_expressions)
+    }
+
     pub fn get_field_index(&self, expr_id: ExprId) -> usize {
         self.field_indices[&expr_id]
     }
@@ -998,6 +1081,11 @@ impl NodeInterner {
         }
     }
 
+    /// Returns every trait impl, including those of dependencies
+    pub fn trait_implementations(&self) -> impl Iterator<Item = &Shared<TraitImpl>> {
+        self.trait_implementations.iter()
+    }
+
     pub fn get_trait_implementation(&self, id: TraitImplId) -> Shared<TraitImpl> {
         self.trait_implementations[id.0].clone()
     }
@@ -1122,17 +1210,86 @@ impl NodeInterner {
         &mut self,
         object_type: Type,
         trait_id: TraitId,
+        associated_types: Vec<(String, Type)>,
     ) -> bool {
         // Make sure there are no overlapping impls
         if self.try_lookup_trait_implementation(&object_type, trait_id).is_ok() {
             return false;
         }
 
+        let entries = self.assumed_associated_types.entry(trait_id).or_default();
+        entries.push((object_type.clone(), associated_types));
+
         let entries = self.trait_implementation_map.entry(trait_id).or_default();
         entries.push((object_type.clone(), TraitImplKind::Assumed { object_type }));
         true
     }
 
+    /// Returns the associated types and constants given by the impl selected for `object_type`.
+    /// For normal impls these may refer to the impl's generics or to the associated types of
+    /// the impl's where clause, which are substituted with their values for `object_type` here.
+    pub fn get_associated_types(
+        &self,
+        object_type: &Type,
+        trait_id: TraitId,
+        impl_kind: &TraitImplKind,
+    ) -> Vec<(String, Type)> {
+        let impl_id = match impl_kind {
+            TraitImplKind::Normal(impl_id) => *impl_id,
+            TraitImplKind::Assumed { object_type } => {
+                let entries = self.assumed_associated_types.get(&trait_id);
+                let entry = entries.and_then(|entries| {
+                    entries.iter().find(|(assumed_type, _)| assumed_type == object_type)
+                });
+                return entry
+                    .map(|(_, associated_types)| associated_types.clone())
+                    .unwrap_or_default();
+            }
+        };
+
+        let trait_impl = self.get_trait_implementation(impl_id);
+        let trait_impl = trait_impl.borrow();
+        if trait_impl.associated_types.is_empty() {
+            return Vec::new();
+        }
+
+        let (impl_type, instantiation_bindings) = trait_impl.typ.instantiate_type_variables(self);
+        let mut bindings = TypeBindings::new();
+        if impl_type.try_unify(object_type, &mut bindings).is_err() {
+            return Vec::new();
+        }
+
+        for constraint in &trait_impl.where_clause {
+            if constraint.associated_types.is_empty() {
+                continue;
+            }
+
+            let constraint_type = constraint.typ.substitute(&instantiation_bindings);
+            let constraint_type = constraint_type.substitute(&bindings);
+            let values =
+                match self.try_lookup_trait_implementation(&constraint_type, constraint.trait_id) {
+                    Ok((impl_kind, _)) => {
+                        self.get_associated_types(&constraint_type, constraint.trait_id, &impl_kind)
+                    }
+                    Err(_) => continue,
+                };
+
+            for (name, generic) in &constraint.associated_types {
+                let value = values.iter().find(|(value_name, _)| value_name == name);
+                if let (Type::NamedGeneric(type_var, _), Some((_, value))) = (generic, value) {
+                    if let TypeBinding::Unbound(id) = &*type_var.borrow() {
+                        bindings.insert(*id, (type_var.clone(), value.clone()));
+                    }
+                }
+            }
+        }
+
+        vecmap(&trait_impl.associated_types, |(name, typ)| {
+            let typ = typ.substitute(&instantiation_bindings).substitute(&bindings);
+            (name.0.contents.clone(), typ)
+        })
+    }
+
     /// Adds a trait implementation to the list of known implementations.
     pub fn add_trait_implementation(
         &mut self,
@@ -1249,6 +1406,7 @@ impl NodeInterner {
     pub fn remove_assumed_trait_implementations_for_trait(&mut self, trait_id: TraitId) {
         let entries = self.trait_implementation_map.entry(trait_id).or_default();
         entries.retain(|(_, kind)| matches!(kind, TraitImplKind::Normal(_)));
+        self.assumed_associated_types.remove(&trait_id);
     }
 
     /// Tags the given identifier with the selected trait_impl so that monomorphization
@@ -36,12 +36,36 @@ pub enum ParserErrorReason {
     MultipleFunctionAttributesFound,
     #[error("A function attribute cannot be placed on a struct")]
     NoFunctionAttributesAllowedOnStruct,
+    #[error("A function attribute cannot be placed on a {0}")]
+    NoFunctionAttributesAllowedOnItem(&'static str),
     #[error("Assert statements can only accept string literals")]
     AssertMessageNotString,
     #[error("{0}")]
     Lexer(LexerErrorKind),
 }
 
+impl ParserErrorReason {
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParserErrorReason::ExpectedFieldName(_) => "E0002",
+            ParserErrorReason::ExpectedPatternButFoundType(_) => "E0003",
+            ParserErrorReason::MissingSeparatingSemi => "E0004",
+            ParserErrorReason::ConstrainDeprecated => "E0005",
+            ParserErrorReason::InvalidArrayLengthExpression(_) => "E0006",
+            ParserErrorReason::EarlyReturn => "E0007",
+            ParserErrorReason::PatternInTraitFunctionParameter => "E0008",
+            ParserErrorReason::ComptimeDeprecated => "E0009",
+            ParserErrorReason::ExperimentalFeature(_) => "E0010",
+            ParserErrorReason::MultipleFunctionAttributesFound => "E0011",
+            ParserErrorReason::NoFunctionAttributesAllowedOnStruct => "E0012",
+            ParserErrorReason::AssertMessageNotString => "E0013",
+            ParserErrorReason::NoFunctionAttributesAllowedOnItem(_) => "E0014",
+            ParserErrorReason::Lexer(error) => error.code(),
+        }
+    }
+}
+
 /// Represents a parsing error, or a parsing error in the making.
 ///
 /// `ParserError` is used extensively by the parser, as it not only used to report badly formed
@@ -101,6 +125,15 @@ impl ParserError {
     pub fn is_warning(&self) -> bool {
         matches!(self.reason(), Some(ParserErrorReason::ExperimentalFeature(_)))
     }
+
+    /// The stable error code of this error.
+    pub fn code(&self) -> &'static str {
+        match self.reason() {
+            Some(reason) => reason.code(),
+            // Errors without a reason are all unexpected tokens
+            None => "E0001",
+        }
+    }
 }
 
 impl std::fmt::Display for ParserError {
@@ -130,7 +163,8 @@ impl std::fmt::Display for ParserError {
 
 impl From<ParserError> for Diagnostic {
     fn from(error: ParserError) -> Diagnostic {
-        match error.reason {
+        let code = error.code();
+        let mut diagnostic = match error.reason {
             Some(reason) => {
                 match reason {
                     ParserErrorReason::ConstrainDeprecated => Diagnostic::simple_error(
@@ -161,7 +195,9 @@ impl From<ParserError> for Diagnostic {
                 let primary = error.to_string();
                 Diagnostic::simple_error(primary, String::new(), error.span)
             }
-        }
+        };
+        diagnostic.code = Some(code);
+        diagnostic
     }
 }
 
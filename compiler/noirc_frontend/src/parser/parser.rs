@@ -26,8 +26,8 @@
 use super::{
     foldl_with_span, labels::ParsingRuleLabel, parameter_name_recovery, parameter_recovery,
     parenthesized, then_commit, then_commit_ignore, top_level_statement_recovery, ExprParser,
-    NoirParser, ParsedModule, ParsedSubModule, ParserError, ParserErrorReason, Precedence,
-    TopLevelStatement,
+    ModuleDeclaration, NoirParser, ParsedModule, ParsedSubModule, ParserError, ParserErrorReason,
+    Precedence, TopLevelStatement,
 };
 use super::{spanned, Item, ItemKind};
 use crate::ast::{
@@ -38,11 +38,11 @@ use crate::parser::{force, ignore_then_commit, statement_recovery};
 use crate::token::{Attribute, Attributes, Keyword, SecondaryAttribute, Token, TokenKind};
 use crate::{
     BinaryOp, BinaryOpKind, BlockExpression, ConstrainKind, ConstrainStatement, Distinctness,
-    ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility, Ident,
-    IfExpression, InfixExpression, LValue, Lambda, Literal, NoirFunction, NoirStruct, NoirTrait,
+    ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, Ident, IfExpression,
+    InfixExpression, ItemVisibility, LValue, Lambda, Literal, NoirFunction, NoirStruct, NoirTrait,
     NoirTraitImpl, NoirTypeAlias, Param, Path, PathKind, Pattern, Recoverable, Statement,
-    TraitBound, TraitImplItem, TraitItem, TypeImpl, UnaryOp, UnresolvedTraitConstraint,
-    UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility,
+    StructField, TraitBound, TraitImplItem, TraitItem, TypeImpl, UnaryOp,
+    UnresolvedTraitConstraint, UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility,
 };
 
 use chumsky::prelude::*;
@@ -82,14 +82,18 @@ fn module() -> impl NoirParser<ParsedModule> {
                 match statement {
                     TopLevelStatement::Function(f) => push_item(ItemKind::Function(f)),
                     TopLevelStatement::Module(m) => push_item(ItemKind::ModuleDecl(m)),
-                    TopLevelStatement::Import(i) => push_item(ItemKind::Import(i)),
+                    TopLevelStatement::Import(i, attributes) => {
+                        push_item(ItemKind::Import(i, attributes));
+                    }
                     TopLevelStatement::Struct(s) => push_item(ItemKind::Struct(s)),
                     TopLevelStatement::Trait(t) => push_item(ItemKind::Trait(t)),
                     TopLevelStatement::TraitImpl(t) => push_item(ItemKind::TraitImpl(t)),
                     TopLevelStatement::Impl(i) => push_item(ItemKind::Impl(i)),
                     TopLevelStatement::TypeAlias(t) => push_item(ItemKind::TypeAlias(t)),
                     TopLevelStatement::SubModule(s) => push_item(ItemKind::Submodules(s)),
-                    TopLevelStatement::Global(c) => push_item(ItemKind::Global(c)),
+                    TopLevelStatement::Global(c, visibility, attributes) => {
+                        push_item(ItemKind::Global(c, visibility, attributes));
+                    }
                     TopLevelStatement::Error => (),
                 }
                 program
@@ -124,7 +128,7 @@ fn top_level_statement(
     .recover_via(top_level_statement_recovery())
 }
 
-/// global_declaration: 'global' ident global_type_annotation '=' literal
+/// global_declaration: attributes? item_visibility 'global' ident global_type_annotation '=' literal
 fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     let p = ignore_then_commit(
         keyword(Keyword::Global).labelled(ParsingRuleLabel::Global),
@@ -133,18 +137,29 @@ fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     let p = then_commit(p, optional_type_annotation());
     let p = then_commit_ignore(p, just(Token::Assign));
     let p = then_commit(p, literal_or_collection(expression()).map_with_span(Expression::new));
-    p.map(LetStatement::new_let).map(TopLevelStatement::Global)
+    attributes().or_not().then(item_visibility()).then(p.map(LetStatement::new_let)).validate(
+        |((raw_attributes, visibility), global), span, emit| {
+            let attributes = validate_secondary_attributes("global", raw_attributes, span, emit);
+            TopLevelStatement::Global(global, visibility, attributes)
+        },
+    )
 }
 
-/// submodule: 'mod' ident '{' module '}'
+/// submodule: item_visibility 'mod' ident '{' module '}'
 fn submodule(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod)
-        .ignore_then(ident())
+    item_visibility()
+        .then_ignore(keyword(Keyword::Mod))
+        .then(ident())
         .then_ignore(just(Token::LeftBrace))
         .then(module_parser)
         .then_ignore(just(Token::RightBrace))
-        .map(|(name, contents)| {
-            TopLevelStatement::SubModule(ParsedSubModule { name, contents, is_contract: false })
+        .map(|((visibility, name), contents)| {
+            TopLevelStatement::SubModule(ParsedSubModule {
+                name,
+                visibility,
+                contents,
+                is_contract: false,
+            })
         })
 }
 
@@ -156,7 +171,12 @@ fn contract(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<Top
         .then(module_parser)
         .then_ignore(just(Token::RightBrace))
         .map(|(name, contents)| {
-            TopLevelStatement::SubModule(ParsedSubModule { name, contents, is_contract: true })
+            TopLevelStatement::SubModule(ParsedSubModule {
+                name,
+                visibility: ItemVisibility::Public,
+                contents,
+                is_contract: true,
+            })
         })
 }
 
@@ -183,14 +203,15 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
                 name,
                 attributes,
                 is_unconstrained: modifiers.0,
+                is_comptime: modifiers.5,
                 is_open: modifiers.2,
                 is_internal: modifiers.3,
                 visibility: if modifiers.1 {
-                    FunctionVisibility::PublicCrate
+                    ItemVisibility::PublicCrate
                 } else if modifiers.4 {
-                    FunctionVisibility::Public
+                    ItemVisibility::Public
                 } else {
-                    FunctionVisibility::Private
+                    ItemVisibility::Private
                 },
                 generics,
                 parameters,
@@ -204,23 +225,25 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
         })
 }
 
-/// function_modifiers: 'unconstrained'? 'pub(crate)'? 'pub'? 'open'? 'internal'?
+/// function_modifiers: 'unconstrained'? 'pub(crate)'? 'pub'? 'open'? 'internal'? 'comptime'?
 ///
-/// returns (is_unconstrained, is_pub_crate, is_open, is_internal, is_pub) for whether each keyword was present
-fn function_modifiers() -> impl NoirParser<(bool, bool, bool, bool, bool)> {
+/// returns (is_unconstrained, is_pub_crate, is_open, is_internal, is_pub, is_comptime) for whether each keyword was present
+fn function_modifiers() -> impl NoirParser<(bool, bool, bool, bool, bool, bool)> {
     keyword(Keyword::Unconstrained)
         .or_not()
         .then(is_pub_crate())
         .then(keyword(Keyword::Pub).or_not())
         .then(keyword(Keyword::Open).or_not())
         .then(keyword(Keyword::Internal).or_not())
-        .map(|((((unconstrained, pub_crate), public), open), internal)| {
+        .then(keyword(Keyword::CompTime).or_not())
+        .map(|(((((unconstrained, pub_crate), public), open), internal), comptime)| {
             (
                 unconstrained.is_some(),
                 pub_crate,
                 open.is_some(),
                 internal.is_some(),
                 public.is_some(),
+                comptime.is_some(),
             )
         })
 }
@@ -233,6 +256,23 @@ fn is_pub_crate() -> impl NoirParser<bool> {
     .map(|a| a.is_some())
 }
 
+/// item_visibility: 'pub(crate)'
+///                | 'pub'
+///                | %empty
+///
+/// Items without a visibility modifier are private to their module
+fn item_visibility() -> impl NoirParser<ItemVisibility> {
+    is_pub_crate().then(keyword(Keyword::Pub).or_not()).map(|(pub_crate, public)| {
+        if pub_crate {
+            ItemVisibility::PublicCrate
+        } else if public.is_some() {
+            ItemVisibility::Public
+        } else {
+            ItemVisibility::Private
+        }
+    })
+}
+
 /// non_empty_ident_list: ident ',' non_empty_ident_list
 ///                     | ident
 ///
@@ -264,13 +304,21 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
 
     attributes()
         .or_not()
+        .then(item_visibility())
         .then_ignore(keyword(Struct))
         .then(ident())
         .then(generics())
         .then(fields)
-        .validate(|(((raw_attributes, name), generics), fields), span, emit| {
+        .validate(|((((raw_attributes, visibility), name), generics), fields), span, emit| {
             let attributes = validate_struct_attributes(raw_attributes, span, emit);
-            TopLevelStatement::Struct(NoirStruct { name, attributes, generics, fields, span })
+            TopLevelStatement::Struct(NoirStruct {
+                name,
+                attributes,
+                visibility,
+                generics,
+                fields,
+                span,
+            })
         })
 }
 
@@ -320,10 +368,12 @@ fn attributes() -> impl NoirParser<Vec<Attribute>> {
     attribute().repeated()
 }
 
-fn struct_fields() -> impl NoirParser<Vec<(Ident, UnresolvedType)>> {
-    ident()
+fn struct_fields() -> impl NoirParser<Vec<StructField>> {
+    item_visibility()
+        .then(ident())
         .then_ignore(just(Token::Colon))
         .then(parse_type())
+        .map(|((visibility, name), typ)| StructField { visibility, name, typ })
         .separated_by(just(Token::Comma))
         .allow_trailing()
 }
@@ -406,21 +456,29 @@ fn self_parameter() -> impl NoirParser<Param> {
 }
 
 fn trait_definition() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Trait)
-        .ignore_then(ident())
+    item_visibility()
+        .then_ignore(keyword(Keyword::Trait))
+        .then(ident())
         .then(generics())
         .then(where_clause())
         .then_ignore(just(Token::LeftBrace))
         .then(trait_body())
         .then_ignore(just(Token::RightBrace))
-        .validate(|(((name, generics), where_clause), items), span, emit| {
+        .validate(|((((visibility, name), generics), where_clause), items), span, emit| {
             if !generics.is_empty() {
                 emit(ParserError::with_reason(
                     ParserErrorReason::ExperimentalFeature("Generic traits"),
                     span,
                 ));
             }
-            TopLevelStatement::Trait(NoirTrait { name, generics, where_clause, span, items })
+            TopLevelStatement::Trait(NoirTrait {
+                name,
+                visibility,
+                generics,
+                where_clause,
+                span,
+                items,
+            })
         })
 }
 
@@ -442,13 +500,7 @@ fn trait_constant_declaration() -> impl NoirParser<TraitItem> {
         .then(parse_type())
         .then(optional_default_value())
         .then_ignore(just(Token::Semicolon))
-        .validate(|((name, typ), default_value), span, emit| {
-            emit(ParserError::with_reason(
-                ParserErrorReason::ExperimentalFeature("Associated constants"),
-                span,
-            ));
-            TraitItem::Constant { name, typ, default_value }
-        })
+        .map(|((name, typ), default_value)| TraitItem::Constant { name, typ, default_value })
 }
 
 /// trait_function_declaration: 'fn' ident generics '(' declaration_parameters ')' function_return_type
@@ -523,6 +575,30 @@ fn validate_struct_attributes(
     struct_attributes
 }
 
+/// Validates the attributes of an item which, like a struct, may only have secondary attributes.
+fn validate_secondary_attributes(
+    item: &'static str,
+    attributes: Option<Vec<Attribute>>,
+    span: Span,
+    emit: &mut dyn FnMut(ParserError),
+) -> Vec<SecondaryAttribute> {
+    let mut secondary_attributes = vec![];
+
+    for attribute in attributes.unwrap_or_default() {
+        match attribute {
+            Attribute::Function(..) => {
+                emit(ParserError::with_reason(
+                    ParserErrorReason::NoFunctionAttributesAllowedOnItem(item),
+                    span,
+                ));
+            }
+            Attribute::Secondary(attr) => secondary_attributes.push(attr),
+        }
+    }
+
+    secondary_attributes
+}
+
 /// Function declaration parameters differ from other parameters in that parameter
 /// patterns are not allowed in declarations. All parameters must be identifiers.
 fn function_declaration_parameters() -> impl NoirParser<Vec<(Ident, UnresolvedType)>> {
@@ -555,15 +631,10 @@ fn function_declaration_parameters() -> impl NoirParser<Vec<(Ident, UnresolvedTy
 
 /// trait_type_declaration: 'type' ident generics
 fn trait_type_declaration() -> impl NoirParser<TraitItem> {
-    keyword(Keyword::Type).ignore_then(ident()).then_ignore(just(Token::Semicolon)).validate(
-        |name, span, emit| {
-            emit(ParserError::with_reason(
-                ParserErrorReason::ExperimentalFeature("Associated types"),
-                span,
-            ));
-            TraitItem::Type { name }
-        },
-    )
+    keyword(Keyword::Type)
+        .ignore_then(ident())
+        .then_ignore(just(Token::Semicolon))
+        .map(|name| TraitItem::Type { name })
 }
 
 /// Parses a non-trait implementation, adding a set of methods to a type.
@@ -622,7 +693,16 @@ fn trait_implementation_body() -> impl NoirParser<Vec<TraitImplItem>> {
         .then_ignore(just(Token::Semicolon))
         .map(|(name, alias)| TraitImplItem::Type { name, alias });
 
-    function.or(alias).repeated()
+    let constant = keyword(Keyword::Let)
+        .ignore_then(ident())
+        .then_ignore(just(Token::Colon))
+        .then(parse_type())
+        .then_ignore(just(Token::Assign))
+        .then(expression())
+        .then_ignore(just(Token::Semicolon))
+        .map(|((name, typ), value)| TraitImplItem::Constant(name, typ, value));
+
+    function.or(alias).or(constant).repeated()
 }
 
 fn where_clause() -> impl NoirParser<Vec<UnresolvedTraitConstraint>> {
@@ -712,11 +792,18 @@ fn optional_type_annotation<'a>() -> impl NoirParser<UnresolvedType> + 'a {
 }
 
 fn module_declaration() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod).ignore_then(ident()).map(TopLevelStatement::Module)
+    item_visibility().then_ignore(keyword(Keyword::Mod)).then(ident()).map(|(visibility, ident)| {
+        TopLevelStatement::Module(ModuleDeclaration { visibility, ident })
+    })
 }
 
 fn use_statement() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Use).ignore_then(use_tree()).map(TopLevelStatement::Import)
+    attributes().or_not().then_ignore(keyword(Keyword::Use)).then(use_tree()).validate(
+        |(raw_attributes, use_tree), span, emit| {
+            let attributes = validate_secondary_attributes("import", raw_attributes, span, emit);
+            TopLevelStatement::Import(use_tree, attributes)
+        },
+    )
 }
 
 fn keyword(keyword: Keyword) -> impl NoirParser<Token> {
@@ -1068,6 +1155,13 @@ fn optional_distinctness() -> impl NoirParser<Distinctness> {
     })
 }
 
+fn comptime_expr<'a, S>(statement: S) -> impl NoirParser<ExpressionKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::CompTime).ignore_then(block(statement)).map(ExpressionKind::Comptime)
+}
+
 fn maybe_comp_time() -> impl NoirParser<()> {
     keyword(Keyword::CompTime).or_not().validate(|opt, span, emit| {
         if opt.is_some() {
@@ -1608,6 +1702,7 @@ where
             nothing().boxed()
         },
         lambda(expr_parser.clone()),
+        comptime_expr(statement.clone()),
         block(statement).map(ExpressionKind::Block),
         variable(),
         literal(),
@@ -2174,6 +2269,8 @@ mod test {
                 "fn f<T>(f: pub Field, y : T, z : Field) -> u8 { x + a }",
                 "fn func_name(x: [Field], y : [Field;2],y : pub [Field;2], z : pub [u8;5])  {}",
                 "fn main(x: pub u8, y: pub u8) -> distinct pub [u8; 2] { [x, y] }",
+                "comptime fn f(x: Field) -> Field { x + 1 }",
+                "pub comptime fn f(x: Field) -> Field { x + 1 }",
                 "fn f(f: pub Field, y : Field, z : comptime Field) -> u8 { x + a }",
                 "fn f<T>(f: pub Field, y : T, z : comptime Field) -> u8 { x + a }",
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait {}",
@@ -2382,6 +2479,7 @@ mod test {
                 "use foo::{bar as bar2, hello}",
                 "use foo::{bar as bar2, hello::{foo}, nested::{foo, bar}}",
                 "use dep::{std::println, bar::baz}",
+                "#[allow(dead_code)] use foo::bar",
             ],
         );
 
@@ -2394,6 +2492,7 @@ mod test {
                 "use foo bar::baz",
                 "use foo bar::{baz}",
                 "use foo::{,}",
+                "#[test] use foo::bar",
             ],
         );
     }
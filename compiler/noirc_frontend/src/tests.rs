@@ -10,7 +10,7 @@ mod test {
     use fm::FileId;
 
     use iter_extended::vecmap;
-    use noirc_errors::Location;
+    use noirc_errors::{CustomDiagnostic, Location};
 
     use crate::hir::def_collector::dc_crate::CompilationError;
     use crate::hir::def_collector::errors::{DefCollectorErrorKind, DuplicateType};
@@ -21,11 +21,14 @@ mod test {
     use crate::hir::Context;
     use crate::node_interner::{NodeInterner, StmtId};
 
+    use crate::graph::CrateId;
+    use crate::hir::comptime::InterpreterError;
     use crate::hir::def_collector::dc_crate::DefCollector;
-    use crate::hir_def::expr::HirExpression;
+    use crate::hir_def::expr::{HirArrayLiteral, HirExpression, HirLiteral};
     use crate::hir_def::stmt::HirStatement;
+    use crate::macros_api::{MacroError, MacroProcessor};
     use crate::monomorphization::monomorphize;
-    use crate::parser::ParserErrorReason;
+    use crate::parser::{ParserErrorReason, SortedModule};
     use crate::ParsedModule;
     use crate::{
         hir::def_map::{CrateDefMap, LocalModuleId},
@@ -47,8 +50,30 @@ mod test {
         });
     }
 
+    /// Removes the dead code warnings of programs which declare items that are never used
+    /// only to check for other errors.
+    pub(crate) fn remove_dead_code_warnings(errors: &mut Vec<(CompilationError, FileId)>) {
+        errors.retain(|(error, _)| {
+            !matches!(
+                error,
+                CompilationError::ResolverError(
+                    ResolverError::UnusedPrivateItem { .. }
+                        | ResolverError::UnusedStructField { .. }
+                        | ResolverError::UnusedTraitImpl { .. }
+                )
+            )
+        });
+    }
+
     pub(crate) fn get_program(
         src: &str,
+    ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
+        get_program_with_macros(src, Vec::new())
+    }
+
+    pub(crate) fn get_program_with_macros(
+        src: &str,
+        macro_processors: Vec<&dyn MacroProcessor>,
     ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
         let root = std::path::Path::new("/");
         let fm = FileManager::new(root);
@@ -78,7 +103,7 @@ mod test {
                 &mut context,
                 program.clone().into_sorted(),
                 root_file_id,
-                Vec::new(), // No macro processors
+                macro_processors,
             ));
         }
         (program, context, errors)
@@ -113,7 +138,8 @@ mod test {
         
         fn main() {}";
 
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
 
@@ -154,7 +180,8 @@ mod test {
         fn main() {
         }
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
 
@@ -195,7 +222,8 @@ mod test {
         
         fn main() {
         }";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
 
@@ -240,7 +268,8 @@ mod test {
         fn main() {
         }
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
 
@@ -280,7 +309,8 @@ mod test {
         }
         
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
         for (err, _file_id) in errors {
@@ -317,7 +347,8 @@ mod test {
         
         fn main() {
         }";
-        let compilation_errors = get_program_errors(src);
+        let mut compilation_errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut compilation_errors);
         assert!(!has_parser_error(&compilation_errors));
         assert!(
             compilation_errors.len() == 1,
@@ -361,7 +392,8 @@ mod test {
         fn main() {
         }
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
 
@@ -405,7 +437,8 @@ mod test {
         fn main() {
         }";
 
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
 
@@ -477,7 +510,8 @@ mod test {
         fn main() {
         }
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
         for (err, _file_id) in errors {
@@ -560,7 +594,8 @@ mod test {
         }
         
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
         for (err, _file_id) in errors {
@@ -602,7 +637,8 @@ mod test {
         
         fn main() {
         }";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
         for (err, _file_id) in errors {
@@ -639,7 +675,8 @@ mod test {
         fn main() {
         }
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
         for (err, _file_id) in errors {
@@ -677,7 +714,8 @@ mod test {
         fn main() {
         }
         ";
-        let errors = get_program_errors(src);
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
         assert!(!has_parser_error(&errors));
         assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
         for (err, _file_id) in errors {
@@ -794,6 +832,99 @@ mod test {
         }
     }
 
+    #[test]
+    fn does_not_warn_on_unused_self() {
+        let src = r#"
+            struct Foo {}
+
+            impl Foo {
+                fn zero(self) -> Field {
+                    0
+                }
+            }
+
+            fn main() {
+                let foo = Foo {};
+                assert(foo.zero() == 0);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn diagnostics_carry_stable_error_codes() {
+        let src = r#"
+            fn main(x : Field) {
+                assert(x == z);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+
+        let diagnostic = CustomDiagnostic::from(errors[0].0.clone());
+        assert_eq!(diagnostic.code, Some("E0106"));
+    }
+
+    #[test]
+    fn every_error_code_is_registered() {
+        // The sources of every `code()` function in the frontend
+        let sources = [
+            include_str!("lexer/errors.rs"),
+            include_str!("parser/errors.rs"),
+            include_str!("hir/comptime/errors.rs"),
+            include_str!("hir/def_collector/errors.rs"),
+            include_str!("hir/resolution/errors.rs"),
+            include_str!("hir/resolution/import.rs"),
+            include_str!("hir/type_check/errors.rs"),
+        ];
+
+        let mut codes = 0;
+        for source in sources {
+            for (index, _) in source.match_indices("=> \"E") {
+                let code = &source[index + 4..index + 9];
+                assert!(
+                    noirc_errors::error_codes::is_known_code(code),
+                    "{code} is not registered in noirc_errors::error_codes"
+                );
+                codes += 1;
+            }
+        }
+        assert!(codes > 100, "Expected to find the frontend's error codes, found {codes}");
+    }
+
+    #[test]
+    fn test_functions_may_have_program_input_parameters() {
+        let src = r#"
+            fn main() {}
+
+            #[test]
+            fn test_addition(x: u8, y: [Field; 2]) {
+                assert(y[0] + y[1] != x as Field + 1);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn test_function_parameters_must_be_valid_program_inputs() {
+        let src = r#"
+            fn main() {}
+
+            #[test]
+            fn test_mutation(x: &mut Field) {
+                *x = 1;
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::ResolverError(ResolverError::InvalidTypeForEntryPoint { .. })
+        ));
+    }
+
     #[test]
     fn resolve_unresolved_var() {
         let src = r#"
@@ -1111,4 +1242,624 @@ fn lambda$f1(mut env$l1: (Field)) -> Field {
 "#;
         check_rewrite(src, expected_rewrite);
     }
+
+    #[test]
+    fn comptime_block_is_replaced_by_its_value() {
+        let src = r#"
+        fn triangle(n: u32) -> u32 {
+            let mut sum = 0;
+            for i in 0..n {
+                sum = sum + i + 1;
+            }
+            sum
+        }
+
+        fn main() -> pub [u32; 4] {
+            let table = comptime {
+                let mut table = [0; 4];
+                for i in 0..4 {
+                    table[i] = triangle(i as u32);
+                }
+                table
+            };
+            table
+        }
+        "#;
+
+        let (_program, context, errors) = get_program(src);
+        assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+
+        let interner = &context.def_interner;
+        let main = interner.find_function("main").unwrap();
+        let statements = interner.function(&main).block(interner).0;
+        let table = match interner.statement(&statements[0]) {
+            HirStatement::Let(let_statement) => let_statement.expression,
+            other => panic!("Expected a let statement, found {other:?}"),
+        };
+        let elements = match interner.expression(&table) {
+            HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements))) => {
+                elements
+            }
+            other => panic!("Expected comptime block to be replaced by an array, found {other:?}"),
+        };
+        let values = vecmap(elements, |element| match interner.expression(&element) {
+            HirExpression::Literal(HirLiteral::Integer(value, false)) => value.to_u128(),
+            other => panic!("Expected an integer literal, found {other:?}"),
+        });
+        assert_eq!(values, vec![0, 1, 3, 6]);
+    }
+
+    #[test]
+    fn comptime_global_as_array_length() {
+        let src = r#"
+        global SIZE = comptime {
+            let mut size = 1;
+            for i in 0..3 {
+                size = size + i;
+            }
+            size
+        };
+
+        fn main(x: [Field; SIZE]) -> pub [Field; 4] {
+            x
+        }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+    }
+
+    #[test]
+    fn comptime_block_errors() {
+        let src = r#"
+        fn main(x: Field) -> pub Field {
+            comptime {
+                assert(1 == 2, "unreachable");
+            }
+            comptime { x + 1 }
+        }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 2, "Expected 2 errors, got: {errors:?}");
+        for (error, _file_id) in errors {
+            match error {
+                CompilationError::InterpreterError(InterpreterError::AssertionFailed {
+                    message,
+                    ..
+                }) => {
+                    assert_eq!(message.as_deref(), Some("unreachable"));
+                }
+                CompilationError::InterpreterError(
+                    InterpreterError::NonComptimeVarReferenced { name, .. },
+                ) => {
+                    assert_eq!(name, "x");
+                }
+                _ => panic!("Unexpected error: {error:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn comptime_method_call_in_global_array_length() {
+        let src = r#"
+        global SIZE = comptime {
+            let table = [1, 2, 3];
+            table.len()
+        };
+
+        fn main(x: [Field; SIZE]) -> pub [Field; 3] {
+            x
+        }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+    }
+
+    #[test]
+    fn comptime_global_array_length_cannot_call_functions() {
+        // `triple` is resolved before `main`, but calling it is rejected regardless of order
+        let src = r#"
+        fn triple(x: u32) -> u32 {
+            x * 3
+        }
+
+        global SIZE = comptime { triple(2) };
+
+        fn main(x: [Field; SIZE]) -> pub [Field; 6] {
+            x
+        }
+        "#;
+
+        let errors = get_program_errors(src);
+        let called_before_resolution = errors.iter().any(|(error, _)| {
+            matches!(
+                error,
+                CompilationError::ResolverError(ResolverError::ComptimeError(
+                    InterpreterError::CallBeforeResolution { name, .. },
+                )) if name == "triple"
+            )
+        });
+        assert!(called_before_resolution, "Expected `triple` to be rejected, got: {errors:?}");
+    }
+
+    #[test]
+    fn comptime_function_called_from_comptime_code() {
+        let src = r#"
+        comptime fn double(x: Field) -> Field {
+            x * 2
+        }
+
+        comptime fn quadruple(x: Field) -> Field {
+            double(double(x))
+        }
+
+        fn main() -> pub Field {
+            comptime { quadruple(3) }
+        }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 0, "Expected no errors, got: {errors:?}");
+    }
+
+    #[test]
+    fn comptime_function_called_at_runtime() {
+        let src = r#"
+        comptime fn double(x: Field) -> Field {
+            x * 2
+        }
+
+        fn main(x: Field) -> pub Field {
+            double(x)
+        }
+        "#;
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {errors:?}");
+        match &errors[0].0 {
+            CompilationError::ResolverError(ResolverError::ComptimeFunctionCalledAtRuntime {
+                name,
+                ..
+            }) => {
+                assert_eq!(name, "double");
+            }
+            error => panic!("Unexpected error: {error:?}"),
+        }
+    }
+
+    #[test]
+    fn trait_associated_type_in_signatures() {
+        let src = "
+        trait Container {
+            type Item;
+            fn first(self) -> Self::Item;
+        }
+
+        struct Wrapper {
+            inner: [u8; 2],
+        }
+
+        impl Container for Wrapper {
+            type Item = u8;
+            fn first(self) -> u8 {
+                self.inner[0]
+            }
+        }
+
+        fn first_of<T>(container: T) -> T::Item where T: Container {
+            container.first()
+        }
+
+        fn main() {
+            let x: u8 = first_of(Wrapper { inner: [1, 2] });
+            assert(x == 1);
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {errors:?}");
+    }
+
+    #[test]
+    fn trait_associated_type_mismatch() {
+        let src = "
+        trait Container {
+            type Item;
+            fn first(self) -> Self::Item;
+        }
+
+        struct Wrapper {
+            inner: [u8; 2],
+        }
+
+        impl Container for Wrapper {
+            type Item = u8;
+            fn first(self) -> u8 {
+                self.inner[0]
+            }
+        }
+
+        fn first_of<T>(container: T) -> T::Item where T: Container {
+            container.first()
+        }
+
+        fn main() {
+            let _x: bool = first_of(Wrapper { inner: [1, 2] });
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {errors:?}");
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn trait_associated_constant_as_array_length() {
+        let src = "
+        trait Sized {
+            let N: u64;
+        }
+
+        struct Pair {}
+
+        impl Sized for Pair {
+            let N: u64 = 2;
+        }
+
+        fn sum<T>(_x: T, values: [Field; T::N]) -> Field where T: Sized {
+            let mut sum = 0;
+            for i in 0..T::N {
+                sum += values[i];
+            }
+            sum
+        }
+
+        fn main() {
+            assert(sum(Pair {}, [1, 2]) == 3);
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {errors:?}");
+    }
+
+    #[test]
+    fn trait_impl_associated_items_must_match_trait() {
+        let src = "
+        trait Container {
+            type Item;
+        }
+
+        struct Foo {}
+
+        impl Container for Foo {
+            type Other = u8;
+        }
+
+        fn main() {}
+        ";
+
+        let mut errors = get_program_errors(src);
+        remove_dead_code_warnings(&mut errors);
+        assert_eq!(errors.len(), 2, "Expected 2 errors, got: {errors:?}");
+        for (error, _file_id) in errors {
+            match error {
+                CompilationError::DefinitionError(
+                    DefCollectorErrorKind::AssociatedItemNotInTrait { item_name, .. },
+                ) => assert_eq!(item_name.0.contents, "Other"),
+                CompilationError::DefinitionError(
+                    DefCollectorErrorKind::TraitMissingAssociatedItem { item_name, .. },
+                ) => assert_eq!(item_name.0.contents, "Item"),
+                _ => panic!("Unexpected error: {error:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn unused_items_are_reported() {
+        let src = "
+        fn unused_function() {}
+
+        pub fn unused_public_function() {}
+
+        global UNUSED_GLOBAL = 1;
+
+        struct UnusedStruct {}
+
+        struct UsedStruct {}
+
+        fn main() {
+            let _used = UsedStruct {};
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 3, "Expected 3 errors, got: {errors:?}");
+        for (error, _file_id) in errors {
+            match error {
+                CompilationError::ResolverError(ResolverError::UnusedPrivateItem {
+                    ident,
+                    item_type,
+                }) => match ident.0.contents.as_str() {
+                    "unused_function" => assert_eq!(item_type, "function"),
+                    "UNUSED_GLOBAL" => assert_eq!(item_type, "global"),
+                    "UnusedStruct" => assert_eq!(item_type, "struct"),
+                    name => panic!("Unexpected unused item: {name}"),
+                },
+                _ => panic!("Unexpected error: {error:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn private_items_are_not_visible_outside_their_module() {
+        let src = "
+        mod foo {
+            struct Private {}
+            pub(crate) global VISIBLE = 1;
+
+            pub mod bar {
+                pub fn use_parent_item() -> crate::foo::Private {
+                    crate::foo::Private {}
+                }
+            }
+        }
+
+        fn main() {
+            let _a = foo::bar::use_parent_item();
+            let _b = foo::Private {};
+            let _c = foo::VISIBLE;
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {errors:?}");
+        match &errors[0].0 {
+            CompilationError::ResolverError(ResolverError::PathResolutionError(
+                PathResolutionError::Private(ident),
+            )) => assert_eq!(ident.0.contents, "Private"),
+            error => panic!("Unexpected error: {error:?}"),
+        }
+    }
+
+    #[test]
+    fn private_struct_fields_are_not_visible_outside_their_module() {
+        let src = "
+        mod foo {
+            pub struct Foo {
+                pub visible: Field,
+                hidden: Field,
+            }
+
+            pub fn new() -> Foo {
+                Foo { visible: 1, hidden: 2 }
+            }
+
+            pub fn hidden(foo: Foo) -> Field {
+                foo.hidden
+            }
+        }
+
+        fn main() {
+            let foo = foo::Foo { visible: 1, hidden: 2 };
+            assert(foo.visible == foo.hidden);
+            assert(foo::hidden(foo::new()) == 2);
+            let foo::Foo { visible: _, hidden } = foo::new();
+            assert(hidden == 2);
+        }
+        ";
+
+        // The constructor, the member access and the pattern are each reported once
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 3, "Expected 3 errors, got: {errors:?}");
+        for (error, _file_id) in errors {
+            match error {
+                CompilationError::TypeError(TypeCheckError::PrivateField {
+                    field_name, ..
+                }) => assert_eq!(field_name, "hidden"),
+                _ => panic!("Unexpected error: {error:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn items_only_used_by_dead_code_are_reported() {
+        let src = "
+        mod foo {
+            pub fn used() -> Field {
+                1
+            }
+
+            pub fn unused() -> Field {
+                2
+            }
+
+            pub fn allowed_import() -> Field {
+                3
+            }
+        }
+
+        use foo::used;
+        use foo::unused;
+
+        #[allow(dead_code)]
+        use foo::allowed_import;
+
+        global USED_BY_DEAD_CODE = 1;
+
+        #[allow(dead_code)]
+        global ALLOWED_GLOBAL = 2;
+
+        fn dead() -> Field {
+            used_by_dead_code() + USED_BY_DEAD_CODE
+        }
+
+        fn used_by_dead_code() -> Field {
+            1
+        }
+
+        #[allow(dead_code)]
+        fn allowed() {}
+
+        fn main(x: Field) {
+            assert(used() != x);
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        let unused = vecmap(&errors, |(error, _file_id)| match error {
+            CompilationError::ResolverError(ResolverError::UnusedPrivateItem {
+                ident,
+                item_type,
+            }) => {
+                format!("{item_type} {ident}")
+            }
+            _ => panic!("Unexpected error: {error:?}"),
+        });
+        assert_eq!(
+            unused,
+            vec![
+                "global USED_BY_DEAD_CODE",
+                "function dead",
+                "function used_by_dead_code",
+                "import unused",
+            ]
+        );
+    }
+
+    #[test]
+    fn unread_struct_fields_and_impls_of_unused_structs_are_reported() {
+        let src = "
+        struct Used {
+            read: Field,
+            written: Field,
+            pub public: Field,
+        }
+
+        struct Unused {}
+
+        trait IsUsed {
+            fn is_used(self) -> bool;
+        }
+
+        impl IsUsed for Unused {
+            fn is_used(_self: Self) -> bool {
+                false
+            }
+        }
+
+        #[allow(dead_code)]
+        struct Allowed {
+            never_read: Field,
+        }
+
+        fn main() {
+            let mut used = Used { read: 1, written: 2, public: 3 };
+            used.written = used.read;
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 3, "Expected 3 errors, got: {errors:?}");
+        for (error, _file_id) in errors {
+            match error {
+                CompilationError::ResolverError(ResolverError::UnusedStructField {
+                    struct_name,
+                    field_name,
+                }) => {
+                    assert_eq!(struct_name, "Used");
+                    assert_eq!(field_name.0.contents, "written");
+                }
+                CompilationError::ResolverError(ResolverError::UnusedPrivateItem {
+                    ident, ..
+                }) => {
+                    assert_eq!(ident.0.contents, "Unused");
+                }
+                CompilationError::ResolverError(ResolverError::UnusedTraitImpl {
+                    trait_name,
+                    ..
+                }) => {
+                    assert_eq!(trait_name.0.contents, "IsUsed");
+                }
+                _ => panic!("Unexpected error: {error:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn only_supported_traits_can_be_derived() {
+        let src = "
+        #[derive(Debug)]
+        struct Foo {}
+
+        fn main() {
+            let _foo = Foo {};
+        }
+        ";
+
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {errors:?}");
+        match &errors[0].0 {
+            CompilationError::DefinitionError(DefCollectorErrorKind::UnsupportedDerive {
+                trait_name,
+                ..
+            }) => assert_eq!(trait_name, "Debug"),
+            error => panic!("Unexpected error: {error:?}"),
+        }
+    }
+
+    struct TestMacro;
+
+    impl MacroProcessor for TestMacro {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn process_untyped_ast(
+            &self,
+            mut ast: SortedModule,
+            _crate_id: &CrateId,
+            _context: &Context,
+        ) -> Result<SortedModule, (MacroError, FileId)> {
+            let (generated, _) = parse_program("fn generated() -> Field { 1 }");
+            ast.functions.extend(generated.into_sorted().functions);
+            Ok(ast)
+        }
+
+        fn process_typed_ast(
+            &self,
+            _crate_id: &CrateId,
+            _context: &mut Context,
+        ) -> Vec<(MacroError, FileId)> {
+            let error = MacroError {
+                primary_message: "reported by a macro".to_string(),
+                secondary_message: None,
+                span: None,
+            };
+            vec![(error, FileId::dummy())]
+        }
+    }
+
+    #[test]
+    fn macro_processors_transform_the_ast_and_report_errors() {
+        let src = "
+        fn main() {
+            assert(generated() == 1);
+        }
+        ";
+
+        let errors = get_program_with_macros(src, vec![&TestMacro]).2;
+        assert_eq!(errors.len(), 1, "Expected 1 error, got: {errors:?}");
+        match &errors[0].0 {
+            CompilationError::DefinitionError(DefCollectorErrorKind::MacroError(error)) => {
+                assert_eq!(error.primary_message, "reported by a macro");
+            }
+            error => panic!("Unexpected error: {error:?}"),
+        }
+    }
 }
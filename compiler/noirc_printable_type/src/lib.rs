@@ -29,6 +29,9 @@ pub enum PrintableType {
     String {
         length: u64,
     },
+    Tuple {
+        types: Vec<PrintableType>,
+    },
 }
 
 impl PrintableType {
@@ -44,6 +47,9 @@ impl PrintableType {
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
             Self::String { length } => *length as u32,
+            Self::Tuple { types } => {
+                types.iter().fold(0, |acc, field_type| acc + field_type.field_count())
+            }
         }
     }
 }
@@ -233,6 +239,25 @@ fn to_string(value: &PrintableValue, typ: &PrintableType) -> Option<String> {
             output.push_str(" }");
         }
 
+        (PrintableValue::Vec(values), PrintableType::Tuple { types }) => {
+            output.push('(');
+            let mut elements = values.iter().zip(types).peekable();
+            while let Some((value, typ)) = elements.next() {
+                output.push_str(&format!(
+                    "{}",
+                    PrintableValueDisplay::Plain(value.clone(), typ.clone())
+                ));
+                if elements.peek().is_some() {
+                    output.push_str(", ");
+                }
+            }
+            // A single element tuple is written with a trailing comma to distinguish it from a parenthesized value
+            if types.len() == 1 {
+                output.push(',');
+            }
+            output.push(')');
+        }
+
         _ => return None,
     };
 
@@ -333,6 +358,9 @@ fn decode_value(
 
             PrintableValue::Struct(struct_map)
         }
+        PrintableType::Tuple { types } => {
+            PrintableValue::Vec(vecmap(types, |typ| decode_value(field_iterator, typ)))
+        }
     }
 }
 
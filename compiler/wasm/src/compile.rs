@@ -303,8 +303,11 @@ pub(crate) fn preprocess_contract(contract: CompiledContract) -> CompileResult {
             name: func.name,
             function_type: func.function_type,
             is_internal: func.is_internal,
+            selector: func.selector,
             abi: func.abi,
             bytecode: func.bytecode,
+            gate_count: None,
+            verification_key: None,
         })
         .collect();
 
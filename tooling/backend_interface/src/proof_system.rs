@@ -131,6 +131,38 @@ impl Backend {
             .run(binary_path)
     }
 
+    /// Generates the verification key for `circuit`, returning it along with its hash.
+    pub fn get_verification_key(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<(Vec<u8>, FieldElement), BackendError> {
+        let binary_path = self.assert_binary_exists()?;
+        self.assert_correct_version()?;
+
+        let temp_directory = tempdir().expect("could not create a temporary directory");
+        let temp_directory = temp_directory.path().to_path_buf();
+
+        // Create a temporary file for the circuit
+        let bytecode_path = temp_directory.join("circuit").with_extension("bytecode");
+        let serialized_circuit = Circuit::serialize_circuit(circuit);
+        write_to_file(&serialized_circuit, &bytecode_path);
+
+        // Create the verification key and write it to the specified path
+        let vk_path = temp_directory.join("vk");
+
+        WriteVkCommand {
+            crs_path: self.crs_directory(),
+            bytecode_path,
+            vk_path_output: vk_path.clone(),
+        }
+        .run(binary_path)?;
+
+        let verification_key = std::fs::read(&vk_path)?;
+        let (vk_hash, _) = VkAsFieldsCommand { vk_path }.run(binary_path)?;
+
+        Ok((verification_key, vk_hash))
+    }
+
     pub fn get_intermediate_proof_artifacts(
         &self,
         circuit: &Circuit,
@@ -1,6 +1,6 @@
 use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
 use acvm::acir::native_types::{Witness, WitnessMap};
-use acvm::brillig_vm::{brillig::Value, Registers};
+use acvm::brillig_vm::{brillig::Value, ExecutionLimits, Registers};
 use acvm::pwg::{
     ACVMStatus, BrilligSolver, BrilligSolverStatus, ForeignCallWaitInfo, StepResult, ACVM,
 };
@@ -36,9 +36,11 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         debug_artifact: &'a DebugArtifact,
         initial_witness: WitnessMap,
         foreign_call_executor: Box<dyn ForeignCallExecutor + 'a>,
+        brillig_limits: ExecutionLimits,
     ) -> Self {
         Self {
-            acvm: ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness),
+            acvm: ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness)
+                .with_brillig_limits(brillig_limits),
             brillig_solver: None,
             foreign_call_executor,
             debug_artifact,
@@ -530,6 +532,7 @@ mod tests {
             debug_artifact,
             initial_witness,
             Box::new(DefaultForeignCallExecutor::new(true, None)),
+            ExecutionLimits::UNLIMITED,
         );
 
         assert_eq!(context.get_current_opcode_location(), Some(OpcodeLocation::Acir(0)));
@@ -624,6 +627,7 @@ mod tests {
             debug_artifact,
             initial_witness,
             Box::new(DefaultForeignCallExecutor::new(true, None)),
+            ExecutionLimits::UNLIMITED,
         );
 
         // set breakpoint
@@ -674,6 +678,7 @@ mod tests {
             &debug_artifact,
             WitnessMap::new(),
             Box::new(DefaultForeignCallExecutor::new(true, None)),
+            ExecutionLimits::UNLIMITED,
         );
 
         assert_eq!(context.offset_opcode_location(&None, 0), (None, 0));
@@ -4,6 +4,7 @@ use std::str::FromStr;
 
 use acvm::acir::circuit::{Circuit, OpcodeLocation};
 use acvm::acir::native_types::WitnessMap;
+use acvm::brillig_vm::ExecutionLimits;
 use acvm::BlackBoxFunctionSolver;
 use codespan_reporting::files::{Files, SimpleFile};
 
@@ -58,6 +59,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver> DapSession<'a, R, W, B> {
             debug_artifact,
             initial_witness,
             Box::new(DefaultForeignCallExecutor::new(true, None)),
+            ExecutionLimits::UNLIMITED,
         );
         Self {
             server,
@@ -2,6 +2,7 @@ use crate::context::{DebugCommandResult, DebugContext};
 
 use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
 use acvm::acir::native_types::{Witness, WitnessMap};
+use acvm::brillig_vm::ExecutionLimits;
 use acvm::{BlackBoxFunctionSolver, FieldElement};
 
 use nargo::{artifacts::debug::DebugArtifact, ops::DefaultForeignCallExecutor, NargoError};
@@ -22,6 +23,7 @@ pub struct ReplDebugger<'a, B: BlackBoxFunctionSolver> {
     circuit: &'a Circuit,
     debug_artifact: &'a DebugArtifact,
     initial_witness: WitnessMap,
+    brillig_limits: ExecutionLimits,
     last_result: DebugCommandResult,
 }
 
@@ -31,6 +33,7 @@ impl<'a, B: BlackBoxFunctionSolver> ReplDebugger<'a, B> {
         circuit: &'a Circuit,
         debug_artifact: &'a DebugArtifact,
         initial_witness: WitnessMap,
+        brillig_limits: ExecutionLimits,
     ) -> Self {
         let context = DebugContext::new(
             blackbox_solver,
@@ -38,6 +41,7 @@ impl<'a, B: BlackBoxFunctionSolver> ReplDebugger<'a, B> {
             debug_artifact,
             initial_witness.clone(),
             Box::new(DefaultForeignCallExecutor::new(true, None)),
+            brillig_limits,
         );
         Self {
             context,
@@ -45,6 +49,7 @@ impl<'a, B: BlackBoxFunctionSolver> ReplDebugger<'a, B> {
             circuit,
             debug_artifact,
             initial_witness,
+            brillig_limits,
             last_result: DebugCommandResult::Ok,
         }
     }
@@ -279,6 +284,7 @@ impl<'a, B: BlackBoxFunctionSolver> ReplDebugger<'a, B> {
             self.debug_artifact,
             self.initial_witness.clone(),
             Box::new(DefaultForeignCallExecutor::new(true, None)),
+            self.brillig_limits,
         );
         for opcode_location in breakpoints {
             self.context.add_breakpoint(opcode_location);
@@ -397,9 +403,15 @@ pub fn run<B: BlackBoxFunctionSolver>(
     circuit: &Circuit,
     debug_artifact: &DebugArtifact,
     initial_witness: WitnessMap,
+    brillig_limits: ExecutionLimits,
 ) -> Result<Option<WitnessMap>, NargoError> {
-    let context =
-        RefCell::new(ReplDebugger::new(blackbox_solver, circuit, debug_artifact, initial_witness));
+    let context = RefCell::new(ReplDebugger::new(
+        blackbox_solver,
+        circuit,
+        debug_artifact,
+        initial_witness,
+        brillig_limits,
+    ));
     let ref_context = &context;
 
     ref_context.borrow().show_current_vm_status();
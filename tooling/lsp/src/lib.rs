@@ -19,9 +19,12 @@ use async_lsp::{
 };
 use fm::codespan_files as files;
 use lsp_types::CodeLens;
-use nargo::workspace::Workspace;
+use nargo::{package::Package, workspace::Workspace};
 use nargo_toml::{find_file_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use noirc_driver::{
+    check_crate_with_macros, select_macros, CompilationResult, CompileOptions,
+    NOIR_ARTIFACT_VERSION_STRING,
+};
 use noirc_frontend::{
     graph::{CrateId, CrateName},
     hir::{Context, FunctionNameMatch},
@@ -214,6 +217,19 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
     Ok(workspace)
 }
 
+/// Checks `crate_id` with the macro plugins selected by `package`'s manifest, so that the
+/// diagnostics reported in the editor match those of `nargo check`.
+pub(crate) fn check_package_crate(
+    context: &mut Context,
+    crate_id: CrateId,
+    package: &Package,
+) -> CompilationResult<()> {
+    // Resolving the default profile cannot fail
+    let options = package.compile_options(&CompileOptions::default()).unwrap_or_default();
+    let macros = select_macros(context, crate_id, &options)?;
+    check_crate_with_macros(context, crate_id, false, &macros)
+}
+
 /// Prepares a package from a source string
 /// This is useful for situations when we don't need dependencies
 /// and just need to operate on single file.
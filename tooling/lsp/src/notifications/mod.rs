@@ -2,18 +2,19 @@ use std::ops::ControlFlow;
 
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
 use nargo::{insert_all_files_for_workspace_into_file_manager, prepare_package};
-use noirc_driver::{check_crate, file_manager_with_stdlib};
+use noirc_driver::file_manager_with_stdlib;
 use noirc_errors::{DiagnosticKind, FileDiagnostic};
 
 use crate::requests::collect_lenses_for_package;
 use crate::types::{
     notification, Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams,
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializedParams, NargoPackageTests, PublishDiagnosticsParams,
+    DidSaveTextDocumentParams, InitializedParams, NargoPackageTests, NumberOrString,
+    PublishDiagnosticsParams,
 };
 
 use crate::{
-    byte_span_to_range, get_package_tests_in_crate, prepare_source,
+    byte_span_to_range, check_package_crate, get_package_tests_in_crate, prepare_source,
     resolve_workspace_for_source_path, LspState,
 };
 
@@ -46,9 +47,6 @@ pub(super) fn on_did_change_text_document(
     let text = params.content_changes.into_iter().next().unwrap().text;
     state.input_files.insert(params.text_document.uri.to_string(), text.clone());
 
-    let (mut context, crate_id) = prepare_source(text);
-    let _ = check_crate(&mut context, crate_id, false, false);
-
     let workspace = match resolve_workspace_for_source_path(
         params.text_document.uri.to_file_path().unwrap().as_path(),
     ) {
@@ -72,6 +70,9 @@ pub(super) fn on_did_change_text_document(
         }
     };
 
+    let (mut context, crate_id) = prepare_source(text);
+    let _ = check_package_crate(&mut context, crate_id, package);
+
     let lenses = collect_lenses_for_package(&context, crate_id, &workspace, package, None);
 
     state.cached_lenses.insert(params.text_document.uri.to_string(), lenses);
@@ -122,7 +123,7 @@ pub(super) fn on_did_save_text_document(
         .flat_map(|package| -> Vec<Diagnostic> {
             let (mut context, crate_id) = prepare_package(&workspace_file_manager, package);
 
-            let file_diagnostics = match check_crate(&mut context, crate_id, false, false) {
+            let file_diagnostics = match check_package_crate(&mut context, crate_id, package) {
                 Ok(((), warnings)) => warnings,
                 Err(errors_and_warnings) => errors_and_warnings,
             };
@@ -171,6 +172,7 @@ pub(super) fn on_did_save_text_document(
                     Some(Diagnostic {
                         range,
                         severity: Some(severity),
+                        code: diagnostic.code.map(|code| NumberOrString::String(code.to_string())),
                         message: diagnostic.message,
                         ..Default::default()
                     })
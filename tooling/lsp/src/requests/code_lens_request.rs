@@ -3,11 +3,10 @@ use std::future::{self, Future};
 use async_lsp::{ErrorCode, ResponseError};
 
 use nargo::{package::Package, workspace::Workspace};
-use noirc_driver::check_crate;
 use noirc_frontend::hir::FunctionNameMatch;
 
 use crate::{
-    byte_span_to_range, prepare_source, resolve_workspace_for_source_path,
+    byte_span_to_range, check_package_crate, prepare_source, resolve_workspace_for_source_path,
     types::{CodeLens, CodeLensParams, CodeLensResult, Command},
     LspState,
 };
@@ -67,7 +66,7 @@ fn on_code_lens_request_inner(
     let (mut context, crate_id) = prepare_source(source_string);
     // We ignore the warnings and errors produced by compilation for producing code lenses
     // because we can still get the test functions even if compilation fails
-    let _ = check_crate(&mut context, crate_id, false, false);
+    let _ = check_package_crate(&mut context, crate_id, package);
 
     let collected_lenses =
         collect_lenses_for_package(&context, crate_id, &workspace, package, None);
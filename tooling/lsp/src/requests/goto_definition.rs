@@ -1,6 +1,6 @@
 use std::future::{self, Future};
 
-use crate::resolve_workspace_for_source_path;
+use crate::{check_package_crate, resolve_workspace_for_source_path};
 use crate::{types::GotoDefinitionResult, LspState};
 use async_lsp::{ErrorCode, ResponseError};
 use fm::codespan_files::Error;
@@ -35,7 +35,7 @@ fn on_goto_definition_inner(
     let (mut context, crate_id) = nargo::prepare_package(&workspace_file_manager, package);
 
     // We ignore the warnings and errors produced by compilation while resolving the definition
-    let _ = noirc_driver::check_crate(&mut context, crate_id, false, false);
+    let _ = check_package_crate(&mut context, crate_id, package);
 
     let files = context.file_manager.as_file_map();
     let file_id = context.file_manager.name_to_id(file_path.clone()).ok_or(ResponseError::new(
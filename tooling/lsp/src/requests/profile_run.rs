@@ -10,7 +10,7 @@ use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSele
 use noirc_driver::{
     file_manager_with_stdlib, CompileOptions, DebugFile, NOIR_ARTIFACT_VERSION_STRING,
 };
-use noirc_errors::{debug_info::OpCodesCount, Location};
+use noirc_errors::{debug_info::OpCodesCount, reporter::MessageFormat, Location};
 
 use crate::{
     types::{NargoProfileRunParams, NargoProfileRunResult},
@@ -71,6 +71,7 @@ fn on_profile_run_request_inner(
                 &contract_packages,
                 expression_width,
                 &CompileOptions::default(),
+                MessageFormat::Human,
             )
             .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err))?;
 
@@ -1,18 +1,18 @@
 use std::future::{self, Future};
 
+use acvm::brillig_vm::ExecutionLimits;
 use async_lsp::{ErrorCode, ResponseError};
 use nargo::{
     insert_all_files_for_workspace_into_file_manager,
-    ops::{run_test, TestStatus},
+    ops::{run_test, FuzzingConfig, TestStatus},
     prepare_package,
 };
 use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{
-    check_crate, file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING,
-};
+use noirc_driver::{file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::hir::FunctionNameMatch;
 
 use crate::{
+    check_package_crate,
     types::{NargoTestRunParams, NargoTestRunResult},
     LspState,
 };
@@ -57,7 +57,7 @@ fn on_test_run_request_inner(
     match workspace.into_iter().next() {
         Some(package) => {
             let (mut context, crate_id) = prepare_package(&workspace_file_manager, package);
-            if check_crate(&mut context, crate_id, false, false).is_err() {
+            if check_package_crate(&mut context, crate_id, package).is_err() {
                 let result = NargoTestRunResult {
                     id: params.id.clone(),
                     result: "error".to_string(),
@@ -85,9 +85,11 @@ fn on_test_run_request_inner(
                 false,
                 None,
                 &CompileOptions::default(),
+                ExecutionLimits::UNLIMITED,
+                &FuzzingConfig::default(),
             );
             let result = match test_result {
-                TestStatus::Pass => NargoTestRunResult {
+                TestStatus::Pass { .. } => NargoTestRunResult {
                     id: params.id.clone(),
                     result: "pass".to_string(),
                     message: None,
@@ -4,10 +4,10 @@ use async_lsp::{ErrorCode, LanguageClient, ResponseError};
 use lsp_types::{LogMessageParams, MessageType};
 use nargo::{insert_all_files_for_workspace_into_file_manager, prepare_package};
 use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{check_crate, file_manager_with_stdlib, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_driver::{file_manager_with_stdlib, NOIR_ARTIFACT_VERSION_STRING};
 
 use crate::{
-    get_package_tests_in_crate,
+    check_package_crate, get_package_tests_in_crate,
     types::{NargoPackageTests, NargoTestsParams, NargoTestsResult},
     LspState,
 };
@@ -59,7 +59,7 @@ fn on_tests_request_inner(
             let (mut context, crate_id) = prepare_package(&workspace_file_manager, package);
             // We ignore the warnings and errors produced by compilation for producing tests
             // because we can still get the test functions even if compilation fails
-            let _ = check_crate(&mut context, crate_id, false, false);
+            let _ = check_package_crate(&mut context, crate_id, package);
 
             // We don't add test headings for a package if it contains no `#[test]` functions
             get_package_tests_in_crate(&context, &crate_id, &package.name)
@@ -12,7 +12,8 @@ pub(crate) use lsp_types::{
     CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic, DiagnosticSeverity,
     DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
     DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializedParams,
-    Position, PublishDiagnosticsParams, Range, ServerInfo, TextDocumentSyncCapability, Url,
+    NumberOrString, Position, PublishDiagnosticsParams, Range, ServerInfo,
+    TextDocumentSyncCapability, Url,
 };
 
 pub(crate) mod request {
@@ -1,4 +1,5 @@
 use acvm::acir::circuit::Circuit;
+use acvm::FieldElement;
 use noirc_abi::{Abi, ContractEvent};
 use noirc_driver::ContractFunctionType;
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,9 @@ pub struct PreprocessedContractFunction {
 
     pub is_internal: bool,
 
+    /// The selector used to dispatch calls to this function.
+    pub selector: u32,
+
     pub abi: Abi,
 
     #[serde(
@@ -39,4 +43,25 @@ pub struct PreprocessedContractFunction {
         deserialize_with = "Circuit::deserialize_circuit_base64"
     )]
     pub bytecode: Circuit,
+
+    /// The number of gates in the function's circuit, as reported by the backend.
+    ///
+    /// Only present for secret functions of contracts compiled with `--with-vk`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gate_count: Option<u32>,
+
+    /// The verification key of the function's circuit.
+    ///
+    /// Only present for secret functions of contracts compiled with `--with-vk`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_key: Option<ContractFunctionVerificationKey>,
+}
+
+/// A verification key generated by the backend for a contract function.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContractFunctionVerificationKey {
+    /// The hex-encoded verification key.
+    pub key: String,
+    /// The hash of the verification key, as computed by the backend.
+    pub hash: FieldElement,
 }
@@ -16,8 +16,14 @@ pub const VERIFIER_INPUT_FILE: &str = "Verifier";
 /// The package definition file for a Noir project.
 pub const PKG_FILE: &str = "Nargo.toml";
 
+// Profiles
+/// The profile used when no `--profile` is passed on the command line.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
 // Extensions
 /// The extension for files containing circuit proofs.
 pub const PROOF_EXT: &str = "proof";
 /// The extension for files containing proof witnesses.
 pub const WITNESS_EXT: &str = "gz";
+/// The extension for files containing Brillig execution profiles in the folded stacks format.
+pub const BRILLIG_PROFILE_EXT: &str = "folded";
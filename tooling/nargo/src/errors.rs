@@ -21,6 +21,11 @@ pub enum CompileError {
     #[error("Package `{0}` is expected to have a `main` function but it does not")]
     MissingMainFunction(CrateName),
 
+    #[error(
+        "Package `{package}` does not define a `[profile.{profile}]` section in its Nargo.toml"
+    )]
+    MissingProfile { package: CrateName, profile: String },
+
     /// Errors encountered while compiling the Noir program.
     /// These errors are already written to stderr.
     #[error("Aborting due to {} previous error{}", .0.error_count, if .0.error_count == 1 { "" } else { "s" })]
@@ -64,7 +69,8 @@ impl NargoError {
             ExecutionError::SolvingError(error) => match error {
                 OpcodeResolutionError::IndexOutOfBounds { .. }
                 | OpcodeResolutionError::OpcodeNotSolvable(_)
-                | OpcodeResolutionError::UnsatisfiedConstrain { .. } => None,
+                | OpcodeResolutionError::UnsatisfiedConstrain { .. }
+                | OpcodeResolutionError::BrilligResourceExhausted { .. } => None,
                 OpcodeResolutionError::BrilligFunctionFailed { message, .. } => Some(message),
                 OpcodeResolutionError::BlackBoxFunctionFailed(_, reason) => Some(reason),
             },
@@ -91,6 +97,10 @@ fn extract_locations_from_error(
             call_stack,
             ..
         })
+        | ExecutionError::SolvingError(OpcodeResolutionError::BrilligResourceExhausted {
+            call_stack,
+            ..
+        })
         | ExecutionError::AssertionFailed(_, call_stack) => Some(call_stack.clone()),
         ExecutionError::SolvingError(OpcodeResolutionError::IndexOutOfBounds {
             opcode_location: error_location,
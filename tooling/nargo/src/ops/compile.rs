@@ -1,6 +1,7 @@
 use acvm::ExpressionWidth;
 use fm::FileManager;
 use noirc_driver::{CompilationResult, CompileOptions, CompiledContract, CompiledProgram};
+use noirc_errors::reporter::MessageFormat;
 
 use crate::errors::CompileError;
 use crate::prepare_package;
@@ -20,6 +21,7 @@ pub fn compile_workspace(
     contract_packages: &[Package],
     expression_width: ExpressionWidth,
     compile_options: &CompileOptions,
+    message_format: MessageFormat,
 ) -> Result<(Vec<CompiledProgram>, Vec<CompiledContract>), CompileError> {
     // Compile all of the packages in parallel.
     let program_results: Vec<CompilationResult<CompiledProgram>> = binary_packages
@@ -42,6 +44,7 @@ pub fn compile_workspace(
                 file_manager,
                 compile_options.deny_warnings,
                 compile_options.silence_warnings,
+                message_format,
             )
         })
         .collect::<Result<_, _>>()?;
@@ -53,6 +56,7 @@ pub fn compile_workspace(
                 file_manager,
                 compile_options.deny_warnings,
                 compile_options.silence_warnings,
+                message_format,
             )
         })
         .collect::<Result<_, _>>()?;
@@ -112,6 +116,7 @@ pub(crate) fn report_errors<T>(
     file_manager: &FileManager,
     deny_warnings: bool,
     silence_warnings: bool,
+    message_format: MessageFormat,
 ) -> Result<T, CompileError> {
     let (t, warnings) = result.map_err(|errors| {
         noirc_errors::reporter::report_all(
@@ -119,6 +124,7 @@ pub(crate) fn report_errors<T>(
             &errors,
             deny_warnings,
             silence_warnings,
+            message_format,
         )
     })?;
 
@@ -127,6 +133,7 @@ pub(crate) fn report_errors<T>(
         &warnings,
         deny_warnings,
         silence_warnings,
+        message_format,
     );
 
     Ok(t)
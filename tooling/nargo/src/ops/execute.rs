@@ -1,3 +1,4 @@
+use acvm::brillig_vm::ExecutionLimits;
 use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM};
 use acvm::BlackBoxFunctionSolver;
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
@@ -6,6 +7,7 @@ use crate::errors::ExecutionError;
 use crate::NargoError;
 
 use super::foreign_calls::ForeignCallExecutor;
+use super::profile::BrilligProfile;
 
 #[tracing::instrument(level = "trace", skip_all)]
 pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
@@ -14,8 +16,61 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
     blackbox_solver: &B,
     foreign_call_executor: &mut F,
 ) -> Result<WitnessMap, NargoError> {
-    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
+    execute_circuit_with_limits(
+        circuit,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        ExecutionLimits::UNLIMITED,
+    )
+    .map(|(solved_witness, _)| solved_witness)
+}
+
+/// Executes `circuit` while enforcing `brillig_limits` on any unconstrained functions it calls.
+///
+/// Returns the solved witness along with the number of Brillig opcodes which were executed.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_circuit_with_limits<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut F,
+    brillig_limits: ExecutionLimits,
+) -> Result<(WitnessMap, usize), NargoError> {
+    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness)
+        .with_brillig_limits(brillig_limits);
+    solve_circuit(&mut acvm, circuit, foreign_call_executor)?;
 
+    let brillig_steps = acvm.brillig_step_count();
+    Ok((acvm.finalize(), brillig_steps))
+}
+
+/// Executes `circuit` while counting the number of times each Brillig opcode is executed.
+///
+/// Returns the solved witness along with the resulting [BrilligProfile].
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_circuit_with_profiling<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut F,
+    brillig_limits: ExecutionLimits,
+) -> Result<(WitnessMap, BrilligProfile), NargoError> {
+    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness)
+        .with_brillig_limits(brillig_limits)
+        .with_brillig_profiling();
+    solve_circuit(&mut acvm, circuit, foreign_call_executor)?;
+
+    let profile = BrilligProfile::new(acvm.brillig_profile().cloned().unwrap_or_default());
+    Ok((acvm.finalize(), profile))
+}
+
+/// Drives `acvm` to completion, resolving any foreign calls with `foreign_call_executor`.
+fn solve_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+    acvm: &mut ACVM<'_, B>,
+    circuit: &Circuit,
+    foreign_call_executor: &mut F,
+) -> Result<(), NargoError> {
     loop {
         let solver_status = acvm.solve();
 
@@ -55,5 +110,5 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
         }
     }
 
-    Ok(acvm.finalize())
+    Ok(())
 }
@@ -0,0 +1,376 @@
+//! Generation and shrinking of inputs for test functions which take parameters.
+//!
+//! Inputs are generated from the `AbiType` of each parameter so that they can be encoded with
+//! [`Abi::encode`] like any other program inputs. Integers are generated within their bit width,
+//! and signed integers use the same two's complement representation used by the input parsers.
+
+use std::collections::BTreeMap;
+
+use acvm::FieldElement;
+use iter_extended::vecmap;
+use noirc_abi::{input_parser::InputValue, Abi, AbiType, InputMap, Sign};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Signed, Zero};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The number of sets of inputs each test function with parameters is run against by default.
+pub const DEFAULT_FUZZ_RUNS: u32 = 100;
+
+/// The maximum number of times a failing test is re-run while searching for smaller failing inputs.
+const MAX_SHRINK_ATTEMPTS: usize = 1024;
+
+/// Settings for running test functions which take parameters against random inputs.
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzingConfig {
+    /// The number of sets of inputs to run each test function against.
+    pub runs: u32,
+    /// The seed used to generate inputs. Running with the same seed generates the same inputs.
+    pub seed: u64,
+}
+
+impl Default for FuzzingConfig {
+    fn default() -> Self {
+        FuzzingConfig { runs: DEFAULT_FUZZ_RUNS, seed: rand::random() }
+    }
+}
+
+/// Generates random values for the parameters described by an [`Abi`].
+pub(crate) struct InputGenerator {
+    rng: StdRng,
+}
+
+impl InputGenerator {
+    pub(crate) fn new(seed: u64) -> Self {
+        InputGenerator { rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub(crate) fn generate_input_map(&mut self, abi: &Abi) -> InputMap {
+        abi.parameters
+            .iter()
+            .map(|param| (param.name.clone(), self.generate_value(&param.typ)))
+            .collect()
+    }
+
+    fn generate_value(&mut self, typ: &AbiType) -> InputValue {
+        match typ {
+            AbiType::Field => InputValue::Field(self.generate_field()),
+            AbiType::Integer { sign, width } => {
+                InputValue::Field(self.generate_integer(*sign, *width))
+            }
+            AbiType::Boolean => InputValue::Field(self.rng.gen::<bool>().into()),
+            AbiType::String { length } => {
+                // Strings are restricted to printable ASCII so that failing inputs can be displayed.
+                InputValue::String((0..*length).map(|_| self.rng.gen_range(' '..='~')).collect())
+            }
+            AbiType::Array { length, typ } => {
+                InputValue::Vec((0..*length).map(|_| self.generate_value(typ)).collect())
+            }
+            AbiType::Tuple { fields } => {
+                InputValue::Vec(vecmap(fields, |typ| self.generate_value(typ)))
+            }
+            AbiType::Struct { fields, .. } => InputValue::Struct(
+                fields.iter().map(|(name, typ)| (name.clone(), self.generate_value(typ))).collect(),
+            ),
+        }
+    }
+
+    /// Edge cases such as zero and `-1` are much more likely to trigger bugs than a uniformly
+    /// random field element, so they are generated with a higher probability.
+    fn generate_field(&mut self) -> FieldElement {
+        match self.rng.gen_range(0..8) {
+            0 => FieldElement::zero(),
+            1 => FieldElement::one(),
+            2 => -FieldElement::one(),
+            3 => FieldElement::from(self.rng.gen::<u64>() as u128),
+            _ => FieldElement::from_be_bytes_reduce(&self.rng.gen::<[u8; 32]>()),
+        }
+    }
+
+    /// Generates an integer which fits in `width` bits, favouring the boundaries of its range.
+    fn generate_integer(&mut self, sign: Sign, width: u32) -> FieldElement {
+        let width = clamp_width(width);
+        let all_ones = low_bits_mask(width);
+        let value = match (sign, self.rng.gen_range(0..8)) {
+            (_, 0) => BigUint::zero(),
+            (_, 1) => BigUint::from(1u32),
+            // `-1` for signed integers and the maximum value for unsigned integers.
+            (_, 2) => all_ones,
+            // The minimum and maximum values of a signed integer.
+            (Sign::Signed, 3) => BigUint::from(1u32) << (width - 1),
+            (Sign::Signed, 4) => low_bits_mask(width - 1),
+            _ => BigUint::from_bytes_be(&self.rng.gen::<[u8; 32]>()) & all_ones,
+        };
+        biguint_to_field(&value)
+    }
+}
+
+/// Repeatedly replaces `inputs` with a smaller set of inputs for which `fails` still returns true,
+/// until no smaller failing inputs can be found.
+pub(crate) fn shrink_input_map(
+    abi: &Abi,
+    inputs: InputMap,
+    mut fails: impl FnMut(&InputMap) -> bool,
+) -> InputMap {
+    let mut smallest = inputs;
+    let mut attempts = 0;
+
+    'shrink: loop {
+        for candidate in shrink_input_map_once(abi, &smallest) {
+            if attempts == MAX_SHRINK_ATTEMPTS {
+                break 'shrink;
+            }
+            attempts += 1;
+
+            if fails(&candidate) {
+                smallest = candidate;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+
+    smallest
+}
+
+/// Returns each input map which differs from `inputs` by a single parameter being made smaller.
+fn shrink_input_map_once(abi: &Abi, inputs: &InputMap) -> Vec<InputMap> {
+    abi.parameters
+        .iter()
+        .flat_map(|param| {
+            let value = &inputs[&param.name];
+            vecmap(shrink_value(value, &param.typ), |smaller_value| {
+                let mut candidate = inputs.clone();
+                candidate.insert(param.name.clone(), smaller_value);
+                candidate
+            })
+        })
+        .collect()
+}
+
+/// Returns values which are strictly smaller than `value`, starting with the smallest.
+///
+/// Numbers shrink towards zero, strings towards a run of `a`s and aggregates shrink one
+/// element at a time.
+fn shrink_value(value: &InputValue, typ: &AbiType) -> Vec<InputValue> {
+    match (value, typ) {
+        (InputValue::Field(field), AbiType::Field) => {
+            let value = field_to_biguint(*field);
+            let mut candidates = shrink_unsigned(&value);
+
+            // Small negative values such as `-1` are better displayed as their negation.
+            let negated = field_to_biguint(-*field);
+            if negated < value {
+                candidates.push(negated);
+            }
+            vecmap(candidates, |candidate| InputValue::Field(biguint_to_field(&candidate)))
+        }
+        (InputValue::Field(field), AbiType::Integer { sign: Sign::Unsigned, .. }) => {
+            vecmap(shrink_unsigned(&field_to_biguint(*field)), |candidate| {
+                InputValue::Field(biguint_to_field(&candidate))
+            })
+        }
+        (InputValue::Field(field), AbiType::Integer { sign: Sign::Signed, width }) => {
+            let width = clamp_width(*width);
+            let value = to_signed(field_to_biguint(*field), width);
+            vecmap(shrink_signed(&value), |candidate| {
+                InputValue::Field(biguint_to_field(&from_signed(candidate, width)))
+            })
+        }
+        (InputValue::Field(field), AbiType::Boolean) if field.is_one() => {
+            vec![InputValue::Field(FieldElement::zero())]
+        }
+        (InputValue::String(string), AbiType::String { .. }) => string
+            .char_indices()
+            .filter(|(_, char)| *char != 'a')
+            .map(|(index, _)| {
+                let mut smaller_string = string.clone();
+                smaller_string.replace_range(index..index + 1, "a");
+                InputValue::String(smaller_string)
+            })
+            .collect(),
+        (InputValue::Vec(elements), AbiType::Array { typ, .. }) => {
+            let types = vec![&**typ; elements.len()];
+            vecmap(shrink_elements(elements, &types), InputValue::Vec)
+        }
+        (InputValue::Vec(elements), AbiType::Tuple { fields }) => {
+            let types: Vec<_> = fields.iter().collect();
+            vecmap(shrink_elements(elements, &types), InputValue::Vec)
+        }
+        (InputValue::Struct(struct_fields), AbiType::Struct { fields, .. }) => fields
+            .iter()
+            .flat_map(|(name, typ)| {
+                vecmap(shrink_value(&struct_fields[name], typ), |smaller_field| {
+                    let mut smaller_struct = struct_fields.clone();
+                    smaller_struct.insert(name.clone(), smaller_field);
+                    InputValue::Struct(smaller_struct)
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn shrink_elements(elements: &[InputValue], types: &[&AbiType]) -> Vec<Vec<InputValue>> {
+    elements
+        .iter()
+        .zip(types)
+        .enumerate()
+        .flat_map(|(index, (element, typ))| {
+            vecmap(shrink_value(element, typ), move |smaller_element| {
+                let mut smaller_elements = elements.to_vec();
+                smaller_elements[index] = smaller_element;
+                smaller_elements
+            })
+        })
+        .collect()
+}
+
+fn shrink_unsigned(value: &BigUint) -> Vec<BigUint> {
+    if value.is_zero() {
+        return Vec::new();
+    }
+    let mut candidates = vec![BigUint::zero(), value >> 1, value.clone() - 1u32];
+    candidates.dedup();
+    candidates.retain(|candidate| candidate != value);
+    candidates
+}
+
+fn shrink_signed(value: &BigInt) -> Vec<BigInt> {
+    if value.is_zero() {
+        return Vec::new();
+    }
+    let mut candidates = vec![BigInt::zero(), value.clone() / 2, value - value.signum()];
+    if value.is_negative() {
+        candidates.push(-value);
+    }
+    candidates.dedup();
+    candidates.retain(|candidate| candidate != value);
+    candidates
+}
+
+/// Integers wider than the field cannot be represented, so generated values are limited to the
+/// widest integer which always fits below the field modulus.
+fn clamp_width(width: u32) -> u32 {
+    width.min(FieldElement::max_num_bits() - 1)
+}
+
+fn low_bits_mask(width: u32) -> BigUint {
+    (BigUint::from(1u32) << width) - 1u32
+}
+
+/// Interprets the lowest `width` bits of `value` as a two's complement integer.
+fn to_signed(value: BigUint, width: u32) -> BigInt {
+    if value.bit(u64::from(width - 1)) {
+        BigInt::from(value) - (BigInt::from(1) << width)
+    } else {
+        BigInt::from(value)
+    }
+}
+
+/// Returns the two's complement representation of `value` in `width` bits.
+///
+/// Negative values of `-(2^w)` or less cannot be produced by shrinking a value which fits in the
+/// width, so the result always fits in `width` bits.
+fn from_signed(value: BigInt, width: u32) -> BigUint {
+    let value = if value.is_negative() { value + (BigInt::from(1) << width) } else { value };
+    value.to_biguint().expect("value should be non-negative")
+}
+
+fn field_to_biguint(field: FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&field.to_be_bytes())
+}
+
+fn biguint_to_field(value: &BigUint) -> FieldElement {
+    FieldElement::from_be_bytes_reduce(&value.to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use noirc_abi::{AbiParameter, AbiVisibility};
+
+    use super::*;
+
+    fn abi_with_parameter(typ: AbiType) -> Abi {
+        Abi {
+            parameters: vec![AbiParameter {
+                name: "x".to_string(),
+                typ,
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::new(),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn generated_inputs_match_the_abi() {
+        let typ = AbiType::Struct {
+            path: "Foo".to_string(),
+            fields: vec![
+                ("a".to_string(), AbiType::Integer { sign: Sign::Signed, width: 8 }),
+                (
+                    "b".to_string(),
+                    AbiType::Array {
+                        length: 3,
+                        typ: Box::new(AbiType::Integer { sign: Sign::Unsigned, width: 1 }),
+                    },
+                ),
+                ("c".to_string(), AbiType::String { length: 5 }),
+                (
+                    "d".to_string(),
+                    AbiType::Tuple { fields: vec![AbiType::Boolean, AbiType::Field] },
+                ),
+            ],
+        };
+        let abi = abi_with_parameter(typ.clone());
+
+        let mut generator = InputGenerator::new(42);
+        for _ in 0..100 {
+            let inputs = generator.generate_input_map(&abi);
+            assert!(inputs["x"].matches_abi(&typ));
+        }
+    }
+
+    #[test]
+    fn generation_is_deterministic_for_a_seed() {
+        let abi = abi_with_parameter(AbiType::Field);
+        let first = InputGenerator::new(7).generate_input_map(&abi);
+        let second = InputGenerator::new(7).generate_input_map(&abi);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn shrinks_to_the_smallest_failing_integer() {
+        let typ = AbiType::Integer { sign: Sign::Unsigned, width: 32 };
+        let abi = abi_with_parameter(typ);
+        let inputs = BTreeMap::from([(
+            "x".to_string(),
+            InputValue::Field(FieldElement::from(123_456_u128)),
+        )]);
+
+        let shrunk = shrink_input_map(&abi, inputs, |inputs| match &inputs["x"] {
+            InputValue::Field(value) => value.to_u128() >= 100,
+            _ => unreachable!(),
+        });
+        assert_eq!(shrunk["x"], InputValue::Field(FieldElement::from(100_u128)));
+    }
+
+    #[test]
+    fn shrinks_signed_integers_towards_zero() {
+        let width = 8;
+        let abi = abi_with_parameter(AbiType::Integer { sign: Sign::Signed, width });
+        // -100 as an 8 bit two's complement integer.
+        let inputs =
+            BTreeMap::from([("x".to_string(), InputValue::Field(FieldElement::from(156_u128)))]);
+
+        let shrunk = shrink_input_map(&abi, inputs, |inputs| match &inputs["x"] {
+            InputValue::Field(value) => {
+                to_signed(field_to_biguint(*value), width) <= BigInt::from(-10)
+            }
+            _ => unreachable!(),
+        });
+        // -10 as an 8 bit two's complement integer.
+        assert_eq!(shrunk["x"], InputValue::Field(FieldElement::from(246_u128)));
+    }
+}
@@ -1,11 +1,17 @@
 pub use self::compile::{compile_program, compile_workspace};
-pub use self::execute::execute_circuit;
+pub use self::execute::{
+    execute_circuit, execute_circuit_with_limits, execute_circuit_with_profiling,
+};
 pub use self::foreign_calls::{DefaultForeignCallExecutor, ForeignCallExecutor};
+pub use self::fuzz::{FuzzingConfig, DEFAULT_FUZZ_RUNS};
 pub use self::optimize::{optimize_contract, optimize_program};
-pub use self::test::{run_test, TestStatus};
+pub use self::profile::{BrilligProfile, SourceLine, SourceLineCost};
+pub use self::test::{run_test, TestStatus, DEFAULT_TEST_STEP_LIMIT};
 
 mod compile;
 mod execute;
 mod foreign_calls;
+mod fuzz;
 mod optimize;
+mod profile;
 mod test;
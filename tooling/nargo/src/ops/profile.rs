@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+use acvm::acir::{
+    brillig::Opcode as BrilligOpcode,
+    circuit::{Circuit, Opcode, OpcodeLocation},
+};
+use noirc_errors::{debug_info::DebugInfo, Location};
+
+use crate::artifacts::debug::DebugArtifact;
+
+/// Frame used for Brillig opcodes which have no source location attached.
+const UNKNOWN_FRAME: &str = "<unknown>";
+/// Leaf frame appended to the stacks of Brillig black box calls.
+const BLACK_BOX_FRAME: &str = "<black box>";
+
+/// The number of times each Brillig opcode was executed while solving a circuit.
+#[derive(Debug, Default, Clone)]
+pub struct BrilligProfile {
+    opcode_counts: BTreeMap<OpcodeLocation, usize>,
+}
+
+/// A line of Noir source code, along with the function which contains it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceLine {
+    pub path: String,
+    pub line: usize,
+    /// The name of the enclosing function, if it could be determined.
+    pub function: Option<String>,
+}
+
+impl Display for SourceLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.function {
+            Some(function) => write!(f, "{function} ({}:{})", self.path, self.line),
+            None => write!(f, "{}:{}", self.path, self.line),
+        }
+    }
+}
+
+/// The Brillig opcodes executed on behalf of a single line of Noir source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLineCost {
+    pub source_line: SourceLine,
+    pub opcodes: usize,
+    pub black_box_calls: usize,
+}
+
+impl BrilligProfile {
+    pub fn new(opcode_counts: BTreeMap<OpcodeLocation, usize>) -> Self {
+        BrilligProfile { opcode_counts }
+    }
+
+    /// Returns the number of times each Brillig opcode was executed.
+    pub fn opcode_counts(&self) -> &BTreeMap<OpcodeLocation, usize> {
+        &self.opcode_counts
+    }
+
+    /// Returns the total number of Brillig opcodes executed.
+    pub fn total_opcodes(&self) -> usize {
+        self.opcode_counts.values().sum()
+    }
+
+    /// Renders the profile in the folded stacks format consumed by flamegraph tools,
+    /// with a stack for each Noir call stack which led to Brillig opcodes being executed.
+    ///
+    /// `debug_info` must belong to `circuit` while `debug_artifact` provides the source files it refers to.
+    pub fn to_folded_stacks(
+        &self,
+        circuit: &Circuit,
+        debug_info: &DebugInfo,
+        debug_artifact: &DebugArtifact,
+    ) -> String {
+        let mut stacks: BTreeMap<String, usize> = BTreeMap::new();
+        for (opcode_location, count) in &self.opcode_counts {
+            let mut frames: Vec<String> = debug_info
+                .opcode_location(opcode_location)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|location| source_line(location, debug_info, debug_artifact).to_string())
+                .collect();
+            if frames.is_empty() {
+                frames.push(UNKNOWN_FRAME.to_owned());
+            }
+            if is_black_box_call(circuit, *opcode_location) {
+                frames.push(BLACK_BOX_FRAME.to_owned());
+            }
+            *stacks.entry(frames.join(";")).or_default() += count;
+        }
+
+        stacks.into_iter().map(|(stack, count)| format!("{stack} {count}\n")).collect()
+    }
+
+    /// Attributes executed Brillig opcodes to the innermost line of Noir source code they were generated from.
+    ///
+    /// The costs are sorted from most to fewest opcodes executed.
+    pub fn source_line_costs(
+        &self,
+        circuit: &Circuit,
+        debug_info: &DebugInfo,
+        debug_artifact: &DebugArtifact,
+    ) -> Vec<SourceLineCost> {
+        let mut costs: HashMap<SourceLine, SourceLineCost> = HashMap::new();
+        for (opcode_location, count) in &self.opcode_counts {
+            let source_line = match debug_info
+                .opcode_location(opcode_location)
+                .and_then(|call_stack| call_stack.last().copied())
+            {
+                Some(location) => source_line(location, debug_info, debug_artifact),
+                None => SourceLine { path: UNKNOWN_FRAME.to_owned(), line: 0, function: None },
+            };
+
+            let cost = costs.entry(source_line.clone()).or_insert(SourceLineCost {
+                source_line,
+                opcodes: 0,
+                black_box_calls: 0,
+            });
+            cost.opcodes += count;
+            if is_black_box_call(circuit, *opcode_location) {
+                cost.black_box_calls += count;
+            }
+        }
+
+        let mut costs: Vec<_> = costs.into_values().collect();
+        costs.sort_by(|a, b| {
+            b.opcodes.cmp(&a.opcodes).then_with(|| a.source_line.cmp(&b.source_line))
+        });
+        costs
+    }
+}
+
+fn source_line(
+    location: Location,
+    debug_info: &DebugInfo,
+    debug_artifact: &DebugArtifact,
+) -> SourceLine {
+    let Some(debug_file) = debug_artifact.file_map.get(&location.file) else {
+        return SourceLine { path: UNKNOWN_FRAME.to_owned(), line: 0, function: None };
+    };
+
+    SourceLine {
+        path: debug_file.path.display().to_string(),
+        line: debug_artifact.location_line_number(location).unwrap_or_default(),
+        function: debug_info.function_name(&location).map(ToOwned::to_owned),
+    }
+}
+
+/// Returns whether the opcode at `opcode_location` is a Brillig black box function call.
+fn is_black_box_call(circuit: &Circuit, opcode_location: OpcodeLocation) -> bool {
+    let OpcodeLocation::Brillig { acir_index, brillig_index } = opcode_location else {
+        return false;
+    };
+    match circuit.opcodes.get(acir_index) {
+        Some(Opcode::Brillig(brillig)) => {
+            matches!(brillig.bytecode.get(brillig_index), Some(BrilligOpcode::BlackBox(_)))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    use acvm::acir::circuit::OpcodeLocation;
+    use fm::FileManager;
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::source_line;
+    use crate::artifacts::debug::DebugArtifact;
+
+    #[test]
+    fn attributes_source_lines_to_innermost_function() {
+        let source =
+            "fn main(x: Field) {\n    // fn comment(x)\n    let f = |y| y + x;\n    f(x);\n}\n";
+
+        let mut file_manager = FileManager::new(Path::new("/"));
+        let file =
+            file_manager.add_file_with_source(Path::new("main.nr"), source.to_owned()).unwrap();
+        let location_of = |text: &str| {
+            let start = source.find(text).unwrap() as u32;
+            Location::new(Span::from(start..start + text.len() as u32), file)
+        };
+
+        let call = location_of("f(x);");
+        let debug_info = DebugInfo::new(
+            BTreeMap::from([(OpcodeLocation::Acir(0), vec![call])]),
+            vec![
+                (location_of(source.trim_end()), "main".to_owned()),
+                (location_of("|y| y + x"), "lambda".to_owned()),
+            ],
+            BTreeMap::new(),
+        );
+        let debug_artifact = DebugArtifact::new(vec![debug_info.clone()], &file_manager);
+
+        let function_of =
+            |text: &str| source_line(location_of(text), &debug_info, &debug_artifact).function;
+
+        // Comments mentioning `fn` do not start a new function.
+        assert_eq!(function_of("f(x);"), Some("main".to_owned()));
+        // Closures are attributed to themselves rather than the function defining them.
+        assert_eq!(function_of("y + x"), Some("lambda".to_owned()));
+        assert_eq!(source_line(call, &debug_info, &debug_artifact).line, 4);
+    }
+}
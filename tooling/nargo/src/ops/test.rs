@@ -1,19 +1,45 @@
-use acvm::{acir::native_types::WitnessMap, BlackBoxFunctionSolver};
-use noirc_driver::{compile_no_check, CompileOptions};
+use acvm::{
+    acir::native_types::WitnessMap, brillig_vm::ExecutionLimits, pwg::OpcodeResolutionError,
+    BlackBoxFunctionSolver,
+};
+use noirc_abi::{input_parser::Format, InputMap};
+use noirc_driver::{compile_no_check, CompileOptions, CompiledProgram};
 use noirc_errors::{debug_info::DebugInfo, FileDiagnostic};
 use noirc_evaluator::errors::RuntimeError;
 use noirc_frontend::hir::{def_map::TestFunction, Context};
 
-use crate::{errors::try_to_diagnose_runtime_error, NargoError};
+use crate::{
+    errors::{try_to_diagnose_runtime_error, ExecutionError},
+    NargoError,
+};
 
-use super::{execute_circuit, DefaultForeignCallExecutor};
+use super::{
+    execute_circuit_with_limits,
+    fuzz::{shrink_input_map, FuzzingConfig, InputGenerator},
+    DefaultForeignCallExecutor,
+};
+
+/// The number of Brillig opcodes a test may execute when no step limit is given, so that an
+/// unconstrained test which never terminates fails instead of hanging the test runner.
+pub const DEFAULT_TEST_STEP_LIMIT: usize = 100_000_000;
 
 pub enum TestStatus {
-    Pass,
-    Fail { message: String, error_diagnostic: Option<FileDiagnostic> },
+    /// The test passed, having executed `brillig_steps` Brillig opcodes if the circuit ran to completion.
+    Pass {
+        brillig_steps: Option<usize>,
+    },
+    Fail {
+        message: String,
+        error_diagnostic: Option<FileDiagnostic>,
+    },
     CompileError(FileDiagnostic),
 }
 
+/// Compiles and runs a test function.
+///
+/// Test functions which take parameters are run `fuzzing_config.runs` times against randomly
+/// generated inputs.
+#[allow(clippy::too_many_arguments)]
 pub fn run_test<B: BlackBoxFunctionSolver>(
     blackbox_solver: &B,
     context: &Context,
@@ -21,22 +47,97 @@ pub fn run_test<B: BlackBoxFunctionSolver>(
     show_output: bool,
     foreign_call_resolver_url: Option<&str>,
     config: &CompileOptions,
+    brillig_limits: ExecutionLimits,
+    fuzzing_config: &FuzzingConfig,
 ) -> TestStatus {
     let program = compile_no_check(context, config, test_function.get_id(), None, false);
     match program {
+        Ok(program) if !program.abi.parameters.is_empty() => run_test_with_random_inputs(
+            blackbox_solver,
+            &test_function,
+            &program,
+            show_output,
+            foreign_call_resolver_url,
+            brillig_limits,
+            fuzzing_config,
+        ),
         Ok(program) => {
             // Run the backend to ensure the PWG evaluates functions like std::hash::pedersen,
             // otherwise constraints involving these expressions will not error.
-            let circuit_execution = execute_circuit(
+            let circuit_execution = execute_circuit_with_limits(
                 &program.circuit,
                 WitnessMap::new(),
                 blackbox_solver,
                 &mut DefaultForeignCallExecutor::new(show_output, foreign_call_resolver_url),
+                brillig_limits,
             );
-            test_status_program_compile_pass(test_function, program.debug, circuit_execution)
+            test_status_program_compile_pass(&test_function, &program.debug, circuit_execution)
+        }
+        Err(err) => test_status_program_compile_fail(err, &test_function),
+    }
+}
+
+/// Runs a test function which takes parameters against randomly generated inputs.
+///
+/// The first set of inputs for which the test fails is shrunk to a smaller set of failing inputs,
+/// which is reported alongside the seed needed to reproduce the failure.
+fn run_test_with_random_inputs<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    test_function: &TestFunction,
+    program: &CompiledProgram,
+    show_output: bool,
+    foreign_call_resolver_url: Option<&str>,
+    brillig_limits: ExecutionLimits,
+    fuzzing_config: &FuzzingConfig,
+) -> TestStatus {
+    let run_with_inputs = |inputs: &InputMap| {
+        let initial_witness = match program.abi.encode(inputs, None) {
+            Ok(initial_witness) => initial_witness,
+            Err(err) => {
+                return TestStatus::Fail {
+                    message: format!("error: Failed to encode generated inputs: {err}"),
+                    error_diagnostic: None,
+                }
+            }
+        };
+        let circuit_execution = execute_circuit_with_limits(
+            &program.circuit,
+            initial_witness,
+            blackbox_solver,
+            &mut DefaultForeignCallExecutor::new(show_output, foreign_call_resolver_url),
+            brillig_limits,
+        );
+        test_status_program_compile_pass(test_function, &program.debug, circuit_execution)
+    };
+
+    let mut generator = InputGenerator::new(fuzzing_config.seed);
+    for run in 1..=fuzzing_config.runs {
+        let inputs = generator.generate_input_map(&program.abi);
+        if !matches!(run_with_inputs(&inputs), TestStatus::Fail { .. }) {
+            continue;
         }
-        Err(err) => test_status_program_compile_fail(err, test_function),
+
+        let inputs = shrink_input_map(&program.abi, inputs, |candidate| {
+            matches!(run_with_inputs(candidate), TestStatus::Fail { .. })
+        });
+        return match run_with_inputs(&inputs) {
+            TestStatus::Fail { message, error_diagnostic } => {
+                let displayed_inputs = Format::Toml
+                    .serialize(&inputs, &program.abi)
+                    .unwrap_or_else(|_| format!("{inputs:?}"));
+                TestStatus::Fail {
+                    message: format!(
+                        "{message}\n\nTest failed on run {run} with inputs:\n{displayed_inputs}\nRe-run with `--fuzz-seed {}` to reproduce this failure.",
+                        fuzzing_config.seed
+                    ),
+                    error_diagnostic,
+                }
+            }
+            status => status,
+        };
     }
+
+    TestStatus::Pass { brillig_steps: None }
 }
 
 /// Test function failed to compile
@@ -45,7 +146,7 @@ pub fn run_test<B: BlackBoxFunctionSolver>(
 /// that a constraint was never satisfiable.
 /// An example of this is the program `assert(false)`
 /// In that case, we check if the test function should fail, and if so, we return `TestStatus::Pass`.
-fn test_status_program_compile_fail(err: RuntimeError, test_function: TestFunction) -> TestStatus {
+fn test_status_program_compile_fail(err: RuntimeError, test_function: &TestFunction) -> TestStatus {
     // The test has failed compilation, but it should never fail. Report error.
     if !test_function.should_fail() {
         return TestStatus::CompileError(err.into());
@@ -66,29 +167,43 @@ fn test_status_program_compile_fail(err: RuntimeError, test_function: TestFuncti
 /// We now check whether execution passed/failed and whether it should have
 /// passed/failed to determine the test status.
 fn test_status_program_compile_pass(
-    test_function: TestFunction,
-    debug: DebugInfo,
-    circuit_execution: Result<WitnessMap, NargoError>,
+    test_function: &TestFunction,
+    debug: &DebugInfo,
+    circuit_execution: Result<(WitnessMap, usize), NargoError>,
 ) -> TestStatus {
     let circuit_execution_err = match circuit_execution {
         // Circuit execution was successful; ie no errors or unsatisfied constraints
         // were encountered.
-        Ok(_) => {
+        Ok((_, brillig_steps)) => {
             if test_function.should_fail() {
                 return TestStatus::Fail {
                     message: "error: Test passed when it should have failed".to_string(),
                     error_diagnostic: None,
                 };
             }
-            return TestStatus::Pass;
+            return TestStatus::Pass { brillig_steps: Some(brillig_steps) };
         }
         Err(err) => err,
     };
 
     // If we reach here, then the circuit execution failed.
-    //
+    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, debug);
+
+    // Exceeding a Brillig execution limit says nothing about whether the test's assertions hold,
+    // so it is reported as a failure even for tests which are expected to fail.
+    if matches!(
+        circuit_execution_err,
+        NargoError::ExecutionError(ExecutionError::SolvingError(
+            OpcodeResolutionError::BrilligResourceExhausted { .. }
+        ))
+    ) {
+        return TestStatus::Fail {
+            message: circuit_execution_err.to_string(),
+            error_diagnostic: diagnostic,
+        };
+    }
+
     // Check if the function should have passed
-    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, &debug);
     let test_should_have_passed = !test_function.should_fail();
     if test_should_have_passed {
         return TestStatus::Fail {
@@ -105,7 +220,7 @@ fn test_status_program_compile_pass(
 }
 
 fn check_expected_failure_message(
-    test_function: TestFunction,
+    test_function: &TestFunction,
     failed_assertion: Option<String>,
     error_diagnostic: Option<FileDiagnostic>,
 ) -> TestStatus {
@@ -116,13 +231,13 @@ fn check_expected_failure_message(
     //
     let expected_failure_message = match test_function.failure_reason() {
         Some(reason) => reason,
-        None => return TestStatus::Pass,
+        None => return TestStatus::Pass { brillig_steps: None },
     };
 
     let expected_failure_message_matches =
         matches!(&failed_assertion, Some(message) if message == expected_failure_message);
     if expected_failure_message_matches {
-        return TestStatus::Pass;
+        return TestStatus::Pass { brillig_steps: None };
     }
 
     // The expected failure message does not match the actual failure message
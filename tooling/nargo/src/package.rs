@@ -1,8 +1,11 @@
 use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
 
+use acvm::ExpressionWidth;
+use noirc_driver::CompileOptions;
 use noirc_frontend::graph::CrateName;
 
-use crate::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
+use crate::constants::{DEFAULT_PROFILE_NAME, PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
+use crate::errors::CompileError;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PackageType {
@@ -41,6 +44,42 @@ impl Dependency {
     }
 }
 
+/// A named set of compiler settings, declared in a `[profile.<name>]` section of `Nargo.toml`.
+///
+/// Any setting which is left unspecified falls back to the value passed on the command line.
+#[derive(Debug, Default, Clone)]
+pub struct CompileProfile {
+    pub show_ssa: Option<bool>,
+    pub show_brillig: Option<bool>,
+    pub print_acir: Option<bool>,
+    pub deny_warnings: Option<bool>,
+    pub silence_warnings: Option<bool>,
+    pub disable_macros: Option<bool>,
+    pub expression_width: Option<ExpressionWidth>,
+}
+
+impl CompileProfile {
+    /// Merges this profile with the `options` passed on the command line.
+    ///
+    /// Flags enabled on the command line always take precedence over the profile.
+    pub fn apply(&self, options: &CompileOptions) -> CompileOptions {
+        let mut options = options.clone();
+        options.show_ssa |= self.show_ssa.unwrap_or_default();
+        options.show_brillig |= self.show_brillig.unwrap_or_default();
+        options.print_acir |= self.print_acir.unwrap_or_default();
+        options.disable_macros |= self.disable_macros.unwrap_or_default();
+
+        // `deny_warnings` and `silence_warnings` conflict, so the profile may only enable
+        // one of them if the command line hasn't already picked the other.
+        if !options.deny_warnings && !options.silence_warnings {
+            options.deny_warnings = self.deny_warnings.unwrap_or_default();
+            options.silence_warnings =
+                !options.deny_warnings && self.silence_warnings.unwrap_or_default();
+        }
+        options
+    }
+}
+
 #[derive(Clone)]
 pub struct Package {
     pub version: Option<String>,
@@ -51,6 +90,9 @@ pub struct Package {
     pub entry_path: PathBuf,
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
+    pub profiles: BTreeMap<String, CompileProfile>,
+    /// The names of the macro plugins to run when compiling this package, if not all of them
+    pub macros: Option<Vec<String>>,
 }
 
 impl Package {
@@ -76,4 +118,42 @@ impl Package {
     pub fn is_library(&self) -> bool {
         self.package_type == PackageType::Library
     }
+
+    /// Returns the compile profile selected by `name`.
+    ///
+    /// If no name is given then the `default` profile is used if the package declares one.
+    pub fn profile(&self, name: Option<&str>) -> Result<CompileProfile, CompileError> {
+        match name {
+            Some(name) => self.profiles.get(name).cloned().ok_or_else(|| {
+                CompileError::MissingProfile { package: self.name.clone(), profile: name.into() }
+            }),
+            None => Ok(self.profiles.get(DEFAULT_PROFILE_NAME).cloned().unwrap_or_default()),
+        }
+    }
+
+    /// Resolves the compile options to use when compiling this package, applying the profile
+    /// selected in `options` on top of the command line settings.
+    ///
+    /// The package's macro plugins are used unless others were selected on the command line.
+    pub fn compile_options(
+        &self,
+        options: &CompileOptions,
+    ) -> Result<CompileOptions, CompileError> {
+        let mut options = self.profile(options.profile.as_deref())?.apply(options);
+        if options.macros.is_none() {
+            options.macros = self.macros.clone();
+        }
+        Ok(options)
+    }
+
+    /// Resolves the compile options and expression width to use when compiling this package.
+    pub fn resolve_compile_options(
+        &self,
+        options: &CompileOptions,
+        expression_width: ExpressionWidth,
+    ) -> Result<(CompileOptions, ExpressionWidth), CompileError> {
+        let profile = self.profile(options.profile.as_deref())?;
+        let expression_width = profile.expression_width.unwrap_or(expression_width);
+        Ok((self.compile_options(options)?, expression_width))
+    }
 }
@@ -151,6 +151,8 @@ fn noir_test_failure_{test_name}() {{
     cmd.env("NARGO_BACKEND_PATH", path_to_mock_backend());
     cmd.arg("--program-dir").arg(test_program_dir);
     cmd.arg("test");
+    // Bound unconstrained execution so that tests which loop forever fail instead of hanging.
+    cmd.arg("--brillig-step-limit").arg("1000000");
 
     cmd.assert().failure();
 }}
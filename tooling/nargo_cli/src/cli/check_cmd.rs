@@ -11,9 +11,10 @@ use nargo::{
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{
-    check_crate, compute_function_abi, file_manager_with_stdlib, CompileOptions,
-    NOIR_ARTIFACT_VERSION_STRING,
+    check_crate_with_macros, compute_function_abi, file_manager_with_stdlib, select_macros,
+    CompileOptions, NOIR_ARTIFACT_VERSION_STRING,
 };
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::{
     graph::{CrateId, CrateName},
     hir::Context,
@@ -56,7 +57,12 @@ pub(crate) fn run(
     insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
 
     for package in &workspace {
-        check_package(&workspace_file_manager, package, &args.compile_options)?;
+        check_package(
+            &workspace_file_manager,
+            package,
+            &args.compile_options,
+            config.message_format,
+        )?;
         println!("[{}] Constraint system successfully built!", package.name);
     }
     Ok(())
@@ -66,15 +72,11 @@ fn check_package(
     file_manager: &FileManager,
     package: &Package,
     compile_options: &CompileOptions,
+    message_format: MessageFormat,
 ) -> Result<(), CompileError> {
+    let compile_options = &package.compile_options(compile_options)?;
     let (mut context, crate_id) = prepare_package(file_manager, package);
-    check_crate_and_report_errors(
-        &mut context,
-        crate_id,
-        compile_options.deny_warnings,
-        compile_options.disable_macros,
-        compile_options.silence_warnings,
-    )?;
+    check_crate_and_report_errors(&mut context, crate_id, compile_options, message_format)?;
 
     if package.is_library() || package.is_contract() {
         // Libraries do not have ABIs while contracts have many, so we cannot generate a `Prover.toml` file.
@@ -193,15 +195,17 @@ d2 = ["", "", ""]
 pub(crate) fn check_crate_and_report_errors(
     context: &mut Context,
     crate_id: CrateId,
-    deny_warnings: bool,
-    disable_macros: bool,
-    silence_warnings: bool,
+    compile_options: &CompileOptions,
+    message_format: MessageFormat,
 ) -> Result<(), CompileError> {
-    let result = check_crate(context, crate_id, deny_warnings, disable_macros);
+    let deny_warnings = compile_options.deny_warnings;
+    let result = select_macros(context, crate_id, compile_options)
+        .and_then(|macros| check_crate_with_macros(context, crate_id, deny_warnings, &macros));
     super::compile_cmd::report_errors(
         result,
         &context.file_manager,
         deny_warnings,
-        silence_warnings,
+        compile_options.silence_warnings,
+        message_format,
     )
 }
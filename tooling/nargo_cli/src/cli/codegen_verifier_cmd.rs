@@ -15,6 +15,7 @@ use nargo::package::Package;
 use nargo::workspace::Workspace;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::graph::CrateName;
 
 /// Generates a Solidity verifier smart contract for the program
@@ -59,6 +60,7 @@ pub(crate) fn run(
             package,
             &args.compile_options,
             expression_width,
+            config.message_format,
         )?;
 
         let contract_dir = workspace.contracts_directory_path(package);
@@ -79,9 +81,16 @@ fn smart_contract_for_package(
     package: &Package,
     compile_options: &CompileOptions,
     expression_width: ExpressionWidth,
+    message_format: MessageFormat,
 ) -> Result<String, CliError> {
-    let program =
-        compile_bin_package(file_manager, workspace, package, compile_options, expression_width)?;
+    let program = compile_bin_package(
+        file_manager,
+        workspace,
+        package,
+        compile_options,
+        expression_width,
+        message_format,
+    )?;
 
     let mut smart_contract_string = backend.eth_contract(&program.circuit)?;
 
@@ -2,9 +2,9 @@ use std::path::Path;
 
 use acvm::ExpressionWidth;
 use fm::FileManager;
-use iter_extended::vecmap;
+use iter_extended::try_vecmap;
 use nargo::artifacts::contract::PreprocessedContract;
-use nargo::artifacts::contract::PreprocessedContractFunction;
+use nargo::artifacts::contract::{ContractFunctionVerificationKey, PreprocessedContractFunction};
 use nargo::artifacts::debug::DebugArtifact;
 use nargo::artifacts::program::PreprocessedProgram;
 use nargo::errors::CompileError;
@@ -15,7 +15,10 @@ use nargo::workspace::Workspace;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::file_manager_with_stdlib;
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
-use noirc_driver::{CompilationResult, CompileOptions, CompiledContract, CompiledProgram};
+use noirc_driver::{
+    CompilationResult, CompileOptions, CompiledContract, CompiledProgram, ContractFunctionType,
+};
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::graph::CrateName;
 
 use clap::Args;
@@ -42,6 +45,11 @@ pub(crate) struct CompileCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Use the backend to add gate counts and verification keys for the secret functions of
+    /// any contracts to their artifacts
+    #[clap(long)]
+    with_vk: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -80,11 +88,13 @@ pub(crate) fn run(
         &contract_packages,
         expression_width,
         &args.compile_options,
+        config.message_format,
     )?;
 
     // Save build artifacts to disk.
+    let backend_for_vks = args.with_vk.then_some(backend);
     for (package, contract) in contract_packages.into_iter().zip(compiled_contracts) {
-        save_contract(contract, &package, &circuit_dir);
+        save_contract(contract, &package, &circuit_dir, backend_for_vks)?;
     }
 
     Ok(())
@@ -97,39 +107,57 @@ pub(super) fn compile_workspace(
     contract_packages: &[Package],
     expression_width: ExpressionWidth,
     compile_options: &CompileOptions,
+    message_format: MessageFormat,
 ) -> Result<(Vec<CompiledProgram>, Vec<CompiledContract>), CliError> {
+    // Resolve each package's compile profile up front so that a missing profile is reported
+    // before we start compiling anything.
+    let resolve_options =
+        |package: &Package| package.resolve_compile_options(compile_options, expression_width);
+    let binary_options: Vec<_> =
+        binary_packages.iter().map(resolve_options).collect::<Result<_, _>>()?;
+    let contract_options: Vec<_> =
+        contract_packages.iter().map(resolve_options).collect::<Result<_, _>>()?;
+
     // Compile all of the packages in parallel.
     let program_results: Vec<CompilationResult<CompiledProgram>> = binary_packages
         .par_iter()
-        .map(|package| {
-            compile_program(file_manager, workspace, package, compile_options, expression_width)
+        .zip(&binary_options)
+        .map(|(package, (compile_options, expression_width))| {
+            compile_program(file_manager, workspace, package, compile_options, *expression_width)
         })
         .collect();
     let contract_results: Vec<CompilationResult<CompiledContract>> = contract_packages
         .par_iter()
-        .map(|package| compile_contract(file_manager, package, compile_options, expression_width))
+        .zip(&contract_options)
+        .map(|(package, (compile_options, expression_width))| {
+            compile_contract(file_manager, package, compile_options, *expression_width)
+        })
         .collect();
 
     // Report any warnings/errors which were encountered during compilation.
     let compiled_programs: Vec<CompiledProgram> = program_results
         .into_iter()
-        .map(|compilation_result| {
+        .zip(&binary_options)
+        .map(|(compilation_result, (compile_options, _))| {
             report_errors(
                 compilation_result,
                 file_manager,
                 compile_options.deny_warnings,
                 compile_options.silence_warnings,
+                message_format,
             )
         })
         .collect::<Result<_, _>>()?;
     let compiled_contracts: Vec<CompiledContract> = contract_results
         .into_iter()
-        .map(|compilation_result| {
+        .zip(&contract_options)
+        .map(|(compilation_result, (compile_options, _))| {
             report_errors(
                 compilation_result,
                 file_manager,
                 compile_options.deny_warnings,
                 compile_options.silence_warnings,
+                message_format,
             )
         })
         .collect::<Result<_, _>>()?;
@@ -143,19 +171,23 @@ pub(crate) fn compile_bin_package(
     package: &Package,
     compile_options: &CompileOptions,
     expression_width: ExpressionWidth,
+    message_format: MessageFormat,
 ) -> Result<CompiledProgram, CliError> {
     if package.is_library() {
         return Err(CompileError::LibraryCrate(package.name.clone()).into());
     }
 
+    let (compile_options, expression_width) =
+        package.resolve_compile_options(compile_options, expression_width)?;
     let compilation_result =
-        compile_program(file_manager, workspace, package, compile_options, expression_width);
+        compile_program(file_manager, workspace, package, &compile_options, expression_width);
 
     let program = report_errors(
         compilation_result,
         file_manager,
         compile_options.deny_warnings,
         compile_options.silence_warnings,
+        message_format,
     )?;
 
     Ok(program)
@@ -260,7 +292,17 @@ fn save_program(
     save_debug_artifact_to_file(&debug_artifact, &circuit_name, circuit_dir);
 }
 
-fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Path) {
+/// Saves the artifacts of a compiled contract to `circuit_dir`.
+///
+/// If a backend is provided, it is used to compute the gate count and verification key
+/// of each secret function. Open and unconstrained functions are not proven by the backend,
+/// so they are left without either.
+fn save_contract(
+    contract: CompiledContract,
+    package: &Package,
+    circuit_dir: &Path,
+    backend: Option<&Backend>,
+) -> Result<(), CliError> {
     // TODO(#1389): I wonder if it is incorrect for nargo-core to know anything about contracts.
     // As can be seen here, It seems like a leaky abstraction where ContractFunctions (essentially CompiledPrograms)
     // are compiled via nargo-core and then the PreprocessedContract is constructed here.
@@ -271,13 +313,29 @@ fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Pa
         warnings: contract.warnings,
     };
 
-    let preprocessed_functions = vecmap(contract.functions, |func| PreprocessedContractFunction {
-        name: func.name,
-        function_type: func.function_type,
-        is_internal: func.is_internal,
-        abi: func.abi,
-        bytecode: func.bytecode,
-    });
+    let preprocessed_functions = try_vecmap(contract.functions, |func| {
+        let (gate_count, verification_key) = match backend {
+            Some(backend) if func.function_type == ContractFunctionType::Secret => {
+                let gate_count = backend.get_exact_circuit_size(&func.bytecode)?;
+                let (key, hash) = backend.get_verification_key(&func.bytecode)?;
+                let verification_key =
+                    ContractFunctionVerificationKey { key: hex::encode(key), hash };
+                (Some(gate_count), Some(verification_key))
+            }
+            _ => (None, None),
+        };
+
+        Ok::<_, CliError>(PreprocessedContractFunction {
+            name: func.name,
+            function_type: func.function_type,
+            is_internal: func.is_internal,
+            selector: func.selector,
+            abi: func.abi,
+            bytecode: func.bytecode,
+            gate_count,
+            verification_key,
+        })
+    })?;
 
     let preprocessed_contract = PreprocessedContract {
         noir_version: contract.noir_version,
@@ -297,6 +355,8 @@ fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Pa
         &format!("{}-{}", package.name, preprocessed_contract.name),
         circuit_dir,
     );
+
+    Ok(())
 }
 
 /// Helper function for reporting any errors in a `CompilationResult<T>`
@@ -306,6 +366,7 @@ pub(crate) fn report_errors<T>(
     file_manager: &FileManager,
     deny_warnings: bool,
     silence_warnings: bool,
+    message_format: MessageFormat,
 ) -> Result<T, CompileError> {
     let (t, warnings) = result.map_err(|errors| {
         noirc_errors::reporter::report_all(
@@ -313,6 +374,7 @@ pub(crate) fn report_errors<T>(
             &errors,
             deny_warnings,
             silence_warnings,
+            message_format,
         )
     })?;
 
@@ -321,6 +383,7 @@ pub(crate) fn report_errors<T>(
         &warnings,
         deny_warnings,
         silence_warnings,
+        message_format,
     );
 
     Ok(t)
@@ -9,6 +9,7 @@ use noirc_abi::input_parser::Format;
 use noirc_driver::{
     file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
 };
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::graph::CrateName;
 
 use std::io::{BufReader, BufWriter, Read, Write};
@@ -77,6 +78,7 @@ fn load_and_compile_project(
         package,
         &CompileOptions::default(),
         expression_width,
+        MessageFormat::default(),
     )
     .map_err(|_| LoadError("Failed to compile project"))?;
 
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use acvm::acir::native_types::WitnessMap;
+use acvm::brillig_vm::ExecutionLimits;
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 
@@ -17,6 +18,7 @@ use noirc_driver::{
 use noirc_frontend::graph::CrateName;
 
 use super::compile_cmd::compile_bin_package;
+use super::execute_cmd::BrilligLimitOptions;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
 use super::NargoConfig;
 use crate::backends::Backend;
@@ -38,6 +40,9 @@ pub(crate) struct DebugCommand {
 
     #[clap(flatten)]
     compile_options: CompileOptions,
+
+    #[clap(flatten)]
+    brillig_limits: BrilligLimitOptions,
 }
 
 pub(crate) fn run(
@@ -71,9 +76,17 @@ pub(crate) fn run(
         package,
         &args.compile_options,
         expression_width,
+        config.message_format,
     )?;
 
-    run_async(package, compiled_program, &args.prover_name, &args.witness_name, target_dir)
+    run_async(
+        package,
+        compiled_program,
+        &args.prover_name,
+        &args.witness_name,
+        target_dir,
+        (&args.brillig_limits).into(),
+    )
 }
 
 fn run_async(
@@ -82,6 +95,7 @@ fn run_async(
     prover_name: &str,
     witness_name: &Option<String>,
     target_dir: &PathBuf,
+    brillig_limits: ExecutionLimits,
 ) -> Result<(), CliError> {
     use tokio::runtime::Builder;
     let runtime = Builder::new_current_thread().enable_all().build().unwrap();
@@ -89,7 +103,7 @@ fn run_async(
     runtime.block_on(async {
         println!("[{}] Starting debugger", package.name);
         let (return_value, solved_witness) =
-            debug_program_and_decode(program, package, prover_name)?;
+            debug_program_and_decode(program, package, prover_name, brillig_limits)?;
 
         if let Some(solved_witness) = solved_witness {
             println!("[{}] Circuit witness successfully solved", package.name);
@@ -115,11 +129,12 @@ fn debug_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
+    brillig_limits: ExecutionLimits,
 ) -> Result<(Option<InputValue>, Option<WitnessMap>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let solved_witness = debug_program(&program, &inputs_map)?;
+    let solved_witness = debug_program(&program, &inputs_map, brillig_limits)?;
     let public_abi = program.abi.public_abi();
 
     match solved_witness {
@@ -134,6 +149,7 @@ fn debug_program_and_decode(
 pub(crate) fn debug_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
+    brillig_limits: ExecutionLimits,
 ) -> Result<Option<WitnessMap>, CliError> {
     let blackbox_solver = Bn254BlackBoxSolver::new();
 
@@ -150,6 +166,7 @@ pub(crate) fn debug_program(
         &compiled_program.circuit,
         debug_artifact,
         initial_witness,
+        brillig_limits,
     )
     .map_err(CliError::from)
 }
@@ -1,4 +1,7 @@
+use std::path::Path;
+
 use acvm::acir::native_types::WitnessMap;
+use acvm::brillig_vm::ExecutionLimits;
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 
@@ -6,18 +9,24 @@ use nargo::artifacts::debug::DebugArtifact;
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
 use nargo::insert_all_files_for_workspace_into_file_manager;
-use nargo::ops::DefaultForeignCallExecutor;
+use nargo::ops::{BrilligProfile, DefaultForeignCallExecutor};
 use nargo::package::Package;
+use nargo::NargoError;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
 use noirc_driver::{
     file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
 };
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::graph::CrateName;
+use prettytable::{row, table};
 
 use super::compile_cmd::compile_bin_package;
-use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
+use super::fs::{
+    inputs::read_inputs_from_file, profile::save_brillig_profile_to_dir,
+    witness::save_witness_to_dir,
+};
 use super::NargoConfig;
 use crate::backends::Backend;
 use crate::errors::CliError;
@@ -46,6 +55,44 @@ pub(crate) struct ExecuteCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    #[clap(flatten)]
+    brillig_limits: BrilligLimitOptions,
+
+    /// Count the Brillig opcodes executed by each line of source code, saving them to a
+    /// flamegraph-compatible folded stacks file in the target directory
+    #[clap(long)]
+    profile_brillig: bool,
+}
+
+/// The number of source lines shown in the Brillig profile summary.
+const PROFILE_SUMMARY_LENGTH: usize = 20;
+
+/// Limits on the resources which unconstrained functions may consume during execution
+#[derive(Debug, Clone, Default, Args)]
+pub(crate) struct BrilligLimitOptions {
+    /// Abort execution once this many Brillig opcodes have been executed
+    /// [default: no limit, or 100000000 for each test run by `nargo test`]
+    #[clap(long)]
+    brillig_step_limit: Option<usize>,
+
+    /// Abort execution if an unconstrained function grows its memory past this many slots
+    #[clap(long)]
+    brillig_memory_limit: Option<usize>,
+
+    /// Abort execution once this many foreign calls have been made
+    #[clap(long)]
+    brillig_foreign_call_limit: Option<usize>,
+}
+
+impl From<&BrilligLimitOptions> for ExecutionLimits {
+    fn from(options: &BrilligLimitOptions) -> Self {
+        ExecutionLimits {
+            max_steps: options.brillig_step_limit,
+            max_memory: options.brillig_memory_limit,
+            max_foreign_calls: options.brillig_foreign_call_limit,
+        }
+    }
 }
 
 pub(crate) fn run(
@@ -75,16 +122,24 @@ pub(crate) fn run(
             package,
             &args.compile_options,
             expression_width,
+            config.message_format,
         )?;
 
-        let (return_value, solved_witness) = execute_program_and_decode(
-            compiled_program,
-            package,
-            &args.prover_name,
-            args.oracle_resolver.as_deref(),
-        )?;
+        let (return_value, solved_witness, brillig_steps, brillig_profile) =
+            execute_program_and_decode(
+                &compiled_program,
+                package,
+                &args.prover_name,
+                args.oracle_resolver.as_deref(),
+                (&args.brillig_limits).into(),
+                args.profile_brillig,
+                config.message_format,
+            )?;
 
         println!("[{}] Circuit witness successfully solved", package.name);
+        if brillig_steps > 0 {
+            println!("[{}] Executed {brillig_steps} Brillig opcodes", package.name);
+        }
         if let Some(return_value) = return_value {
             println!("[{}] Circuit output: {return_value:?}", package.name);
         }
@@ -93,55 +148,145 @@ pub(crate) fn run(
 
             println!("[{}] Witness saved to {}", package.name, witness_path.display());
         }
+        if let Some(brillig_profile) = brillig_profile {
+            report_brillig_profile(&compiled_program, package, &brillig_profile, target_dir);
+        }
     }
     Ok(())
 }
 
 fn execute_program_and_decode(
-    program: CompiledProgram,
+    program: &CompiledProgram,
     package: &Package,
     prover_name: &str,
     foreign_call_resolver_url: Option<&str>,
-) -> Result<(Option<InputValue>, WitnessMap), CliError> {
+    brillig_limits: ExecutionLimits,
+    profile_brillig: bool,
+    message_format: MessageFormat,
+) -> Result<(Option<InputValue>, WitnessMap, usize, Option<BrilligProfile>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let solved_witness = execute_program(&program, &inputs_map, foreign_call_resolver_url)?;
+    let (solved_witness, brillig_steps, brillig_profile) = if profile_brillig {
+        let (solved_witness, brillig_profile) = profile_program(
+            program,
+            &inputs_map,
+            foreign_call_resolver_url,
+            brillig_limits,
+            message_format,
+        )?;
+        (solved_witness, brillig_profile.total_opcodes(), Some(brillig_profile))
+    } else {
+        let (solved_witness, brillig_steps) = execute_program(
+            program,
+            &inputs_map,
+            foreign_call_resolver_url,
+            brillig_limits,
+            message_format,
+        )?;
+        (solved_witness, brillig_steps, None)
+    };
     let public_abi = program.abi.public_abi();
     let (_, return_value) = public_abi.decode(&solved_witness)?;
 
-    Ok((return_value, solved_witness))
+    Ok((return_value, solved_witness, brillig_steps, brillig_profile))
 }
 
 pub(crate) fn execute_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
     foreign_call_resolver_url: Option<&str>,
-) -> Result<WitnessMap, CliError> {
+    brillig_limits: ExecutionLimits,
+    message_format: MessageFormat,
+) -> Result<(WitnessMap, usize), CliError> {
     let blackbox_solver = Bn254BlackBoxSolver::new();
 
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    let solved_witness_err = nargo::ops::execute_circuit(
+    nargo::ops::execute_circuit_with_limits(
         &compiled_program.circuit,
         initial_witness,
         &blackbox_solver,
         &mut DefaultForeignCallExecutor::new(true, foreign_call_resolver_url),
-    );
-    match solved_witness_err {
-        Ok(solved_witness) => Ok(solved_witness),
-        Err(err) => {
-            let debug_artifact = DebugArtifact {
-                debug_symbols: vec![compiled_program.debug.clone()],
-                file_map: compiled_program.file_map.clone(),
-                warnings: compiled_program.warnings.clone(),
-            };
-
-            if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &compiled_program.debug) {
-                diagnostic.report(&debug_artifact, false);
-            }
-
-            Err(crate::errors::CliError::NargoError(err))
-        }
+        brillig_limits,
+    )
+    .map_err(|err| report_execution_error(compiled_program, err, message_format))
+}
+
+/// Executes the program while counting the Brillig opcodes executed, see [BrilligProfile].
+fn profile_program(
+    compiled_program: &CompiledProgram,
+    inputs_map: &InputMap,
+    foreign_call_resolver_url: Option<&str>,
+    brillig_limits: ExecutionLimits,
+    message_format: MessageFormat,
+) -> Result<(WitnessMap, BrilligProfile), CliError> {
+    let blackbox_solver = Bn254BlackBoxSolver::new();
+
+    let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
+
+    nargo::ops::execute_circuit_with_profiling(
+        &compiled_program.circuit,
+        initial_witness,
+        &blackbox_solver,
+        &mut DefaultForeignCallExecutor::new(true, foreign_call_resolver_url),
+        brillig_limits,
+    )
+    .map_err(|err| report_execution_error(compiled_program, err, message_format))
+}
+
+/// Reports the diagnostic for a failed execution against the program's source, if it can be found.
+fn report_execution_error(
+    compiled_program: &CompiledProgram,
+    err: NargoError,
+    message_format: MessageFormat,
+) -> CliError {
+    let debug_artifact = DebugArtifact {
+        debug_symbols: vec![compiled_program.debug.clone()],
+        file_map: compiled_program.file_map.clone(),
+        warnings: compiled_program.warnings.clone(),
+    };
+
+    if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &compiled_program.debug) {
+        diagnostic.report(&debug_artifact, false, message_format);
+    }
+
+    CliError::NargoError(err)
+}
+
+/// Saves the Brillig profile as folded stacks and prints the most expensive source lines.
+fn report_brillig_profile(
+    compiled_program: &CompiledProgram,
+    package: &Package,
+    brillig_profile: &BrilligProfile,
+    target_dir: &Path,
+) {
+    let debug_artifact: DebugArtifact = compiled_program.clone().into();
+    let debug_info = &compiled_program.debug;
+
+    let folded_stacks =
+        brillig_profile.to_folded_stacks(&compiled_program.circuit, debug_info, &debug_artifact);
+    let profile_name = format!("brillig_{}", package.name);
+    let profile_path = save_brillig_profile_to_dir(&folded_stacks, &profile_name, target_dir);
+    println!("[{}] Brillig profile saved to {}", package.name, profile_path.display());
+
+    let total_opcodes = brillig_profile.total_opcodes();
+    if total_opcodes == 0 {
+        return;
+    }
+
+    let mut table = table!([Fm->"Function", Fm->"Location", Fm->"Brillig Opcodes", Fm->"Black Box Calls", Fm->"% of Opcodes"]);
+    let source_line_costs =
+        brillig_profile.source_line_costs(&compiled_program.circuit, debug_info, &debug_artifact);
+    for cost in source_line_costs.into_iter().take(PROFILE_SUMMARY_LENGTH) {
+        let source_line = cost.source_line;
+        table.add_row(row![
+            Fm->format!("{}", source_line.function.unwrap_or_default()),
+            format!("{}:{}", source_line.path, source_line.line),
+            Fc->format!("{}", cost.opcodes),
+            Fc->format!("{}", cost.black_box_calls),
+            Fc->format!("{:.2}", 100.0 * cost.opcodes as f64 / total_opcodes as f64),
+        ]);
     }
+    table.printstd();
 }
@@ -0,0 +1,27 @@
+use clap::Args;
+use noirc_errors::error_codes;
+
+use crate::errors::CliError;
+
+/// Print an extended explanation of an error code
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExplainCommand {
+    /// The error code to explain, e.g. `E0228`
+    code: String,
+}
+
+pub(crate) fn run(args: ExplainCommand) -> Result<(), CliError> {
+    // Accept codes written without the leading `E` as well, e.g. `0228`
+    let code = args.code.trim_start_matches(['E', 'e']);
+    let code = format!("E{code}");
+
+    if !error_codes::is_known_code(&code) {
+        return Err(CliError::Generic(format!("`{}` is not a valid error code", args.code)));
+    }
+
+    match error_codes::explanation(&code) {
+        Some(explanation) => println!("{explanation}"),
+        None => println!("There is no extended explanation for {code} yet."),
+    }
+    Ok(())
+}
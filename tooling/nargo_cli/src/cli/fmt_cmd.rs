@@ -21,6 +21,7 @@ pub(crate) struct FormatCommand {
 
 pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliError> {
     let check_mode = args.check;
+    let message_format = config.message_format;
 
     let toml_path = get_package_manifest(&config.program_dir)?;
     let workspace = resolve_workspace_from_toml(
@@ -57,6 +58,7 @@ pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliErr
                     &workspace_file_manager,
                     false,
                     false,
+                    message_format,
                 );
                 return Ok(());
             }
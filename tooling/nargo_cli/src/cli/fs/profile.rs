@@ -0,0 +1,18 @@
+use std::path::{Path, PathBuf};
+
+use nargo::constants::BRILLIG_PROFILE_EXT;
+
+use super::{create_named_dir, write_to_file};
+
+pub(crate) fn save_brillig_profile_to_dir<P: AsRef<Path>>(
+    folded_stacks: &str,
+    profile_name: &str,
+    profile_dir: P,
+) -> PathBuf {
+    create_named_dir(profile_dir.as_ref(), "profile");
+    let profile_path = profile_dir.as_ref().join(profile_name).with_extension(BRILLIG_PROFILE_EXT);
+
+    write_to_file(folded_stacks.as_bytes(), &profile_path);
+
+    profile_path
+}
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use acvm::acir::circuit::{Circuit, Opcode};
 use acvm::ExpressionWidth;
 use backend_interface::BackendError;
 use clap::Args;
@@ -13,7 +14,10 @@ use noirc_driver::{
     file_manager_with_stdlib, CompileOptions, CompiledContract, CompiledProgram,
     NOIR_ARTIFACT_VERSION_STRING,
 };
-use noirc_errors::{debug_info::OpCodesCount, Location};
+use noirc_errors::{
+    debug_info::{DebugInfo, OpCodesCount},
+    Location,
+};
 use noirc_frontend::graph::CrateName;
 use prettytable::{row, table, Row};
 use rayon::prelude::*;
@@ -29,6 +33,9 @@ use super::{compile_cmd::compile_workspace, NargoConfig};
 /// Current information provided:
 /// 1. The number of ACIR opcodes
 /// 2. Counts the final number gates in the circuit used by a backend
+/// 3. Optionally, the number of opcodes generated by each function
+/// 4. With `--remarks`, why each opcode was generated, by source line and in total
+/// 5. Optionally, the size and usage of each memory block used for dynamic array indexing
 #[derive(Debug, Clone, Args)]
 pub(crate) struct InfoCommand {
     /// The name of the package to detail
@@ -46,6 +53,15 @@ pub(crate) struct InfoCommand {
     #[clap(long, hide = true)]
     profile_info: bool,
 
+    /// Display the number of opcodes generated by each function, including inlined functions
+    #[clap(long)]
+    function_costs: bool,
+
+    /// Display each memory block used for dynamic array indexing, with its size,
+    /// number of reads and writes, and whether it is read-only (ROM) or read-write (RAM)
+    #[clap(long)]
+    memory_blocks: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -82,6 +98,7 @@ pub(crate) fn run(
         &contract_packages,
         expression_width,
         &args.compile_options,
+        config.message_format,
     )?;
 
     if args.profile_info {
@@ -101,17 +118,48 @@ pub(crate) fn run(
         }
     }
 
+    if args.compile_options.remarks && !args.json {
+        for compiled_program in &compiled_programs {
+            let span_remarks = compiled_program.debug.count_span_remark_opcodes();
+            let debug_artifact: DebugArtifact = compiled_program.clone().into();
+            print_span_remarks(span_remarks, &debug_artifact);
+        }
+
+        for compiled_contract in &compiled_contracts {
+            let debug_artifact: DebugArtifact = compiled_contract.clone().into();
+            for contract_function in &compiled_contract.functions {
+                let span_remarks = contract_function.debug.count_span_remark_opcodes();
+                print_span_remarks(span_remarks, &debug_artifact);
+            }
+        }
+    }
+
     let program_info = binary_packages
         .into_par_iter()
         .zip(compiled_programs)
         .map(|(package, program)| {
-            count_opcodes_and_gates_in_program(backend, program, &package, expression_width)
+            count_opcodes_and_gates_in_program(
+                backend,
+                program,
+                &package,
+                expression_width,
+                args.function_costs,
+                args.memory_blocks,
+            )
         })
         .collect::<Result<_, _>>()?;
 
     let contract_info = compiled_contracts
         .into_par_iter()
-        .map(|contract| count_opcodes_and_gates_in_contract(backend, contract, expression_width))
+        .map(|contract| {
+            count_opcodes_and_gates_in_contract(
+                backend,
+                contract,
+                expression_width,
+                args.function_costs,
+                args.memory_blocks,
+            )
+        })
         .collect::<Result<_, _>>()?;
 
     let info_report = InfoReport { programs: program_info, contracts: contract_info };
@@ -124,10 +172,56 @@ pub(crate) fn run(
         if !info_report.programs.is_empty() {
             let mut program_table = table!([Fm->"Package", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Backend Circuit Size"]);
 
-            for program in info_report.programs {
+            for program in &info_report.programs {
                 program_table.add_row(program.into());
             }
             program_table.printstd();
+
+            if args.compile_options.remarks {
+                let mut remark_table = table!([Fm->"Package", Fm->"Remark", Fm->"ACIR Opcodes"]);
+                for program in &info_report.programs {
+                    for (remark, count) in sort_remarks(&program.remarks) {
+                        remark_table.add_row(row![
+                            Fm->format!("{}", program.name),
+                            format!("{}", remark),
+                            Fc->format!("{}", count),
+                        ]);
+                    }
+                }
+                remark_table.printstd();
+            }
+
+            if args.function_costs {
+                let mut function_table = table!([
+                    Fm->"Package",
+                    Fm->"Function",
+                    Fm->"ACIR Opcodes",
+                    Fm->"Brillig Opcodes"
+                ]);
+                for program in &info_report.programs {
+                    for function in &program.functions {
+                        function_table.add_row(function.to_row(&program.name));
+                    }
+                }
+                function_table.printstd();
+            }
+
+            if args.memory_blocks {
+                let mut memory_table = table!([
+                    Fm->"Package",
+                    Fm->"Block",
+                    Fm->"Size",
+                    Fm->"Reads",
+                    Fm->"Writes",
+                    Fm->"Type"
+                ]);
+                for program in &info_report.programs {
+                    for block in &program.memory_blocks {
+                        memory_table.add_row(block.to_row(&program.name));
+                    }
+                }
+                memory_table.printstd();
+            }
         }
         if !info_report.contracts.is_empty() {
             let mut contract_table = table!([
@@ -137,7 +231,7 @@ pub(crate) fn run(
                 Fm->"ACIR Opcodes",
                 Fm->"Backend Circuit Size"
             ]);
-            for contract_info in info_report.contracts {
+            for contract_info in &info_report.contracts {
                 let contract_rows: Vec<Row> = contract_info.into();
                 for row in contract_rows {
                     contract_table.add_row(row);
@@ -145,6 +239,61 @@ pub(crate) fn run(
             }
 
             contract_table.printstd();
+
+            if args.compile_options.remarks {
+                let mut remark_table =
+                    table!([Fm->"Contract Function", Fm->"Remark", Fm->"ACIR Opcodes"]);
+                for contract_info in &info_report.contracts {
+                    for function in &contract_info.functions {
+                        for (remark, count) in sort_remarks(&function.remarks) {
+                            remark_table.add_row(row![
+                                Fm->format!("{}::{}", contract_info.name, function.name),
+                                format!("{}", remark),
+                                Fc->format!("{}", count),
+                            ]);
+                        }
+                    }
+                }
+                remark_table.printstd();
+            }
+
+            if args.function_costs {
+                let mut function_table = table!([
+                    Fm->"Contract Function",
+                    Fm->"Function",
+                    Fm->"ACIR Opcodes",
+                    Fm->"Brillig Opcodes"
+                ]);
+                for contract_info in &info_report.contracts {
+                    for contract_function in &contract_info.functions {
+                        let name = format!("{}::{}", contract_info.name, contract_function.name);
+                        for function in &contract_function.functions {
+                            function_table.add_row(function.to_row(&name));
+                        }
+                    }
+                }
+                function_table.printstd();
+            }
+
+            if args.memory_blocks {
+                let mut memory_table = table!([
+                    Fm->"Contract Function",
+                    Fm->"Block",
+                    Fm->"Size",
+                    Fm->"Reads",
+                    Fm->"Writes",
+                    Fm->"Type"
+                ]);
+                for contract_info in &info_report.contracts {
+                    for function in &contract_info.functions {
+                        let name = format!("{}::{}", contract_info.name, function.name);
+                        for block in &function.memory_blocks {
+                            memory_table.add_row(block.to_row(&name));
+                        }
+                    }
+                }
+                memory_table.printstd();
+            }
         }
     }
 
@@ -166,23 +315,56 @@ fn print_span_opcodes(
     });
 
     for (location, opcodes_count) in pairs {
-        let debug_file = debug_artifact.file_map.get(&location.file).unwrap();
-
-        let start_byte = byte_index(&debug_file.source, location.span.start() + 1);
-        let end_byte = byte_index(&debug_file.source, location.span.end() + 1);
-        let range = start_byte..end_byte;
-        let span_content = &debug_file.source[range];
-        let line = debug_artifact.location_line_index(*location).unwrap() + 1;
+        let (line, span_content, path) = describe_location(location, debug_artifact);
         println!(
             "Ln. {}: {} (ACIR:{}, Brillig:{} opcode|s) in file: {}",
-            line,
-            span_content,
-            opcodes_count.acir_size,
-            opcodes_count.brillig_size,
-            debug_file.path.to_str().unwrap()
+            line, span_content, opcodes_count.acir_size, opcodes_count.brillig_size, path
         );
     }
 }
+
+/// Prints the number of ACIR opcodes with each remark generated by each source location,
+/// in the same format as `print_span_opcodes`
+fn print_span_remarks(
+    span_remarks_map: HashMap<Location, BTreeMap<&str, usize>>,
+    debug_artifact: &DebugArtifact,
+) {
+    let mut remarks: Vec<(&Location, &str, usize)> = span_remarks_map
+        .iter()
+        .flat_map(|(location, remarks)| {
+            remarks.iter().map(move |(remark, count)| (location, *remark, *count))
+        })
+        .collect();
+
+    remarks.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.1.cmp(b.1)));
+
+    for (location, remark, count) in remarks {
+        let (line, span_content, path) = describe_location(location, debug_artifact);
+        println!("Ln. {line}: {span_content} ({remark}, ACIR:{count} opcode|s) in file: {path}");
+    }
+}
+
+/// Returns the line number, source code and file path of `location`
+fn describe_location<'a>(
+    location: &Location,
+    debug_artifact: &'a DebugArtifact,
+) -> (usize, &'a str, &'a str) {
+    let debug_file = debug_artifact.file_map.get(&location.file).unwrap();
+
+    let start_byte = byte_index(&debug_file.source, location.span.start() + 1);
+    let end_byte = byte_index(&debug_file.source, location.span.end() + 1);
+    let range = start_byte..end_byte;
+    let span_content = &debug_file.source[range];
+    let line = debug_artifact.location_line_index(*location).unwrap() + 1;
+    (line, span_content, debug_file.path.to_str().unwrap())
+}
+
+/// Orders remarks from the most to the least opcodes
+fn sort_remarks(remarks: &BTreeMap<String, usize>) -> Vec<(&String, &usize)> {
+    let mut remarks: Vec<_> = remarks.iter().collect();
+    remarks.sort_by(|a, b| b.1.cmp(a.1));
+    remarks
+}
 fn byte_index(string: &str, index: u32) -> usize {
     let mut byte_index = 0;
     let mut char_index = 0;
@@ -213,10 +395,16 @@ struct ProgramInfo {
     expression_width: ExpressionWidth,
     acir_opcodes: usize,
     circuit_size: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    functions: Vec<FunctionCost>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    remarks: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    memory_blocks: Vec<MemoryBlockInfo>,
 }
 
-impl From<ProgramInfo> for Row {
-    fn from(program_info: ProgramInfo) -> Self {
+impl From<&ProgramInfo> for Row {
+    fn from(program_info: &ProgramInfo) -> Self {
         row![
             Fm->format!("{}", program_info.name),
             format!("{:?}", program_info.expression_width),
@@ -239,11 +427,17 @@ struct FunctionInfo {
     name: String,
     acir_opcodes: usize,
     circuit_size: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    functions: Vec<FunctionCost>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    remarks: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    memory_blocks: Vec<MemoryBlockInfo>,
 }
 
-impl From<ContractInfo> for Vec<Row> {
-    fn from(contract_info: ContractInfo) -> Self {
-        vecmap(contract_info.functions, |function| {
+impl From<&ContractInfo> for Vec<Row> {
+    fn from(contract_info: &ContractInfo) -> Self {
+        vecmap(&contract_info.functions, |function| {
             row![
                 Fm->format!("{}", contract_info.name),
                 Fc->format!("{}", function.name),
@@ -255,17 +449,117 @@ impl From<ContractInfo> for Vec<Row> {
     }
 }
 
+/// The number of opcodes generated by a single function within a program, including the
+/// opcodes of each call to it which was inlined into its caller.
+#[derive(Debug, Serialize)]
+struct FunctionCost {
+    name: String,
+    acir_opcodes: usize,
+    brillig_opcodes: usize,
+}
+
+impl FunctionCost {
+    fn to_row(&self, program_name: &str) -> Row {
+        row![
+            Fm->format!("{}", program_name),
+            Fc->format!("{}", self.name),
+            Fc->format!("{}", self.acir_opcodes),
+            Fc->format!("{}", self.brillig_opcodes),
+        ]
+    }
+}
+
+/// The size and usage of a block of memory, which is created for each dynamically indexed array.
+///
+/// A block which is never written to after its initialization is read-only memory (ROM),
+/// which backends can usually constrain more cheaply than read-write memory (RAM).
+#[derive(Debug, Serialize)]
+struct MemoryBlockInfo {
+    block_id: u32,
+    size: usize,
+    reads: usize,
+    writes: usize,
+    memory_type: &'static str,
+}
+
+impl MemoryBlockInfo {
+    fn to_row(&self, program_name: &str) -> Row {
+        row![
+            Fm->format!("{}", program_name),
+            format!("{}", self.block_id),
+            Fc->format!("{}", self.size),
+            Fc->format!("{}", self.reads),
+            Fc->format!("{}", self.writes),
+            format!("{}", self.memory_type),
+        ]
+    }
+}
+
+/// Lists the memory blocks of `circuit` by their `BlockId`, counting the reads and writes
+/// performed by its memory opcodes.
+fn count_memory_blocks(circuit: &Circuit) -> Vec<MemoryBlockInfo> {
+    let mut blocks: BTreeMap<u32, MemoryBlockInfo> = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        let block_id = match opcode {
+            Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } => block_id.0,
+            _ => continue,
+        };
+        let block = blocks.entry(block_id).or_insert(MemoryBlockInfo {
+            block_id,
+            size: 0,
+            reads: 0,
+            writes: 0,
+            memory_type: "ROM",
+        });
+        match opcode {
+            Opcode::MemoryInit { init, .. } => block.size = init.len(),
+            Opcode::MemoryOp { op, .. } if op.operation.is_zero() => block.reads += 1,
+            _ => {
+                block.writes += 1;
+                block.memory_type = "RAM";
+            }
+        }
+    }
+    blocks.into_values().collect()
+}
+
+/// Counts the opcodes generated by each function, from the most to the least expensive.
+fn count_function_costs(debug: &DebugInfo, main_name: &str) -> Vec<FunctionCost> {
+    let mut costs = vecmap(debug.count_function_opcodes(main_name), |(name, count)| FunctionCost {
+        name,
+        acir_opcodes: count.acir_size,
+        brillig_opcodes: count.brillig_size,
+    });
+    costs.sort_by(|a, b| {
+        b.acir_opcodes.cmp(&a.acir_opcodes).then_with(|| b.brillig_opcodes.cmp(&a.brillig_opcodes))
+    });
+    costs
+}
+
 fn count_opcodes_and_gates_in_program(
     backend: &Backend,
     compiled_program: CompiledProgram,
     package: &Package,
     expression_width: ExpressionWidth,
+    function_costs: bool,
+    memory_blocks: bool,
 ) -> Result<ProgramInfo, CliError> {
     Ok(ProgramInfo {
         name: package.name.to_string(),
         expression_width,
         acir_opcodes: compiled_program.circuit.opcodes.len(),
         circuit_size: backend.get_exact_circuit_size(&compiled_program.circuit)?,
+        functions: if function_costs {
+            count_function_costs(&compiled_program.debug, "main")
+        } else {
+            Vec::new()
+        },
+        remarks: compiled_program.debug.count_remark_opcodes(),
+        memory_blocks: if memory_blocks {
+            count_memory_blocks(&compiled_program.circuit)
+        } else {
+            Vec::new()
+        },
     })
 }
 
@@ -273,18 +567,88 @@ fn count_opcodes_and_gates_in_contract(
     backend: &Backend,
     contract: CompiledContract,
     expression_width: ExpressionWidth,
+    function_costs: bool,
+    memory_blocks: bool,
 ) -> Result<ContractInfo, CliError> {
     let functions = contract
         .functions
         .into_par_iter()
         .map(|function| -> Result<_, BackendError> {
             Ok(FunctionInfo {
-                name: function.name,
                 acir_opcodes: function.bytecode.opcodes.len(),
                 circuit_size: backend.get_exact_circuit_size(&function.bytecode)?,
+                functions: if function_costs {
+                    count_function_costs(&function.debug, &function.name)
+                } else {
+                    Vec::new()
+                },
+                remarks: function.debug.count_remark_opcodes(),
+                memory_blocks: if memory_blocks {
+                    count_memory_blocks(&function.bytecode)
+                } else {
+                    Vec::new()
+                },
+                name: function.name,
             })
         })
         .collect::<Result<_, _>>()?;
 
     Ok(ContractInfo { name: contract.name, expression_width, functions })
 }
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::{
+        circuit::{
+            opcodes::{BlockId, MemOp},
+            Circuit, Opcode,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    use super::count_memory_blocks;
+
+    #[test]
+    fn counts_reads_and_writes_of_rom_and_ram_blocks() {
+        let rom = BlockId(0);
+        let ram = BlockId(1);
+        let read = |block_id| Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(Expression::zero(), Witness(10)),
+            predicate: None,
+        };
+        let write = |block_id| Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(Expression::one(), Witness(11).into()),
+            predicate: None,
+        };
+        let circuit = Circuit {
+            opcodes: vec![
+                Opcode::MemoryInit { block_id: rom, init: vec![Witness(1), Witness(2)] },
+                Opcode::MemoryInit {
+                    block_id: ram,
+                    init: vec![Witness(3), Witness(4), Witness(5)],
+                },
+                read(rom),
+                read(ram),
+                write(ram),
+                read(rom),
+                write(ram),
+            ],
+            ..Circuit::default()
+        };
+
+        let blocks = count_memory_blocks(&circuit);
+        assert_eq!(blocks.len(), 2);
+
+        assert_eq!(blocks[0].block_id, 0);
+        assert_eq!(blocks[0].size, 2);
+        assert_eq!((blocks[0].reads, blocks[0].writes), (2, 0));
+        assert_eq!(blocks[0].memory_type, "ROM");
+
+        assert_eq!(blocks[1].block_id, 1);
+        assert_eq!(blocks[1].size, 3);
+        assert_eq!((blocks[1].reads, blocks[1].writes), (1, 2));
+        assert_eq!(blocks[1].memory_type, "RAM");
+    }
+}
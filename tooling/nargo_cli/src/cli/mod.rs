@@ -2,6 +2,7 @@ use clap::{Args, Parser, Subcommand};
 use const_format::formatcp;
 use nargo_toml::find_package_root;
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use noirc_errors::reporter::MessageFormat;
 use std::path::PathBuf;
 
 use color_eyre::eyre;
@@ -17,6 +18,7 @@ mod compile_cmd;
 mod dap_cmd;
 mod debug_cmd;
 mod execute_cmd;
+mod explain_cmd;
 mod fmt_cmd;
 mod info_cmd;
 mod init_cmd;
@@ -54,6 +56,10 @@ pub(crate) struct NargoConfig {
     // REMINDER: Also change this flag in the LSP test lens if renamed
     #[arg(long, hide = true, global = true, default_value = "./")]
     program_dir: PathBuf,
+
+    /// The format in which to report errors and warnings, either `human` or `json`
+    #[arg(long, global = true, default_value = "human")]
+    message_format: MessageFormat,
 }
 
 #[non_exhaustive]
@@ -74,6 +80,7 @@ enum NargoCommand {
     Verify(verify_cmd::VerifyCommand),
     Test(test_cmd::TestCommand),
     Info(info_cmd::InfoCommand),
+    Explain(explain_cmd::ExplainCommand),
     Lsp(lsp_cmd::LspCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
@@ -94,6 +101,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
             | NargoCommand::Init(_)
             | NargoCommand::Lsp(_)
             | NargoCommand::Backend(_)
+            | NargoCommand::Explain(_)
             | NargoCommand::Dap(_)
     ) {
         config.program_dir = find_package_root(&config.program_dir)?;
@@ -115,6 +123,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Info(args) => info_cmd::run(&backend, args, config),
         NargoCommand::CodegenVerifier(args) => codegen_verifier_cmd::run(&backend, args, config),
         NargoCommand::Backend(args) => backend_cmd::run(args),
+        NargoCommand::Explain(args) => explain_cmd::run(args),
         NargoCommand::Lsp(args) => lsp_cmd::run(&backend, args, config),
         NargoCommand::Dap(args) => dap_cmd::run(&backend, args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
@@ -1,3 +1,4 @@
+use acvm::brillig_vm::ExecutionLimits;
 use clap::Args;
 use nargo::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
 use nargo::insert_all_files_for_workspace_into_file_manager;
@@ -8,6 +9,7 @@ use noirc_abi::input_parser::Format;
 use noirc_driver::{
     file_manager_with_stdlib, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
 };
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::graph::CrateName;
 
 use super::compile_cmd::compile_bin_package;
@@ -75,6 +77,7 @@ pub(crate) fn run(
             package,
             &args.compile_options,
             expression_width,
+            config.message_format,
         )?;
 
         prove_package(
@@ -86,6 +89,7 @@ pub(crate) fn run(
             &args.verifier_name,
             args.verify,
             args.oracle_resolver.as_deref(),
+            config.message_format,
         )?;
     }
 
@@ -102,13 +106,19 @@ pub(crate) fn prove_package(
     verifier_name: &str,
     check_proof: bool,
     foreign_call_resolver_url: Option<&str>,
+    message_format: MessageFormat,
 ) -> Result<(), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
 
-    let solved_witness =
-        execute_program(&compiled_program, &inputs_map, foreign_call_resolver_url)?;
+    let (solved_witness, _) = execute_program(
+        &compiled_program,
+        &inputs_map,
+        foreign_call_resolver_url,
+        ExecutionLimits::UNLIMITED,
+        message_format,
+    )?;
 
     // Write public inputs into Verifier.toml
     let public_abi = compiled_program.abi.public_abi();
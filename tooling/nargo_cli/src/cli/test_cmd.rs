@@ -1,23 +1,24 @@
 use std::io::Write;
 
-use acvm::BlackBoxFunctionSolver;
+use acvm::{brillig_vm::ExecutionLimits, BlackBoxFunctionSolver};
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 use fm::FileManager;
 use nargo::{
     insert_all_files_for_workspace_into_file_manager,
-    ops::{run_test, TestStatus},
+    ops::{run_test, FuzzingConfig, TestStatus, DEFAULT_FUZZ_RUNS, DEFAULT_TEST_STEP_LIMIT},
     package::Package,
     prepare_package,
 };
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{file_manager_with_stdlib, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::reporter::MessageFormat;
 use noirc_frontend::{graph::CrateName, hir::FunctionNameMatch};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{backends::Backend, cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
-use super::NargoConfig;
+use super::{execute_cmd::BrilligLimitOptions, NargoConfig};
 
 /// Run the tests for this program
 #[derive(Debug, Clone, Args)]
@@ -47,6 +48,17 @@ pub(crate) struct TestCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    #[clap(flatten)]
+    brillig_limits: BrilligLimitOptions,
+
+    /// The number of sets of random inputs each test function with parameters is run against
+    #[clap(long, default_value_t = DEFAULT_FUZZ_RUNS)]
+    fuzz_runs: u32,
+
+    /// The seed used to generate inputs for test functions with parameters [default: random]
+    #[clap(long)]
+    fuzz_seed: Option<u64>,
 }
 
 pub(crate) fn run(
@@ -78,6 +90,14 @@ pub(crate) fn run(
         None => FunctionNameMatch::Anything,
     };
 
+    let fuzzing_config = FuzzingConfig {
+        runs: args.fuzz_runs,
+        seed: args.fuzz_seed.unwrap_or_else(|| FuzzingConfig::default().seed),
+    };
+
+    let mut brillig_limits: ExecutionLimits = (&args.brillig_limits).into();
+    brillig_limits.max_steps = brillig_limits.max_steps.or(Some(DEFAULT_TEST_STEP_LIMIT));
+
     let blackbox_solver = Bn254BlackBoxSolver::new();
     for package in &workspace {
         // By unwrapping here with `?`, we stop the test runner upon a package failing
@@ -90,12 +110,16 @@ pub(crate) fn run(
             args.show_output,
             args.oracle_resolver.as_deref(),
             &args.compile_options,
+            brillig_limits,
+            &fuzzing_config,
+            config.message_format,
         )?;
     }
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_tests<S: BlackBoxFunctionSolver>(
     file_manager: &FileManager,
     blackbox_solver: &S,
@@ -104,15 +128,13 @@ fn run_tests<S: BlackBoxFunctionSolver>(
     show_output: bool,
     foreign_call_resolver_url: Option<&str>,
     compile_options: &CompileOptions,
+    brillig_limits: ExecutionLimits,
+    fuzzing_config: &FuzzingConfig,
+    message_format: MessageFormat,
 ) -> Result<(), CliError> {
+    let compile_options = &package.compile_options(compile_options)?;
     let (mut context, crate_id) = prepare_package(file_manager, package);
-    check_crate_and_report_errors(
-        &mut context,
-        crate_id,
-        compile_options.deny_warnings,
-        compile_options.disable_macros,
-        compile_options.silence_warnings,
-    )?;
+    check_crate_and_report_errors(&mut context, crate_id, compile_options, message_format)?;
 
     let test_functions = context.get_all_test_functions_in_crate_matching(&crate_id, fn_name);
     let count_all = test_functions.len();
@@ -154,12 +176,22 @@ fn run_tests<S: BlackBoxFunctionSolver>(
             show_output,
             foreign_call_resolver_url,
             compile_options,
+            brillig_limits,
+            fuzzing_config,
         ) {
-            TestStatus::Pass { .. } => {
+            TestStatus::Pass { brillig_steps } => {
                 writer
                     .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
                     .expect("Failed to set color");
-                writeln!(writer, "ok").expect("Failed to write to stdout");
+                write!(writer, "ok").expect("Failed to write to stdout");
+                writer.reset().expect("Failed to reset writer");
+                match brillig_steps {
+                    Some(brillig_steps) if brillig_steps > 0 => {
+                        writeln!(writer, " ({brillig_steps} Brillig opcodes executed)")
+                    }
+                    _ => writeln!(writer),
+                }
+                .expect("Failed to write to stdout");
             }
             TestStatus::Fail { message, error_diagnostic } => {
                 writer
@@ -172,6 +204,7 @@ fn run_tests<S: BlackBoxFunctionSolver>(
                         &[diag],
                         compile_options.deny_warnings,
                         compile_options.silence_warnings,
+                        message_format,
                     );
                 }
                 count_failed += 1;
@@ -182,6 +215,7 @@ fn run_tests<S: BlackBoxFunctionSolver>(
                     &[err],
                     compile_options.deny_warnings,
                     compile_options.silence_warnings,
+                    message_format,
                 );
                 count_failed += 1;
             }
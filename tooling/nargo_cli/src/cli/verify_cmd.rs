@@ -62,6 +62,7 @@ pub(crate) fn run(
             package,
             &args.compile_options,
             expression_width,
+            config.message_format,
         )?;
 
         verify_package(backend, &workspace, package, program, &args.verifier_name)?;
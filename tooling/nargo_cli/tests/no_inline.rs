@@ -0,0 +1,51 @@
+//! This integration test checks that functions marked `#[no_inline]` are kept as separate
+//! functions when called from unconstrained code, rather than only checking that they execute.
+
+use acvm::acir::{brillig::Opcode as BrilligOpcode, circuit::Opcode};
+use assert_cmd::prelude::*;
+use assert_fs::prelude::{PathChild, PathCopy};
+use nargo::artifacts::program::PreprocessedProgram;
+use std::{path::PathBuf, process::Command};
+
+test_binary::build_test_binary_once!(mock_backend, "../backend_interface/test-binaries");
+
+#[test]
+fn no_inline_function_is_called_from_brillig() {
+    let program_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../test_programs/execution_success/no_inline");
+
+    // Compile a copy of the program so its artifacts don't clash with the `execution_success` test.
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    test_dir.copy_from(program_dir, &["Nargo.toml", "src/*.nr"]).unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.env("NARGO_BACKEND_PATH", path_to_mock_backend());
+    cmd.arg("--program-dir").arg(test_dir.path());
+    cmd.arg("compile");
+    cmd.assert().success();
+
+    let artifact = std::fs::read(test_dir.child("target/no_inline.json").path()).unwrap();
+    let program: PreprocessedProgram = serde_json::from_slice(&artifact).unwrap();
+
+    // Every Brillig entry point calls the function it was generated from, so any further calls
+    // are calls between Brillig functions.
+    let brillig_calls: Vec<usize> = program
+        .bytecode
+        .opcodes
+        .iter()
+        .filter_map(|opcode| match opcode {
+            Opcode::Brillig(brillig) => Some(
+                brillig
+                    .bytecode
+                    .iter()
+                    .filter(|opcode| matches!(opcode, BrilligOpcode::Call { .. }))
+                    .count(),
+            ),
+            _ => None,
+        })
+        .collect();
+
+    // `unconstrained_sum_of_squares` calls `square` rather than inlining it.
+    assert_eq!(brillig_calls.len(), 1);
+    assert!(brillig_calls[0] > 1, "`square` was inlined into `unconstrained_sum_of_squares`");
+}
@@ -67,7 +67,10 @@ impl super::FmtVisitor<'_> {
         } else {
             let fn_start = result
                 .find_token_with(|token| {
-                    matches!(token, Token::Keyword(Keyword::Fn | Keyword::Unconstrained))
+                    matches!(
+                        token,
+                        Token::Keyword(Keyword::Fn | Keyword::Unconstrained | Keyword::CompTime)
+                    )
                 })
                 .unwrap()
                 .start();
@@ -172,12 +175,13 @@ impl super::FmtVisitor<'_> {
                     }
 
                     let name = module.name;
+                    let visibility = module.visibility;
                     let after_brace = self.span_after(span, Token::LeftBrace).start();
                     self.last_position = after_brace;
 
                     let keyword = if module.is_contract { "contract" } else { "mod" };
 
-                    self.push_str(&format!("{keyword} {name} "));
+                    self.push_str(&format!("{visibility}{keyword} {name} "));
 
                     if module.contents.items.is_empty() {
                         self.visit_empty_block((after_brace - 1..span.end()).into());
@@ -191,13 +195,13 @@ impl super::FmtVisitor<'_> {
                     self.close_block((self.last_position..span.end() - 1).into());
                     self.last_position = span.end();
                 }
-                ItemKind::Import(_)
+                ItemKind::Import(..)
                 | ItemKind::Struct(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::Impl(_)
                 | ItemKind::TypeAlias(_)
-                | ItemKind::Global(_)
+                | ItemKind::Global(..)
                 | ItemKind::ModuleDecl(_) => {
                     self.push_rewrite(self.slice(span).to_string(), span);
                     self.last_position = span.end();
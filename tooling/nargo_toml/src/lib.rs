@@ -8,10 +8,11 @@ use std::{
     path::{Component, Path, PathBuf},
 };
 
+use acvm::ExpressionWidth;
 use errors::SemverError;
 use fm::{NormalizePath, FILE_EXTENSION};
 use nargo::{
-    package::{Dependency, Package, PackageType},
+    package::{CompileProfile, Dependency, Package, PackageType},
     workspace::Workspace,
 };
 use noirc_frontend::graph::CrateName;
@@ -117,6 +118,8 @@ struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
     dependencies: BTreeMap<String, DependencyConfig>,
+    #[serde(default)]
+    profile: BTreeMap<String, ProfileConfig>,
 }
 
 impl PackageConfig {
@@ -195,6 +198,14 @@ impl PackageConfig {
             })?;
         }
 
+        let profiles = self
+            .profile
+            .iter()
+            .map(|(profile_name, profile_config)| {
+                (profile_name.clone(), profile_config.resolve_to_profile())
+            })
+            .collect();
+
         Ok(Package {
             version: self.package.version.clone(),
             compiler_required_version: self.package.compiler_version.clone(),
@@ -203,6 +214,8 @@ impl PackageConfig {
             package_type,
             name,
             dependencies,
+            profiles,
+            macros: self.package.macros.clone(),
         })
     }
 }
@@ -271,6 +284,41 @@ struct PackageMetadata {
     // so you will not need to supply an ACIR and compiler version
     compiler_version: Option<String>,
     license: Option<String>,
+    // The macro plugins to run when compiling this package. All builtin plugins are run by default
+    macros: Option<Vec<String>>,
+}
+
+/// Compiler settings declared in a `[profile.<name>]` section.
+#[derive(Default, Debug, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ProfileConfig {
+    show_ssa: Option<bool>,
+    show_brillig: Option<bool>,
+    print_acir: Option<bool>,
+    deny_warnings: Option<bool>,
+    silence_warnings: Option<bool>,
+    disable_macros: Option<bool>,
+    /// The maximum width of expressions in the generated ACIR, where `0` means unbounded.
+    expression_width: Option<usize>,
+}
+
+impl ProfileConfig {
+    fn resolve_to_profile(&self) -> CompileProfile {
+        let expression_width = self.expression_width.map(|width| match width {
+            0 => ExpressionWidth::Unbounded,
+            width => ExpressionWidth::Bounded { width },
+        });
+
+        CompileProfile {
+            show_ssa: self.show_ssa,
+            show_brillig: self.show_brillig,
+            print_acir: self.print_acir,
+            deny_warnings: self.deny_warnings,
+            silence_warnings: self.silence_warnings,
+            disable_macros: self.disable_macros,
+            expression_width,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -490,3 +538,47 @@ fn parse_workspace_default_member_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_toml_with_profiles() {
+    let src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+
+        [profile.default]
+        deny-warnings = true
+
+        [profile.release]
+        print-acir = true
+        expression-width = 0
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.profile.len(), 2);
+
+    let default_profile = package_config.profile["default"].resolve_to_profile();
+    assert_eq!(default_profile.deny_warnings, Some(true));
+    assert_eq!(default_profile.print_acir, None);
+
+    let release_profile = package_config.profile["release"].resolve_to_profile();
+    assert_eq!(release_profile.print_acir, Some(true));
+    assert!(matches!(release_profile.expression_width, Some(ExpressionWidth::Unbounded)));
+}
+
+#[test]
+fn parse_package_macros() {
+    let src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+        macros = ["aztec"]
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.package.macros, Some(vec!["aztec".to_string()]));
+}
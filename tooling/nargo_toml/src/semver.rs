@@ -89,6 +89,8 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            profiles: BTreeMap::new(),
+            macros: None,
         };
         if let Err(err) = semver_check_package(&package, &compiler_version) {
             panic!("semver check should have passed. compiler version is 0.1.0 and required version from the package is 0.1.0\n error: {err:?}")
@@ -120,6 +122,8 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            profiles: BTreeMap::new(),
+            macros: None,
         };
 
         let valid_dependency = Package {
@@ -130,6 +134,8 @@ mod tests {
             name: CrateName::from_str("good_dependency").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            profiles: BTreeMap::new(),
+            macros: None,
         };
         let invalid_dependency = Package {
             compiler_required_version: Some("0.2.0".to_string()),
@@ -139,6 +145,8 @@ mod tests {
             name: CrateName::from_str("bad_dependency").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            profiles: BTreeMap::new(),
+            macros: None,
         };
 
         package.dependencies.insert(
@@ -179,6 +187,8 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            profiles: BTreeMap::new(),
+            macros: None,
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -198,6 +208,8 @@ mod tests {
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
+            profiles: BTreeMap::new(),
+            macros: None,
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
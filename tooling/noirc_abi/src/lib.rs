@@ -608,4 +608,41 @@ mod test {
         // We also decode the return value (we can do this immediately as we know it shares a witness with an input).
         assert_eq!(return_value.unwrap(), reconstructed_inputs["thing2"]);
     }
+
+    #[test]
+    fn tuple_return_value_decoding() {
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "x".to_string(),
+                typ: AbiType::Field,
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::from([("x".to_string(), vec![(Witness(1)..Witness(2))])]),
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Tuple {
+                    fields: vec![
+                        AbiType::Field,
+                        AbiType::Tuple { fields: vec![AbiType::Boolean, AbiType::Field] },
+                    ],
+                },
+                visibility: AbiVisibility::Public,
+            }),
+            return_witnesses: vec![Witness(2), Witness(3), Witness(4)],
+        };
+
+        let inputs: InputMap =
+            BTreeMap::from([("x".to_string(), InputValue::Field(FieldElement::from(3_u128)))]);
+        let return_value = InputValue::Vec(vec![
+            InputValue::Field(FieldElement::from(5_u128)),
+            InputValue::Vec(vec![
+                InputValue::Field(FieldElement::one()),
+                InputValue::Field(FieldElement::from(7_u128)),
+            ]),
+        ]);
+
+        let witness_map = abi.encode(&inputs, Some(return_value.clone())).unwrap();
+        let (_, decoded_return_value) = abi.decode(&witness_map).unwrap();
+
+        assert_eq!(decoded_return_value, Some(return_value));
+    }
 }